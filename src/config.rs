@@ -1,3 +1,5 @@
+#![allow(unused_imports)]
+
 use std::{fs::File};
 use std::io::Read;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -1,16 +1,54 @@
-use std::fmt;
+use std::{fmt, io};
+
+use crate::read::tokenizer::Span;
 
 #[derive(Debug)]
 pub enum Errors {
-    TokenError(&'static str),
+    TokenError(&'static str, Span),
+    EncodingError(&'static str, Span),
+    ParseError(&'static str, Span),
     DeveloperError(&'static str),
+    IoError(io::Error),
 }
 
 impl fmt::Display for Errors {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
-            Errors::TokenError(ref desc) => write!(f, "Syntax Error: {}", desc),
+            Errors::TokenError(ref desc, ref span) => write!(
+                f,
+                "Syntax Error: {} at {}:{}",
+                desc,
+                span.line,
+                span.column + 1
+            ),
+            Errors::EncodingError(ref desc, ref span) => write!(
+                f,
+                "Encoding Error: {} at {}:{}",
+                desc,
+                span.line,
+                span.column + 1
+            ),
+            Errors::ParseError(ref desc, ref span) => write!(
+                f,
+                "Parse Error: {} at {}:{}",
+                desc,
+                span.line,
+                span.column + 1
+            ),
             Errors::DeveloperError(ref desc) => write!(f, "Developer Error: {}", desc),
+            Errors::IoError(ref error) => write!(f, "IO Error: {}", error),
+        }
+    }
+}
+
+impl Errors {
+    // Where in the source the error is, None for errors that aren't about the source
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Errors::TokenError(_, span)
+            | Errors::EncodingError(_, span)
+            | Errors::ParseError(_, span) => Some(*span),
+            Errors::DeveloperError(_) | Errors::IoError(_) => None,
         }
     }
 }
@@ -1,11 +1,14 @@
 pub mod config;
-pub mod test;
+#[cfg(test)]
+mod test;
 pub mod generators;
 pub mod utils;
 pub mod read;
 pub mod write;
 pub mod error;
+pub mod parse;
 
+#[allow(dead_code)]
 fn main() {
     match config::Config::read_config("../cheetah.config.json") {
         Ok(config) => {
@@ -0,0 +1,53 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::{env, process};
+
+use cheetah::error::errors::Errors;
+use cheetah::parse::{ast::SourceType, estree::to_estree, parser::parse};
+use cheetah::read::{read::read_source, typescript::Dialect};
+
+const USAGE: &str = "Usage: cheetah ast <file>";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.as_slice() {
+        [command, file] if command == "ast" => print_ast(file),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+/**
+ * Prints the ESTree JSON of a file, to compare with what Node tools make of it
+ *
+ * `.mjs` and `.mts` are modules and `.cjs` and `.cts` scripts. Anything else is read as a
+ * module first and as a script when that fails, sloppy code like `with` only parses as one.
+ */
+fn print_ast(file: &str) -> Result<(), Errors> {
+    let source = read_source(file)?;
+    let bytes = source.text.as_bytes();
+    let dialect = Dialect::from_path(file);
+
+    let extension = Path::new(file)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let program = match extension {
+        "mjs" | "mts" => parse(bytes, dialect, SourceType::Module)?,
+        "cjs" | "cts" => parse(bytes, dialect, SourceType::Script)?,
+        _ => parse(bytes, dialect, SourceType::Module)
+            .or_else(|error| parse(bytes, dialect, SourceType::Script).map_err(|_| error))?,
+    };
+
+    let json = serde_json::to_string_pretty(&to_estree(&program, bytes))
+        .map_err(|_| Errors::DeveloperError("The syntax tree couldn't be written as JSON"))?;
+    // A closed pipe, `cheetah ast a.js | head`, is an error rather than a panic
+    writeln!(io::stdout(), "{}", json).map_err(Errors::IoError)
+}
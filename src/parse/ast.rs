@@ -0,0 +1,1641 @@
+use crate::read::interner::Symbol;
+use crate::read::number::NumberValue;
+use crate::read::tokenizer::Span;
+
+/**
+ * Typed syntax tree of a script or module
+ *
+ * Shaped after ESTree, names and fields follow it where Rust allows. Every node carries
+ * the span of its source text, enums hand it out through `span()`.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    pub source_type: SourceType,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SourceType {
+    #[default]
+    Script,
+    Module,
+}
+
+// `name` as written, escapes decoded
+#[derive(Clone, Debug, PartialEq)]
+pub struct Identifier {
+    pub name: Symbol,
+    pub span: Span,
+}
+
+// `#name`, name without the `#`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrivateName {
+    pub name: Symbol,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement {
+    Block(Box<BlockStatement>),
+    Variable(Box<VariableDeclaration>),
+    Function(Box<Function>),
+    Class(Box<Class>),
+    Empty(Span),
+    Expression(Box<ExpressionStatement>),
+    If(Box<IfStatement>),
+    DoWhile(Box<DoWhileStatement>),
+    While(Box<WhileStatement>),
+    For(Box<ForStatement>),
+    ForIn(Box<ForInStatement>),
+    ForOf(Box<ForOfStatement>),
+    Continue(Box<JumpStatement>),
+    Break(Box<JumpStatement>),
+    Return(Box<ReturnStatement>),
+    With(Box<WithStatement>),
+    Switch(Box<SwitchStatement>),
+    Labeled(Box<LabeledStatement>),
+    Throw(Box<ThrowStatement>),
+    Try(Box<TryStatement>),
+    Debugger(Span),
+    Import(Box<ImportDeclaration>),
+    ExportNamed(Box<ExportNamedDeclaration>),
+    ExportDefault(Box<ExportDefaultDeclaration>),
+    ExportAll(Box<ExportAllDeclaration>),
+    TsInterface(Box<TsInterfaceDeclaration>),
+    TsTypeAlias(Box<TsTypeAliasDeclaration>),
+    TsEnum(Box<TsEnumDeclaration>),
+    TsModule(Box<TsModuleDeclaration>),
+    Error(Span), // source skipped by parse_recovering, up to where parsing picked up again
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockStatement {
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VariableKind {
+    Var,
+    Let,
+    Const,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableDeclaration {
+    pub kind: VariableKind,
+    pub declarations: Vec<VariableDeclarator>,
+    pub declare: bool,
+    pub span: Span,
+}
+
+// definite is the `!` of `let a!: number`
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableDeclarator {
+    pub id: Pattern,
+    pub type_annotation: Option<TsTypeAnnotation>,
+    pub definite: bool,
+    pub init: Option<Expression>,
+    pub span: Span,
+}
+
+// directive holds the raw text of a prologue string, `use strict` for "use strict"
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpressionStatement {
+    pub expression: Expression,
+    pub directive: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IfStatement {
+    pub test: Expression,
+    pub consequent: Statement,
+    pub alternate: Option<Statement>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoWhileStatement {
+    pub body: Statement,
+    pub test: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhileStatement {
+    pub test: Expression,
+    pub body: Statement,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForStatement {
+    pub init: Option<ForInit>,
+    pub test: Option<Expression>,
+    pub update: Option<Expression>,
+    pub body: Statement,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ForInit {
+    Variable(VariableDeclaration),
+    Expression(Expression),
+}
+
+// What `for (... in x)` and `for (... of x)` assign to
+#[derive(Clone, Debug, PartialEq)]
+pub enum ForHead {
+    Variable(VariableDeclaration),
+    Pattern(Pattern),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForInStatement {
+    pub left: ForHead,
+    pub right: Expression,
+    pub body: Statement,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForOfStatement {
+    pub is_await: bool,
+    pub left: ForHead,
+    pub right: Expression,
+    pub body: Statement,
+    pub span: Span,
+}
+
+// `break` and `continue`
+#[derive(Clone, Debug, PartialEq)]
+pub struct JumpStatement {
+    pub label: Option<Identifier>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReturnStatement {
+    pub argument: Option<Expression>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithStatement {
+    pub object: Expression,
+    pub body: Statement,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwitchStatement {
+    pub discriminant: Expression,
+    pub cases: Vec<SwitchCase>,
+    pub span: Span,
+}
+
+// test is None for `default:`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwitchCase {
+    pub test: Option<Expression>,
+    pub consequent: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabeledStatement {
+    pub label: Identifier,
+    pub body: Statement,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThrowStatement {
+    pub argument: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TryStatement {
+    pub block: BlockStatement,
+    pub handler: Option<CatchClause>,
+    pub finalizer: Option<BlockStatement>,
+    pub span: Span,
+}
+
+// param is None for `catch {`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatchClause {
+    pub param: Option<Pattern>,
+    pub type_annotation: Option<TsTypeAnnotation>,
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Identifier(Identifier),
+    PrivateName(PrivateName), // only as the left side of `#x in object`
+    This(Span),
+    Super(Span), // only as the callee or object of a member
+    Null(Span),
+    Boolean(BooleanLiteral),
+    Number(NumberLiteral),
+    String(StringLiteral),
+    RegExp(RegExpLiteral),
+    Template(Box<TemplateLiteral>),
+    TaggedTemplate(Box<TaggedTemplateExpression>),
+    Array(Box<ArrayExpression>),
+    Object(Box<ObjectExpression>),
+    Function(Box<Function>),
+    Arrow(Box<ArrowFunction>),
+    Class(Box<Class>),
+    Unary(Box<UnaryExpression>),
+    Update(Box<UpdateExpression>),
+    Binary(Box<BinaryExpression>),
+    Logical(Box<LogicalExpression>),
+    Conditional(Box<ConditionalExpression>),
+    Assignment(Box<AssignmentExpression>),
+    Sequence(Box<SequenceExpression>),
+    Member(Box<MemberExpression>),
+    Call(Box<CallExpression>),
+    New(Box<NewExpression>),
+    Chain(Box<ChainExpression>),
+    Yield(Box<YieldExpression>),
+    Await(Box<AwaitExpression>),
+    MetaProperty(Box<MetaProperty>),
+    Import(Box<ImportExpression>),
+    TsAs(Box<TsAsExpression>),
+    TsSatisfies(Box<TsAsExpression>),
+    TsTypeAssertion(Box<TsAsExpression>), // `<T>value`, not in .tsx
+    TsNonNull(Box<TsNonNullExpression>),
+    JsxElement(Box<JsxElement>),
+    JsxFragment(Box<JsxFragment>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BooleanLiteral {
+    pub value: bool,
+    pub span: Span,
+}
+
+// raw is the literal as written, `0x1F` or `1_000n`
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberLiteral {
+    pub value: NumberValue,
+    pub raw: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StringLiteral {
+    pub value: String,
+    pub raw: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegExpLiteral {
+    pub pattern: String,
+    pub flags: String,
+    pub span: Span,
+}
+
+// quasis has one more element than expressions, `a${b}c` is ["a", "c"] and [b]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateLiteral {
+    pub quasis: Vec<TemplateElement>,
+    pub expressions: Vec<Expression>,
+    pub span: Span,
+}
+
+// cooked is None for an invalid escape in a tagged template
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateElement {
+    pub cooked: Option<String>,
+    pub raw: String,
+    pub tail: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaggedTemplateExpression {
+    pub tag: Expression,
+    pub type_arguments: Option<TsTypeArguments>,
+    pub quasi: TemplateLiteral,
+    pub span: Span,
+}
+
+// None elements are holes, `[a, , b]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayExpression {
+    pub elements: Vec<Option<ExpressionOrSpread>>,
+    pub span: Span,
+}
+
+// Array elements and call arguments
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionOrSpread {
+    Expression(Expression),
+    Spread(SpreadElement),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpreadElement {
+    pub argument: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectExpression {
+    pub properties: Vec<ObjectMember>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObjectMember {
+    Property(Property),
+    Spread(SpreadElement),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PropertyKind {
+    Init,
+    Get,
+    Set,
+}
+
+/**
+ * Member of an object literal
+ *
+ * Methods and accessors have an Expression::Function value. A shorthand with a default,
+ * `{ a = 1 }`, is only valid once the object turns into a pattern, until then the value is
+ * the assignment `a = 1`.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Property {
+    pub key: PropertyKey,
+    pub value: Expression,
+    pub kind: PropertyKind,
+    pub method: bool,
+    pub shorthand: bool,
+    pub computed: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyKey {
+    Identifier(Identifier),
+    PrivateName(PrivateName), // class members only
+    String(StringLiteral),
+    Number(NumberLiteral),
+    Computed(Expression), // `[key]`
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Minus,
+    Plus,
+    Not,
+    BitwiseNot,
+    Typeof,
+    Void,
+    Delete,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnaryExpression {
+    pub operator: UnaryOperator,
+    pub argument: Expression,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UpdateOperator {
+    Increment,
+    Decrement,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateExpression {
+    pub operator: UpdateOperator,
+    pub prefix: bool,
+    pub argument: Expression,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Equal,
+    NotEqual,
+    StrictEqual,
+    StrictNotEqual,
+    LessThan,
+    LessEqual,
+    GreaterThan,
+    GreaterEqual,
+    ShiftLeft,
+    ShiftRight,
+    UnsignedShiftRight,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Exponent,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    In,
+    Instanceof,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryExpression {
+    pub operator: BinaryOperator,
+    pub left: Expression,
+    pub right: Expression,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogicalOperator {
+    Or,
+    And,
+    Coalesce,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogicalExpression {
+    pub operator: LogicalOperator,
+    pub left: Expression,
+    pub right: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionalExpression {
+    pub test: Expression,
+    pub consequent: Expression,
+    pub alternate: Expression,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AssignmentOperator {
+    Assign,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Exponent,
+    ShiftLeft,
+    ShiftRight,
+    UnsignedShiftRight,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    LogicalOr,
+    LogicalAnd,
+    Coalesce,
+}
+
+// left is a destructuring pattern only for `=`, compound operators take identifiers and members
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignmentExpression {
+    pub operator: AssignmentOperator,
+    pub left: Pattern,
+    pub right: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SequenceExpression {
+    pub expressions: Vec<Expression>,
+    pub span: Span,
+}
+
+// optional is set on the `?.` link itself, the chain as a whole is a ChainExpression
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemberExpression {
+    pub object: Expression,
+    pub property: MemberProperty,
+    pub optional: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MemberProperty {
+    Identifier(Identifier),   // `a.b`
+    PrivateName(PrivateName), // `a.#b`
+    Computed(Expression),     // `a[b]`
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallExpression {
+    pub callee: Expression,
+    pub type_arguments: Option<TsTypeArguments>,
+    pub arguments: Vec<ExpressionOrSpread>,
+    pub optional: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewExpression {
+    pub callee: Expression,
+    pub type_arguments: Option<TsTypeArguments>,
+    pub arguments: Vec<ExpressionOrSpread>,
+    pub span: Span,
+}
+
+// `a?.b.c`, expression is the outermost member or call of the chain
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainExpression {
+    pub expression: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct YieldExpression {
+    pub argument: Option<Expression>,
+    pub delegate: bool, // `yield*`
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AwaitExpression {
+    pub argument: Expression,
+    pub span: Span,
+}
+
+// `new.target` and `import.meta`
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetaProperty {
+    pub meta: Identifier,
+    pub property: Identifier,
+    pub span: Span,
+}
+
+// `import(source, options)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportExpression {
+    pub source: Expression,
+    pub options: Option<Expression>,
+    pub span: Span,
+}
+
+/**
+ * Binding and assignment targets
+ *
+ * Member is only valid as an assignment target, declarations and parameters bind names.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+    Identifier(Identifier),
+    Object(Box<ObjectPattern>),
+    Array(Box<ArrayPattern>),
+    Rest(Box<RestElement>),
+    Assignment(Box<AssignmentPattern>),
+    Member(Box<MemberExpression>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectPattern {
+    pub properties: Vec<ObjectPatternMember>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObjectPatternMember {
+    Property(PatternProperty),
+    Rest(RestElement),
+}
+
+// `{ key: value }`, `{ a }` is shorthand for `{ a: a }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternProperty {
+    pub key: PropertyKey,
+    pub value: Pattern,
+    pub shorthand: bool,
+    pub computed: bool,
+    pub span: Span,
+}
+
+// None elements are holes, `[, b]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayPattern {
+    pub elements: Vec<Option<Pattern>>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RestElement {
+    pub argument: Pattern,
+    pub span: Span,
+}
+
+// `left = right`, a target with a default value
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignmentPattern {
+    pub left: Pattern,
+    pub right: Expression,
+    pub span: Span,
+}
+
+/**
+ * Function declaration, expression or the value of a method
+ *
+ * id is None for expressions without a name, methods and `export default function`.
+ * body is None for TypeScript overloads, `declare function` and abstract methods.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    pub id: Option<Identifier>,
+    pub type_parameters: Option<TsTypeParameters>,
+    pub params: Vec<Param>,
+    pub return_type: Option<TsTypeAnnotation>,
+    pub body: Option<FunctionBody>,
+    pub is_async: bool,
+    pub is_generator: bool,
+    pub declare: bool,
+    pub span: Span,
+}
+
+// modifiers are only set on constructor parameter properties, `constructor(private a)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    pub decorators: Vec<Decorator>,
+    pub modifiers: TsModifiers,
+    pub pattern: Pattern,
+    pub optional: bool,
+    pub type_annotation: Option<TsTypeAnnotation>,
+    pub span: Span,
+}
+
+// The statements between the braces, directives come first
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionBody {
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrowFunction {
+    pub type_parameters: Option<TsTypeParameters>,
+    pub params: Vec<Param>,
+    pub return_type: Option<TsTypeAnnotation>,
+    pub body: ArrowBody,
+    pub is_async: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArrowBody {
+    Expression(Expression), // `x => x * 2`
+    Block(FunctionBody),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Class {
+    pub decorators: Vec<Decorator>,
+    pub id: Option<Identifier>,
+    pub type_parameters: Option<TsTypeParameters>,
+    pub super_class: Option<Expression>,
+    pub super_type_arguments: Option<TsTypeArguments>,
+    pub implements: Vec<TsHeritage>,
+    pub is_abstract: bool,
+    pub declare: bool,
+    pub body: ClassBody,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassBody {
+    pub body: Vec<ClassMember>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClassMember {
+    Method(MethodDefinition),
+    Property(PropertyDefinition),
+    StaticBlock(StaticBlock),
+    IndexSignature(TsIndexSignature), // `[key: string]: number;`
+    Error(Span),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MethodKind {
+    Constructor,
+    Method,
+    Get,
+    Set,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MethodDefinition {
+    pub decorators: Vec<Decorator>,
+    pub modifiers: TsModifiers,
+    pub key: PropertyKey,
+    pub value: Function,
+    pub kind: MethodKind,
+    pub is_static: bool,
+    pub computed: bool,
+    pub optional: bool,
+    pub span: Span,
+}
+
+// Class field, `static #count = 0;` or `private readonly a?: string;`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyDefinition {
+    pub decorators: Vec<Decorator>,
+    pub modifiers: TsModifiers,
+    pub key: PropertyKey,
+    pub type_annotation: Option<TsTypeAnnotation>,
+    pub value: Option<Expression>,
+    pub is_static: bool,
+    pub accessor: bool, // `accessor count = 0`, a getter and setter pair over a private field
+    pub computed: bool,
+    pub optional: bool,
+    pub definite: bool,
+    pub span: Span,
+}
+
+// `static { ... }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaticBlock {
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+// `@name`, `@a.b(c)` or `@(expression)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decorator {
+    pub expression: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportDeclaration {
+    pub type_only: bool, // `import type { A } from "a"`
+    pub specifiers: Vec<ImportSpecifier>,
+    pub source: StringLiteral,
+    pub attributes: Vec<ImportAttribute>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportSpecifier {
+    Default(Identifier),   // `import a from`
+    Namespace(Identifier), // `import * as a from`, span without the `* as`
+    Named(NamedImport),
+}
+
+// `{ imported as local }`, both are the same name without `as`
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedImport {
+    pub imported: ModuleExportName,
+    pub local: Identifier,
+    pub type_only: bool,
+    pub span: Span,
+}
+
+// Names in import and export lists can be strings, `export { a as "a-b" }`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModuleExportName {
+    Identifier(Identifier),
+    String(StringLiteral),
+}
+
+// `with { type: "json" }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportAttribute {
+    pub key: ModuleExportName,
+    pub value: StringLiteral,
+    pub span: Span,
+}
+
+/**
+ * `export const a = 1`, `export { a as b }` and `export { a } from "b"`
+ *
+ * declaration and specifiers are never both set, source only comes with specifiers.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportNamedDeclaration {
+    pub type_only: bool,
+    pub declaration: Option<Statement>,
+    pub specifiers: Vec<ExportSpecifier>,
+    pub source: Option<StringLiteral>,
+    pub attributes: Vec<ImportAttribute>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportSpecifier {
+    pub local: ModuleExportName,
+    pub exported: ModuleExportName,
+    pub type_only: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportDefaultDeclaration {
+    pub declaration: ExportDefault,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportDefault {
+    Function(Function),
+    Class(Class),
+    TsInterface(TsInterfaceDeclaration),
+    Expression(Expression),
+}
+
+// `export * from "a"` and `export * as b from "a"`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportAllDeclaration {
+    pub exported: Option<ModuleExportName>,
+    pub source: StringLiteral,
+    pub attributes: Vec<ImportAttribute>,
+    pub span: Span,
+}
+
+/**
+ * Modifiers TypeScript puts in front of class members and parameter properties
+ *
+ * `static` isn't one of them, JavaScript has it too.
+ */
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TsModifiers {
+    pub accessibility: Option<Accessibility>,
+    pub is_abstract: bool,
+    pub is_override: bool,
+    pub readonly: bool,
+    pub declare: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Accessibility {
+    Public,
+    Protected,
+    Private,
+}
+
+// `: type`, the span starts at the colon (at `=>` for function types)
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeAnnotation {
+    pub type_annotation: TsType,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TsType {
+    Keyword(TsKeywordType),
+    This(Span),
+    Reference(Box<TsTypeReference>),
+    Literal(Box<TsLiteralType>),
+    TemplateLiteral(Box<TsTemplateLiteralType>),
+    Array(Box<TsArrayType>),
+    Tuple(Box<TsTupleType>),
+    Union(Box<TsUnionType>),
+    Intersection(Box<TsIntersectionType>),
+    Function(Box<TsFunctionType>),
+    TypeLiteral(Box<TsTypeLiteral>),
+    Mapped(Box<TsMappedType>),
+    Conditional(Box<TsConditionalType>),
+    Infer(Box<TsInferType>),
+    Operator(Box<TsTypeOperator>),
+    IndexedAccess(Box<TsIndexedAccessType>),
+    Query(Box<TsTypeQuery>),
+    Import(Box<TsImportType>),
+    Predicate(Box<TsTypePredicate>), // only as a return type
+}
+
+// `string`, `void` and the other built in names
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TsKeywordType {
+    pub kind: TsKeywordKind,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TsKeywordKind {
+    Any,
+    Unknown,
+    Number,
+    BigInt,
+    Boolean,
+    String,
+    Symbol,
+    Object,
+    Never,
+    Void,
+    Undefined,
+    Null,
+}
+
+// `A` or `A.B.C`
+#[derive(Clone, Debug, PartialEq)]
+pub enum TsEntityName {
+    Identifier(Identifier),
+    Qualified(Box<TsQualifiedName>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsQualifiedName {
+    pub left: TsEntityName,
+    pub right: Identifier,
+    pub span: Span,
+}
+
+// `Map<K, V>`, also `as const` with `const` as the name
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeReference {
+    pub type_name: TsEntityName,
+    pub type_arguments: Option<TsTypeArguments>,
+    pub span: Span,
+}
+
+// literal is a string, number, boolean, `-1` or a template without substitutions
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsLiteralType {
+    pub literal: Expression,
+    pub span: Span,
+}
+
+// `` `on${Capitalize<T>}` ``
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTemplateLiteralType {
+    pub quasis: Vec<TemplateElement>,
+    pub types: Vec<TsType>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsArrayType {
+    pub element_type: TsType,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTupleType {
+    pub element_types: Vec<TsTupleElement>,
+    pub span: Span,
+}
+
+// `T`, `name: T`, `name?: T`, `T?` or `...T[]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTupleElement {
+    pub label: Option<Identifier>,
+    pub element_type: TsType,
+    pub optional: bool,
+    pub rest: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsUnionType {
+    pub types: Vec<TsType>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsIntersectionType {
+    pub types: Vec<TsType>,
+    pub span: Span,
+}
+
+// `(a: A) => B`, `new (a: A) => B` sets is_constructor
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsFunctionType {
+    pub type_parameters: Option<TsTypeParameters>,
+    pub params: Vec<Param>,
+    pub return_type: TsTypeAnnotation,
+    pub is_constructor: bool,
+    pub is_abstract: bool,
+    pub span: Span,
+}
+
+// `{ a: string; b(): void }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeLiteral {
+    pub members: Vec<TsTypeMember>,
+    pub span: Span,
+}
+
+/**
+ * `{ readonly [K in keyof T as Name<K>]?: T[K] }`
+ *
+ * The modifiers are None when absent, `+`, `-` or plain otherwise.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsMappedType {
+    pub key: Identifier,
+    pub constraint: TsType,
+    pub name_type: Option<TsType>,
+    pub optional: Option<TsMappedModifier>,
+    pub readonly: Option<TsMappedModifier>,
+    pub type_annotation: Option<TsType>,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TsMappedModifier {
+    Plus,
+    Minus,
+    True,
+}
+
+// `T extends U ? X : Y`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsConditionalType {
+    pub check_type: TsType,
+    pub extends_type: TsType,
+    pub true_type: TsType,
+    pub false_type: TsType,
+    pub span: Span,
+}
+
+// `infer U extends string` inside the extends of a conditional type
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsInferType {
+    pub type_parameter: TsTypeParameter,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeOperator {
+    pub operator: TsTypeOperatorKind,
+    pub type_annotation: TsType,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TsTypeOperatorKind {
+    Keyof,
+    Unique,
+    Readonly,
+}
+
+// `T["key"]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsIndexedAccessType {
+    pub object_type: TsType,
+    pub index_type: TsType,
+    pub span: Span,
+}
+
+// `typeof a.b` or `typeof import("a")`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeQuery {
+    pub expr_name: TsTypeQueryName,
+    pub type_arguments: Option<TsTypeArguments>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TsTypeQueryName {
+    Entity(TsEntityName),
+    Import(TsImportType),
+}
+
+// `import("./a").B<C>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsImportType {
+    pub argument: StringLiteral,
+    pub qualifier: Option<TsEntityName>,
+    pub type_arguments: Option<TsTypeArguments>,
+    pub span: Span,
+}
+
+// `value is string`, `asserts value` or `asserts this is T`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypePredicate {
+    pub asserts: bool,
+    pub parameter_name: TsPredicateName,
+    pub type_annotation: Option<TsType>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TsPredicateName {
+    Identifier(Identifier),
+    This(Span),
+}
+
+// `<in out T extends U = V>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeParameter {
+    pub name: Identifier,
+    pub constraint: Option<TsType>,
+    pub default: Option<TsType>,
+    pub is_in: bool,
+    pub is_out: bool,
+    pub is_const: bool,
+    pub span: Span,
+}
+
+// Type parameters a declaration introduces, `function f<T>()`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeParameters {
+    pub params: Vec<TsTypeParameter>,
+    pub span: Span,
+}
+
+// Type arguments a use passes, `f<string>()` and `Map<K, V>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeArguments {
+    pub params: Vec<TsType>,
+    pub span: Span,
+}
+
+// Members of interfaces and type literals
+#[derive(Clone, Debug, PartialEq)]
+pub enum TsTypeMember {
+    Property(TsPropertySignature),
+    Method(TsMethodSignature),
+    Call(TsSignature),      // `(a: A): B`
+    Construct(TsSignature), // `new (a: A): B`
+    Index(TsIndexSignature),
+    Error(Span),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsPropertySignature {
+    pub key: PropertyKey,
+    pub computed: bool,
+    pub optional: bool,
+    pub readonly: bool,
+    pub type_annotation: Option<TsTypeAnnotation>,
+    pub span: Span,
+}
+
+// kind tells methods from `get a(): T` and `set a(v: T)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsMethodSignature {
+    pub key: PropertyKey,
+    pub computed: bool,
+    pub optional: bool,
+    pub kind: PropertyKind,
+    pub type_parameters: Option<TsTypeParameters>,
+    pub params: Vec<Param>,
+    pub return_type: Option<TsTypeAnnotation>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsSignature {
+    pub type_parameters: Option<TsTypeParameters>,
+    pub params: Vec<Param>,
+    pub return_type: Option<TsTypeAnnotation>,
+    pub span: Span,
+}
+
+// `[key: string]: T`, in type members and class bodies
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsIndexSignature {
+    pub parameters: Vec<Param>,
+    pub type_annotation: Option<TsTypeAnnotation>,
+    pub readonly: bool,
+    pub is_static: bool,
+    pub span: Span,
+}
+
+// `value as T` and `value satisfies T`, `<T>value` shares the node
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsAsExpression {
+    pub expression: Expression,
+    pub type_annotation: TsType,
+    pub span: Span,
+}
+
+// `value!`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsNonNullExpression {
+    pub expression: Expression,
+    pub span: Span,
+}
+
+// `interface A<T> extends B, C<T> { ... }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsInterfaceDeclaration {
+    pub id: Identifier,
+    pub type_parameters: Option<TsTypeParameters>,
+    pub extends: Vec<TsHeritage>,
+    pub body: TsInterfaceBody,
+    pub declare: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsInterfaceBody {
+    pub body: Vec<TsTypeMember>,
+    pub span: Span,
+}
+
+// An entry of `extends` on interfaces and `implements` on classes, `B.C<T>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsHeritage {
+    pub expression: Expression,
+    pub type_arguments: Option<TsTypeArguments>,
+    pub span: Span,
+}
+
+// `type A<T> = B;`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsTypeAliasDeclaration {
+    pub id: Identifier,
+    pub type_parameters: Option<TsTypeParameters>,
+    pub type_annotation: TsType,
+    pub declare: bool,
+    pub span: Span,
+}
+
+// `const enum A { B = 1, C }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsEnumDeclaration {
+    pub id: Identifier,
+    pub members: Vec<TsEnumMember>,
+    pub is_const: bool,
+    pub declare: bool,
+    pub span: Span,
+}
+
+// id is an identifier or a string, `{ "a-b" = 1 }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsEnumMember {
+    pub id: PropertyKey,
+    pub initializer: Option<Expression>,
+    pub span: Span,
+}
+
+/**
+ * `namespace A.B { }`, `module "a" { }` and `declare global { }`
+ *
+ * body is None for a shorthand ambient module, `declare module "a";`.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsModuleDeclaration {
+    pub id: TsModuleName,
+    pub body: Option<TsModuleBlock>,
+    pub kind: TsModuleKind,
+    pub declare: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TsModuleName {
+    Entity(TsEntityName),
+    String(StringLiteral),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TsModuleKind {
+    Module,
+    Namespace,
+    Global,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsModuleBlock {
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+/**
+ * `<a b="c">children</a>`, closing_element is None for `<a />`
+ *
+ * Only read in the Jsx and Tsx dialects, where the lexer tells elements from `<`.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxElement {
+    pub opening_element: JsxOpeningElement,
+    pub children: Vec<JsxChild>,
+    pub closing_element: Option<JsxClosingElement>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxOpeningElement {
+    pub name: JsxElementName,
+    pub attributes: Vec<JsxAttributeItem>,
+    pub self_closing: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxClosingElement {
+    pub name: JsxElementName,
+    pub span: Span,
+}
+
+// `<>children</>`, the spans of `<>` and `</>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxFragment {
+    pub opening_fragment: Span,
+    pub children: Vec<JsxChild>,
+    pub closing_fragment: Span,
+    pub span: Span,
+}
+
+// `div`, `svg:rect` or `Foo.Bar`, names can contain `-`
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxElementName {
+    Identifier(Identifier),
+    Namespaced(JsxNamespacedName),
+    Member(Box<JsxMemberExpression>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxNamespacedName {
+    pub namespace: Identifier,
+    pub name: Identifier,
+    pub span: Span,
+}
+
+// object is an identifier or another member, `A.B.C`
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxMemberExpression {
+    pub object: JsxElementName,
+    pub property: Identifier,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxAttributeItem {
+    Attribute(JsxAttribute),
+    Spread(SpreadElement), // `{...props}`
+}
+
+// value is None for a bare `disabled`
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxAttribute {
+    pub name: JsxAttributeName,
+    pub value: Option<JsxAttributeValue>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxAttributeName {
+    Identifier(Identifier),
+    Namespaced(JsxNamespacedName),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxAttributeValue {
+    String(StringLiteral), // entities decoded in value, raw as written
+    ExpressionContainer(JsxExpressionContainer),
+    Element(Box<JsxElement>),
+    Fragment(Box<JsxFragment>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxChild {
+    Text(JsxText),
+    Element(Box<JsxElement>),
+    Fragment(Box<JsxFragment>),
+    ExpressionContainer(JsxExpressionContainer),
+    Spread(SpreadElement), // `{...children}`
+}
+
+// `{expression}`, expression is None for `{}` and `{/* comment */}`
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxExpressionContainer {
+    pub expression: Option<Expression>,
+    pub span: Span,
+}
+
+// Text between tags, value has the entities decoded and raw is the source
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxText {
+    pub value: String,
+    pub raw: String,
+    pub span: Span,
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::Block(node) => node.span,
+            Statement::Variable(node) => node.span,
+            Statement::Function(node) => node.span,
+            Statement::Class(node) => node.span,
+            Statement::Empty(span) | Statement::Debugger(span) | Statement::Error(span) => *span,
+            Statement::Expression(node) => node.span,
+            Statement::If(node) => node.span,
+            Statement::DoWhile(node) => node.span,
+            Statement::While(node) => node.span,
+            Statement::For(node) => node.span,
+            Statement::ForIn(node) => node.span,
+            Statement::ForOf(node) => node.span,
+            Statement::Continue(node) | Statement::Break(node) => node.span,
+            Statement::Return(node) => node.span,
+            Statement::With(node) => node.span,
+            Statement::Switch(node) => node.span,
+            Statement::Labeled(node) => node.span,
+            Statement::Throw(node) => node.span,
+            Statement::Try(node) => node.span,
+            Statement::Import(node) => node.span,
+            Statement::ExportNamed(node) => node.span,
+            Statement::ExportDefault(node) => node.span,
+            Statement::ExportAll(node) => node.span,
+            Statement::TsInterface(node) => node.span,
+            Statement::TsTypeAlias(node) => node.span,
+            Statement::TsEnum(node) => node.span,
+            Statement::TsModule(node) => node.span,
+        }
+    }
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Identifier(node) => node.span,
+            Expression::PrivateName(node) => node.span,
+            Expression::This(span) | Expression::Super(span) | Expression::Null(span) => *span,
+            Expression::Boolean(node) => node.span,
+            Expression::Number(node) => node.span,
+            Expression::String(node) => node.span,
+            Expression::RegExp(node) => node.span,
+            Expression::Template(node) => node.span,
+            Expression::TaggedTemplate(node) => node.span,
+            Expression::Array(node) => node.span,
+            Expression::Object(node) => node.span,
+            Expression::Function(node) => node.span,
+            Expression::Arrow(node) => node.span,
+            Expression::Class(node) => node.span,
+            Expression::Unary(node) => node.span,
+            Expression::Update(node) => node.span,
+            Expression::Binary(node) => node.span,
+            Expression::Logical(node) => node.span,
+            Expression::Conditional(node) => node.span,
+            Expression::Assignment(node) => node.span,
+            Expression::Sequence(node) => node.span,
+            Expression::Member(node) => node.span,
+            Expression::Call(node) => node.span,
+            Expression::New(node) => node.span,
+            Expression::Chain(node) => node.span,
+            Expression::Yield(node) => node.span,
+            Expression::Await(node) => node.span,
+            Expression::MetaProperty(node) => node.span,
+            Expression::Import(node) => node.span,
+            Expression::TsAs(node)
+            | Expression::TsSatisfies(node)
+            | Expression::TsTypeAssertion(node) => node.span,
+            Expression::TsNonNull(node) => node.span,
+            Expression::JsxElement(node) => node.span,
+            Expression::JsxFragment(node) => node.span,
+        }
+    }
+}
+
+impl JsxElementName {
+    pub fn span(&self) -> Span {
+        match self {
+            JsxElementName::Identifier(node) => node.span,
+            JsxElementName::Namespaced(node) => node.span,
+            JsxElementName::Member(node) => node.span,
+        }
+    }
+}
+
+impl ExpressionOrSpread {
+    pub fn span(&self) -> Span {
+        match self {
+            ExpressionOrSpread::Expression(expression) => expression.span(),
+            ExpressionOrSpread::Spread(spread) => spread.span,
+        }
+    }
+}
+
+impl PropertyKey {
+    pub fn span(&self) -> Span {
+        match self {
+            PropertyKey::Identifier(node) => node.span,
+            PropertyKey::PrivateName(node) => node.span,
+            PropertyKey::String(node) => node.span,
+            PropertyKey::Number(node) => node.span,
+            PropertyKey::Computed(expression) => expression.span(),
+        }
+    }
+
+    // Name of a key that isn't computed, `a`, `"a"` and `#a` are all `a`
+    pub fn static_name(&self) -> Option<String> {
+        match self {
+            PropertyKey::Identifier(node) => Some(node.name.to_string()),
+            PropertyKey::PrivateName(node) => Some(node.name.to_string()),
+            PropertyKey::String(node) => Some(node.value.clone()),
+            PropertyKey::Number(node) => Some(node.raw.clone()),
+            PropertyKey::Computed(_) => None,
+        }
+    }
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Pattern::Identifier(node) => node.span,
+            Pattern::Object(node) => node.span,
+            Pattern::Array(node) => node.span,
+            Pattern::Rest(node) => node.span,
+            Pattern::Assignment(node) => node.span,
+            Pattern::Member(node) => node.span,
+        }
+    }
+
+    /**
+     * Names the pattern binds, in source order
+     *
+     * `{ a, b: [c = 1, ...d] }` binds a, c and d. Members don't bind anything.
+     */
+    pub fn bound_names(&self) -> Vec<&Identifier> {
+        let mut names = Vec::new();
+        self.collect_bound_names(&mut names);
+        names
+    }
+
+    fn collect_bound_names<'a>(&'a self, names: &mut Vec<&'a Identifier>) {
+        match self {
+            Pattern::Identifier(identifier) => names.push(identifier),
+            Pattern::Object(object) => {
+                for member in &object.properties {
+                    match member {
+                        ObjectPatternMember::Property(property) => {
+                            property.value.collect_bound_names(names)
+                        }
+                        ObjectPatternMember::Rest(rest) => rest.argument.collect_bound_names(names),
+                    }
+                }
+            }
+            Pattern::Array(array) => {
+                for element in array.elements.iter().flatten() {
+                    element.collect_bound_names(names);
+                }
+            }
+            Pattern::Rest(rest) => rest.argument.collect_bound_names(names),
+            Pattern::Assignment(assignment) => assignment.left.collect_bound_names(names),
+            Pattern::Member(_) => {}
+        }
+    }
+}
+
+impl ClassMember {
+    pub fn span(&self) -> Span {
+        match self {
+            ClassMember::Method(node) => node.span,
+            ClassMember::Property(node) => node.span,
+            ClassMember::StaticBlock(node) => node.span,
+            ClassMember::IndexSignature(node) => node.span,
+            ClassMember::Error(span) => *span,
+        }
+    }
+}
+
+impl ModuleExportName {
+    pub fn span(&self) -> Span {
+        match self {
+            ModuleExportName::Identifier(node) => node.span,
+            ModuleExportName::String(node) => node.span,
+        }
+    }
+}
+
+impl TsType {
+    pub fn span(&self) -> Span {
+        match self {
+            TsType::Keyword(node) => node.span,
+            TsType::This(span) => *span,
+            TsType::Reference(node) => node.span,
+            TsType::Literal(node) => node.span,
+            TsType::TemplateLiteral(node) => node.span,
+            TsType::Array(node) => node.span,
+            TsType::Tuple(node) => node.span,
+            TsType::Union(node) => node.span,
+            TsType::Intersection(node) => node.span,
+            TsType::Function(node) => node.span,
+            TsType::TypeLiteral(node) => node.span,
+            TsType::Mapped(node) => node.span,
+            TsType::Conditional(node) => node.span,
+            TsType::Infer(node) => node.span,
+            TsType::Operator(node) => node.span,
+            TsType::IndexedAccess(node) => node.span,
+            TsType::Query(node) => node.span,
+            TsType::Import(node) => node.span,
+            TsType::Predicate(node) => node.span,
+        }
+    }
+}
+
+impl TsEntityName {
+    pub fn span(&self) -> Span {
+        match self {
+            TsEntityName::Identifier(node) => node.span,
+            TsEntityName::Qualified(node) => node.span,
+        }
+    }
+}
@@ -0,0 +1,453 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+
+use crate::error::errors::Errors;
+use crate::read::interner::Symbol;
+use crate::read::tokenizer::{Identifier as Keyword, Literal, Span, TokenKind};
+
+use super::ast::{
+    Accessibility, Class, ClassBody, ClassMember, Decorator, MethodDefinition, MethodKind,
+    PrivateName, PropertyDefinition, PropertyKey, PropertyKind, StaticBlock, TsModifiers,
+};
+use super::parser::{Boundary, Context, Parser};
+use super::visit::{walk_class_body, Ancestors, Visit};
+
+impl<'a> Parser<'a> {
+    /**
+     * `class Name extends Base { members }`, the decorators in front were already read
+     *
+     * Class bodies and heritage are always strict mode code. TypeScript's `abstract class`
+     * is read here too.
+     *
+     * @args declaration: bool - a declaration needs a name, an expression doesn't
+     */
+    pub(super) fn parse_class(
+        &mut self,
+        decorators: Vec<Decorator>,
+        declaration: bool,
+    ) -> Result<Class, Errors> {
+        let start = decorators
+            .first()
+            .map_or(self.current().span, |decorator| decorator.span);
+        let is_abstract = self.at_abstract_class();
+        if is_abstract {
+            self.advance();
+        }
+        self.expect_keyword(Keyword::CLASS, "Expected class")?;
+
+        let strict = std::mem::replace(&mut self.context.strict, true);
+        let class = self.parse_class_rest(declaration);
+        self.context.strict = strict;
+
+        Ok(Class {
+            decorators,
+            is_abstract,
+            span: self.finish(start),
+            ..class?
+        })
+    }
+
+    // `abstract class`, on one line
+    pub(super) fn at_abstract_class(&self) -> bool {
+        self.typescript()
+            && self.at_word("abstract")
+            && matches!(self.peek(1).kind, TokenKind::Identifier(Keyword::CLASS))
+            && !self.peek(1).newline_before
+    }
+
+    // Name, type parameters, heritage and body, parsed in strict mode
+    fn parse_class_rest(&mut self, declaration: bool) -> Result<Class, Errors> {
+        let start = self.current().span;
+        let id = if self.at_identifier()
+            && !self.at_keyword(Keyword::EXTENDS)
+            && !self.at_keyword(Keyword::STRICT_IMPLEMENTS)
+        {
+            Some(self.parse_binding_identifier()?)
+        } else if declaration && !self.at(Literal::CURLY_BRACKET_LEFT) {
+            return Err(self.error("Expected a class name"));
+        } else {
+            None
+        };
+        let type_parameters = self.parse_type_parameters_opt()?;
+
+        let (super_class, super_type_arguments) = if self.eat_keyword(Keyword::EXTENDS) {
+            let super_class = self.parse_left_hand_side()?;
+            let type_arguments = if self.typescript() {
+                self.parse_type_arguments_opt()?
+            } else {
+                None
+            };
+            (Some(super_class), type_arguments)
+        } else {
+            (None, None)
+        };
+        let implements = if self.typescript() && self.eat_keyword(Keyword::STRICT_IMPLEMENTS) {
+            self.parse_heritage_list()?
+        } else {
+            Vec::new()
+        };
+
+        let body = self.parse_class_body()?;
+        // The outermost class checks the names used in all of them
+        if self.class_bodies == 0 {
+            if let Some(span) = undeclared_private_name(&body) {
+                return Err(Errors::ParseError("Undeclared private name", span));
+            }
+        }
+        Ok(Class {
+            decorators: Vec::new(),
+            id,
+            type_parameters,
+            super_class,
+            super_type_arguments,
+            implements,
+            is_abstract: false,
+            declare: false,
+            body,
+            span: self.finish(start),
+        })
+    }
+
+    // `{ members }`, two members can't declare the same private name unless a getter and setter
+    fn parse_class_body(&mut self) -> Result<ClassBody, Errors> {
+        self.class_bodies += 1;
+        let body = self.parse_class_members();
+        self.class_bodies -= 1;
+
+        let body = body?;
+        if let Some(span) = duplicate_private_name(&body) {
+            return Err(Errors::ParseError("Duplicate private name", span));
+        }
+        Ok(body)
+    }
+
+    fn parse_class_members(&mut self) -> Result<ClassBody, Errors> {
+        let start = self.expect(Literal::CURLY_BRACKET_LEFT, "Expected {")?;
+        let mut body = Vec::new();
+        let mut has_constructor = false;
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            if self.at_end() {
+                self.expect_closing_brace()?;
+                break;
+            }
+            if self.eat(Literal::SEMICOLON) {
+                continue;
+            }
+
+            let member = self.recover(
+                Boundary::ClassMember,
+                Self::parse_class_member,
+                ClassMember::Error,
+            )?;
+            if let ClassMember::Method(method) = &member {
+                // TypeScript overloads the constructor with signatures that have no body
+                if method.kind == MethodKind::Constructor && method.value.body.is_some() {
+                    if has_constructor {
+                        return Err(Errors::ParseError(
+                            "A class can only have one constructor",
+                            method.span,
+                        ));
+                    }
+                    has_constructor = true;
+                }
+            }
+            body.push(member);
+        }
+
+        Ok(ClassBody {
+            body,
+            span: self.finish(start),
+        })
+    }
+
+    fn parse_class_member(&mut self) -> Result<ClassMember, Errors> {
+        let start = self.current().span;
+        let decorators = self.parse_decorators()?;
+
+        let (ts_modifiers, is_static) = self.parse_member_modifiers();
+        if is_static
+            && decorators.is_empty()
+            && ts_modifiers == TsModifiers::default()
+            && self.at(Literal::CURLY_BRACKET_LEFT)
+        {
+            return self.parse_static_block(start);
+        }
+        let start = decorators.first().map_or(start, |decorator| decorator.span);
+
+        // `[key: string]: T`, TypeScript only
+        if self.typescript() && self.at_index_signature() {
+            let signature = self.parse_index_signature(start, ts_modifiers.readonly, is_static)?;
+            self.consume_semicolon()?;
+            return Ok(ClassMember::IndexSignature(signature));
+        }
+
+        // `accessor` is a name when the member ends after it, `accessor = 1` or `accessor() {}`
+        let accessor =
+            self.at_word("accessor") && !self.ends_member_name(1) && !self.peek(1).newline_before;
+        if accessor {
+            self.advance();
+        }
+
+        let modifiers = self.parse_method_modifiers();
+        let key = self.parse_property_key(true)?;
+        let computed = matches!(key, PropertyKey::Computed(_));
+        let named_constructor = !is_static && is_constructor_key(&key);
+        let optional = self.typescript() && self.eat(Literal::QUESTION_MARK);
+        let definite = self.typescript()
+            && !optional
+            && !self.current().newline_before
+            && self.eat(Literal::EXCLAMATION_MARK);
+
+        let generic = self.typescript() && self.at(Literal::LESS_THAN);
+        if self.at(Literal::OPEN_BRACKET_LEFT) || generic || modifiers.is_method() {
+            if accessor {
+                return Err(Errors::ParseError(
+                    "An accessor field can't be a method",
+                    key.span(),
+                ));
+            }
+            if named_constructor && modifiers.is_method() {
+                return Err(Errors::ParseError(
+                    "A constructor can't be a generator, async or an accessor",
+                    key.span(),
+                ));
+            }
+            let value = self.parse_method(modifiers.is_async, modifiers.is_generator, true)?;
+            self.check_accessor_params(modifiers.kind, &value)?;
+
+            let kind = match modifiers.kind {
+                _ if named_constructor => MethodKind::Constructor,
+                PropertyKind::Init => MethodKind::Method,
+                PropertyKind::Get => MethodKind::Get,
+                PropertyKind::Set => MethodKind::Set,
+            };
+            return Ok(ClassMember::Method(MethodDefinition {
+                decorators,
+                modifiers: ts_modifiers,
+                key,
+                value,
+                kind,
+                is_static,
+                computed,
+                optional,
+                span: self.finish(start),
+            }));
+        }
+
+        if named_constructor
+            || matches!(&key, PropertyKey::PrivateName(name) if name.name == "constructor")
+        {
+            return Err(Errors::ParseError(
+                "A class field can't be named constructor",
+                key.span(),
+            ));
+        }
+
+        // Initialisers run like methods, `arguments` and `await` aren't theirs to use
+        let type_annotation = self.parse_type_annotation_opt()?;
+        let value = if self.eat(Literal::EQUAL) {
+            Some(self.in_function(false, false, |parser| parser.parse_assignment())?)
+        } else {
+            None
+        };
+        self.consume_semicolon()?;
+
+        Ok(ClassMember::Property(PropertyDefinition {
+            decorators,
+            modifiers: ts_modifiers,
+            key,
+            type_annotation,
+            value,
+            is_static,
+            accessor,
+            computed,
+            optional,
+            definite,
+            span: self.finish(start),
+        }))
+    }
+
+    /**
+     * `static` and TypeScript's `public`, `abstract`, `override`, `readonly` and `declare`
+     *
+     * Each is a modifier unless it is the name itself, `static() {}` or `readonly: boolean`.
+     * TypeScript's have to share a line with what follows them.
+     *
+     * @returns the TypeScript modifiers and whether the member is static
+     */
+    fn parse_member_modifiers(&mut self) -> (TsModifiers, bool) {
+        let mut modifiers = TsModifiers::default();
+        let mut is_static = false;
+
+        while !self.ends_member_name(1) {
+            if self.at_word("static") && !is_static {
+                is_static = true;
+                self.advance();
+                continue;
+            }
+            if !self.typescript() || self.peek(1).newline_before {
+                break;
+            }
+            match self.kind() {
+                TokenKind::Identifier(Keyword::STRICT_PUBLIC) => {
+                    modifiers.accessibility = Some(Accessibility::Public)
+                }
+                TokenKind::Identifier(Keyword::STRICT_PROTECTED) => {
+                    modifiers.accessibility = Some(Accessibility::Protected)
+                }
+                TokenKind::Identifier(Keyword::STRICT_PRIVATE) => {
+                    modifiers.accessibility = Some(Accessibility::Private)
+                }
+                _ if self.at_word("abstract") => modifiers.is_abstract = true,
+                _ if self.at_word("override") => modifiers.is_override = true,
+                _ if self.at_word("readonly") => modifiers.readonly = true,
+                _ if self.at_word("declare") => modifiers.declare = true,
+                _ => break,
+            }
+            self.advance();
+        }
+        (modifiers, is_static)
+    }
+
+    // Whether the token `offset` places ahead ends a member name, `(`, `=`, `;`, `}` or
+    // what TypeScript puts after one
+    fn ends_member_name(&self, offset: usize) -> bool {
+        let next = self.peek(offset);
+        matches!(
+            next.kind,
+            TokenKind::Literal(
+                Literal::OPEN_BRACKET_LEFT
+                    | Literal::EQUAL
+                    | Literal::SEMICOLON
+                    | Literal::CURLY_BRACKET_RIGHT
+                    | Literal::COLON
+                    | Literal::QUESTION_MARK
+                    | Literal::EXCLAMATION_MARK
+                    | Literal::LESS_THAN
+            ) | TokenKind::EndOfFile
+        )
+    }
+
+    // `#name` after a `.` or before `in`, only usable inside a class body
+    pub(super) fn parse_private_name_reference(&mut self) -> Result<PrivateName, Errors> {
+        let TokenKind::PrivateName(name) = *self.kind() else {
+            return Err(self.unexpected());
+        };
+        if self.class_bodies == 0 {
+            return Err(self.error("Undeclared private name"));
+        }
+        let span = self.advance();
+        Ok(PrivateName { name, span })
+    }
+
+    // `static { ... }`, runs like a function body without parameters
+    fn parse_static_block(&mut self, start: Span) -> Result<ClassMember, Errors> {
+        let context = Context {
+            strict: true,
+            ..Context::default()
+        };
+        let outer = std::mem::replace(&mut self.context, context);
+        let block = self.parse_block();
+        self.context = outer;
+
+        Ok(ClassMember::StaticBlock(StaticBlock {
+            body: block?.body,
+            span: self.finish(start),
+        }))
+    }
+}
+
+// `constructor` or `"constructor"`, a computed key never names the constructor
+fn is_constructor_key(key: &PropertyKey) -> bool {
+    match key {
+        PropertyKey::Identifier(identifier) => identifier.name == "constructor",
+        PropertyKey::String(string) => string.value == "constructor",
+        _ => false,
+    }
+}
+
+// The private name a member declares, with the kind of accessor and whether it's static
+fn declared_private_name(member: &ClassMember) -> Option<(&PrivateName, Option<MethodKind>, bool)> {
+    match member {
+        // An overload signature declares nothing of its own
+        ClassMember::Method(method) if method.value.body.is_some() => match &method.key {
+            PropertyKey::PrivateName(name) => {
+                let accessor = matches!(method.kind, MethodKind::Get | MethodKind::Set);
+                Some((name, accessor.then_some(method.kind), method.is_static))
+            }
+            _ => None,
+        },
+        ClassMember::Property(property) => match &property.key {
+            PropertyKey::PrivateName(name) => Some((name, None, property.is_static)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Span of the first private name declared again, only a getter and setter can share one
+fn duplicate_private_name(body: &ClassBody) -> Option<Span> {
+    let mut declared: HashMap<Symbol, Vec<(Option<MethodKind>, bool)>> = HashMap::new();
+    for (name, accessor, is_static) in body.body.iter().filter_map(declared_private_name) {
+        let earlier = declared.entry(name.name).or_default();
+        let pair = match earlier[..] {
+            [] => true,
+            [(Some(MethodKind::Get), other_static)] => {
+                accessor == Some(MethodKind::Set) && other_static == is_static
+            }
+            [(Some(MethodKind::Set), other_static)] => {
+                accessor == Some(MethodKind::Get) && other_static == is_static
+            }
+            _ => false,
+        };
+        if !pair {
+            return Some(name.span);
+        }
+        earlier.push((accessor, is_static));
+    }
+    None
+}
+
+// Span of the first private name used in the class that neither it nor a class inside declares
+fn undeclared_private_name(body: &ClassBody) -> Option<Span> {
+    let mut names = PrivateNames {
+        declared: Vec::new(),
+        undeclared: None,
+    };
+    let _ = names.visit_class_body(body, &mut Ancestors::default());
+    names.undeclared
+}
+
+// Walks a class body with the private names of the class bodies around each node
+struct PrivateNames {
+    declared: Vec<HashSet<Symbol>>, // innermost last
+    undeclared: Option<Span>,
+}
+
+impl<'ast> Visit<'ast> for PrivateNames {
+    fn visit_class_body(
+        &mut self,
+        node: &'ast ClassBody,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        let declared = node.body.iter().filter_map(declared_private_name);
+        self.declared
+            .push(declared.map(|(name, _, _)| name.name).collect());
+        let flow = walk_class_body(self, node, ancestors);
+        self.declared.pop();
+        flow
+    }
+
+    fn visit_private_name(
+        &mut self,
+        node: &'ast PrivateName,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        if self.declared.iter().any(|names| names.contains(&node.name)) {
+            return ControlFlow::Continue(());
+        }
+        self.undeclared = Some(node.span);
+        ControlFlow::Break(())
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::HashSet;
+
+use crate::read::interner::Symbol;
+use crate::read::tokenizer::Span;
+
+use super::ast::{
+    ArrowBody, ArrowFunction, ExportDefault, Expression, ExpressionOrSpread, Function, Identifier,
+    MemberProperty, ObjectPatternMember, Param, Pattern, Program, Statement, TsEntityName,
+    TsPropertySignature, TsType, TsTypeMember,
+};
+
+/**
+ * A React component, a function or arrow function that returns JSX
+ *
+ * Names have to start with an uppercase letter, React reads `<row />` as an HTML tag, so
+ * `renderRow` returning JSX stays a plain helper.
+ */
+#[derive(Clone, Debug)]
+pub struct Component<'a> {
+    pub name: Option<&'a Identifier>, // None for `export default () => <a />`
+    pub function: ComponentFunction<'a>,
+    pub props: Vec<ComponentProp<'a>>,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum ComponentFunction<'a> {
+    Function(&'a Function),
+    Arrow(&'a ArrowFunction),
+}
+
+/**
+ * A prop the component reads, from its destructuring or from the type of its props
+ *
+ * rest is set for `...rest`, whose name is the binding rather than a prop.
+ */
+#[derive(Clone, Debug)]
+pub struct ComponentProp<'a> {
+    pub name: String,
+    pub type_annotation: Option<&'a TsType>,
+    pub default: Option<&'a Expression>,
+    pub optional: bool,
+    pub rest: bool,
+}
+
+/**
+ * Components declared at the top level of a .jsx or .tsx program, exported or not
+ *
+ * `memo(...)` and `forwardRef(...)` wrappers are looked through, the function passed as their
+ * first argument is the component.
+ *
+ * @returns the components in source order
+ */
+pub fn find_components(program: &Program) -> Vec<Component<'_>> {
+    let mut components = Vec::new();
+    for statement in &program.body {
+        collect_statement(program, statement, &mut components);
+    }
+    components
+}
+
+fn collect_statement<'a>(
+    program: &'a Program,
+    statement: &'a Statement,
+    components: &mut Vec<Component<'a>>,
+) {
+    match statement {
+        Statement::Function(function) => {
+            let function = ComponentFunction::Function(function);
+            components.extend(component(program, function.id(), function, None));
+        }
+        Statement::Variable(declaration) => {
+            for declarator in &declaration.declarations {
+                let (Pattern::Identifier(name), Some(init)) = (&declarator.id, &declarator.init)
+                else {
+                    continue;
+                };
+                // `const A: FC<Props> = ...` types the props through the variable
+                let props_type = declarator
+                    .type_annotation
+                    .as_ref()
+                    .and_then(|annotation| first_type_argument(&annotation.type_annotation));
+                if let Some(function) = component_function(init) {
+                    components.extend(component(program, Some(name), function, props_type));
+                }
+            }
+        }
+        Statement::ExportNamed(export) => {
+            if let Some(declaration) = &export.declaration {
+                collect_statement(program, declaration, components);
+            }
+        }
+        Statement::ExportDefault(export) => {
+            let function = match &export.declaration {
+                ExportDefault::Function(function) => Some(ComponentFunction::Function(function)),
+                ExportDefault::Expression(expression) => component_function(expression),
+                ExportDefault::Class(_) | ExportDefault::TsInterface(_) => None,
+            };
+            if let Some(function) = function {
+                components.extend(component(program, function.id(), function, None));
+            }
+        }
+        _ => {}
+    }
+}
+
+// The component unless it is named in lowercase or never returns JSX
+fn component<'a>(
+    program: &'a Program,
+    name: Option<&'a Identifier>,
+    function: ComponentFunction<'a>,
+    props_type: Option<&'a TsType>,
+) -> Option<Component<'a>> {
+    let capitalized = name.is_none_or(|name| {
+        name.name
+            .with_str(|name| name.starts_with(|character: char| character.is_uppercase()))
+    });
+    if !capitalized || !function.returns_jsx() {
+        return None;
+    }
+
+    Some(Component {
+        name,
+        function,
+        props: props(program, function.params().first(), props_type),
+        span: function.span(),
+    })
+}
+
+// A function or arrow, or one wrapped as in `memo((props) => ...)`
+fn component_function(expression: &Expression) -> Option<ComponentFunction<'_>> {
+    match expression {
+        Expression::Function(function) => Some(ComponentFunction::Function(function)),
+        Expression::Arrow(arrow) => Some(ComponentFunction::Arrow(arrow)),
+        Expression::Call(call) if is_wrapper(&call.callee) => match call.arguments.first() {
+            Some(ExpressionOrSpread::Expression(argument)) => component_function(argument),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// `memo`, `forwardRef` and the same on `React.`, not hooks like `useCallback` or `styled.div`
+fn is_wrapper(callee: &Expression) -> bool {
+    let name = match callee {
+        Expression::Identifier(identifier) => identifier,
+        Expression::Member(member) => match (&member.object, &member.property) {
+            (Expression::Identifier(object), MemberProperty::Identifier(property))
+                if object.name == "React" =>
+            {
+                property
+            }
+            _ => return false,
+        },
+        _ => return false,
+    };
+    name.name == "memo" || name.name == "forwardRef"
+}
+
+impl<'a> ComponentFunction<'a> {
+    fn id(&self) -> Option<&'a Identifier> {
+        match self {
+            ComponentFunction::Function(function) => function.id.as_ref(),
+            ComponentFunction::Arrow(_) => None,
+        }
+    }
+
+    pub fn params(&self) -> &'a [Param] {
+        match self {
+            ComponentFunction::Function(function) => &function.params,
+            ComponentFunction::Arrow(arrow) => &arrow.params,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            ComponentFunction::Function(function) => function.span,
+            ComponentFunction::Arrow(arrow) => arrow.span,
+        }
+    }
+
+    // An expression body that is JSX or a `return` of JSX outside nested functions
+    pub fn returns_jsx(&self) -> bool {
+        match self {
+            ComponentFunction::Function(function) => function
+                .body
+                .as_ref()
+                .is_some_and(|body| body.body.iter().any(returns_jsx)),
+            ComponentFunction::Arrow(arrow) => match &arrow.body {
+                ArrowBody::Expression(expression) => is_jsx(expression),
+                ArrowBody::Block(body) => body.body.iter().any(returns_jsx),
+            },
+        }
+    }
+}
+
+fn returns_jsx(statement: &Statement) -> bool {
+    match statement {
+        Statement::Return(node) => node.argument.as_ref().is_some_and(is_jsx),
+        Statement::Block(node) => node.body.iter().any(returns_jsx),
+        Statement::If(node) => {
+            returns_jsx(&node.consequent) || node.alternate.as_ref().is_some_and(returns_jsx)
+        }
+        Statement::For(node) => returns_jsx(&node.body),
+        Statement::ForIn(node) => returns_jsx(&node.body),
+        Statement::ForOf(node) => returns_jsx(&node.body),
+        Statement::While(node) => returns_jsx(&node.body),
+        Statement::DoWhile(node) => returns_jsx(&node.body),
+        Statement::Labeled(node) => returns_jsx(&node.body),
+        Statement::Switch(node) => node
+            .cases
+            .iter()
+            .any(|case| case.consequent.iter().any(returns_jsx)),
+        Statement::Try(node) => {
+            node.block.body.iter().any(returns_jsx)
+                || node
+                    .handler
+                    .as_ref()
+                    .is_some_and(|handler| handler.body.body.iter().any(returns_jsx))
+                || node
+                    .finalizer
+                    .as_ref()
+                    .is_some_and(|finalizer| finalizer.body.iter().any(returns_jsx))
+        }
+        _ => false,
+    }
+}
+
+// `<a />`, `cond ? <a /> : null`, `cond && <a />` and `<a /> as T`
+fn is_jsx(expression: &Expression) -> bool {
+    match expression {
+        Expression::JsxElement(_) | Expression::JsxFragment(_) => true,
+        Expression::Conditional(node) => is_jsx(&node.consequent) || is_jsx(&node.alternate),
+        Expression::Logical(node) => is_jsx(&node.left) || is_jsx(&node.right),
+        Expression::Sequence(node) => node.expressions.last().is_some_and(is_jsx),
+        Expression::TsAs(node) | Expression::TsSatisfies(node) => is_jsx(&node.expression),
+        Expression::TsNonNull(node) => is_jsx(&node.expression),
+        _ => false,
+    }
+}
+
+// `FC<Props>` and `React.FunctionComponent<Props>` hand the props type over
+fn first_type_argument(type_annotation: &TsType) -> Option<&TsType> {
+    match type_annotation {
+        TsType::Reference(reference) => reference
+            .type_arguments
+            .as_ref()
+            .and_then(|arguments| arguments.params.first()),
+        _ => None,
+    }
+}
+
+/**
+ * Props of the first parameter, named by its destructuring or listed by its type
+ *
+ * A parameter without either, `(props) => ...`, has no props that can be told.
+ */
+fn props<'a>(
+    program: &'a Program,
+    param: Option<&'a Param>,
+    props_type: Option<&'a TsType>,
+) -> Vec<ComponentProp<'a>> {
+    let Some(param) = param else {
+        return Vec::new();
+    };
+    let props_type = param
+        .type_annotation
+        .as_ref()
+        .map(|annotation| &annotation.type_annotation)
+        .or(props_type);
+    let mut members = Vec::new();
+    if let Some(props_type) = props_type {
+        type_members(program, props_type, &mut HashSet::new(), &mut members);
+    }
+
+    let pattern = match &param.pattern {
+        Pattern::Assignment(assignment) => &assignment.left,
+        pattern => pattern,
+    };
+    let Pattern::Object(object) = pattern else {
+        return members.iter().map(|member| member_prop(member)).collect();
+    };
+
+    let mut props = Vec::new();
+    for property in &object.properties {
+        match property {
+            ObjectPatternMember::Property(property) => {
+                let Some(name) = property.key.static_name() else {
+                    continue;
+                };
+                let default = match &property.value {
+                    Pattern::Assignment(assignment) => Some(&assignment.right),
+                    _ => None,
+                };
+                let member = members
+                    .iter()
+                    .find(|member| member.key.static_name().as_ref() == Some(&name));
+
+                props.push(ComponentProp {
+                    name,
+                    type_annotation: member.and_then(|member| {
+                        member.type_annotation.as_ref().map(|t| &t.type_annotation)
+                    }),
+                    default,
+                    optional: default.is_some() || member.is_some_and(|member| member.optional),
+                    rest: false,
+                });
+            }
+            ObjectPatternMember::Rest(rest) => {
+                if let Pattern::Identifier(identifier) = &rest.argument {
+                    props.push(ComponentProp {
+                        name: identifier.name.to_string(),
+                        type_annotation: None,
+                        default: None,
+                        optional: true,
+                        rest: true,
+                    });
+                }
+            }
+        }
+    }
+    props
+}
+
+fn member_prop<'a>(member: &'a TsPropertySignature) -> ComponentProp<'a> {
+    ComponentProp {
+        name: member.key.static_name().unwrap_or_default(),
+        type_annotation: member
+            .type_annotation
+            .as_ref()
+            .map(|annotation| &annotation.type_annotation),
+        default: None,
+        optional: member.optional,
+        rest: false,
+    }
+}
+
+/**
+ * Property signatures of a props type, following local type aliases and interfaces
+ *
+ * Types from other modules can't be seen from here and add nothing.
+ *
+ * @args expanded: &mut HashSet<Symbol> - names already followed, each adds its members once
+ */
+fn type_members<'a>(
+    program: &'a Program,
+    props_type: &'a TsType,
+    expanded: &mut HashSet<Symbol>,
+    members: &mut Vec<&'a TsPropertySignature>,
+) {
+    match props_type {
+        TsType::TypeLiteral(literal) => members.extend(property_signatures(&literal.members)),
+        TsType::Intersection(intersection) => {
+            for part in &intersection.types {
+                type_members(program, part, expanded, members);
+            }
+        }
+        TsType::Reference(reference) => {
+            if let TsEntityName::Identifier(name) = &reference.type_name {
+                named_members(program, name.name, expanded, members);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Members of a type alias or interface declared in the program, inherited ones after its own
+fn named_members<'a>(
+    program: &'a Program,
+    name: Symbol,
+    expanded: &mut HashSet<Symbol>,
+    members: &mut Vec<&'a TsPropertySignature>,
+) {
+    // Cycles like `interface A extends B` and `interface B extends A` stop here, so do bases
+    // reached twice
+    if !expanded.insert(name) {
+        return;
+    }
+    for statement in &program.body {
+        let statement = match statement {
+            Statement::ExportNamed(export) => match &export.declaration {
+                Some(declaration) => declaration,
+                None => continue,
+            },
+            statement => statement,
+        };
+        match statement {
+            Statement::TsTypeAlias(alias) if alias.id.name == name => {
+                type_members(program, &alias.type_annotation, expanded, members);
+            }
+            Statement::TsInterface(interface) if interface.id.name == name => {
+                members.extend(property_signatures(&interface.body.body));
+                for heritage in &interface.extends {
+                    if let Expression::Identifier(base) = &heritage.expression {
+                        named_members(program, base.name, expanded, members);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn property_signatures(members: &[TsTypeMember]) -> impl Iterator<Item = &TsPropertySignature> {
+    members.iter().filter_map(|member| match member {
+        TsTypeMember::Property(property) if !property.computed => Some(property),
+        _ => None,
+    })
+}
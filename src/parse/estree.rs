@@ -0,0 +1,1593 @@
+use serde_json::{json, Map, Value};
+
+use crate::read::line_index::{ColumnUnit, LineIndex};
+use crate::read::number::NumberValue;
+use crate::read::tokenizer::Span;
+
+use super::ast::{
+    Accessibility, ArrowBody, AssignmentOperator, BinaryOperator, Class, ClassMember, Decorator,
+    ExportDefault, Expression, ExpressionOrSpread, ForHead, ForInit, Function, FunctionBody,
+    Identifier, ImportAttribute, ImportSpecifier, JsxAttributeItem, JsxAttributeName,
+    JsxAttributeValue, JsxChild, JsxElement, JsxElementName, JsxExpressionContainer, JsxFragment,
+    JsxNamespacedName, LogicalOperator, MemberExpression, MemberProperty, MethodKind,
+    ModuleExportName, NumberLiteral, ObjectMember, ObjectPatternMember, Param, Pattern,
+    PrivateName, Program, PropertyKey, PropertyKind, SourceType, Statement, StringLiteral,
+    TemplateElement, TemplateLiteral, TsEntityName, TsHeritage, TsImportType, TsIndexSignature,
+    TsInterfaceDeclaration, TsKeywordKind, TsMappedModifier, TsModifiers, TsModuleKind,
+    TsModuleName, TsPredicateName, TsSignature, TsTupleElement, TsType, TsTypeAnnotation,
+    TsTypeArguments, TsTypeMember, TsTypeOperatorKind, TsTypeParameter, TsTypeParameters,
+    TsTypeQueryName, UnaryOperator, UpdateOperator, VariableDeclaration, VariableKind,
+};
+
+/**
+ * The program as ESTree JSON, TypeScript and JSX nodes named like typescript-estree names them
+ *
+ * Every node has `range`, `[start, end]`, and `loc`, `{ start: { line, column }, end }`. Offsets
+ * and columns count UTF-16 code units like JavaScript strings do, so they line up with Node
+ * tools. Fields TypeScript adds to ESTree nodes (`typeAnnotation`, `declare`, `importKind`, ...)
+ * are left out where they aren't set, plain JavaScript comes out as plain ESTree.
+ *
+ * @args program: &Program - the parsed program
+ * @args source: &[u8] - the source it was parsed from, for positions and raw regex literals
+ */
+pub fn to_estree(program: &Program, source: &[u8]) -> Value {
+    let estree = Estree {
+        source,
+        lines: LineIndex::new(source),
+    };
+    estree.program(program)
+}
+
+struct Estree<'a> {
+    source: &'a [u8],
+    lines: LineIndex,
+}
+
+// A node being built, range and loc go last once the fields are in
+struct Node {
+    fields: Map<String, Value>,
+    range: Value,
+    loc: Value,
+}
+
+impl Node {
+    fn set(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+
+    // A TypeScript field, left out when there is nothing to say
+    fn maybe(self, key: &str, value: Option<Value>) -> Self {
+        match value {
+            Some(value) => self.set(key, value),
+            None => self,
+        }
+    }
+
+    fn flag(self, key: &str, value: bool) -> Self {
+        self.maybe(key, value.then_some(Value::Bool(true)))
+    }
+
+    fn list(self, key: &str, values: Vec<Value>) -> Self {
+        self.maybe(key, (!values.is_empty()).then_some(Value::Array(values)))
+    }
+
+    fn build(mut self) -> Value {
+        self.fields.insert("range".to_string(), self.range);
+        self.fields.insert("loc".to_string(), self.loc);
+        Value::Object(self.fields)
+    }
+}
+
+impl<'a> Estree<'a> {
+    fn node(&self, kind: &str, span: Span) -> Node {
+        let (range, loc) = self.location(span.start, span.end);
+        let mut fields = Map::new();
+        fields.insert("type".to_string(), Value::from(kind));
+
+        Node { fields, range, loc }
+    }
+
+    // range and loc of start..end, spans only come with the line and column of their start
+    fn location(&self, start: usize, end: usize) -> (Value, Value) {
+        let (start, start_position) = self.position(start);
+        let (end, end_position) = self.position(end);
+        (
+            json!([start, end]),
+            json!({ "start": start_position, "end": end_position }),
+        )
+    }
+
+    fn position(&self, offset: usize) -> (usize, Value) {
+        let offset = offset.min(self.source.len());
+        let unit = ColumnUnit::Utf16;
+        let index = self.lines.offset_in(offset, unit).unwrap();
+        let position = self.lines.position(offset, unit).unwrap();
+        (
+            index,
+            json!({ "line": position.line, "column": position.column }),
+        )
+    }
+
+    fn raw(&self, span: Span) -> String {
+        String::from_utf8_lossy(&self.source[span.start..span.end]).into_owned()
+    }
+
+    fn program(&self, program: &Program) -> Value {
+        let source_type = match program.source_type {
+            SourceType::Script => "script",
+            SourceType::Module => "module",
+        };
+        self.node("Program", program.span)
+            .set("body", self.statements(&program.body))
+            .set("sourceType", source_type)
+            .build()
+    }
+
+    fn statements(&self, statements: &[Statement]) -> Vec<Value> {
+        statements
+            .iter()
+            .map(|statement| self.statement(statement))
+            .collect()
+    }
+
+    fn statement(&self, statement: &Statement) -> Value {
+        match statement {
+            Statement::Block(node) => self.block(&node.body, node.span),
+            Statement::Variable(node) => self.variable(node),
+            Statement::Function(node) => self.function_declaration(node),
+            Statement::Class(node) => self.class("ClassDeclaration", node),
+            Statement::Empty(span) => self.node("EmptyStatement", *span).build(),
+            Statement::Debugger(span) => self.node("DebuggerStatement", *span).build(),
+            Statement::Expression(node) => self
+                .node("ExpressionStatement", node.span)
+                .set("expression", self.expression(&node.expression))
+                .maybe("directive", node.directive.clone().map(Value::from))
+                .build(),
+            Statement::If(node) => self
+                .node("IfStatement", node.span)
+                .set("test", self.expression(&node.test))
+                .set("consequent", self.statement(&node.consequent))
+                .set(
+                    "alternate",
+                    node.alternate.as_ref().map(|node| self.statement(node)),
+                )
+                .build(),
+            Statement::DoWhile(node) => self
+                .node("DoWhileStatement", node.span)
+                .set("body", self.statement(&node.body))
+                .set("test", self.expression(&node.test))
+                .build(),
+            Statement::While(node) => self
+                .node("WhileStatement", node.span)
+                .set("test", self.expression(&node.test))
+                .set("body", self.statement(&node.body))
+                .build(),
+            Statement::For(node) => {
+                let init = node.init.as_ref().map(|init| match init {
+                    ForInit::Variable(declaration) => self.variable(declaration),
+                    ForInit::Expression(expression) => self.expression(expression),
+                });
+                self.node("ForStatement", node.span)
+                    .set("init", init)
+                    .set("test", self.optional_expression(node.test.as_ref()))
+                    .set("update", self.optional_expression(node.update.as_ref()))
+                    .set("body", self.statement(&node.body))
+                    .build()
+            }
+            Statement::ForIn(node) => self
+                .node("ForInStatement", node.span)
+                .set("left", self.for_head(&node.left))
+                .set("right", self.expression(&node.right))
+                .set("body", self.statement(&node.body))
+                .build(),
+            Statement::ForOf(node) => self
+                .node("ForOfStatement", node.span)
+                .set("await", node.is_await)
+                .set("left", self.for_head(&node.left))
+                .set("right", self.expression(&node.right))
+                .set("body", self.statement(&node.body))
+                .build(),
+            Statement::Continue(node) | Statement::Break(node) => {
+                let kind = match statement {
+                    Statement::Continue(_) => "ContinueStatement",
+                    _ => "BreakStatement",
+                };
+                self.node(kind, node.span)
+                    .set("label", self.optional_identifier(node.label.as_ref()))
+                    .build()
+            }
+            Statement::Return(node) => self
+                .node("ReturnStatement", node.span)
+                .set("argument", self.optional_expression(node.argument.as_ref()))
+                .build(),
+            Statement::With(node) => self
+                .node("WithStatement", node.span)
+                .set("object", self.expression(&node.object))
+                .set("body", self.statement(&node.body))
+                .build(),
+            Statement::Switch(node) => {
+                let cases = node.cases.iter().map(|case| {
+                    self.node("SwitchCase", case.span)
+                        .set("test", self.optional_expression(case.test.as_ref()))
+                        .set("consequent", self.statements(&case.consequent))
+                        .build()
+                });
+                self.node("SwitchStatement", node.span)
+                    .set("discriminant", self.expression(&node.discriminant))
+                    .set("cases", cases.collect::<Vec<_>>())
+                    .build()
+            }
+            Statement::Labeled(node) => self
+                .node("LabeledStatement", node.span)
+                .set("label", self.identifier(&node.label))
+                .set("body", self.statement(&node.body))
+                .build(),
+            Statement::Throw(node) => self
+                .node("ThrowStatement", node.span)
+                .set("argument", self.expression(&node.argument))
+                .build(),
+            Statement::Try(node) => {
+                let handler = node.handler.as_ref().map(|handler| {
+                    let param = handler
+                        .param
+                        .as_ref()
+                        .map(|param| self.binding(param, handler.type_annotation.as_ref(), false));
+                    self.node("CatchClause", handler.span)
+                        .set("param", param)
+                        .set("body", self.block(&handler.body.body, handler.body.span))
+                        .build()
+                });
+                let finalizer = node
+                    .finalizer
+                    .as_ref()
+                    .map(|finalizer| self.block(&finalizer.body, finalizer.span));
+                self.node("TryStatement", node.span)
+                    .set("block", self.block(&node.block.body, node.block.span))
+                    .set("handler", handler)
+                    .set("finalizer", finalizer)
+                    .build()
+            }
+            Statement::Import(node) => {
+                let specifiers = node.specifiers.iter().map(|specifier| match specifier {
+                    ImportSpecifier::Default(local) => self
+                        .node("ImportDefaultSpecifier", local.span)
+                        .set("local", self.identifier(local))
+                        .build(),
+                    ImportSpecifier::Namespace(local) => self
+                        .node("ImportNamespaceSpecifier", local.span)
+                        .set("local", self.identifier(local))
+                        .build(),
+                    ImportSpecifier::Named(named) => self
+                        .node("ImportSpecifier", named.span)
+                        .set("imported", self.module_export_name(&named.imported))
+                        .set("local", self.identifier(&named.local))
+                        .maybe("importKind", type_kind(named.type_only))
+                        .build(),
+                });
+                self.node("ImportDeclaration", node.span)
+                    .set("specifiers", specifiers.collect::<Vec<_>>())
+                    .set("source", self.string(&node.source))
+                    .set("attributes", self.import_attributes(&node.attributes))
+                    .maybe("importKind", type_kind(node.type_only))
+                    .build()
+            }
+            Statement::ExportNamed(node) => {
+                let specifiers = node.specifiers.iter().map(|specifier| {
+                    self.node("ExportSpecifier", specifier.span)
+                        .set("local", self.module_export_name(&specifier.local))
+                        .set("exported", self.module_export_name(&specifier.exported))
+                        .maybe("exportKind", type_kind(specifier.type_only))
+                        .build()
+                });
+                self.node("ExportNamedDeclaration", node.span)
+                    .set(
+                        "declaration",
+                        node.declaration.as_ref().map(|node| self.statement(node)),
+                    )
+                    .set("specifiers", specifiers.collect::<Vec<_>>())
+                    .set("source", node.source.as_ref().map(|node| self.string(node)))
+                    .set("attributes", self.import_attributes(&node.attributes))
+                    .maybe("exportKind", type_kind(node.type_only))
+                    .build()
+            }
+            Statement::ExportDefault(node) => {
+                let declaration = match &node.declaration {
+                    ExportDefault::Function(function) => self.function_declaration(function),
+                    ExportDefault::Class(class) => self.class("ClassDeclaration", class),
+                    ExportDefault::TsInterface(interface) => self.interface(interface),
+                    ExportDefault::Expression(expression) => self.expression(expression),
+                };
+                self.node("ExportDefaultDeclaration", node.span)
+                    .set("declaration", declaration)
+                    .build()
+            }
+            Statement::ExportAll(node) => self
+                .node("ExportAllDeclaration", node.span)
+                .set(
+                    "exported",
+                    node.exported
+                        .as_ref()
+                        .map(|name| self.module_export_name(name)),
+                )
+                .set("source", self.string(&node.source))
+                .set("attributes", self.import_attributes(&node.attributes))
+                .build(),
+            Statement::TsInterface(node) => self.interface(node),
+            Statement::TsTypeAlias(node) => self
+                .node("TSTypeAliasDeclaration", node.span)
+                .set("id", self.identifier(&node.id))
+                .maybe(
+                    "typeParameters",
+                    self.type_parameters(&node.type_parameters),
+                )
+                .set("typeAnnotation", self.ts_type(&node.type_annotation))
+                .set("declare", node.declare)
+                .build(),
+            Statement::TsEnum(node) => {
+                let members = node.members.iter().map(|member| {
+                    self.node("TSEnumMember", member.span)
+                        .set("id", self.property_key(&member.id))
+                        .maybe(
+                            "initializer",
+                            member
+                                .initializer
+                                .as_ref()
+                                .map(|initializer| self.expression(initializer)),
+                        )
+                        .build()
+                });
+                self.node("TSEnumDeclaration", node.span)
+                    .set("id", self.identifier(&node.id))
+                    .set("members", members.collect::<Vec<_>>())
+                    .set("const", node.is_const)
+                    .set("declare", node.declare)
+                    .build()
+            }
+            Statement::TsModule(node) => {
+                let id = match &node.id {
+                    TsModuleName::Entity(name) => self.entity_name(name),
+                    TsModuleName::String(name) => self.string(name),
+                };
+                let kind = match node.kind {
+                    TsModuleKind::Module => "module",
+                    TsModuleKind::Namespace => "namespace",
+                    TsModuleKind::Global => "global",
+                };
+                let body = node.body.as_ref().map(|body| {
+                    self.node("TSModuleBlock", body.span)
+                        .set("body", self.statements(&body.body))
+                        .build()
+                });
+                self.node("TSModuleDeclaration", node.span)
+                    .set("id", id)
+                    .maybe("body", body)
+                    .set("kind", kind)
+                    .set("declare", node.declare)
+                    .build()
+            }
+            // ESTree has no node for source that didn't parse, Babel's recovery drops it
+            Statement::Error(span) => self.node("Error", *span).build(),
+        }
+    }
+
+    fn block(&self, body: &[Statement], span: Span) -> Value {
+        self.node("BlockStatement", span)
+            .set("body", self.statements(body))
+            .build()
+    }
+
+    fn variable(&self, declaration: &VariableDeclaration) -> Value {
+        let kind = match declaration.kind {
+            VariableKind::Var => "var",
+            VariableKind::Let => "let",
+            VariableKind::Const => "const",
+        };
+        let declarations = declaration.declarations.iter().map(|declarator| {
+            self.node("VariableDeclarator", declarator.span)
+                .set(
+                    "id",
+                    self.binding(&declarator.id, declarator.type_annotation.as_ref(), false),
+                )
+                .set("init", self.optional_expression(declarator.init.as_ref()))
+                .flag("definite", declarator.definite)
+                .build()
+        });
+        self.node("VariableDeclaration", declaration.span)
+            .set("declarations", declarations.collect::<Vec<_>>())
+            .set("kind", kind)
+            .flag("declare", declaration.declare)
+            .build()
+    }
+
+    fn for_head(&self, head: &ForHead) -> Value {
+        match head {
+            ForHead::Variable(declaration) => self.variable(declaration),
+            ForHead::Pattern(pattern) => self.pattern(pattern),
+        }
+    }
+
+    // Overloads and `declare function` have no body and a node of their own
+    fn function_declaration(&self, function: &Function) -> Value {
+        let kind = match function.body {
+            Some(_) => "FunctionDeclaration",
+            None => "TSDeclareFunction",
+        };
+        self.function(kind, function)
+    }
+
+    fn function(&self, kind: &str, function: &Function) -> Value {
+        self.node(kind, function.span)
+            .set("id", self.optional_identifier(function.id.as_ref()))
+            .set("params", self.params(&function.params))
+            .set(
+                "body",
+                function.body.as_ref().map(|body| self.function_body(body)),
+            )
+            .set("async", function.is_async)
+            .set("generator", function.is_generator)
+            .set("expression", false)
+            .maybe(
+                "typeParameters",
+                self.type_parameters(&function.type_parameters),
+            )
+            .maybe("returnType", self.type_annotation(&function.return_type))
+            .flag("declare", function.declare)
+            .build()
+    }
+
+    fn function_body(&self, body: &FunctionBody) -> Value {
+        self.block(&body.body, body.span)
+    }
+
+    fn params(&self, params: &[Param]) -> Vec<Value> {
+        params.iter().map(|param| self.param(param)).collect()
+    }
+
+    // `constructor(private a)` wraps the parameter in a TSParameterProperty
+    fn param(&self, param: &Param) -> Value {
+        let mut binding = self.binding(
+            &param.pattern,
+            param.type_annotation.as_ref(),
+            param.optional,
+        );
+        if param.modifiers == TsModifiers::default() {
+            if let Value::Object(fields) = &mut binding {
+                if !param.decorators.is_empty() {
+                    fields.insert(
+                        "decorators".to_string(),
+                        Value::Array(self.decorators(&param.decorators)),
+                    );
+                }
+            }
+            return binding;
+        }
+
+        let node = self
+            .node("TSParameterProperty", param.span)
+            .set("parameter", binding);
+        self.modifiers(node, &param.modifiers)
+            .list("decorators", self.decorators(&param.decorators))
+            .build()
+    }
+
+    /**
+     * A binding with the TypeScript annotation and `?` written after it
+     *
+     * The annotation of `a: T = 1` belongs to `a`, not to the assignment pattern, and widens the
+     * range of the binding to its end like typescript-estree does.
+     */
+    fn binding(
+        &self,
+        pattern: &Pattern,
+        type_annotation: Option<&TsTypeAnnotation>,
+        optional: bool,
+    ) -> Value {
+        if let (Pattern::Assignment(assignment), true) =
+            (pattern, type_annotation.is_some() || optional)
+        {
+            return self
+                .node("AssignmentPattern", assignment.span)
+                .set(
+                    "left",
+                    self.binding(&assignment.left, type_annotation, optional),
+                )
+                .set("right", self.expression(&assignment.right))
+                .build();
+        }
+
+        let mut value = self.pattern(pattern);
+        let Value::Object(fields) = &mut value else {
+            return value;
+        };
+        if optional {
+            fields.insert("optional".to_string(), Value::Bool(true));
+        }
+        if let Some(annotation) = type_annotation {
+            fields.insert(
+                "typeAnnotation".to_string(),
+                self.ts_type_annotation(annotation),
+            );
+            let start = pattern.span().start;
+            let end = annotation.span.end.max(pattern.span().end);
+            let (range, loc) = self.location(start, end);
+            fields.insert("range".to_string(), range);
+            fields.insert("loc".to_string(), loc);
+        }
+        value
+    }
+
+    fn class(&self, kind: &str, class: &Class) -> Value {
+        let body = self
+            .node("ClassBody", class.body.span)
+            .set(
+                "body",
+                class
+                    .body
+                    .body
+                    .iter()
+                    .map(|member| self.class_member(member))
+                    .collect::<Vec<_>>(),
+            )
+            .build();
+        let implements = class
+            .implements
+            .iter()
+            .map(|heritage| self.heritage("TSClassImplements", heritage))
+            .collect();
+
+        self.node(kind, class.span)
+            .set("id", self.optional_identifier(class.id.as_ref()))
+            .set(
+                "superClass",
+                self.optional_expression(class.super_class.as_ref()),
+            )
+            .set("body", body)
+            .list("decorators", self.decorators(&class.decorators))
+            .maybe(
+                "typeParameters",
+                self.type_parameters(&class.type_parameters),
+            )
+            .maybe(
+                "superTypeArguments",
+                self.type_arguments(&class.super_type_arguments),
+            )
+            .list("implements", implements)
+            .flag("abstract", class.is_abstract)
+            .flag("declare", class.declare)
+            .build()
+    }
+
+    fn class_member(&self, member: &ClassMember) -> Value {
+        match member {
+            ClassMember::Method(method) => {
+                let kind = match method.modifiers.is_abstract {
+                    true => "TSAbstractMethodDefinition",
+                    false => "MethodDefinition",
+                };
+                let value = match method.value.body {
+                    Some(_) => self.function("FunctionExpression", &method.value),
+                    None => self.function("TSEmptyBodyFunctionExpression", &method.value),
+                };
+                let method_kind = match method.kind {
+                    MethodKind::Constructor => "constructor",
+                    MethodKind::Method => "method",
+                    MethodKind::Get => "get",
+                    MethodKind::Set => "set",
+                };
+                let node = self
+                    .node(kind, method.span)
+                    .set("key", self.property_key(&method.key))
+                    .set("value", value)
+                    .set("kind", method_kind)
+                    .set("computed", method.computed)
+                    .set("static", method.is_static)
+                    .list("decorators", self.decorators(&method.decorators))
+                    .flag("optional", method.optional);
+                self.modifiers(node, &method.modifiers).build()
+            }
+            ClassMember::Property(property) => {
+                let kind = match (property.modifiers.is_abstract, property.accessor) {
+                    (true, true) => "TSAbstractAccessorProperty",
+                    (true, false) => "TSAbstractPropertyDefinition",
+                    (false, true) => "AccessorProperty",
+                    (false, false) => "PropertyDefinition",
+                };
+                let node = self
+                    .node(kind, property.span)
+                    .set("key", self.property_key(&property.key))
+                    .set("value", self.optional_expression(property.value.as_ref()))
+                    .set("computed", property.computed)
+                    .set("static", property.is_static)
+                    .maybe(
+                        "typeAnnotation",
+                        self.type_annotation(&property.type_annotation),
+                    )
+                    .list("decorators", self.decorators(&property.decorators))
+                    .flag("optional", property.optional)
+                    .flag("definite", property.definite);
+                self.modifiers(node, &property.modifiers).build()
+            }
+            ClassMember::StaticBlock(block) => self
+                .node("StaticBlock", block.span)
+                .set("body", self.statements(&block.body))
+                .build(),
+            ClassMember::IndexSignature(signature) => self.index_signature(signature),
+            ClassMember::Error(span) => self.node("Error", *span).build(),
+        }
+    }
+
+    // abstract is told by the node type, the rest become fields
+    fn modifiers(&self, node: Node, modifiers: &TsModifiers) -> Node {
+        let accessibility = modifiers.accessibility.map(|accessibility| {
+            Value::from(match accessibility {
+                Accessibility::Public => "public",
+                Accessibility::Protected => "protected",
+                Accessibility::Private => "private",
+            })
+        });
+        node.maybe("accessibility", accessibility)
+            .flag("readonly", modifiers.readonly)
+            .flag("override", modifiers.is_override)
+            .flag("declare", modifiers.declare)
+    }
+
+    fn decorators(&self, decorators: &[Decorator]) -> Vec<Value> {
+        decorators
+            .iter()
+            .map(|decorator| {
+                self.node("Decorator", decorator.span)
+                    .set("expression", self.expression(&decorator.expression))
+                    .build()
+            })
+            .collect()
+    }
+
+    fn interface(&self, node: &TsInterfaceDeclaration) -> Value {
+        let extends = node
+            .extends
+            .iter()
+            .map(|heritage| self.heritage("TSInterfaceHeritage", heritage));
+        let body = self
+            .node("TSInterfaceBody", node.body.span)
+            .set("body", self.type_members(&node.body.body))
+            .build();
+        self.node("TSInterfaceDeclaration", node.span)
+            .set("id", self.identifier(&node.id))
+            .maybe(
+                "typeParameters",
+                self.type_parameters(&node.type_parameters),
+            )
+            .set("extends", extends.collect::<Vec<_>>())
+            .set("body", body)
+            .set("declare", node.declare)
+            .build()
+    }
+
+    fn heritage(&self, kind: &str, heritage: &TsHeritage) -> Value {
+        self.node(kind, heritage.span)
+            .set("expression", self.expression(&heritage.expression))
+            .maybe(
+                "typeArguments",
+                self.type_arguments(&heritage.type_arguments),
+            )
+            .build()
+    }
+
+    fn module_export_name(&self, name: &ModuleExportName) -> Value {
+        match name {
+            ModuleExportName::Identifier(identifier) => self.identifier(identifier),
+            ModuleExportName::String(string) => self.string(string),
+        }
+    }
+
+    fn import_attributes(&self, attributes: &[ImportAttribute]) -> Vec<Value> {
+        attributes
+            .iter()
+            .map(|attribute| {
+                self.node("ImportAttribute", attribute.span)
+                    .set("key", self.module_export_name(&attribute.key))
+                    .set("value", self.string(&attribute.value))
+                    .build()
+            })
+            .collect()
+    }
+
+    fn identifier(&self, identifier: &Identifier) -> Value {
+        self.node("Identifier", identifier.span)
+            .set("name", identifier.name.to_string())
+            .build()
+    }
+
+    fn optional_identifier(&self, identifier: Option<&Identifier>) -> Value {
+        identifier
+            .map(|identifier| self.identifier(identifier))
+            .into()
+    }
+
+    fn private_name(&self, name: &PrivateName) -> Value {
+        self.node("PrivateIdentifier", name.span)
+            .set("name", name.name.to_string())
+            .build()
+    }
+
+    fn literal(&self, span: Span, value: Value, raw: &str) -> Value {
+        self.node("Literal", span)
+            .set("value", value)
+            .set("raw", raw)
+            .build()
+    }
+
+    fn string(&self, string: &StringLiteral) -> Value {
+        self.literal(string.span, Value::from(string.value.as_str()), &string.raw)
+    }
+
+    // JSON has no BigInt, Infinity or NaN, they are null like JSON.stringify makes them
+    fn number(&self, number: &NumberLiteral) -> Value {
+        match &number.value {
+            NumberValue::Number(value) => {
+                let value = if value.fract() == 0.0 && value.abs() < 9007199254740992.0 {
+                    Value::from(*value as i64)
+                } else {
+                    serde_json::Number::from_f64(*value).map_or(Value::Null, Value::Number)
+                };
+                self.literal(number.span, value, &number.raw)
+            }
+            NumberValue::BigInt(digits) => self
+                .node("Literal", number.span)
+                .set("value", Value::Null)
+                .set("raw", number.raw.as_str())
+                .set("bigint", digits.as_str())
+                .build(),
+        }
+    }
+
+    fn template(&self, template: &TemplateLiteral) -> Value {
+        self.node("TemplateLiteral", template.span)
+            .set("quasis", self.template_elements(&template.quasis))
+            .set(
+                "expressions",
+                template
+                    .expressions
+                    .iter()
+                    .map(|expression| self.expression(expression))
+                    .collect::<Vec<_>>(),
+            )
+            .build()
+    }
+
+    fn template_elements(&self, quasis: &[TemplateElement]) -> Vec<Value> {
+        quasis
+            .iter()
+            .map(|quasi| {
+                self.node("TemplateElement", quasi.span)
+                    .set("value", json!({ "raw": quasi.raw, "cooked": quasi.cooked }))
+                    .set("tail", quasi.tail)
+                    .build()
+            })
+            .collect()
+    }
+
+    fn property_key(&self, key: &PropertyKey) -> Value {
+        match key {
+            PropertyKey::Identifier(identifier) => self.identifier(identifier),
+            PropertyKey::PrivateName(name) => self.private_name(name),
+            PropertyKey::String(string) => self.string(string),
+            PropertyKey::Number(number) => self.number(number),
+            PropertyKey::Computed(expression) => self.expression(expression),
+        }
+    }
+
+    fn optional_expression(&self, expression: Option<&Expression>) -> Value {
+        expression
+            .map(|expression| self.expression(expression))
+            .into()
+    }
+
+    fn arguments(&self, arguments: &[ExpressionOrSpread]) -> Vec<Value> {
+        arguments
+            .iter()
+            .map(|argument| self.expression_or_spread(argument))
+            .collect()
+    }
+
+    fn expression_or_spread(&self, element: &ExpressionOrSpread) -> Value {
+        match element {
+            ExpressionOrSpread::Expression(expression) => self.expression(expression),
+            ExpressionOrSpread::Spread(spread) => self
+                .node("SpreadElement", spread.span)
+                .set("argument", self.expression(&spread.argument))
+                .build(),
+        }
+    }
+
+    fn expression(&self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Identifier(node) => self.identifier(node),
+            Expression::PrivateName(node) => self.private_name(node),
+            Expression::This(span) => self.node("ThisExpression", *span).build(),
+            Expression::Super(span) => self.node("Super", *span).build(),
+            Expression::Null(span) => self.literal(*span, Value::Null, "null"),
+            Expression::Boolean(node) => self.literal(
+                node.span,
+                Value::Bool(node.value),
+                if node.value { "true" } else { "false" },
+            ),
+            Expression::Number(node) => self.number(node),
+            Expression::String(node) => self.string(node),
+            Expression::RegExp(node) => self
+                .node("Literal", node.span)
+                .set("value", Value::Null)
+                .set("raw", self.raw(node.span))
+                .set(
+                    "regex",
+                    json!({ "pattern": node.pattern, "flags": node.flags }),
+                )
+                .build(),
+            Expression::Template(node) => self.template(node),
+            Expression::TaggedTemplate(node) => self
+                .node("TaggedTemplateExpression", node.span)
+                .set("tag", self.expression(&node.tag))
+                .set("quasi", self.template(&node.quasi))
+                .maybe("typeArguments", self.type_arguments(&node.type_arguments))
+                .build(),
+            Expression::Array(node) => {
+                let elements = node.elements.iter().map(|element| {
+                    element
+                        .as_ref()
+                        .map(|element| self.expression_or_spread(element))
+                        .into()
+                });
+                self.node("ArrayExpression", node.span)
+                    .set("elements", elements.collect::<Vec<Value>>())
+                    .build()
+            }
+            Expression::Object(node) => {
+                let properties = node.properties.iter().map(|member| match member {
+                    ObjectMember::Property(property) => {
+                        let kind = match property.kind {
+                            PropertyKind::Init => "init",
+                            PropertyKind::Get => "get",
+                            PropertyKind::Set => "set",
+                        };
+                        self.node("Property", property.span)
+                            .set("key", self.property_key(&property.key))
+                            .set("value", self.expression(&property.value))
+                            .set("kind", kind)
+                            .set("method", property.method)
+                            .set("shorthand", property.shorthand)
+                            .set("computed", property.computed)
+                            .build()
+                    }
+                    ObjectMember::Spread(spread) => self
+                        .node("SpreadElement", spread.span)
+                        .set("argument", self.expression(&spread.argument))
+                        .build(),
+                });
+                self.node("ObjectExpression", node.span)
+                    .set("properties", properties.collect::<Vec<_>>())
+                    .build()
+            }
+            Expression::Function(node) => self.function("FunctionExpression", node),
+            Expression::Arrow(node) => {
+                let (body, is_expression) = match &node.body {
+                    ArrowBody::Expression(expression) => (self.expression(expression), true),
+                    ArrowBody::Block(body) => (self.function_body(body), false),
+                };
+                self.node("ArrowFunctionExpression", node.span)
+                    .set("id", Value::Null)
+                    .set("params", self.params(&node.params))
+                    .set("body", body)
+                    .set("async", node.is_async)
+                    .set("generator", false)
+                    .set("expression", is_expression)
+                    .maybe(
+                        "typeParameters",
+                        self.type_parameters(&node.type_parameters),
+                    )
+                    .maybe("returnType", self.type_annotation(&node.return_type))
+                    .build()
+            }
+            Expression::Class(node) => self.class("ClassExpression", node),
+            Expression::Unary(node) => self
+                .node("UnaryExpression", node.span)
+                .set("operator", unary_operator(node.operator))
+                .set("prefix", true)
+                .set("argument", self.expression(&node.argument))
+                .build(),
+            Expression::Update(node) => {
+                let operator = match node.operator {
+                    UpdateOperator::Increment => "++",
+                    UpdateOperator::Decrement => "--",
+                };
+                self.node("UpdateExpression", node.span)
+                    .set("operator", operator)
+                    .set("prefix", node.prefix)
+                    .set("argument", self.expression(&node.argument))
+                    .build()
+            }
+            Expression::Binary(node) => self
+                .node("BinaryExpression", node.span)
+                .set("operator", binary_operator(node.operator))
+                .set("left", self.expression(&node.left))
+                .set("right", self.expression(&node.right))
+                .build(),
+            Expression::Logical(node) => {
+                let operator = match node.operator {
+                    LogicalOperator::Or => "||",
+                    LogicalOperator::And => "&&",
+                    LogicalOperator::Coalesce => "??",
+                };
+                self.node("LogicalExpression", node.span)
+                    .set("operator", operator)
+                    .set("left", self.expression(&node.left))
+                    .set("right", self.expression(&node.right))
+                    .build()
+            }
+            Expression::Conditional(node) => self
+                .node("ConditionalExpression", node.span)
+                .set("test", self.expression(&node.test))
+                .set("consequent", self.expression(&node.consequent))
+                .set("alternate", self.expression(&node.alternate))
+                .build(),
+            Expression::Assignment(node) => self
+                .node("AssignmentExpression", node.span)
+                .set("operator", assignment_operator(node.operator))
+                .set("left", self.pattern(&node.left))
+                .set("right", self.expression(&node.right))
+                .build(),
+            Expression::Sequence(node) => self
+                .node("SequenceExpression", node.span)
+                .set(
+                    "expressions",
+                    node.expressions
+                        .iter()
+                        .map(|expression| self.expression(expression))
+                        .collect::<Vec<_>>(),
+                )
+                .build(),
+            Expression::Member(node) => self.member(node),
+            Expression::Call(node) => self
+                .node("CallExpression", node.span)
+                .set("callee", self.expression(&node.callee))
+                .set("arguments", self.arguments(&node.arguments))
+                .set("optional", node.optional)
+                .maybe("typeArguments", self.type_arguments(&node.type_arguments))
+                .build(),
+            Expression::New(node) => self
+                .node("NewExpression", node.span)
+                .set("callee", self.expression(&node.callee))
+                .set("arguments", self.arguments(&node.arguments))
+                .maybe("typeArguments", self.type_arguments(&node.type_arguments))
+                .build(),
+            Expression::Chain(node) => self
+                .node("ChainExpression", node.span)
+                .set("expression", self.expression(&node.expression))
+                .build(),
+            Expression::Yield(node) => self
+                .node("YieldExpression", node.span)
+                .set("argument", self.optional_expression(node.argument.as_ref()))
+                .set("delegate", node.delegate)
+                .build(),
+            Expression::Await(node) => self
+                .node("AwaitExpression", node.span)
+                .set("argument", self.expression(&node.argument))
+                .build(),
+            Expression::MetaProperty(node) => self
+                .node("MetaProperty", node.span)
+                .set("meta", self.identifier(&node.meta))
+                .set("property", self.identifier(&node.property))
+                .build(),
+            Expression::Import(node) => self
+                .node("ImportExpression", node.span)
+                .set("source", self.expression(&node.source))
+                .set("options", self.optional_expression(node.options.as_ref()))
+                .build(),
+            Expression::TsAs(node) | Expression::TsSatisfies(node) => {
+                let kind = match expression {
+                    Expression::TsAs(_) => "TSAsExpression",
+                    _ => "TSSatisfiesExpression",
+                };
+                self.node(kind, node.span)
+                    .set("expression", self.expression(&node.expression))
+                    .set("typeAnnotation", self.ts_type(&node.type_annotation))
+                    .build()
+            }
+            Expression::TsTypeAssertion(node) => self
+                .node("TSTypeAssertion", node.span)
+                .set("typeAnnotation", self.ts_type(&node.type_annotation))
+                .set("expression", self.expression(&node.expression))
+                .build(),
+            Expression::TsNonNull(node) => self
+                .node("TSNonNullExpression", node.span)
+                .set("expression", self.expression(&node.expression))
+                .build(),
+            Expression::JsxElement(node) => self.jsx_element(node),
+            Expression::JsxFragment(node) => self.jsx_fragment(node),
+        }
+    }
+
+    fn member(&self, member: &MemberExpression) -> Value {
+        let (property, computed) = match &member.property {
+            MemberProperty::Identifier(identifier) => (self.identifier(identifier), false),
+            MemberProperty::PrivateName(name) => (self.private_name(name), false),
+            MemberProperty::Computed(expression) => (self.expression(expression), true),
+        };
+        self.node("MemberExpression", member.span)
+            .set("object", self.expression(&member.object))
+            .set("property", property)
+            .set("computed", computed)
+            .set("optional", member.optional)
+            .build()
+    }
+
+    fn pattern(&self, pattern: &Pattern) -> Value {
+        match pattern {
+            Pattern::Identifier(identifier) => self.identifier(identifier),
+            Pattern::Object(object) => {
+                let properties = object.properties.iter().map(|member| match member {
+                    ObjectPatternMember::Property(property) => self
+                        .node("Property", property.span)
+                        .set("key", self.property_key(&property.key))
+                        .set("value", self.pattern(&property.value))
+                        .set("kind", "init")
+                        .set("method", false)
+                        .set("shorthand", property.shorthand)
+                        .set("computed", property.computed)
+                        .build(),
+                    ObjectPatternMember::Rest(rest) => self
+                        .node("RestElement", rest.span)
+                        .set("argument", self.pattern(&rest.argument))
+                        .build(),
+                });
+                self.node("ObjectPattern", object.span)
+                    .set("properties", properties.collect::<Vec<_>>())
+                    .build()
+            }
+            Pattern::Array(array) => {
+                let elements = array
+                    .elements
+                    .iter()
+                    .map(|element| element.as_ref().map(|element| self.pattern(element)).into());
+                self.node("ArrayPattern", array.span)
+                    .set("elements", elements.collect::<Vec<Value>>())
+                    .build()
+            }
+            Pattern::Rest(rest) => self
+                .node("RestElement", rest.span)
+                .set("argument", self.pattern(&rest.argument))
+                .build(),
+            Pattern::Assignment(assignment) => self
+                .node("AssignmentPattern", assignment.span)
+                .set("left", self.pattern(&assignment.left))
+                .set("right", self.expression(&assignment.right))
+                .build(),
+            Pattern::Member(member) => self.member(member),
+        }
+    }
+
+    fn jsx_element(&self, element: &JsxElement) -> Value {
+        let opening = &element.opening_element;
+        let attributes = opening.attributes.iter().map(|attribute| match attribute {
+            JsxAttributeItem::Attribute(attribute) => {
+                let name = match &attribute.name {
+                    JsxAttributeName::Identifier(identifier) => self.jsx_identifier(identifier),
+                    JsxAttributeName::Namespaced(name) => self.jsx_namespaced_name(name),
+                };
+                let value = attribute.value.as_ref().map(|value| match value {
+                    JsxAttributeValue::String(string) => self.string(string),
+                    JsxAttributeValue::ExpressionContainer(container) => {
+                        self.jsx_expression_container(container)
+                    }
+                    JsxAttributeValue::Element(element) => self.jsx_element(element),
+                    JsxAttributeValue::Fragment(fragment) => self.jsx_fragment(fragment),
+                });
+                self.node("JSXAttribute", attribute.span)
+                    .set("name", name)
+                    .set("value", value)
+                    .build()
+            }
+            JsxAttributeItem::Spread(spread) => self
+                .node("JSXSpreadAttribute", spread.span)
+                .set("argument", self.expression(&spread.argument))
+                .build(),
+        });
+        let opening_element = self
+            .node("JSXOpeningElement", opening.span)
+            .set("name", self.jsx_element_name(&opening.name))
+            .set("attributes", attributes.collect::<Vec<_>>())
+            .set("selfClosing", opening.self_closing)
+            .build();
+        let closing_element = element.closing_element.as_ref().map(|closing| {
+            self.node("JSXClosingElement", closing.span)
+                .set("name", self.jsx_element_name(&closing.name))
+                .build()
+        });
+
+        self.node("JSXElement", element.span)
+            .set("openingElement", opening_element)
+            .set("children", self.jsx_children(&element.children))
+            .set("closingElement", closing_element)
+            .build()
+    }
+
+    fn jsx_fragment(&self, fragment: &JsxFragment) -> Value {
+        self.node("JSXFragment", fragment.span)
+            .set(
+                "openingFragment",
+                self.node("JSXOpeningFragment", fragment.opening_fragment)
+                    .build(),
+            )
+            .set("children", self.jsx_children(&fragment.children))
+            .set(
+                "closingFragment",
+                self.node("JSXClosingFragment", fragment.closing_fragment)
+                    .build(),
+            )
+            .build()
+    }
+
+    fn jsx_children(&self, children: &[JsxChild]) -> Vec<Value> {
+        children
+            .iter()
+            .map(|child| match child {
+                JsxChild::Text(text) => self
+                    .node("JSXText", text.span)
+                    .set("value", text.value.as_str())
+                    .set("raw", text.raw.as_str())
+                    .build(),
+                JsxChild::Element(element) => self.jsx_element(element),
+                JsxChild::Fragment(fragment) => self.jsx_fragment(fragment),
+                JsxChild::ExpressionContainer(container) => {
+                    self.jsx_expression_container(container)
+                }
+                JsxChild::Spread(spread) => self
+                    .node("JSXSpreadChild", spread.span)
+                    .set("expression", self.expression(&spread.argument))
+                    .build(),
+            })
+            .collect()
+    }
+
+    // `{}` holds a JSXEmptyExpression spanning what is between the braces
+    fn jsx_expression_container(&self, container: &JsxExpressionContainer) -> Value {
+        let expression = match &container.expression {
+            Some(expression) => self.expression(expression),
+            None => {
+                let span = Span {
+                    start: container.span.start + 1,
+                    end: container.span.end - 1,
+                    ..Span::default()
+                };
+                self.node("JSXEmptyExpression", span).build()
+            }
+        };
+        self.node("JSXExpressionContainer", container.span)
+            .set("expression", expression)
+            .build()
+    }
+
+    fn jsx_element_name(&self, name: &JsxElementName) -> Value {
+        match name {
+            JsxElementName::Identifier(identifier) => self.jsx_identifier(identifier),
+            JsxElementName::Namespaced(name) => self.jsx_namespaced_name(name),
+            JsxElementName::Member(member) => self
+                .node("JSXMemberExpression", member.span)
+                .set("object", self.jsx_element_name(&member.object))
+                .set("property", self.jsx_identifier(&member.property))
+                .build(),
+        }
+    }
+
+    fn jsx_namespaced_name(&self, name: &JsxNamespacedName) -> Value {
+        self.node("JSXNamespacedName", name.span)
+            .set("namespace", self.jsx_identifier(&name.namespace))
+            .set("name", self.jsx_identifier(&name.name))
+            .build()
+    }
+
+    fn jsx_identifier(&self, identifier: &Identifier) -> Value {
+        self.node("JSXIdentifier", identifier.span)
+            .set("name", identifier.name.to_string())
+            .build()
+    }
+
+    fn type_annotation(&self, annotation: &Option<TsTypeAnnotation>) -> Option<Value> {
+        annotation
+            .as_ref()
+            .map(|annotation| self.ts_type_annotation(annotation))
+    }
+
+    fn ts_type_annotation(&self, annotation: &TsTypeAnnotation) -> Value {
+        self.node("TSTypeAnnotation", annotation.span)
+            .set("typeAnnotation", self.ts_type(&annotation.type_annotation))
+            .build()
+    }
+
+    fn type_parameters(&self, parameters: &Option<TsTypeParameters>) -> Option<Value> {
+        let parameters = parameters.as_ref()?;
+        let params = parameters
+            .params
+            .iter()
+            .map(|parameter| self.type_parameter(parameter));
+        Some(
+            self.node("TSTypeParameterDeclaration", parameters.span)
+                .set("params", params.collect::<Vec<_>>())
+                .build(),
+        )
+    }
+
+    fn type_parameter(&self, parameter: &TsTypeParameter) -> Value {
+        self.node("TSTypeParameter", parameter.span)
+            .set("name", self.identifier(&parameter.name))
+            .maybe(
+                "constraint",
+                parameter.constraint.as_ref().map(|node| self.ts_type(node)),
+            )
+            .maybe(
+                "default",
+                parameter.default.as_ref().map(|node| self.ts_type(node)),
+            )
+            .set("in", parameter.is_in)
+            .set("out", parameter.is_out)
+            .set("const", parameter.is_const)
+            .build()
+    }
+
+    fn type_arguments(&self, arguments: &Option<TsTypeArguments>) -> Option<Value> {
+        let arguments = arguments.as_ref()?;
+        Some(
+            self.node("TSTypeParameterInstantiation", arguments.span)
+                .set("params", self.ts_types(&arguments.params))
+                .build(),
+        )
+    }
+
+    fn ts_types(&self, types: &[TsType]) -> Vec<Value> {
+        types.iter().map(|node| self.ts_type(node)).collect()
+    }
+
+    fn entity_name(&self, name: &TsEntityName) -> Value {
+        match name {
+            TsEntityName::Identifier(identifier) => self.identifier(identifier),
+            TsEntityName::Qualified(qualified) => self
+                .node("TSQualifiedName", qualified.span)
+                .set("left", self.entity_name(&qualified.left))
+                .set("right", self.identifier(&qualified.right))
+                .build(),
+        }
+    }
+
+    fn ts_type(&self, ts_type: &TsType) -> Value {
+        match ts_type {
+            TsType::Keyword(node) => self.node(keyword_type(node.kind), node.span).build(),
+            TsType::This(span) => self.node("TSThisType", *span).build(),
+            TsType::Reference(node) => self
+                .node("TSTypeReference", node.span)
+                .set("typeName", self.entity_name(&node.type_name))
+                .maybe("typeArguments", self.type_arguments(&node.type_arguments))
+                .build(),
+            TsType::Literal(node) => self
+                .node("TSLiteralType", node.span)
+                .set("literal", self.expression(&node.literal))
+                .build(),
+            TsType::TemplateLiteral(node) => self
+                .node("TSTemplateLiteralType", node.span)
+                .set("quasis", self.template_elements(&node.quasis))
+                .set("types", self.ts_types(&node.types))
+                .build(),
+            TsType::Array(node) => self
+                .node("TSArrayType", node.span)
+                .set("elementType", self.ts_type(&node.element_type))
+                .build(),
+            TsType::Tuple(node) => self
+                .node("TSTupleType", node.span)
+                .set(
+                    "elementTypes",
+                    node.element_types
+                        .iter()
+                        .map(|element| self.tuple_element(element))
+                        .collect::<Vec<_>>(),
+                )
+                .build(),
+            TsType::Union(node) => self
+                .node("TSUnionType", node.span)
+                .set("types", self.ts_types(&node.types))
+                .build(),
+            TsType::Intersection(node) => self
+                .node("TSIntersectionType", node.span)
+                .set("types", self.ts_types(&node.types))
+                .build(),
+            TsType::Function(node) => {
+                let kind = match node.is_constructor {
+                    true => "TSConstructorType",
+                    false => "TSFunctionType",
+                };
+                let function = self
+                    .node(kind, node.span)
+                    .set("params", self.params(&node.params))
+                    .set("returnType", self.ts_type_annotation(&node.return_type))
+                    .maybe(
+                        "typeParameters",
+                        self.type_parameters(&node.type_parameters),
+                    );
+                match node.is_constructor {
+                    true => function.set("abstract", node.is_abstract).build(),
+                    false => function.build(),
+                }
+            }
+            TsType::TypeLiteral(node) => self
+                .node("TSTypeLiteral", node.span)
+                .set("members", self.type_members(&node.members))
+                .build(),
+            TsType::Mapped(node) => self
+                .node("TSMappedType", node.span)
+                .set("key", self.identifier(&node.key))
+                .set("constraint", self.ts_type(&node.constraint))
+                .set(
+                    "nameType",
+                    node.name_type.as_ref().map(|node| self.ts_type(node)),
+                )
+                .maybe(
+                    "typeAnnotation",
+                    node.type_annotation.as_ref().map(|node| self.ts_type(node)),
+                )
+                .maybe("optional", node.optional.map(mapped_modifier))
+                .maybe("readonly", node.readonly.map(mapped_modifier))
+                .build(),
+            TsType::Conditional(node) => self
+                .node("TSConditionalType", node.span)
+                .set("checkType", self.ts_type(&node.check_type))
+                .set("extendsType", self.ts_type(&node.extends_type))
+                .set("trueType", self.ts_type(&node.true_type))
+                .set("falseType", self.ts_type(&node.false_type))
+                .build(),
+            TsType::Infer(node) => self
+                .node("TSInferType", node.span)
+                .set("typeParameter", self.type_parameter(&node.type_parameter))
+                .build(),
+            TsType::Operator(node) => {
+                let operator = match node.operator {
+                    TsTypeOperatorKind::Keyof => "keyof",
+                    TsTypeOperatorKind::Unique => "unique",
+                    TsTypeOperatorKind::Readonly => "readonly",
+                };
+                self.node("TSTypeOperator", node.span)
+                    .set("operator", operator)
+                    .set("typeAnnotation", self.ts_type(&node.type_annotation))
+                    .build()
+            }
+            TsType::IndexedAccess(node) => self
+                .node("TSIndexedAccessType", node.span)
+                .set("objectType", self.ts_type(&node.object_type))
+                .set("indexType", self.ts_type(&node.index_type))
+                .build(),
+            TsType::Query(node) => {
+                let expr_name = match &node.expr_name {
+                    TsTypeQueryName::Entity(name) => self.entity_name(name),
+                    TsTypeQueryName::Import(import) => self.import_type(import),
+                };
+                self.node("TSTypeQuery", node.span)
+                    .set("exprName", expr_name)
+                    .maybe("typeArguments", self.type_arguments(&node.type_arguments))
+                    .build()
+            }
+            TsType::Import(node) => self.import_type(node),
+            TsType::Predicate(node) => {
+                let parameter_name = match &node.parameter_name {
+                    TsPredicateName::Identifier(identifier) => self.identifier(identifier),
+                    TsPredicateName::This(span) => self.node("TSThisType", *span).build(),
+                };
+                // The asserted type is wrapped like an annotation, spanning just the type
+                let type_annotation = node.type_annotation.as_ref().map(|node| {
+                    self.node("TSTypeAnnotation", node.span())
+                        .set("typeAnnotation", self.ts_type(node))
+                        .build()
+                });
+                self.node("TSTypePredicate", node.span)
+                    .set("asserts", node.asserts)
+                    .set("parameterName", parameter_name)
+                    .set("typeAnnotation", type_annotation)
+                    .build()
+            }
+        }
+    }
+
+    // `[a: T]`, `[T?]` and `[...T[]]` get nodes of their own, a plain `[T]` is just the type
+    fn tuple_element(&self, element: &TsTupleElement) -> Value {
+        let mut value = match &element.label {
+            Some(label) => self
+                .node("TSNamedTupleMember", element.span)
+                .set("label", self.identifier(label))
+                .set("elementType", self.ts_type(&element.element_type))
+                .set("optional", element.optional)
+                .build(),
+            None if element.optional => self
+                .node("TSOptionalType", element.span)
+                .set("typeAnnotation", self.ts_type(&element.element_type))
+                .build(),
+            None => self.ts_type(&element.element_type),
+        };
+        if element.rest {
+            value = self
+                .node("TSRestType", element.span)
+                .set("typeAnnotation", value)
+                .build();
+        }
+        value
+    }
+
+    // The argument is a TSLiteralType around the string
+    fn import_type(&self, import: &TsImportType) -> Value {
+        let argument = self
+            .node("TSLiteralType", import.argument.span)
+            .set("literal", self.string(&import.argument))
+            .build();
+        self.node("TSImportType", import.span)
+            .set("argument", argument)
+            .set(
+                "qualifier",
+                import.qualifier.as_ref().map(|name| self.entity_name(name)),
+            )
+            .maybe("typeArguments", self.type_arguments(&import.type_arguments))
+            .build()
+    }
+
+    fn type_members(&self, members: &[TsTypeMember]) -> Vec<Value> {
+        members
+            .iter()
+            .map(|member| match member {
+                TsTypeMember::Property(property) => self
+                    .node("TSPropertySignature", property.span)
+                    .set("key", self.property_key(&property.key))
+                    .set("computed", property.computed)
+                    .set("optional", property.optional)
+                    .set("readonly", property.readonly)
+                    .maybe(
+                        "typeAnnotation",
+                        self.type_annotation(&property.type_annotation),
+                    )
+                    .build(),
+                TsTypeMember::Method(method) => {
+                    let kind = match method.kind {
+                        PropertyKind::Init => "method",
+                        PropertyKind::Get => "get",
+                        PropertyKind::Set => "set",
+                    };
+                    self.node("TSMethodSignature", method.span)
+                        .set("key", self.property_key(&method.key))
+                        .set("computed", method.computed)
+                        .set("optional", method.optional)
+                        .set("kind", kind)
+                        .set("params", self.params(&method.params))
+                        .maybe("returnType", self.type_annotation(&method.return_type))
+                        .maybe(
+                            "typeParameters",
+                            self.type_parameters(&method.type_parameters),
+                        )
+                        .build()
+                }
+                TsTypeMember::Call(signature) => {
+                    self.signature("TSCallSignatureDeclaration", signature)
+                }
+                TsTypeMember::Construct(signature) => {
+                    self.signature("TSConstructSignatureDeclaration", signature)
+                }
+                TsTypeMember::Index(signature) => self.index_signature(signature),
+                TsTypeMember::Error(span) => self.node("Error", *span).build(),
+            })
+            .collect()
+    }
+
+    fn signature(&self, kind: &str, signature: &TsSignature) -> Value {
+        self.node(kind, signature.span)
+            .set("params", self.params(&signature.params))
+            .maybe("returnType", self.type_annotation(&signature.return_type))
+            .maybe(
+                "typeParameters",
+                self.type_parameters(&signature.type_parameters),
+            )
+            .build()
+    }
+
+    fn index_signature(&self, signature: &TsIndexSignature) -> Value {
+        self.node("TSIndexSignature", signature.span)
+            .set("parameters", self.params(&signature.parameters))
+            .maybe(
+                "typeAnnotation",
+                self.type_annotation(&signature.type_annotation),
+            )
+            .set("readonly", signature.readonly)
+            .set("static", signature.is_static)
+            .build()
+    }
+}
+
+fn type_kind(type_only: bool) -> Option<Value> {
+    type_only.then(|| Value::from("type"))
+}
+
+fn mapped_modifier(modifier: TsMappedModifier) -> Value {
+    match modifier {
+        TsMappedModifier::Plus => Value::from("+"),
+        TsMappedModifier::Minus => Value::from("-"),
+        TsMappedModifier::True => Value::Bool(true),
+    }
+}
+
+fn keyword_type(kind: TsKeywordKind) -> &'static str {
+    match kind {
+        TsKeywordKind::Any => "TSAnyKeyword",
+        TsKeywordKind::Unknown => "TSUnknownKeyword",
+        TsKeywordKind::Number => "TSNumberKeyword",
+        TsKeywordKind::BigInt => "TSBigIntKeyword",
+        TsKeywordKind::Boolean => "TSBooleanKeyword",
+        TsKeywordKind::String => "TSStringKeyword",
+        TsKeywordKind::Symbol => "TSSymbolKeyword",
+        TsKeywordKind::Object => "TSObjectKeyword",
+        TsKeywordKind::Never => "TSNeverKeyword",
+        TsKeywordKind::Void => "TSVoidKeyword",
+        TsKeywordKind::Undefined => "TSUndefinedKeyword",
+        TsKeywordKind::Null => "TSNullKeyword",
+    }
+}
+
+fn unary_operator(operator: UnaryOperator) -> &'static str {
+    match operator {
+        UnaryOperator::Minus => "-",
+        UnaryOperator::Plus => "+",
+        UnaryOperator::Not => "!",
+        UnaryOperator::BitwiseNot => "~",
+        UnaryOperator::Typeof => "typeof",
+        UnaryOperator::Void => "void",
+        UnaryOperator::Delete => "delete",
+    }
+}
+
+fn binary_operator(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Equal => "==",
+        BinaryOperator::NotEqual => "!=",
+        BinaryOperator::StrictEqual => "===",
+        BinaryOperator::StrictNotEqual => "!==",
+        BinaryOperator::LessThan => "<",
+        BinaryOperator::LessEqual => "<=",
+        BinaryOperator::GreaterThan => ">",
+        BinaryOperator::GreaterEqual => ">=",
+        BinaryOperator::ShiftLeft => "<<",
+        BinaryOperator::ShiftRight => ">>",
+        BinaryOperator::UnsignedShiftRight => ">>>",
+        BinaryOperator::Add => "+",
+        BinaryOperator::Subtract => "-",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Remainder => "%",
+        BinaryOperator::Exponent => "**",
+        BinaryOperator::BitwiseOr => "|",
+        BinaryOperator::BitwiseXor => "^",
+        BinaryOperator::BitwiseAnd => "&",
+        BinaryOperator::In => "in",
+        BinaryOperator::Instanceof => "instanceof",
+    }
+}
+
+fn assignment_operator(operator: AssignmentOperator) -> &'static str {
+    match operator {
+        AssignmentOperator::Assign => "=",
+        AssignmentOperator::Add => "+=",
+        AssignmentOperator::Subtract => "-=",
+        AssignmentOperator::Multiply => "*=",
+        AssignmentOperator::Divide => "/=",
+        AssignmentOperator::Remainder => "%=",
+        AssignmentOperator::Exponent => "**=",
+        AssignmentOperator::ShiftLeft => "<<=",
+        AssignmentOperator::ShiftRight => ">>=",
+        AssignmentOperator::UnsignedShiftRight => ">>>=",
+        AssignmentOperator::BitwiseOr => "|=",
+        AssignmentOperator::BitwiseXor => "^=",
+        AssignmentOperator::BitwiseAnd => "&=",
+        AssignmentOperator::LogicalOr => "||=",
+        AssignmentOperator::LogicalAnd => "&&=",
+        AssignmentOperator::Coalesce => "??=",
+    }
+}
@@ -0,0 +1,1180 @@
+use crate::error::errors::Errors;
+use crate::read::interner::Symbol;
+use crate::read::jsx::JsxToken;
+use crate::read::punctuator::Punctuator;
+use crate::read::template::TemplatePart;
+use crate::read::tokenizer::{Identifier as Keyword, Literal, Span, TokenKind};
+
+use super::ast::{
+    ArrayExpression, AssignmentExpression, AssignmentOperator, AwaitExpression, BinaryExpression,
+    BinaryOperator, BooleanLiteral, CallExpression, ChainExpression, ConditionalExpression,
+    Expression, ExpressionOrSpread, Identifier, ImportExpression, LogicalExpression,
+    LogicalOperator, MemberExpression, MemberProperty, MetaProperty, NewExpression, NumberLiteral,
+    ObjectExpression, ObjectMember, Pattern, PrivateName, Property, PropertyKey, PropertyKind,
+    RegExpLiteral, SequenceExpression, SpreadElement, StringLiteral, TaggedTemplateExpression,
+    TemplateElement, TemplateLiteral, TsAsExpression, TsEntityName, TsNonNullExpression, TsType,
+    TsTypeReference, UnaryExpression, UnaryOperator, UpdateExpression, UpdateOperator,
+    YieldExpression,
+};
+use super::function::FunctionKind;
+use super::parser::Parser;
+
+impl<'a> Parser<'a> {
+    // Expression, a comma separated sequence of assignments
+    pub(super) fn parse_expression(&mut self) -> Result<Expression, Errors> {
+        let start = self.current().span;
+        let first = self.parse_assignment()?;
+        if !self.at(Literal::COMMA) {
+            return Ok(first);
+        }
+
+        let mut expressions = vec![first];
+        while self.eat(Literal::COMMA) {
+            expressions.push(self.parse_assignment()?);
+        }
+        Ok(Expression::Sequence(Box::new(SequenceExpression {
+            expressions,
+            span: self.finish(start),
+        })))
+    }
+
+    /**
+     * AssignmentExpression, an object literal with `{ a = 1 }` left in it is an error here
+     */
+    pub(super) fn parse_assignment(&mut self) -> Result<Expression, Errors> {
+        let outer = self.cover_initializer.take();
+        let expression = self.parse_assignment_or_cover()?;
+        if let Some(span) = self.cover_initializer {
+            return Err(Errors::ParseError(
+                "Invalid shorthand property initializer",
+                span,
+            ));
+        }
+        self.cover_initializer = outer;
+        Ok(expression)
+    }
+
+    /**
+     * AssignmentExpression that may still become a pattern
+     *
+     * Array elements and property values go through here, `[{ a = 1 }] = b` only turns out
+     * to be a pattern at the outer `=`.
+     */
+    pub(super) fn parse_assignment_or_cover(&mut self) -> Result<Expression, Errors> {
+        self.nested(|parser| {
+            if parser.at_keyword(Keyword::STRICT_YIELD) && parser.context.in_generator {
+                return parser.parse_yield();
+            }
+            if let Some(is_async) = parser.at_arrow_function() {
+                return parser.parse_arrow_function(is_async);
+            }
+            if let Some(arrow) = parser.parse_typed_arrow_opt()? {
+                return Ok(arrow);
+            }
+
+            let start = parser.current().span;
+            let left = parser.parse_conditional()?;
+
+            let operator = match assignment_operator(parser.kind()) {
+                Some(operator) => operator,
+                None => return Ok(left),
+            };
+            let target = if operator == AssignmentOperator::Assign
+                && matches!(left, Expression::Object(_) | Expression::Array(_))
+            {
+                parser.cover_initializer = None;
+                parser.to_assignment_pattern(left)?
+            } else {
+                parser.to_simple_target(left)?
+            };
+            parser.advance();
+            let right = parser.parse_assignment()?;
+
+            Ok(Expression::Assignment(Box::new(AssignmentExpression {
+                operator,
+                left: target,
+                right,
+                span: parser.finish(start),
+            })))
+        })
+    }
+
+    fn parse_yield(&mut self) -> Result<Expression, Errors> {
+        let start = self.advance();
+        let delegate = !self.current().newline_before && self.eat(Literal::ASTERISK);
+        let argument = if delegate || self.starts_yield_argument() {
+            Some(self.parse_assignment()?)
+        } else {
+            None
+        };
+
+        Ok(Expression::Yield(Box::new(YieldExpression {
+            argument,
+            delegate,
+            span: self.finish(start),
+        })))
+    }
+
+    // A bare `yield` ends at a line break or at whatever closes the surrounding expression
+    fn starts_yield_argument(&self) -> bool {
+        !self.current().newline_before
+            && !matches!(
+                self.kind(),
+                TokenKind::EndOfFile
+                    | TokenKind::Literal(
+                        Literal::OPEN_BRACKET_RIGHT
+                            | Literal::SQUARE_BRACKET_RIGHT
+                            | Literal::CURLY_BRACKET_RIGHT
+                            | Literal::COMMA
+                            | Literal::SEMICOLON
+                            | Literal::COLON
+                    )
+                    | TokenKind::Identifier(Keyword::IN)
+            )
+            && !matches!(self.kind(), TokenKind::Template(template) if matches!(template.part, TemplatePart::Middle | TemplatePart::Tail))
+    }
+
+    fn parse_conditional(&mut self) -> Result<Expression, Errors> {
+        let start = self.current().span;
+        let test = self.parse_binary(0)?;
+        if !self.eat(Literal::QUESTION_MARK) {
+            return Ok(test);
+        }
+
+        let consequent = self.with_in(|parser| parser.parse_assignment())?;
+        self.expect(Literal::COLON, "Expected :")?;
+        let alternate = self.parse_assignment()?;
+
+        Ok(Expression::Conditional(Box::new(ConditionalExpression {
+            test,
+            consequent,
+            alternate,
+            span: self.finish(start),
+        })))
+    }
+
+    /**
+     * Binary and logical operators by precedence climbing
+     *
+     * @args minimum: u8 - operators binding this loosely or looser are left to the caller
+     */
+    fn parse_binary(&mut self, minimum: u8) -> Result<Expression, Errors> {
+        let start = self.current().span;
+        let mut left = if matches!(self.kind(), TokenKind::PrivateName(_))
+            && matches!(self.peek(1).kind, TokenKind::Identifier(Keyword::IN))
+        {
+            // `#field in object`, the only place a private name stands on its own
+            Expression::PrivateName(self.parse_private_name_reference()?)
+        } else {
+            self.parse_unary()?
+        };
+
+        loop {
+            // `a as T` and `a satisfies T` bind like relational operators
+            if self.at_type_assertion_operator() && RELATIONAL > minimum {
+                left = self.parse_as_expression(left, start)?;
+                continue;
+            }
+
+            let kind = self.kind();
+            let precedence = match kind.binary_precedence() {
+                Some(precedence) if precedence > minimum => precedence,
+                _ => break,
+            };
+            if self.no_in && matches!(kind, TokenKind::Identifier(Keyword::IN)) {
+                break;
+            }
+            let operator = match binary_operator(kind) {
+                Some(operator) => operator,
+                None => break,
+            };
+            self.advance();
+
+            // `**` groups to the right, `a ** b ** c` is `a ** (b ** c)`
+            let right_minimum = if operator == Operator::Binary(BinaryOperator::Exponent) {
+                precedence - 1
+            } else {
+                precedence
+            };
+            let right_start = self.current().span;
+            let right = self.parse_binary(right_minimum)?;
+            let span = self.finish(start);
+
+            if let Operator::Logical(operator) = operator {
+                if mixes_nullish(operator, &left, start)
+                    || mixes_nullish(operator, &right, right_start)
+                {
+                    return Err(Errors::ParseError(
+                        "?? can't be mixed with || or && without parentheses",
+                        span,
+                    ));
+                }
+            }
+
+            left = match operator {
+                Operator::Binary(operator) => Expression::Binary(Box::new(BinaryExpression {
+                    operator,
+                    left,
+                    right,
+                    span,
+                })),
+                Operator::Logical(operator) => Expression::Logical(Box::new(LogicalExpression {
+                    operator,
+                    left,
+                    right,
+                    span,
+                })),
+            };
+        }
+
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, Errors> {
+        self.nested(|parser| {
+            let start = parser.current().span;
+
+            let operator = match parser.kind() {
+                TokenKind::Literal(Literal::MINUS) => Some(UnaryOperator::Minus),
+                TokenKind::Literal(Literal::PLUS) => Some(UnaryOperator::Plus),
+                TokenKind::Literal(Literal::EXCLAMATION_MARK) => Some(UnaryOperator::Not),
+                TokenKind::Literal(Literal::TILDE) => Some(UnaryOperator::BitwiseNot),
+                TokenKind::Identifier(Keyword::TYPEOF) => Some(UnaryOperator::Typeof),
+                TokenKind::Identifier(Keyword::VOID) => Some(UnaryOperator::Void),
+                TokenKind::Identifier(Keyword::DELETE) => Some(UnaryOperator::Delete),
+                _ => None,
+            };
+            if let Some(operator) = operator {
+                parser.advance();
+                let argument = parser.parse_unary()?;
+                if operator == UnaryOperator::Delete
+                    && parser.context.strict
+                    && matches!(argument, Expression::Identifier(_))
+                {
+                    return Err(Errors::ParseError(
+                        "Deleting a variable isn't allowed in strict mode",
+                        argument.span(),
+                    ));
+                }
+                parser.check_exponent_operand()?;
+                return Ok(Expression::Unary(Box::new(UnaryExpression {
+                    operator,
+                    argument,
+                    span: parser.finish(start),
+                })));
+            }
+
+            let update = match parser.kind() {
+                TokenKind::Punctuator(Punctuator::INCREMENT) => Some(UpdateOperator::Increment),
+                TokenKind::Punctuator(Punctuator::DECREMENT) => Some(UpdateOperator::Decrement),
+                _ => None,
+            };
+            if let Some(operator) = update {
+                parser.advance();
+                let argument = parser.parse_unary()?;
+                let argument = parser.check_update_target(argument)?;
+                return Ok(Expression::Update(Box::new(UpdateExpression {
+                    operator,
+                    prefix: true,
+                    argument,
+                    span: parser.finish(start),
+                })));
+            }
+
+            // `<T>value`, the old assertion syntax, JSX took `<` for itself in .tsx files
+            if parser.typescript() && !parser.dialect.is_jsx() && parser.at(Literal::LESS_THAN) {
+                parser.advance();
+                let type_annotation = parser.parse_type()?;
+                parser.expect_greater_than()?;
+                let expression = parser.parse_unary()?;
+                return Ok(Expression::TsTypeAssertion(Box::new(TsAsExpression {
+                    expression,
+                    type_annotation,
+                    span: parser.finish(start),
+                })));
+            }
+
+            if parser.at_keyword(Keyword::MODULE_ASYNC) && parser.context.in_async {
+                parser.advance();
+                let argument = parser.parse_unary()?;
+                parser.check_exponent_operand()?;
+                return Ok(Expression::Await(Box::new(AwaitExpression {
+                    argument,
+                    span: parser.finish(start),
+                })));
+            }
+
+            parser.parse_postfix()
+        })
+    }
+
+    // `-a ** b` is ambiguous, the operand of `**` can't be a unary expression
+    fn check_exponent_operand(&self) -> Result<(), Errors> {
+        if self.at_punctuator(Punctuator::EXPONENT) {
+            return Err(self.error("A unary expression before ** needs parentheses"));
+        }
+        Ok(())
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expression, Errors> {
+        let start = self.current().span;
+        let expression = self.parse_left_hand_side()?;
+
+        // `a \n ++b` is two statements, a line break ends the expression before `++`
+        let operator = match self.kind() {
+            TokenKind::Punctuator(Punctuator::INCREMENT) => UpdateOperator::Increment,
+            TokenKind::Punctuator(Punctuator::DECREMENT) => UpdateOperator::Decrement,
+            _ => return Ok(expression),
+        };
+        if self.current().newline_before {
+            return Ok(expression);
+        }
+        let argument = self.check_update_target(expression)?;
+        self.advance();
+
+        Ok(Expression::Update(Box::new(UpdateExpression {
+            operator,
+            prefix: false,
+            argument,
+            span: self.finish(start),
+        })))
+    }
+
+    // `++` and `--` take a name or a member, nothing else
+    fn check_update_target(&self, argument: Expression) -> Result<Expression, Errors> {
+        match &argument {
+            Expression::Identifier(identifier) => {
+                self.check_binding_name(identifier)?;
+                Ok(argument)
+            }
+            Expression::Member(_) => Ok(argument),
+            Expression::TsNonNull(_) | Expression::TsAs(_) | Expression::TsTypeAssertion(_)
+                if is_simple_target(&argument) =>
+            {
+                Ok(argument)
+            }
+            _ => Err(Errors::ParseError("Invalid update target", argument.span())),
+        }
+    }
+
+    /**
+     * Member accesses, calls and `new` in any order, `a.b?.(c)[d]`
+     */
+    pub(super) fn parse_left_hand_side(&mut self) -> Result<Expression, Errors> {
+        let start = self.current().span;
+        let expression = match self.kind() {
+            TokenKind::Identifier(Keyword::NEW) => self.parse_new()?,
+            TokenKind::Identifier(Keyword::SUPER) => self.parse_super()?,
+            TokenKind::Identifier(Keyword::IMPORT) => self.parse_import_expression()?,
+            _ => self.parse_primary()?,
+        };
+        self.parse_member_tail(expression, start, false)
+    }
+
+    /**
+     * Continues an expression with `.name`, `[key]`, `?.`, calls and tagged templates
+     *
+     * @args no_call: bool - the callee of `new`, its arguments end the member chain
+     */
+    pub(super) fn parse_member_tail(
+        &mut self,
+        mut expression: Expression,
+        start: Span,
+        no_call: bool,
+    ) -> Result<Expression, Errors> {
+        let mut chain = false;
+
+        loop {
+            let optional = self.at_punctuator(Punctuator::OPTIONAL_CHAIN);
+            if optional {
+                if no_call {
+                    return Err(self.error("Optional chaining isn't allowed in new"));
+                }
+                self.advance();
+                chain = true;
+            }
+
+            // `f<T>()` and tag<T>`...`, otherwise `<` is a comparison
+            let mut type_arguments = if self.typescript() && !no_call && self.at(Literal::LESS_THAN)
+            {
+                self.try_parse(|parser| {
+                    let type_arguments = parser.parse_type_arguments()?;
+                    if parser.at(Literal::OPEN_BRACKET_LEFT) || parser.at_template_start() {
+                        Ok(type_arguments)
+                    } else {
+                        Err(parser.unexpected())
+                    }
+                })
+            } else {
+                None
+            };
+
+            expression = match self.kind() {
+                // `a!.b`, a non-null assertion can't start a new line
+                TokenKind::Literal(Literal::EXCLAMATION_MARK)
+                    if self.typescript() && !optional && !self.current().newline_before =>
+                {
+                    self.advance();
+                    Expression::TsNonNull(Box::new(TsNonNullExpression {
+                        expression,
+                        span: self.finish(start),
+                    }))
+                }
+                TokenKind::Literal(Literal::PERIOD) if !optional => {
+                    self.advance();
+                    let property = self.parse_member_property()?;
+                    self.member(expression, property, false, start)
+                }
+                TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT) => {
+                    self.advance();
+                    let property = self.with_in(|parser| parser.parse_expression())?;
+                    self.expect(Literal::SQUARE_BRACKET_RIGHT, "Expected ]")?;
+                    self.member(
+                        expression,
+                        MemberProperty::Computed(property),
+                        optional,
+                        start,
+                    )
+                }
+                TokenKind::Template(template)
+                    if matches!(
+                        template.part,
+                        TemplatePart::NoSubstitution | TemplatePart::Head
+                    ) =>
+                {
+                    if chain {
+                        return Err(
+                            self.error("Tagged templates aren't allowed in optional chains")
+                        );
+                    }
+                    let quasi = self.parse_template(true)?;
+                    Expression::TaggedTemplate(Box::new(TaggedTemplateExpression {
+                        tag: expression,
+                        type_arguments: type_arguments.take(),
+                        quasi,
+                        span: self.finish(start),
+                    }))
+                }
+                TokenKind::Literal(Literal::OPEN_BRACKET_LEFT) if !no_call => {
+                    let arguments = self.parse_arguments()?;
+                    Expression::Call(Box::new(CallExpression {
+                        callee: expression,
+                        type_arguments: type_arguments.take(),
+                        arguments,
+                        optional,
+                        span: self.finish(start),
+                    }))
+                }
+                _ if optional => {
+                    let property = self.parse_member_property()?;
+                    self.member(expression, property, true, start)
+                }
+                _ => break,
+            };
+        }
+
+        if chain {
+            expression = Expression::Chain(Box::new(ChainExpression {
+                expression,
+                span: self.finish(start),
+            }));
+        }
+        Ok(expression)
+    }
+
+    fn member(
+        &self,
+        object: Expression,
+        property: MemberProperty,
+        optional: bool,
+        start: Span,
+    ) -> Expression {
+        Expression::Member(Box::new(MemberExpression {
+            object,
+            property,
+            optional,
+            span: self.finish(start),
+        }))
+    }
+
+    // Name after `.` or `?.`, any word or a private name
+    fn parse_member_property(&mut self) -> Result<MemberProperty, Errors> {
+        if let TokenKind::PrivateName(_) = self.kind() {
+            return Ok(MemberProperty::PrivateName(
+                self.parse_private_name_reference()?,
+            ));
+        }
+        Ok(MemberProperty::Identifier(self.parse_identifier_name()?))
+    }
+
+    // `(a, ...b)`, a trailing comma is allowed
+    pub(super) fn parse_arguments(&mut self) -> Result<Vec<ExpressionOrSpread>, Errors> {
+        self.expect(Literal::OPEN_BRACKET_LEFT, "Expected (")?;
+        self.with_in(|parser| {
+            let mut arguments = Vec::new();
+            while !parser.eat(Literal::OPEN_BRACKET_RIGHT) {
+                arguments.push(parser.parse_expression_or_spread(false)?);
+                if !parser.at(Literal::OPEN_BRACKET_RIGHT) {
+                    parser.expect(Literal::COMMA, "Expected , or )")?;
+                }
+            }
+            Ok(arguments)
+        })
+    }
+
+    fn parse_expression_or_spread(&mut self, cover: bool) -> Result<ExpressionOrSpread, Errors> {
+        let start = self.current().span;
+        if self.eat_punctuator(Punctuator::ELLIPSIS) {
+            let argument = if cover {
+                self.parse_assignment_or_cover()?
+            } else {
+                self.parse_assignment()?
+            };
+            return Ok(ExpressionOrSpread::Spread(SpreadElement {
+                argument,
+                span: self.finish(start),
+            }));
+        }
+        let expression = if cover {
+            self.parse_assignment_or_cover()?
+        } else {
+            self.parse_assignment()?
+        };
+        Ok(ExpressionOrSpread::Expression(expression))
+    }
+
+    // `new X(a)`, `new X` and `new.target`
+    fn parse_new(&mut self) -> Result<Expression, Errors> {
+        let start = self.advance();
+
+        if self.eat(Literal::PERIOD) {
+            let meta = Identifier {
+                name: Symbol::intern("new"),
+                span: start,
+            };
+            if !self.at_word("target") {
+                return Err(self.error("Expected target"));
+            }
+            let property = self.parse_identifier_name()?;
+            if !self.context.in_function {
+                return Err(Errors::ParseError(
+                    "new.target is only allowed in functions",
+                    self.finish(start),
+                ));
+            }
+            return Ok(Expression::MetaProperty(Box::new(MetaProperty {
+                meta,
+                property,
+                span: self.finish(start),
+            })));
+        }
+
+        let callee_start = self.current().span;
+        let callee = match self.kind() {
+            TokenKind::Identifier(Keyword::NEW) => self.nested(Self::parse_new)?,
+            TokenKind::Identifier(Keyword::SUPER) => self.parse_super()?,
+            TokenKind::Identifier(Keyword::IMPORT) if !self.at_import_meta() => {
+                return Err(self.error("import() can't be called with new"))
+            }
+            TokenKind::Identifier(Keyword::IMPORT) => self.parse_import_expression()?,
+            _ => self.parse_primary()?,
+        };
+        let callee = self.parse_member_tail(callee, callee_start, true)?;
+        let type_arguments = if self.typescript() && self.at(Literal::LESS_THAN) {
+            self.try_parse(|parser| {
+                let type_arguments = parser.parse_type_arguments()?;
+                if parser.at(Literal::OPEN_BRACKET_LEFT) {
+                    Ok(type_arguments)
+                } else {
+                    Err(parser.unexpected())
+                }
+            })
+        } else {
+            None
+        };
+        let arguments = if self.at(Literal::OPEN_BRACKET_LEFT) {
+            self.parse_arguments()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Expression::New(Box::new(NewExpression {
+            callee,
+            type_arguments,
+            arguments,
+            span: self.finish(start),
+        })))
+    }
+
+    // `super` only stands before a call or a member access
+    fn parse_super(&mut self) -> Result<Expression, Errors> {
+        let span = self.advance();
+        if !matches!(
+            self.kind(),
+            TokenKind::Literal(
+                Literal::OPEN_BRACKET_LEFT | Literal::PERIOD | Literal::SQUARE_BRACKET_LEFT
+            )
+        ) {
+            return Err(self.error("super must be called or accessed"));
+        }
+        Ok(Expression::Super(span))
+    }
+
+    fn at_import_meta(&self) -> bool {
+        matches!(self.peek(1).kind, TokenKind::Literal(Literal::PERIOD))
+    }
+
+    // `import(source, options)` and `import.meta`
+    fn parse_import_expression(&mut self) -> Result<Expression, Errors> {
+        let start = self.advance();
+
+        if self.eat(Literal::PERIOD) {
+            if !self.at_word("meta") {
+                return Err(self.error("Expected meta"));
+            }
+            if self.source_type != super::ast::SourceType::Module {
+                return Err(self.error("import.meta is only allowed in modules"));
+            }
+            let property = self.parse_identifier_name()?;
+            return Ok(Expression::MetaProperty(Box::new(MetaProperty {
+                meta: Identifier {
+                    name: Symbol::intern("import"),
+                    span: start,
+                },
+                property,
+                span: self.finish(start),
+            })));
+        }
+
+        self.expect(Literal::OPEN_BRACKET_LEFT, "Expected (")?;
+        let (source, options) = self.with_in(|parser| {
+            let source = parser.parse_assignment()?;
+            let mut options = None;
+            if parser.eat(Literal::COMMA) && !parser.at(Literal::OPEN_BRACKET_RIGHT) {
+                options = Some(parser.parse_assignment()?);
+                parser.eat(Literal::COMMA);
+            }
+            Ok((source, options))
+        })?;
+        self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+
+        Ok(Expression::Import(Box::new(ImportExpression {
+            source,
+            options,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, Errors> {
+        let start = self.current().span;
+        self.check_legacy_octal()?;
+
+        let expression = match self.kind() {
+            TokenKind::Identifier(Keyword::THIS) => Expression::This(start),
+            TokenKind::Identifier(Keyword::NULL) => Expression::Null(start),
+            TokenKind::Identifier(Keyword::TRUE) => Expression::Boolean(BooleanLiteral {
+                value: true,
+                span: start,
+            }),
+            TokenKind::Identifier(Keyword::FALSE) => Expression::Boolean(BooleanLiteral {
+                value: false,
+                span: start,
+            }),
+            TokenKind::Identifier(Keyword::FUNCTION) => {
+                return Ok(Expression::Function(Box::new(self.parse_function(
+                    start,
+                    false,
+                    FunctionKind::Expression,
+                )?)))
+            }
+            TokenKind::Identifier(Keyword::CLASS)
+            | TokenKind::Decorator(_)
+            | TokenKind::Literal(Literal::AT) => {
+                let decorators = self.parse_decorators()?;
+                return Ok(Expression::Class(Box::new(
+                    self.parse_class(decorators, false)?,
+                )));
+            }
+            _ if self.at_async_function() => {
+                self.advance();
+                return Ok(Expression::Function(Box::new(self.parse_function(
+                    start,
+                    true,
+                    FunctionKind::Expression,
+                )?)));
+            }
+            _ if self.at_identifier() => {
+                return Ok(Expression::Identifier(self.parse_identifier()?));
+            }
+            TokenKind::Jsx(JsxToken::TagOpen) => return self.parse_jsx(),
+            TokenKind::Number(number) => Expression::Number(NumberLiteral {
+                value: number.value.clone(),
+                raw: self.text(start),
+                span: start,
+            }),
+            TokenKind::String(string) => Expression::String(StringLiteral {
+                value: string.value.clone(),
+                raw: self.text(start),
+                span: start,
+            }),
+            TokenKind::Regex(regex) => Expression::RegExp(RegExpLiteral {
+                pattern: regex.body.clone(),
+                flags: regex.flags.clone(),
+                span: start,
+            }),
+            TokenKind::Template(_) => {
+                return Ok(Expression::Template(Box::new(self.parse_template(false)?)))
+            }
+            TokenKind::Literal(Literal::OPEN_BRACKET_LEFT) => {
+                return self.parse_parenthesized();
+            }
+            TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT) => return self.parse_array(),
+            TokenKind::Literal(Literal::CURLY_BRACKET_LEFT) => return self.parse_object(),
+            TokenKind::Identifier(_) | TokenKind::Name { .. } => {
+                return Err(self.error("Unexpected reserved word"))
+            }
+            _ => return Err(self.unexpected()),
+        };
+
+        self.advance();
+        Ok(expression)
+    }
+
+    // `(expression)`, the parentheses leave no node behind
+    fn parse_parenthesized(&mut self) -> Result<Expression, Errors> {
+        self.advance();
+        let expression = self.with_in(|parser| parser.parse_expression())?;
+        self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+        Ok(expression)
+    }
+
+    fn parse_array(&mut self) -> Result<Expression, Errors> {
+        let start = self.advance();
+        let elements = self.with_in(|parser| {
+            let mut elements = Vec::new();
+            while !parser.eat(Literal::SQUARE_BRACKET_RIGHT) {
+                if parser.eat(Literal::COMMA) {
+                    elements.push(None);
+                    continue;
+                }
+                elements.push(Some(parser.parse_expression_or_spread(true)?));
+                if !parser.at(Literal::SQUARE_BRACKET_RIGHT) {
+                    parser.expect(Literal::COMMA, "Expected , or ]")?;
+                }
+            }
+            Ok(elements)
+        })?;
+
+        Ok(Expression::Array(Box::new(ArrayExpression {
+            elements,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_object(&mut self) -> Result<Expression, Errors> {
+        let start = self.advance();
+        let properties = self.with_in(|parser| {
+            let mut properties = Vec::new();
+            while !parser.eat(Literal::CURLY_BRACKET_RIGHT) {
+                properties.push(parser.parse_object_member()?);
+                if !parser.at(Literal::CURLY_BRACKET_RIGHT) {
+                    parser.expect(Literal::COMMA, "Expected , or }")?;
+                }
+            }
+            Ok(properties)
+        })?;
+
+        Ok(Expression::Object(Box::new(ObjectExpression {
+            properties,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_object_member(&mut self) -> Result<ObjectMember, Errors> {
+        let start = self.current().span;
+        if self.eat_punctuator(Punctuator::ELLIPSIS) {
+            let argument = self.parse_assignment_or_cover()?;
+            return Ok(ObjectMember::Spread(SpreadElement {
+                argument,
+                span: self.finish(start),
+            }));
+        }
+
+        let modifiers = self.parse_method_modifiers();
+        let key_span = self.current().span;
+        let usable_as_name = self.at_identifier();
+        let key = self.parse_property_key(false)?;
+        let computed = matches!(key, PropertyKey::Computed(_));
+
+        if self.at(Literal::OPEN_BRACKET_LEFT) || modifiers.is_method() {
+            let value = self.parse_method(modifiers.is_async, modifiers.is_generator, false)?;
+            self.check_accessor_params(modifiers.kind, &value)?;
+            return Ok(ObjectMember::Property(Property {
+                key,
+                value: Expression::Function(Box::new(value)),
+                kind: modifiers.kind,
+                method: modifiers.kind == PropertyKind::Init,
+                shorthand: false,
+                computed,
+                span: self.finish(start),
+            }));
+        }
+
+        if self.eat(Literal::COLON) {
+            let value = self.parse_assignment_or_cover()?;
+            return Ok(ObjectMember::Property(Property {
+                key,
+                value,
+                kind: PropertyKind::Init,
+                method: false,
+                shorthand: false,
+                computed,
+                span: self.finish(start),
+            }));
+        }
+
+        // `{ a }` and `{ a = 1 }`, the key has to be a plain name usable as a variable
+        let identifier = match &key {
+            PropertyKey::Identifier(identifier) => identifier.clone(),
+            _ => return Err(self.error("Expected :")),
+        };
+        if !usable_as_name {
+            return Err(Errors::ParseError("Unexpected reserved word", key_span));
+        }
+
+        let value = if self.at(Literal::EQUAL) {
+            self.cover_initializer.get_or_insert(self.current().span);
+            self.advance();
+            let right = self.parse_assignment()?;
+            Expression::Assignment(Box::new(AssignmentExpression {
+                operator: AssignmentOperator::Assign,
+                left: Pattern::Identifier(identifier.clone()),
+                right,
+                span: self.finish(start),
+            }))
+        } else {
+            Expression::Identifier(identifier)
+        };
+
+        Ok(ObjectMember::Property(Property {
+            key,
+            value,
+            kind: PropertyKind::Init,
+            method: false,
+            shorthand: true,
+            computed: false,
+            span: self.finish(start),
+        }))
+    }
+
+    /**
+     * Key of a property or class member
+     *
+     * @args private: bool - `#name` is allowed, class members only
+     */
+    pub(super) fn parse_property_key(&mut self, private: bool) -> Result<PropertyKey, Errors> {
+        let span = self.current().span;
+        self.check_legacy_octal()?;
+        let key = match self.kind() {
+            TokenKind::String(string) => PropertyKey::String(StringLiteral {
+                value: string.value.clone(),
+                raw: self.text(span),
+                span,
+            }),
+            TokenKind::Number(number) => PropertyKey::Number(NumberLiteral {
+                value: number.value.clone(),
+                raw: self.text(span),
+                span,
+            }),
+            TokenKind::PrivateName(name) if private => {
+                PropertyKey::PrivateName(PrivateName { name: *name, span })
+            }
+            TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT) => {
+                self.advance();
+                let key = self.with_in(|parser| parser.parse_assignment())?;
+                self.expect(Literal::SQUARE_BRACKET_RIGHT, "Expected ]")?;
+                return Ok(PropertyKey::Computed(key));
+            }
+            _ => return Ok(PropertyKey::Identifier(self.parse_identifier_name()?)),
+        };
+        self.advance();
+        Ok(key)
+    }
+
+    /**
+     * Template literal starting at the current NoSubstitution or Head piece
+     *
+     * @args tagged: bool - invalid escapes are allowed, their cooked value is None
+     */
+    pub(super) fn parse_template(&mut self, tagged: bool) -> Result<TemplateLiteral, Errors> {
+        let start = self.current().span;
+        let (quasis, expressions) = self.parse_template_parts(tagged, |parser| {
+            parser.with_in(|parser| parser.parse_expression())
+        })?;
+
+        Ok(TemplateLiteral {
+            quasis,
+            expressions,
+            span: self.finish(start),
+        })
+    }
+
+    /**
+     * Pieces of a template and what is between them, template literal types reuse it
+     *
+     * @args parse_part: the parser of a `${...}` part
+     */
+    pub(super) fn parse_template_parts<T>(
+        &mut self,
+        tagged: bool,
+        mut parse_part: impl FnMut(&mut Self) -> Result<T, Errors>,
+    ) -> Result<(Vec<TemplateElement>, Vec<T>), Errors> {
+        let mut quasis = Vec::new();
+        let mut parts = Vec::new();
+
+        loop {
+            let span = self.current().span;
+            let TokenKind::Template(template) = self.kind() else {
+                return Err(self.error("Expected the rest of the template"));
+            };
+            if template.cooked.is_none() && !tagged {
+                return Err(self.error("Invalid escape in template"));
+            }
+
+            // Spans of the pieces leave out the backticks, `${` and `}`
+            let tail = matches!(
+                template.part,
+                TemplatePart::NoSubstitution | TemplatePart::Tail
+            );
+            let end = if tail { span.end - 1 } else { span.end - 2 };
+            quasis.push(TemplateElement {
+                cooked: template.cooked.clone(),
+                raw: template.raw.clone(),
+                tail,
+                span: Span {
+                    start: span.start + 1,
+                    end,
+                    line: span.line,
+                    column: span.column + 1,
+                },
+            });
+            self.advance();
+            if tail {
+                break;
+            }
+
+            parts.push(parse_part(self)?);
+            if !matches!(self.kind(), TokenKind::Template(template) if matches!(template.part, TemplatePart::Middle | TemplatePart::Tail))
+            {
+                return Err(self.error("Expected } in template"));
+            }
+        }
+
+        Ok((quasis, parts))
+    }
+
+    // `as` or `satisfies` on the same line as the expression before it
+    fn at_type_assertion_operator(&self) -> bool {
+        self.typescript()
+            && (self.at_word("as") || self.at_word("satisfies"))
+            && !self.current().newline_before
+    }
+
+    /**
+     * `expression as T` or `expression satisfies T`, the current token is the operator
+     *
+     * `as const` reads `const` as a type reference, which is how TS-ESTree has it.
+     */
+    fn parse_as_expression(
+        &mut self,
+        expression: Expression,
+        start: Span,
+    ) -> Result<Expression, Errors> {
+        let satisfies = self.at_word("satisfies");
+        self.advance();
+        let type_annotation = if !satisfies && self.at_keyword(Keyword::CONST) {
+            let name = self.parse_identifier_name()?;
+            TsType::Reference(Box::new(TsTypeReference {
+                span: name.span,
+                type_name: TsEntityName::Identifier(name),
+                type_arguments: None,
+            }))
+        } else {
+            self.parse_type()?
+        };
+
+        let assertion = Box::new(TsAsExpression {
+            expression,
+            type_annotation,
+            span: self.finish(start),
+        });
+        Ok(if satisfies {
+            Expression::TsSatisfies(assertion)
+        } else {
+            Expression::TsAs(assertion)
+        })
+    }
+
+    fn at_template_start(&self) -> bool {
+        matches!(self.kind(), TokenKind::Template(template)
+            if matches!(template.part, TemplatePart::NoSubstitution | TemplatePart::Head))
+    }
+
+    /**
+     * Whether an arrow function starts at the current token, found by looking for the `=>`
+     *
+     * @returns Some(is_async) for `x =>`, `(...) =>`, `async x =>` and `async (...) =>`
+     */
+    pub(super) fn at_arrow_function(&self) -> Option<bool> {
+        if self.at_word("async") && !self.peek(1).newline_before && self.arrow_after(self.index + 1)
+        {
+            return Some(true);
+        }
+        self.arrow_after(self.index).then_some(false)
+    }
+
+    // Whether the parameters starting at `index` are followed by `=>` on the same line
+    fn arrow_after(&self, index: usize) -> bool {
+        let token = &self.tokens[index.min(self.tokens.len() - 1)];
+        let close = match token.kind {
+            TokenKind::Literal(Literal::OPEN_BRACKET_LEFT) => match self.matching_paren(index) {
+                Some(close) => close,
+                None => return false,
+            },
+            TokenKind::Name { .. } | TokenKind::Identifier(_) | TokenKind::TsKeyword(_) => index,
+            _ => return false,
+        };
+        self.tokens.get(close + 1).is_some_and(|arrow| {
+            matches!(arrow.kind, TokenKind::Punctuator(Punctuator::ARROW)) && !arrow.newline_before
+        })
+    }
+
+    /**
+     * Expression to a destructuring assignment target, `[a, b] = c` was read as an array
+     *
+     * Identifiers and members stay targets, objects and arrays become patterns, and the
+     * `a = 1` inside them become defaults.
+     */
+    pub(super) fn to_assignment_pattern(&self, expression: Expression) -> Result<Pattern, Errors> {
+        super::pattern::expression_to_pattern(self, expression)
+    }
+
+    // Target of a compound assignment, only names and members
+    pub(super) fn to_simple_target(&self, expression: Expression) -> Result<Pattern, Errors> {
+        match expression {
+            Expression::Identifier(identifier) => {
+                self.check_binding_name(&identifier)?;
+                Ok(Pattern::Identifier(identifier))
+            }
+            Expression::Member(member) => Ok(Pattern::Member(member)),
+            // `a! = b` and `(a as T) = b` assign to `a`, the pattern drops the assertion
+            Expression::TsNonNull(assertion) => self.to_simple_target(assertion.expression),
+            Expression::TsAs(assertion) | Expression::TsTypeAssertion(assertion) => {
+                self.to_simple_target(assertion.expression)
+            }
+            _ => Err(Errors::ParseError(
+                "Invalid assignment target",
+                expression.span(),
+            )),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Operator {
+    Binary(BinaryOperator),
+    Logical(LogicalOperator),
+}
+
+// Precedence of `<` and `instanceof`, which `as` and `satisfies` share
+const RELATIONAL: u8 = 8;
+
+// A name or member under TypeScript assertions, `a!++` updates `a`
+fn is_simple_target(expression: &Expression) -> bool {
+    match expression {
+        Expression::Identifier(_) | Expression::Member(_) => true,
+        Expression::TsNonNull(assertion) => is_simple_target(&assertion.expression),
+        Expression::TsAs(assertion) | Expression::TsTypeAssertion(assertion) => {
+            is_simple_target(&assertion.expression)
+        }
+        _ => false,
+    }
+}
+
+fn binary_operator(kind: &TokenKind) -> Option<Operator> {
+    let operator = match kind {
+        TokenKind::Punctuator(punctuator) => match punctuator {
+            Punctuator::LOGICAL_OR => return Some(Operator::Logical(LogicalOperator::Or)),
+            Punctuator::LOGICAL_AND => return Some(Operator::Logical(LogicalOperator::And)),
+            Punctuator::NULLISH_COALESCING => {
+                return Some(Operator::Logical(LogicalOperator::Coalesce))
+            }
+            Punctuator::EQUAL_EQUAL => BinaryOperator::Equal,
+            Punctuator::NOT_EQUAL => BinaryOperator::NotEqual,
+            Punctuator::STRICT_EQUAL => BinaryOperator::StrictEqual,
+            Punctuator::STRICT_NOT_EQUAL => BinaryOperator::StrictNotEqual,
+            Punctuator::LESS_EQUAL => BinaryOperator::LessEqual,
+            Punctuator::GREATER_EQUAL => BinaryOperator::GreaterEqual,
+            Punctuator::SHIFT_LEFT => BinaryOperator::ShiftLeft,
+            Punctuator::SHIFT_RIGHT => BinaryOperator::ShiftRight,
+            Punctuator::UNSIGNED_SHIFT_RIGHT => BinaryOperator::UnsignedShiftRight,
+            Punctuator::EXPONENT => BinaryOperator::Exponent,
+            _ => return None,
+        },
+        TokenKind::Literal(literal) => match literal {
+            Literal::LESS_THAN => BinaryOperator::LessThan,
+            Literal::GREATER_THAN => BinaryOperator::GreaterThan,
+            Literal::PLUS => BinaryOperator::Add,
+            Literal::MINUS => BinaryOperator::Subtract,
+            Literal::ASTERISK => BinaryOperator::Multiply,
+            Literal::SLASH => BinaryOperator::Divide,
+            Literal::PERCENT => BinaryOperator::Remainder,
+            Literal::PIPE => BinaryOperator::BitwiseOr,
+            Literal::CARET => BinaryOperator::BitwiseXor,
+            Literal::AMPERSAND => BinaryOperator::BitwiseAnd,
+            _ => return None,
+        },
+        TokenKind::Identifier(Keyword::IN) => BinaryOperator::In,
+        TokenKind::Identifier(Keyword::INSTANCEOF) => BinaryOperator::Instanceof,
+        _ => return None,
+    };
+    Some(Operator::Binary(operator))
+}
+
+pub(super) fn assignment_operator(kind: &TokenKind) -> Option<AssignmentOperator> {
+    let operator = match kind {
+        TokenKind::Literal(Literal::EQUAL) => AssignmentOperator::Assign,
+        TokenKind::Punctuator(punctuator) => match punctuator {
+            Punctuator::PLUS_ASSIGN => AssignmentOperator::Add,
+            Punctuator::MINUS_ASSIGN => AssignmentOperator::Subtract,
+            Punctuator::MULTIPLY_ASSIGN => AssignmentOperator::Multiply,
+            Punctuator::DIVIDE_ASSIGN => AssignmentOperator::Divide,
+            Punctuator::REMAINDER_ASSIGN => AssignmentOperator::Remainder,
+            Punctuator::EXPONENT_ASSIGN => AssignmentOperator::Exponent,
+            Punctuator::SHIFT_LEFT_ASSIGN => AssignmentOperator::ShiftLeft,
+            Punctuator::SHIFT_RIGHT_ASSIGN => AssignmentOperator::ShiftRight,
+            Punctuator::UNSIGNED_SHIFT_RIGHT_ASSIGN => AssignmentOperator::UnsignedShiftRight,
+            Punctuator::BITWISE_OR_ASSIGN => AssignmentOperator::BitwiseOr,
+            Punctuator::BITWISE_XOR_ASSIGN => AssignmentOperator::BitwiseXor,
+            Punctuator::BITWISE_AND_ASSIGN => AssignmentOperator::BitwiseAnd,
+            Punctuator::LOGICAL_OR_ASSIGN => AssignmentOperator::LogicalOr,
+            Punctuator::LOGICAL_AND_ASSIGN => AssignmentOperator::LogicalAnd,
+            Punctuator::NULLISH_ASSIGN => AssignmentOperator::Coalesce,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(operator)
+}
+
+// `a ?? b || c` is an error, a parenthesised operand starts before its expression
+fn mixes_nullish(operator: LogicalOperator, operand: &Expression, operand_start: Span) -> bool {
+    match operand {
+        Expression::Logical(logical) => {
+            let nullish = operator == LogicalOperator::Coalesce;
+            nullish != (logical.operator == LogicalOperator::Coalesce)
+                && logical.span.start == operand_start.start
+        }
+        _ => false,
+    }
+}
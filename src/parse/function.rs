@@ -0,0 +1,526 @@
+use crate::error::errors::Errors;
+use crate::read::punctuator::Punctuator;
+use crate::read::tokenizer::{Identifier as Keyword, Literal, Span, TokenKind};
+
+use super::ast::{
+    Accessibility, ArrowBody, ArrowFunction, AssignmentPattern, CallExpression, Decorator,
+    Expression, Function, FunctionBody, Identifier, MemberExpression, MemberProperty, Param,
+    Pattern, PropertyKind, RestElement, TsModifiers, TsTypeAnnotation, TsTypeParameters,
+};
+use super::parser::{duplicate_name, is_word, Parser};
+
+// Where a function is, decides whether it needs a name and which scope the name goes to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum FunctionKind {
+    Declaration,
+    Expression,
+    DefaultExport, // a declaration whose name is optional
+}
+
+// `async`, `*`, `get` and `set` in front of an object or class member
+#[derive(Copy, Clone, Debug)]
+pub(super) struct MethodModifiers {
+    pub(super) is_async: bool,
+    pub(super) is_generator: bool,
+    pub(super) kind: PropertyKind,
+}
+
+// Parsed ahead of an arrow's body, possibly speculatively
+struct ArrowHead {
+    type_parameters: Option<TsTypeParameters>,
+    params: Vec<Param>,
+    return_type: Option<TsTypeAnnotation>,
+}
+
+impl MethodModifiers {
+    // A modifier was read, a method has to follow
+    pub(super) fn is_method(&self) -> bool {
+        self.is_async || self.is_generator || self.kind != PropertyKind::Init
+    }
+}
+
+impl<'a> Parser<'a> {
+    /**
+     * `function name(params) { body }`, the current token is `function`
+     *
+     * @args start: Span - span of `async` for async functions, of `function` otherwise
+     */
+    pub(super) fn parse_function(
+        &mut self,
+        start: Span,
+        is_async: bool,
+        kind: FunctionKind,
+    ) -> Result<Function, Errors> {
+        self.expect_keyword(Keyword::FUNCTION, "Expected function")?;
+        let is_generator = self.eat(Literal::ASTERISK);
+
+        // The name of an expression is bound inside it, `yield` and `await` follow its own kind
+        let id = if self.at(Literal::OPEN_BRACKET_LEFT) {
+            if kind == FunctionKind::Declaration {
+                return Err(self.error("Function declarations need a name"));
+            }
+            None
+        } else if kind == FunctionKind::Expression {
+            Some(self.in_function(is_async, is_generator, |parser| {
+                parser.parse_binding_identifier()
+            })?)
+        } else {
+            Some(self.parse_binding_identifier()?)
+        };
+
+        // An overload or a `declare function` has no body in TypeScript
+        let body_optional = kind != FunctionKind::Expression;
+        let function = self.parse_function_rest(start, is_async, is_generator, body_optional)?;
+        Ok(Function { id, ..function })
+    }
+
+    /**
+     * Type parameters, parameters, return type and body of a function, method or accessor
+     *
+     * @args body_optional: bool - TypeScript may end the signature with `;` instead of a body
+     * @returns the function without a name
+     */
+    pub(super) fn parse_function_rest(
+        &mut self,
+        start: Span,
+        is_async: bool,
+        is_generator: bool,
+        body_optional: bool,
+    ) -> Result<Function, Errors> {
+        self.in_function(is_async, is_generator, |parser| {
+            let type_parameters = parser.parse_type_parameters_opt()?;
+            let params = parser.parse_formal_params()?;
+            let return_type = parser.parse_return_type()?;
+
+            let body = if body_optional
+                && parser.typescript()
+                && !parser.at(Literal::CURLY_BRACKET_LEFT)
+            {
+                parser.consume_semicolon()?;
+                None
+            } else {
+                Some(parser.parse_function_body()?)
+            };
+            // After the body, its "use strict" applies to the parameters too
+            parser.check_duplicate_params(&params, false)?;
+
+            Ok(Function {
+                id: None,
+                type_parameters,
+                params,
+                return_type,
+                body,
+                is_async,
+                is_generator,
+                declare: false,
+                span: parser.finish(start),
+            })
+        })
+    }
+
+    /**
+     * Two parameters can't share a name in strict mode code, arrows, methods, or when a default,
+     * pattern or rest parameter makes the list not simple
+     *
+     * @args unique: bool - the parameters of an arrow or method, unique in sloppy mode too
+     */
+    fn check_duplicate_params(&self, params: &[Param], unique: bool) -> Result<(), Errors> {
+        let simple = params
+            .iter()
+            .all(|param| matches!(param.pattern, Pattern::Identifier(_)));
+        if !unique && !self.context.strict && simple {
+            return Ok(());
+        }
+
+        let names: Vec<&Identifier> = params
+            .iter()
+            .flat_map(|param| param.pattern.bound_names())
+            .collect();
+        match duplicate_name(&names) {
+            Some(name) => Err(Errors::ParseError("Duplicate parameter name", name.span)),
+            None => Ok(()),
+        }
+    }
+
+    // `{ directives statements }`, the function context is already set up
+    pub(super) fn parse_function_body(&mut self) -> Result<FunctionBody, Errors> {
+        let start = self.expect(Literal::CURLY_BRACKET_LEFT, "Expected {")?;
+        let body = self.parse_body(false)?;
+        self.expect_closing_brace()?;
+
+        Ok(FunctionBody {
+            body,
+            span: self.finish(start),
+        })
+    }
+
+    /**
+     * `(a, { b } = {}, ...c)`, a rest parameter has to be the last one
+     *
+     * TypeScript adds `this: T`, `a?: T` and the modifiers of parameter properties.
+     */
+    pub(super) fn parse_formal_params(&mut self) -> Result<Vec<Param>, Errors> {
+        self.expect(Literal::OPEN_BRACKET_LEFT, "Expected (")?;
+        let mut params = Vec::new();
+
+        while !self.eat(Literal::OPEN_BRACKET_RIGHT) {
+            let start = self.current().span;
+            let decorators = self.parse_decorators()?;
+            let modifiers = self.parse_param_modifiers();
+
+            if self.eat_punctuator(Punctuator::ELLIPSIS) {
+                let argument = self.parse_binding_pattern()?;
+                let rest_end = self.finish(start);
+                let type_annotation = self.parse_type_annotation_opt()?;
+                params.push(Param {
+                    decorators,
+                    modifiers,
+                    pattern: Pattern::Rest(Box::new(RestElement {
+                        argument,
+                        span: rest_end,
+                    })),
+                    optional: false,
+                    type_annotation,
+                    span: self.finish(start),
+                });
+                self.expect(Literal::OPEN_BRACKET_RIGHT, "A rest parameter must be last")?;
+                break;
+            }
+
+            let pattern_start = self.current().span;
+            // `this: T` types what the function is called on
+            let pattern = if self.typescript() && self.at_keyword(Keyword::THIS) {
+                Pattern::Identifier(self.parse_identifier_name()?)
+            } else {
+                self.parse_binding_pattern()?
+            };
+            let optional = self.typescript() && self.eat(Literal::QUESTION_MARK);
+            let type_annotation = self.parse_type_annotation_opt()?;
+
+            let pattern = if self.eat(Literal::EQUAL) {
+                let right = self.with_in(|parser| parser.parse_assignment())?;
+                Pattern::Assignment(Box::new(AssignmentPattern {
+                    left: pattern,
+                    right,
+                    span: self.finish(pattern_start),
+                }))
+            } else {
+                pattern
+            };
+            params.push(Param {
+                decorators,
+                modifiers,
+                pattern,
+                optional,
+                type_annotation,
+                span: self.finish(start),
+            });
+            if !self.at(Literal::OPEN_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or )")?;
+            }
+        }
+
+        Ok(params)
+    }
+
+    // `public`, `private`, `protected`, `readonly` and `override` of a parameter property
+    fn parse_param_modifiers(&mut self) -> TsModifiers {
+        let mut modifiers = TsModifiers::default();
+        if !self.typescript() {
+            return modifiers;
+        }
+
+        loop {
+            // `(readonly)` or `(public: T)` use the word as the parameter name
+            let name_follows = matches!(
+                self.peek(1).kind,
+                TokenKind::Name { .. }
+                    | TokenKind::Identifier(_)
+                    | TokenKind::TsKeyword(_)
+                    | TokenKind::Literal(
+                        Literal::CURLY_BRACKET_LEFT | Literal::SQUARE_BRACKET_LEFT
+                    )
+                    | TokenKind::Punctuator(Punctuator::ELLIPSIS)
+            );
+            if !name_follows {
+                return modifiers;
+            }
+            match self.kind() {
+                TokenKind::Identifier(Keyword::STRICT_PUBLIC) => {
+                    modifiers.accessibility = Some(Accessibility::Public)
+                }
+                TokenKind::Identifier(Keyword::STRICT_PROTECTED) => {
+                    modifiers.accessibility = Some(Accessibility::Protected)
+                }
+                TokenKind::Identifier(Keyword::STRICT_PRIVATE) => {
+                    modifiers.accessibility = Some(Accessibility::Private)
+                }
+                kind if is_word(kind, "readonly") => modifiers.readonly = true,
+                kind if is_word(kind, "override") => modifiers.is_override = true,
+                _ => return modifiers,
+            }
+            self.advance();
+        }
+    }
+
+    /**
+     * Method after its key, `(params) { body }`
+     *
+     * The function spans from the `(`, ESTree leaves the key out of the method's value.
+     *
+     * @args body_optional: bool - class members can be overloads or abstract in TypeScript
+     */
+    pub(super) fn parse_method(
+        &mut self,
+        is_async: bool,
+        is_generator: bool,
+        body_optional: bool,
+    ) -> Result<Function, Errors> {
+        let start = self.current().span;
+        let function = self.parse_function_rest(start, is_async, is_generator, body_optional)?;
+        self.check_duplicate_params(&function.params, true)?;
+        Ok(function)
+    }
+
+    /**
+     * Reads `async`, `*`, `get` or `set` when a member key follows them
+     *
+     * `{ get() {} }` and `{ async: 1 }` use the word as the key itself.
+     */
+    pub(super) fn parse_method_modifiers(&mut self) -> MethodModifiers {
+        let mut modifiers = MethodModifiers {
+            is_async: false,
+            is_generator: false,
+            kind: PropertyKind::Init,
+        };
+
+        if self.at_word("async") && !self.peek(1).newline_before && self.key_follows() {
+            self.advance();
+            modifiers.is_async = true;
+        }
+        if self.eat(Literal::ASTERISK) {
+            modifiers.is_generator = true;
+            return modifiers;
+        }
+        if !modifiers.is_async && self.key_follows() {
+            if self.at_word("get") {
+                self.advance();
+                modifiers.kind = PropertyKind::Get;
+            } else if self.at_word("set") {
+                self.advance();
+                modifiers.kind = PropertyKind::Set;
+            }
+        }
+        modifiers
+    }
+
+    // Whether the token after the current one starts a member key (or `*` of `async *a`)
+    pub(super) fn key_follows(&self) -> bool {
+        matches!(
+            self.peek(1).kind,
+            TokenKind::Name { .. }
+                | TokenKind::Identifier(_)
+                | TokenKind::TsKeyword(_)
+                | TokenKind::String(_)
+                | TokenKind::Number(_)
+                | TokenKind::PrivateName(_)
+                | TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT | Literal::ASTERISK)
+        )
+    }
+
+    // A getter takes no parameters, a setter exactly one that isn't a rest parameter
+    pub(super) fn check_accessor_params(
+        &self,
+        kind: PropertyKind,
+        function: &Function,
+    ) -> Result<(), Errors> {
+        let valid = match kind {
+            PropertyKind::Init => true,
+            PropertyKind::Get => function.params.is_empty(),
+            PropertyKind::Set => {
+                function.params.len() == 1
+                    && !matches!(function.params[0].pattern, Pattern::Rest(_))
+            }
+        };
+        if !valid {
+            let message = if kind == PropertyKind::Get {
+                "A getter can't have parameters"
+            } else {
+                "A setter needs exactly one parameter"
+            };
+            return Err(Errors::ParseError(message, function.span));
+        }
+        Ok(())
+    }
+
+    /**
+     * Arrow function at the current token, at_arrow_function found its `=>`
+     */
+    pub(super) fn parse_arrow_function(&mut self, is_async: bool) -> Result<Expression, Errors> {
+        let start = self.current().span;
+        let head = self.parse_arrow_head(is_async)?;
+        self.parse_arrow_body(start, is_async, head)
+    }
+
+    /**
+     * An arrow function with type parameters or a return type, `<T>(a: T): T => a`
+     *
+     * at_arrow_function looks for `=>` right after the parameters, a return type or type
+     * parameters hide it. The head is parsed speculatively instead, `(a)` followed by anything
+     * but `: T =>` is rewound.
+     *
+     * @returns None when no typed arrow starts here
+     */
+    pub(super) fn parse_typed_arrow_opt(&mut self) -> Result<Option<Expression>, Errors> {
+        if !self.typescript() {
+            return Ok(None);
+        }
+        let start = self.current().span;
+        let opens_head = |kind: &TokenKind| {
+            matches!(
+                kind,
+                TokenKind::Literal(Literal::OPEN_BRACKET_LEFT | Literal::LESS_THAN)
+            )
+        };
+        let is_async =
+            self.at_word("async") && !self.peek(1).newline_before && opens_head(&self.peek(1).kind);
+        if !is_async && !opens_head(self.kind()) {
+            return Ok(None);
+        }
+
+        match self.try_parse(|parser| parser.parse_arrow_head(is_async)) {
+            Some(head) => Ok(Some(self.parse_arrow_body(start, is_async, head)?)),
+            None => Ok(None),
+        }
+    }
+
+    // `async <T>(params): R =>`, everything up to and including the arrow
+    fn parse_arrow_head(&mut self, is_async: bool) -> Result<ArrowHead, Errors> {
+        if is_async {
+            self.advance();
+        }
+
+        self.in_function(is_async, false, |parser| {
+            let type_parameters = parser.parse_type_parameters_opt()?;
+            let params = if parser.at(Literal::OPEN_BRACKET_LEFT) {
+                parser.parse_formal_params()?
+            } else {
+                let param_start = parser.current().span;
+                let identifier = parser.parse_binding_identifier()?;
+                vec![Param {
+                    decorators: Vec::new(),
+                    modifiers: TsModifiers::default(),
+                    pattern: Pattern::Identifier(identifier),
+                    optional: false,
+                    type_annotation: None,
+                    span: parser.finish(param_start),
+                }]
+            };
+            let return_type = parser.parse_return_type()?;
+
+            if parser.current().newline_before {
+                return Err(parser.error("No line break is allowed before =>"));
+            }
+            if !parser.eat_punctuator(Punctuator::ARROW) {
+                return Err(parser.error("Expected =>"));
+            }
+            Ok(ArrowHead {
+                type_parameters,
+                params,
+                return_type,
+            })
+        })
+    }
+
+    fn parse_arrow_body(
+        &mut self,
+        start: Span,
+        is_async: bool,
+        head: ArrowHead,
+    ) -> Result<Expression, Errors> {
+        self.check_duplicate_params(&head.params, true)?;
+        let body = self.in_function(is_async, false, |parser| {
+            if parser.at(Literal::CURLY_BRACKET_LEFT) {
+                Ok(ArrowBody::Block(parser.parse_function_body()?))
+            } else {
+                Ok(ArrowBody::Expression(parser.parse_assignment()?))
+            }
+        })?;
+
+        Ok(Expression::Arrow(Box::new(ArrowFunction {
+            type_parameters: head.type_parameters,
+            params: head.params,
+            return_type: head.return_type,
+            body,
+            is_async,
+            span: self.finish(start),
+        })))
+    }
+
+    /**
+     * Decorators in front of a class, member or parameter
+     *
+     * The lexer reads `@name` as one token, `.b` and `(c)` follow as usual tokens.
+     */
+    pub(super) fn parse_decorators(&mut self) -> Result<Vec<Decorator>, Errors> {
+        let mut decorators = Vec::new();
+
+        loop {
+            let start = self.current().span;
+            let mut expression = match *self.kind() {
+                TokenKind::Decorator(name) => {
+                    self.advance();
+                    Expression::Identifier(Identifier {
+                        name,
+                        span: Span {
+                            start: start.start + 1,
+                            column: start.column + 1,
+                            ..start
+                        },
+                    })
+                }
+                // `@(expression)`
+                TokenKind::Literal(Literal::AT) => {
+                    self.advance();
+                    self.expect(Literal::OPEN_BRACKET_LEFT, "Expected a decorator")?;
+                    let expression = self.with_in(|parser| parser.parse_expression())?;
+                    self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+                    decorators.push(Decorator {
+                        expression,
+                        span: self.finish(start),
+                    });
+                    continue;
+                }
+                _ => break,
+            };
+
+            let expression_start = expression.span();
+            while self.eat(Literal::PERIOD) {
+                let property = self.parse_identifier_name()?;
+                expression = Expression::Member(Box::new(MemberExpression {
+                    object: expression,
+                    property: MemberProperty::Identifier(property),
+                    optional: false,
+                    span: self.finish(expression_start),
+                }));
+            }
+            if self.at(Literal::OPEN_BRACKET_LEFT) {
+                let arguments = self.parse_arguments()?;
+                expression = Expression::Call(Box::new(CallExpression {
+                    callee: expression,
+                    type_arguments: None,
+                    arguments,
+                    optional: false,
+                    span: self.finish(expression_start),
+                }));
+            }
+
+            decorators.push(Decorator {
+                expression,
+                span: self.finish(start),
+            });
+        }
+
+        Ok(decorators)
+    }
+}
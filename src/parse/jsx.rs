@@ -0,0 +1,311 @@
+use crate::error::errors::Errors;
+use crate::read::jsx::JsxToken;
+use crate::read::punctuator::Punctuator;
+use crate::read::tokenizer::{Literal, Span, TokenKind};
+
+use super::ast::{
+    Expression, Identifier, JsxAttribute, JsxAttributeItem, JsxAttributeName, JsxAttributeValue,
+    JsxChild, JsxClosingElement, JsxElement, JsxElementName, JsxExpressionContainer, JsxFragment,
+    JsxMemberExpression, JsxNamespacedName, JsxOpeningElement, JsxText, SpreadElement,
+    StringLiteral,
+};
+use super::parser::Parser;
+
+impl<'a> Parser<'a> {
+    /**
+     * Element or fragment at its `<`
+     *
+     * The lexer only reads TagOpen where an expression can start and tells `<T,>` of a generic
+     * arrow apart, so the parser never has to guess.
+     */
+    pub(super) fn parse_jsx(&mut self) -> Result<Expression, Errors> {
+        if self.at_jsx_fragment() {
+            Ok(Expression::JsxFragment(Box::new(
+                self.parse_jsx_fragment()?,
+            )))
+        } else {
+            Ok(Expression::JsxElement(Box::new(self.parse_jsx_element()?)))
+        }
+    }
+
+    // `<>` is TagOpen and TagEnd without a name between them
+    fn at_jsx_fragment(&self) -> bool {
+        matches!(self.peek(1).kind, TokenKind::Jsx(JsxToken::TagEnd))
+    }
+
+    fn at_jsx(&self, token: JsxToken) -> bool {
+        matches!(self.kind(), TokenKind::Jsx(current) if *current == token)
+    }
+
+    fn expect_jsx(&mut self, token: JsxToken, message: &'static str) -> Result<Span, Errors> {
+        if !self.at_jsx(token) {
+            return Err(self.error(message));
+        }
+        Ok(self.advance())
+    }
+
+    fn parse_jsx_element(&mut self) -> Result<JsxElement, Errors> {
+        self.nested(|parser| {
+            let start = parser.advance();
+            let name = parser.parse_jsx_element_name()?;
+
+            let mut attributes = Vec::new();
+            loop {
+                let attribute = match parser.kind() {
+                    TokenKind::Jsx(JsxToken::TagEnd | JsxToken::SelfClosingEnd) => break,
+                    TokenKind::Jsx(JsxToken::ExpressionStart) => {
+                        JsxAttributeItem::Spread(parser.parse_jsx_spread()?)
+                    }
+                    TokenKind::Jsx(JsxToken::Name(_)) => {
+                        JsxAttributeItem::Attribute(parser.parse_jsx_attribute()?)
+                    }
+                    _ => return Err(parser.error("Expected an attribute or >")),
+                };
+                attributes.push(attribute);
+            }
+
+            let self_closing = parser.at_jsx(JsxToken::SelfClosingEnd);
+            parser.advance();
+            let opening_element = JsxOpeningElement {
+                name,
+                attributes,
+                self_closing,
+                span: parser.finish(start),
+            };
+            if self_closing {
+                return Ok(JsxElement {
+                    opening_element,
+                    children: Vec::new(),
+                    closing_element: None,
+                    span: parser.finish(start),
+                });
+            }
+
+            let children = parser.parse_jsx_children()?;
+            let closing_start = parser.advance();
+            let closing_name = parser.parse_jsx_element_name()?;
+            if !same_name(&opening_element.name, &closing_name) {
+                return Err(Errors::ParseError(
+                    "The closing tag doesn't match the opening tag",
+                    closing_name.span(),
+                ));
+            }
+            parser.expect_jsx(JsxToken::TagEnd, "Expected >")?;
+
+            Ok(JsxElement {
+                opening_element,
+                children,
+                closing_element: Some(JsxClosingElement {
+                    name: closing_name,
+                    span: parser.finish(closing_start),
+                }),
+                span: parser.finish(start),
+            })
+        })
+    }
+
+    fn parse_jsx_fragment(&mut self) -> Result<JsxFragment, Errors> {
+        let start = self.advance();
+        self.advance();
+        let opening_fragment = self.finish(start);
+
+        let children = self.parse_jsx_children()?;
+        let closing_start = self.advance();
+        self.expect_jsx(JsxToken::TagEnd, "A fragment has to be closed by </>")?;
+
+        Ok(JsxFragment {
+            opening_fragment,
+            children,
+            closing_fragment: self.finish(closing_start),
+            span: self.finish(start),
+        })
+    }
+
+    // `div`, `svg:rect` or `Foo.Bar.Baz`
+    fn parse_jsx_element_name(&mut self) -> Result<JsxElementName, Errors> {
+        let start = self.current().span;
+        let first = self.parse_jsx_identifier()?;
+        if self.eat(Literal::COLON) {
+            let name = self.parse_jsx_identifier()?;
+            return Ok(JsxElementName::Namespaced(JsxNamespacedName {
+                namespace: first,
+                name,
+                span: self.finish(start),
+            }));
+        }
+
+        let mut name = JsxElementName::Identifier(first);
+        while self.eat(Literal::PERIOD) {
+            let property = self.parse_jsx_identifier()?;
+            name = JsxElementName::Member(Box::new(JsxMemberExpression {
+                object: name,
+                property,
+                span: self.finish(start),
+            }));
+        }
+        Ok(name)
+    }
+
+    fn parse_jsx_identifier(&mut self) -> Result<Identifier, Errors> {
+        let TokenKind::Jsx(JsxToken::Name(name)) = *self.kind() else {
+            return Err(self.error("Expected a JSX name"));
+        };
+        let span = self.advance();
+        Ok(Identifier { name, span })
+    }
+
+    // `name`, `name="value"`, `name={expression}` or `ns:name=<element />`
+    fn parse_jsx_attribute(&mut self) -> Result<JsxAttribute, Errors> {
+        let start = self.current().span;
+        let first = self.parse_jsx_identifier()?;
+        let name = if self.eat(Literal::COLON) {
+            let name = self.parse_jsx_identifier()?;
+            JsxAttributeName::Namespaced(JsxNamespacedName {
+                namespace: first,
+                name,
+                span: self.finish(start),
+            })
+        } else {
+            JsxAttributeName::Identifier(first)
+        };
+
+        let value = if self.eat(Literal::EQUAL) {
+            Some(self.parse_jsx_attribute_value()?)
+        } else {
+            None
+        };
+        Ok(JsxAttribute {
+            name,
+            value,
+            span: self.finish(start),
+        })
+    }
+
+    fn parse_jsx_attribute_value(&mut self) -> Result<JsxAttributeValue, Errors> {
+        match self.kind() {
+            TokenKind::Jsx(JsxToken::String(value)) => {
+                let value = value.clone();
+                let span = self.advance();
+                Ok(JsxAttributeValue::String(StringLiteral {
+                    value,
+                    raw: self.text(span),
+                    span,
+                }))
+            }
+            TokenKind::Jsx(JsxToken::ExpressionStart) => {
+                let container = self.parse_jsx_expression_container()?;
+                if container.expression.is_none() {
+                    return Err(Errors::ParseError(
+                        "An attribute value can't be an empty expression",
+                        container.span,
+                    ));
+                }
+                Ok(JsxAttributeValue::ExpressionContainer(container))
+            }
+            TokenKind::Jsx(JsxToken::TagOpen) if self.at_jsx_fragment() => Ok(
+                JsxAttributeValue::Fragment(Box::new(self.parse_jsx_fragment()?)),
+            ),
+            TokenKind::Jsx(JsxToken::TagOpen) => Ok(JsxAttributeValue::Element(Box::new(
+                self.parse_jsx_element()?,
+            ))),
+            _ => Err(self.error("Expected an attribute value")),
+        }
+    }
+
+    // Children up to the `</` of the closing tag
+    fn parse_jsx_children(&mut self) -> Result<Vec<JsxChild>, Errors> {
+        let mut children = Vec::new();
+
+        loop {
+            let child = match self.kind() {
+                TokenKind::Jsx(JsxToken::TagClose) => return Ok(children),
+                TokenKind::Jsx(JsxToken::Text(_) | JsxToken::Entity(_)) => {
+                    JsxChild::Text(self.parse_jsx_text())
+                }
+                TokenKind::Jsx(JsxToken::TagOpen) if self.at_jsx_fragment() => {
+                    JsxChild::Fragment(Box::new(self.parse_jsx_fragment()?))
+                }
+                TokenKind::Jsx(JsxToken::TagOpen) => {
+                    JsxChild::Element(Box::new(self.parse_jsx_element()?))
+                }
+                TokenKind::Jsx(JsxToken::ExpressionStart)
+                    if matches!(
+                        self.peek(1).kind,
+                        TokenKind::Punctuator(Punctuator::ELLIPSIS)
+                    ) =>
+                {
+                    JsxChild::Spread(self.parse_jsx_spread()?)
+                }
+                TokenKind::Jsx(JsxToken::ExpressionStart) => {
+                    JsxChild::ExpressionContainer(self.parse_jsx_expression_container()?)
+                }
+                _ => return Err(self.unexpected()),
+            };
+            children.push(child);
+        }
+    }
+
+    // Text and entity tokens next to each other make one text node
+    fn parse_jsx_text(&mut self) -> JsxText {
+        let start = self.current().span;
+        let mut value = String::new();
+        while let TokenKind::Jsx(JsxToken::Text(text) | JsxToken::Entity(text)) = self.kind() {
+            value.push_str(text);
+            self.advance();
+        }
+
+        let span = self.finish(start);
+        JsxText {
+            value,
+            raw: self.text(span),
+            span,
+        }
+    }
+
+    // `{expression}`, or `{}` and `{/* comment */}` with nothing in them
+    fn parse_jsx_expression_container(&mut self) -> Result<JsxExpressionContainer, Errors> {
+        let start = self.advance();
+        let expression = if self.at_jsx(JsxToken::ExpressionEnd) {
+            None
+        } else {
+            Some(self.with_in(|parser| parser.parse_expression())?)
+        };
+        self.expect_jsx(JsxToken::ExpressionEnd, "Expected }")?;
+
+        Ok(JsxExpressionContainer {
+            expression,
+            span: self.finish(start),
+        })
+    }
+
+    // `{...props}` in a tag or `{...children}` between children, the braces belong to the span
+    fn parse_jsx_spread(&mut self) -> Result<SpreadElement, Errors> {
+        let start = self.advance();
+        if !self.eat_punctuator(Punctuator::ELLIPSIS) {
+            return Err(self.error("Expected ..."));
+        }
+        let argument = self.with_in(|parser| parser.parse_assignment())?;
+        self.expect_jsx(JsxToken::ExpressionEnd, "Expected }")?;
+
+        Ok(SpreadElement {
+            argument,
+            span: self.finish(start),
+        })
+    }
+}
+
+// Whether a closing tag names the element it closes, spans aside
+fn same_name(left: &JsxElementName, right: &JsxElementName) -> bool {
+    match (left, right) {
+        (JsxElementName::Identifier(left), JsxElementName::Identifier(right)) => {
+            left.name == right.name
+        }
+        (JsxElementName::Namespaced(left), JsxElementName::Namespaced(right)) => {
+            left.namespace.name == right.namespace.name && left.name.name == right.name.name
+        }
+        (JsxElementName::Member(left), JsxElementName::Member(right)) => {
+            left.property.name == right.property.name && same_name(&left.object, &right.object)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,15 @@
+pub mod ast;
+pub mod class;
+pub mod component;
+pub mod estree;
+pub mod expression;
+pub mod function;
+pub mod jsx;
+pub mod module;
+pub mod parser;
+pub mod pattern;
+pub mod scope;
+pub mod statement;
+pub mod typescript;
+pub mod visit;
+pub mod visit_mut;
@@ -0,0 +1,425 @@
+use crate::error::errors::Errors;
+use crate::read::tokenizer::{Identifier as Keyword, Literal, Span, TokenKind};
+
+use super::ast::{
+    Decorator, ExportAllDeclaration, ExportDefault, ExportDefaultDeclaration,
+    ExportNamedDeclaration, ExportSpecifier, Identifier, ImportAttribute, ImportDeclaration,
+    ImportSpecifier, ModuleExportName, NamedImport, SourceType, Statement, StringLiteral,
+};
+use super::function::FunctionKind;
+use super::parser::{is_word, Parser};
+
+impl<'a> Parser<'a> {
+    /**
+     * A top level item, imports and exports are only read in modules
+     *
+     * `import(...)` and `import.meta` are expressions and go to the statement parser.
+     */
+    pub(super) fn parse_module_item(&mut self) -> Result<Statement, Errors> {
+        if self.source_type != SourceType::Module || self.at_import_call() {
+            return self.parse_statement_list_item();
+        }
+
+        match self.kind() {
+            TokenKind::Identifier(Keyword::IMPORT) => self.parse_import(),
+            TokenKind::Identifier(Keyword::EXPORT) => self.parse_export(Vec::new()),
+            // `@decorator export class A {}`
+            TokenKind::Decorator(_) | TokenKind::Literal(Literal::AT) => {
+                let decorators = self.parse_decorators()?;
+                if self.at_keyword(Keyword::EXPORT) {
+                    self.parse_export(decorators)
+                } else {
+                    Ok(Statement::Class(Box::new(
+                        self.parse_class(decorators, true)?,
+                    )))
+                }
+            }
+            _ => self.parse_statement_list_item(),
+        }
+    }
+
+    pub(super) fn parse_import(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        let mut specifiers = Vec::new();
+
+        // `import type A from`, but `import type from` imports a default named type
+        let type_only = self.typescript()
+            && self.at_word("type")
+            && match &self.peek(1).kind {
+                TokenKind::Literal(Literal::CURLY_BRACKET_LEFT | Literal::ASTERISK) => true,
+                kind if is_word(kind, "from") => is_word(&self.peek(2).kind, "from"),
+                _ => self.peek_is_name(1),
+            };
+        if type_only {
+            self.advance();
+        }
+
+        // `import "a"` only runs the module
+        if !matches!(self.kind(), TokenKind::String(_)) {
+            let default = self.at_identifier();
+            if default {
+                specifiers.push(ImportSpecifier::Default(self.parse_binding_identifier()?));
+            }
+
+            if !default || self.eat(Literal::COMMA) {
+                if self.eat(Literal::ASTERISK) {
+                    self.expect_as()?;
+                    specifiers.push(ImportSpecifier::Namespace(self.parse_binding_identifier()?));
+                } else if self.at(Literal::CURLY_BRACKET_LEFT) {
+                    self.parse_named_imports(&mut specifiers)?;
+                } else {
+                    return Err(self.error("Expected an import specifier"));
+                }
+            }
+            self.expect_from()?;
+        }
+
+        let source = self.parse_module_source()?;
+        let attributes = self.parse_import_attributes()?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Import(Box::new(ImportDeclaration {
+            type_only,
+            specifiers,
+            source,
+            attributes,
+            span: self.finish(start),
+        })))
+    }
+
+    // `{ a, b as c, "d-e" as f }`
+    fn parse_named_imports(&mut self, specifiers: &mut Vec<ImportSpecifier>) -> Result<(), Errors> {
+        self.advance();
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            let start = self.current().span;
+            let type_only = self.eat_type_modifier();
+            let usable_as_binding = self.at_identifier();
+            let imported = self.parse_module_export_name()?;
+
+            let local = if self.at_word("as") {
+                self.advance();
+                self.parse_binding_identifier()?
+            } else {
+                // Without `as` the imported name is the binding, it can't be a string or keyword
+                match &imported {
+                    ModuleExportName::Identifier(identifier) if usable_as_binding => {
+                        self.check_binding_name(identifier)?;
+                        identifier.clone()
+                    }
+                    _ => return Err(self.error("Expected as")),
+                }
+            };
+
+            specifiers.push(ImportSpecifier::Named(NamedImport {
+                imported,
+                local,
+                type_only,
+                span: self.finish(start),
+            }));
+            if !self.at(Literal::CURLY_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or }")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // `type` in front of a name in an import or export list, `{ type A }`
+    fn eat_type_modifier(&mut self) -> bool {
+        let modifier = self.typescript()
+            && self.at_word("type")
+            && (self.peek_is_name(1) || matches!(self.peek(1).kind, TokenKind::String(_)))
+            && !is_word(&self.peek(1).kind, "as");
+        if modifier {
+            self.advance();
+        }
+        modifier
+    }
+
+    /**
+     * Everything after `export`, decorators already read belong to an exported class
+     */
+    pub(super) fn parse_export(&mut self, decorators: Vec<Decorator>) -> Result<Statement, Errors> {
+        let start = decorators
+            .first()
+            .map_or(self.current().span, |decorator| decorator.span);
+        self.advance();
+
+        if self.eat_keyword(Keyword::DEFAULT) {
+            return self.parse_export_default(start, decorators);
+        }
+
+        let decorated = !decorators.is_empty()
+            || matches!(
+                self.kind(),
+                TokenKind::Decorator(_) | TokenKind::Literal(Literal::AT)
+            );
+        if decorated {
+            let mut decorators = decorators;
+            decorators.extend(self.parse_decorators()?);
+            let class = self.parse_class(decorators, true)?;
+            return Ok(self.export_declaration(start, Statement::Class(Box::new(class))));
+        }
+
+        if self.eat(Literal::ASTERISK) {
+            let exported = if self.at_word("as") {
+                self.advance();
+                Some(self.parse_module_export_name()?)
+            } else {
+                None
+            };
+            self.expect_from()?;
+            let source = self.parse_module_source()?;
+            let attributes = self.parse_import_attributes()?;
+            self.consume_semicolon()?;
+
+            return Ok(Statement::ExportAll(Box::new(ExportAllDeclaration {
+                exported,
+                source,
+                attributes,
+                span: self.finish(start),
+            })));
+        }
+
+        if self.at(Literal::CURLY_BRACKET_LEFT) {
+            return self.parse_export_list(start, false);
+        }
+        // `export type { A }`
+        if self.typescript()
+            && self.at_word("type")
+            && matches!(
+                self.peek(1).kind,
+                TokenKind::Literal(Literal::CURLY_BRACKET_LEFT)
+            )
+        {
+            self.advance();
+            return self.parse_export_list(start, true);
+        }
+        if let Some(declaration) = self.parse_ts_declaration_opt()? {
+            return Ok(self.export_declaration(start, declaration));
+        }
+
+        let declaration = match self.kind() {
+            TokenKind::Identifier(
+                Keyword::VAR
+                | Keyword::CONST
+                | Keyword::STRICT_LET
+                | Keyword::FUNCTION
+                | Keyword::CLASS,
+            ) => self.parse_statement_list_item()?,
+            _ if self.at_async_function() => self.parse_statement_list_item()?,
+            _ => return Err(self.unexpected()),
+        };
+        Ok(self.export_declaration(start, declaration))
+    }
+
+    fn export_declaration(&self, start: Span, declaration: Statement) -> Statement {
+        Statement::ExportNamed(Box::new(ExportNamedDeclaration {
+            type_only: false,
+            declaration: Some(declaration),
+            specifiers: Vec::new(),
+            source: None,
+            attributes: Vec::new(),
+            span: self.finish(start),
+        }))
+    }
+
+    // `export default function () {}`, `export default class {}`, `export default interface A {}`
+    // or `export default expression;`
+    fn parse_export_default(
+        &mut self,
+        start: Span,
+        mut decorators: Vec<Decorator>,
+    ) -> Result<Statement, Errors> {
+        decorators.extend(self.parse_decorators()?);
+
+        let declaration = if !decorators.is_empty()
+            || self.at_keyword(Keyword::CLASS)
+            || self.at_abstract_class()
+        {
+            ExportDefault::Class(self.parse_class(decorators, false)?)
+        } else if self.at_keyword(Keyword::FUNCTION) {
+            let function_start = self.current().span;
+            ExportDefault::Function(self.parse_function(
+                function_start,
+                false,
+                FunctionKind::DefaultExport,
+            )?)
+        } else if self.at_async_function() {
+            let function_start = self.advance();
+            ExportDefault::Function(self.parse_function(
+                function_start,
+                true,
+                FunctionKind::DefaultExport,
+            )?)
+        } else if self.at_interface() {
+            let interface_start = self.current().span;
+            ExportDefault::TsInterface(self.parse_interface(interface_start)?)
+        } else {
+            let expression = self.with_in(|parser| parser.parse_assignment())?;
+            self.consume_semicolon()?;
+            ExportDefault::Expression(expression)
+        };
+
+        Ok(Statement::ExportDefault(Box::new(
+            ExportDefaultDeclaration {
+                declaration,
+                span: self.finish(start),
+            },
+        )))
+    }
+
+    /**
+     * `export { a, b as c }` or `export { a } from "b"`
+     *
+     * Only a re-export can name its locals with strings or keywords, they come from the other module.
+     *
+     * @args type_only: bool - `export type { ... }`
+     */
+    fn parse_export_list(&mut self, start: Span, type_only: bool) -> Result<Statement, Errors> {
+        self.advance();
+        let mut specifiers = Vec::new();
+        let mut local_keyword = None;
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            let specifier_start = self.current().span;
+            let type_modifier = self.eat_type_modifier();
+            if local_keyword.is_none() && !self.at_identifier() {
+                local_keyword = Some(specifier_start);
+            }
+            let local = self.parse_module_export_name()?;
+            let exported = if self.at_word("as") {
+                self.advance();
+                self.parse_module_export_name()?
+            } else {
+                local.clone()
+            };
+
+            specifiers.push(ExportSpecifier {
+                local,
+                exported,
+                type_only: type_modifier,
+                span: self.finish(specifier_start),
+            });
+            if !self.at(Literal::CURLY_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or }")?;
+            }
+        }
+
+        let (source, attributes) = if self.at_word("from") {
+            self.advance();
+            let source = self.parse_module_source()?;
+            (Some(source), self.parse_import_attributes()?)
+        } else {
+            if let Some(span) = local_keyword {
+                return Err(Errors::ParseError(
+                    "Only local bindings can be exported",
+                    span,
+                ));
+            }
+            (None, Vec::new())
+        };
+        self.consume_semicolon()?;
+
+        Ok(Statement::ExportNamed(Box::new(ExportNamedDeclaration {
+            type_only,
+            declaration: None,
+            specifiers,
+            source,
+            attributes,
+            span: self.finish(start),
+        })))
+    }
+
+    // Any identifier name, keywords included, or a string
+    fn parse_module_export_name(&mut self) -> Result<ModuleExportName, Errors> {
+        if matches!(self.kind(), TokenKind::String(_)) {
+            return Ok(ModuleExportName::String(self.parse_string_literal()?));
+        }
+        Ok(ModuleExportName::Identifier(self.parse_identifier_name()?))
+    }
+
+    pub(super) fn parse_module_source(&mut self) -> Result<StringLiteral, Errors> {
+        if !matches!(self.kind(), TokenKind::String(_)) {
+            return Err(self.error("Expected a module specifier"));
+        }
+        self.parse_string_literal()
+    }
+
+    pub(super) fn parse_string_literal(&mut self) -> Result<StringLiteral, Errors> {
+        let TokenKind::String(string) = self.kind() else {
+            return Err(self.error("Expected a string"));
+        };
+        self.check_legacy_octal()?;
+        let value = string.value.clone();
+        let span = self.advance();
+
+        Ok(StringLiteral {
+            value,
+            raw: self.text(span),
+            span,
+        })
+    }
+
+    // `with { type: "json" }`, or the older `assert { ... }` on the same line
+    fn parse_import_attributes(&mut self) -> Result<Vec<ImportAttribute>, Errors> {
+        let keyword = self.at_keyword(Keyword::WITH)
+            || self.at_word("assert") && !self.current().newline_before;
+        if !keyword {
+            return Ok(Vec::new());
+        }
+        self.advance();
+        self.expect(Literal::CURLY_BRACKET_LEFT, "Expected {")?;
+
+        let mut attributes: Vec<ImportAttribute> = Vec::new();
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            let start = self.current().span;
+            let key = self.parse_module_export_name()?;
+            self.expect(Literal::COLON, "Expected :")?;
+            let value = self.parse_module_source()?;
+
+            if attributes
+                .iter()
+                .any(|attribute| same_name(&attribute.key, &key))
+            {
+                return Err(Errors::ParseError("Duplicate import attribute", key.span()));
+            }
+            attributes.push(ImportAttribute {
+                key,
+                value,
+                span: self.finish(start),
+            });
+            if !self.at(Literal::CURLY_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or }")?;
+            }
+        }
+
+        Ok(attributes)
+    }
+
+    fn expect_from(&mut self) -> Result<(), Errors> {
+        if !self.at_word("from") {
+            return Err(self.error("Expected from"));
+        }
+        self.advance();
+        Ok(())
+    }
+
+    fn expect_as(&mut self) -> Result<(), Errors> {
+        if !self.at_word("as") {
+            return Err(self.error("Expected as"));
+        }
+        self.advance();
+        Ok(())
+    }
+}
+
+// `type` and `"type"` name the same attribute
+fn same_name(left: &ModuleExportName, right: &ModuleExportName) -> bool {
+    let name = |name: &ModuleExportName| match name {
+        ModuleExportName::Identifier(Identifier { name, .. }) => name.to_string(),
+        ModuleExportName::String(string) => string.value.clone(),
+    };
+    name(left) == name(right)
+}
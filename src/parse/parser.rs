@@ -0,0 +1,815 @@
+use crate::error::errors::Errors;
+use crate::read::interner::Symbol;
+use crate::read::punctuator::Punctuator;
+use crate::read::tokenizer::{
+    Identifier as Keyword, Literal, SharedToken, SourceContext, Span, Token, TokenKind, TokenTrait,
+};
+use crate::read::typescript::Dialect;
+
+use super::ast::{Identifier, Program, SourceType};
+
+/**
+ * Parses a whole source into a Program
+ *
+ * @args source: &[u8] - the source text
+ * @args dialect: Dialect - what the source is lexed as
+ * @args source_type: SourceType - modules are strict and can import and export
+ *
+ * @returns the program, the first lexing or syntax error otherwise
+ */
+pub fn parse(source: &[u8], dialect: Dialect, source_type: SourceType) -> Result<Program, Errors> {
+    Parser::new(source, dialect, source_type)?.parse_program()
+}
+
+/**
+ * Parses a whole source, skipping what doesn't parse instead of giving up on it
+ *
+ * A statement, class member or interface member that fails is replaced by an Error node spanning
+ * the tokens skipped, parsing picks up again at the next `;`, line starting a statement or
+ * closing `}`. Files mid-refactor still give the parts that are fine.
+ *
+ * @args source: &[u8] - the source text
+ * @args dialect: Dialect - what the source is lexed as
+ * @args source_type: SourceType - modules are strict and can import and export
+ *
+ * @returns the partial program and every lexing and syntax error in source order
+ */
+pub fn parse_recovering(
+    source: &[u8],
+    dialect: Dialect,
+    source_type: SourceType,
+) -> (Program, Vec<Errors>) {
+    let (tokens, mut diagnostics) = Token::tokenize_recovering(source, dialect);
+    let mut parser = Parser::with_tokens(source, tokens, dialect, source_type);
+    parser.recovering = true;
+
+    // Every statement recovers, only an error that isn't about the source gets out
+    let body = parser.parse_body(true).unwrap_or_else(|error| {
+        parser.diagnostics.push(error);
+        Vec::new()
+    });
+    let program = Program {
+        source_type,
+        body,
+        span: parser.program_span(),
+    };
+
+    diagnostics.append(&mut parser.diagnostics);
+    diagnostics.sort_by_key(|error| error.span().map(|span| span.start));
+    (program, diagnostics)
+}
+
+/**
+ * Recursive descent parser over the tokens of one source
+ *
+ * The source is lexed up front, so looking ahead (is this `(` the start of an arrow function?)
+ * is indexing into the tokens rather than re-lexing.
+ */
+pub struct Parser<'a> {
+    pub(super) source: &'a [u8],
+    pub(super) tokens: Vec<Token>, // always ends with EndOfFile
+    pub(super) index: usize,
+    pub(super) dialect: Dialect,
+    pub(super) source_type: SourceType,
+    pub(super) context: Context,
+    pub(super) no_in: bool, // parsing the head of a for statement, `in` isn't an operator
+    pub(super) cover_initializer: Option<Span>, // `{ a = 1 }` seen, only valid as a pattern
+    pub(super) split_tokens: Vec<(usize, Token)>, // `>>` split by type arguments, with the original
+    pub(super) recovering: bool, // errors become Error nodes and diagnostics, see parse_recovering
+    pub(super) diagnostics: Vec<Errors>,
+    depth: usize, // expressions, statements, types and JSX elements currently open, see nested
+    pub(super) class_bodies: usize, // class bodies open around the current token
+}
+
+// How deep nodes nest before the parser gives up rather than overflowing the stack
+const MAX_DEPTH: usize = 256;
+
+// Where a node that failed to parse ends, see Parser::recover
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum Boundary {
+    Statement,
+    ClassMember,
+    TypeMember, // interface and type literal members, `,` separates them too
+}
+
+// Where to rewind to when a speculative parse fails
+pub(super) struct Checkpoint {
+    index: usize,
+    split_tokens: usize,
+    cover_initializer: Option<Span>,
+}
+
+// What the code being parsed sits in, saved and replaced around function bodies
+#[derive(Clone, Debug, Default)]
+pub(super) struct Context {
+    pub(super) strict: bool,
+    pub(super) in_function: bool,
+    pub(super) in_async: bool,              // `await` is an operator
+    pub(super) in_generator: bool,          // `yield` is an operator
+    pub(super) in_iteration: bool,          // `continue` is allowed
+    pub(super) in_switch: bool,             // `break` without a label is allowed
+    pub(super) labels: Vec<(Symbol, bool)>, // enclosing labels, true when labelling a loop
+    pub(super) in_ambient: bool, // inside `declare`, nothing has to be initialised or implemented
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(
+        source: &'a [u8],
+        dialect: Dialect,
+        source_type: SourceType,
+    ) -> Result<Self, Errors> {
+        let tokens = Token::tokenize_with_dialect(source, dialect)?;
+        Ok(Self::with_tokens(source, tokens, dialect, source_type))
+    }
+
+    fn with_tokens(
+        source: &'a [u8],
+        tokens: Vec<Token>,
+        dialect: Dialect,
+        source_type: SourceType,
+    ) -> Self {
+        let module = source_type == SourceType::Module;
+
+        Self {
+            source,
+            tokens,
+            index: 0,
+            dialect,
+            source_type,
+            context: Context {
+                strict: module,
+                in_async: module, // top-level await
+                ..Context::default()
+            },
+            no_in: false,
+            cover_initializer: None,
+            split_tokens: Vec::new(),
+            recovering: false,
+            diagnostics: Vec::new(),
+            depth: 0,
+            class_bodies: 0,
+        }
+    }
+
+    pub fn parse_program(mut self) -> Result<Program, Errors> {
+        let body = self.parse_body(true)?;
+
+        Ok(Program {
+            source_type: self.source_type,
+            body,
+            span: self.program_span(),
+        })
+    }
+
+    // The program spans the whole source, comments around the statements included
+    fn program_span(&self) -> Span {
+        Span {
+            start: 0,
+            end: self.current().span.end,
+            line: 1,
+            column: 0,
+        }
+    }
+
+    pub(super) fn current(&self) -> &Token {
+        &self.tokens[self.index]
+    }
+
+    pub(super) fn kind(&self) -> &TokenKind {
+        &self.current().kind
+    }
+
+    // Token `offset` places after the current one, EndOfFile past the end
+    pub(super) fn peek(&self, offset: usize) -> &Token {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.index + offset).min(last)]
+    }
+
+    pub(super) fn at_end(&self) -> bool {
+        matches!(self.kind(), TokenKind::EndOfFile)
+    }
+
+    // Moves past the current token, EndOfFile is never passed
+    pub(super) fn advance(&mut self) -> Span {
+        let span = self.current().span;
+        if !self.at_end() {
+            self.index += 1;
+        }
+        span
+    }
+
+    pub(super) fn at(&self, literal: Literal) -> bool {
+        matches!(self.kind(), TokenKind::Literal(current) if *current == literal)
+    }
+
+    pub(super) fn at_punctuator(&self, punctuator: Punctuator) -> bool {
+        matches!(self.kind(), TokenKind::Punctuator(current) if *current == punctuator)
+    }
+
+    pub(super) fn at_keyword(&self, keyword: Keyword) -> bool {
+        matches!(self.kind(), TokenKind::Identifier(current) if *current == keyword)
+    }
+
+    /**
+     * Whether the current token is a word spelled exactly like `word`
+     *
+     * Contextual keywords (`async`, `of`, `get`) only count without escapes.
+     */
+    pub(super) fn at_word(&self, word: &str) -> bool {
+        is_word(self.kind(), word)
+    }
+
+    pub(super) fn eat(&mut self, literal: Literal) -> bool {
+        let found = self.at(literal);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    pub(super) fn eat_punctuator(&mut self, punctuator: Punctuator) -> bool {
+        let found = self.at_punctuator(punctuator);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    pub(super) fn eat_keyword(&mut self, keyword: Keyword) -> bool {
+        let found = self.at_keyword(keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    pub(super) fn expect(
+        &mut self,
+        literal: Literal,
+        message: &'static str,
+    ) -> Result<Span, Errors> {
+        if !self.at(literal) {
+            return Err(self.error(message));
+        }
+        Ok(self.advance())
+    }
+
+    pub(super) fn expect_keyword(
+        &mut self,
+        keyword: Keyword,
+        message: &'static str,
+    ) -> Result<Span, Errors> {
+        if !self.at_keyword(keyword) {
+            return Err(self.error(message));
+        }
+        Ok(self.advance())
+    }
+
+    /**
+     * The `}` closing a block or body
+     *
+     * When recovering the end of input closes every block still open, with one error for all.
+     */
+    pub(super) fn expect_closing_brace(&mut self) -> Result<Span, Errors> {
+        if self.recovering && self.at_end() {
+            let error = self.error("Expected }");
+            if self.diagnostics.last().and_then(Errors::span) != error.span() {
+                self.diagnostics.push(error);
+            }
+            return Ok(self.current().span);
+        }
+        self.expect(Literal::CURLY_BRACKET_RIGHT, "Expected }")
+    }
+
+    /**
+     * Runs `parse` one level of nesting deeper
+     *
+     * Expressions, statements, types and JSX elements go through here, so thousands of `(` or
+     * `[` are an error instead of a stack overflow.
+     */
+    pub(super) fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Errors>,
+    ) -> Result<T, Errors> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("Too deeply nested"));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    // Error at the current token
+    pub(super) fn error(&self, message: &'static str) -> Errors {
+        if self.at_end() {
+            return Errors::ParseError("Unexpected end of input", self.current().span);
+        }
+        Errors::ParseError(message, self.current().span)
+    }
+
+    pub(super) fn unexpected(&self) -> Errors {
+        self.error("Unexpected token")
+    }
+
+    /**
+     * Span from the start of a node to the end of the last token it consumed
+     *
+     * @args start: Span - span of the node's first token
+     */
+    pub(super) fn finish(&self, start: Span) -> Span {
+        let end = match self.index.checked_sub(1) {
+            Some(previous) => self.tokens[previous].span.end,
+            None => start.end,
+        };
+        Span {
+            start: start.start,
+            end: end.max(start.start),
+            line: start.line,
+            column: start.column,
+        }
+    }
+
+    /**
+     * Ends a statement, `;` or a semicolon inserted before `}`, the end of input or a line break
+     */
+    pub(super) fn consume_semicolon(&mut self) -> Result<(), Errors> {
+        if self.eat(Literal::SEMICOLON) || self.can_insert_semicolon() {
+            return Ok(());
+        }
+        Err(self.error("Missing semicolon"))
+    }
+
+    pub(super) fn can_insert_semicolon(&self) -> bool {
+        self.at_end() || self.at(Literal::CURLY_BRACKET_RIGHT) || self.current().newline_before
+    }
+
+    pub(super) fn source_context(&self) -> SourceContext {
+        if self.source_type == SourceType::Module {
+            SourceContext::Module
+        } else if self.context.strict {
+            SourceContext::Strict
+        } else {
+            SourceContext::Sloppy
+        }
+    }
+
+    // Raw source text of a span
+    pub(super) fn text(&self, span: Span) -> String {
+        String::from_utf8_lossy(&self.source[span.start..span.end]).into_owned()
+    }
+
+    /**
+     * Runs `parse` with `in` allowed again, brackets and bodies inside a for head reset it
+     */
+    pub(super) fn with_in<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Errors>,
+    ) -> Result<T, Errors> {
+        let no_in = std::mem::replace(&mut self.no_in, false);
+        let result = parse(self);
+        self.no_in = no_in;
+        result
+    }
+
+    /**
+     * Runs `parse` in a fresh function context, labels and loops outside don't reach in
+     */
+    pub(super) fn in_function<T>(
+        &mut self,
+        is_async: bool,
+        is_generator: bool,
+        parse: impl FnOnce(&mut Self) -> Result<T, Errors>,
+    ) -> Result<T, Errors> {
+        let context = Context {
+            strict: self.context.strict,
+            in_function: true,
+            in_async: is_async,
+            in_generator: is_generator,
+            ..Context::default()
+        };
+        let outer = std::mem::replace(&mut self.context, context);
+        let result = self.with_in(parse);
+        self.context = outer;
+        result
+    }
+
+    /**
+     * Name of the current token when it can be an IdentifierName, reserved words included
+     *
+     * Property names, `a.if` and `{ class: 1 }`, take any word.
+     */
+    pub(super) fn identifier_name(&self) -> Option<Symbol> {
+        match self.kind() {
+            TokenKind::Name { value, .. } => Some(*value),
+            TokenKind::Identifier(keyword) => Some(Symbol::intern(keyword.as_str())),
+            TokenKind::TsKeyword(keyword) => Some(Symbol::intern(
+                std::str::from_utf8(keyword.get_value()).unwrap_or_default(),
+            )),
+            _ => None,
+        }
+    }
+
+    pub(super) fn parse_identifier_name(&mut self) -> Result<Identifier, Errors> {
+        let name = self
+            .identifier_name()
+            .ok_or_else(|| self.error("Expected a name"))?;
+        let span = self.advance();
+        Ok(Identifier { name, span })
+    }
+
+    /**
+     * Whether the current token can be used as an identifier here
+     *
+     * `yield` is an operator in generators and `await` in async functions and modules,
+     * strict mode code reserves `let`, `static` and friends.
+     */
+    pub(super) fn at_identifier(&self) -> bool {
+        match self.kind() {
+            TokenKind::Name { .. } | TokenKind::TsKeyword(_) => {
+                !self.kind().is_reserved(self.source_context())
+            }
+            TokenKind::Identifier(keyword) => match keyword {
+                Keyword::STRICT_YIELD => !self.context.in_generator && !self.context.strict,
+                Keyword::MODULE_ASYNC => {
+                    !self.context.in_async && self.source_type != SourceType::Module
+                }
+                _ => !keyword.is_reserved(self.source_context()),
+            },
+            _ => false,
+        }
+    }
+
+    // IdentifierReference, a name read or written by an expression
+    pub(super) fn parse_identifier(&mut self) -> Result<Identifier, Errors> {
+        if !self.at_identifier() {
+            return Err(match self.kind() {
+                TokenKind::Identifier(_) | TokenKind::Name { .. } => {
+                    self.error("Unexpected reserved word")
+                }
+                _ => self.error("Expected an identifier"),
+            });
+        }
+        self.parse_identifier_name()
+    }
+
+    /**
+     * BindingIdentifier, a name declared by a declaration, parameter or pattern
+     *
+     * Strict mode code can't declare `eval` or `arguments`.
+     */
+    pub(super) fn parse_binding_identifier(&mut self) -> Result<Identifier, Errors> {
+        let identifier = self.parse_identifier()?;
+        self.check_binding_name(&identifier)?;
+        Ok(identifier)
+    }
+
+    pub(super) fn check_binding_name(&self, identifier: &Identifier) -> Result<(), Errors> {
+        if self.context.strict && (identifier.name == "eval" || identifier.name == "arguments") {
+            return Err(Errors::ParseError(
+                "Invalid binding name in strict mode",
+                identifier.span,
+            ));
+        }
+        Ok(())
+    }
+
+    // `017`, `089` and the `\1` escape at the current token, sloppy mode only
+    pub(super) fn check_legacy_octal(&self) -> Result<(), Errors> {
+        if !self.context.strict {
+            return Ok(());
+        }
+        match self.kind() {
+            TokenKind::Number(number) if number.legacy => {
+                Err(self.error("Numbers with a leading zero aren't allowed in strict mode"))
+            }
+            TokenKind::String(string) if string.legacy_octal => {
+                Err(self.error("Octal escapes aren't allowed in strict mode"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /**
+     * Index of the `)` matching the `(` at `open`
+     *
+     * @returns None when the brackets aren't closed before the end
+     */
+    pub(super) fn matching_paren(&self, open: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        for (index, token) in self.tokens.iter().enumerate().skip(open) {
+            match token.kind {
+                TokenKind::Literal(Literal::OPEN_BRACKET_LEFT) => depth += 1,
+                TokenKind::Literal(Literal::OPEN_BRACKET_RIGHT) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(index);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub(super) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            index: self.index,
+            split_tokens: self.split_tokens.len(),
+            cover_initializer: self.cover_initializer,
+        }
+    }
+
+    // Back to the checkpoint, `>>` split since then is joined again
+    pub(super) fn rewind(&mut self, checkpoint: Checkpoint) {
+        while self.split_tokens.len() > checkpoint.split_tokens {
+            let Some((index, original)) = self.split_tokens.pop() else {
+                break;
+            };
+            self.tokens.remove(index + 1);
+            self.tokens[index] = original;
+        }
+        self.index = checkpoint.index;
+        self.cover_initializer = checkpoint.cover_initializer;
+    }
+
+    /**
+     * Runs `parse` speculatively, TypeScript can't tell `a < b` from `f<T>(b)` up front
+     *
+     * @returns what `parse` returned, None with the parser rewound when it failed
+     */
+    pub(super) fn try_parse<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Errors>,
+    ) -> Option<T> {
+        // A speculative parse has to fail to be rewound, not recover
+        let recovering = std::mem::replace(&mut self.recovering, false);
+        let checkpoint = self.checkpoint();
+        let result = parse(self);
+        self.recovering = recovering;
+        match result {
+            Ok(value) => Some(value),
+            Err(_) => {
+                self.rewind(checkpoint);
+                None
+            }
+        }
+    }
+
+    /**
+     * Parses one statement or member, standing in an Error node for it when it fails
+     *
+     * Without recovery the error is returned as is. With it the error is kept, the tokens up to
+     * the next boundary skipped and `error` makes the node spanning them. A syntax error caused
+     * by a token the lexer already reported isn't reported twice.
+     *
+     * @args boundary: Boundary - what is being parsed, which tells where it ends
+     * @args parse: FnOnce - parses the statement or member
+     * @args error_node: fn(Span) -> T - the Error variant of what is being parsed
+     */
+    pub(super) fn recover<T>(
+        &mut self,
+        boundary: Boundary,
+        parse: impl FnOnce(&mut Self) -> Result<T, Errors>,
+        error_node: fn(Span) -> T,
+    ) -> Result<T, Errors> {
+        if !self.recovering {
+            return parse(self);
+        }
+
+        let start = self.index;
+        let context = self.context.clone();
+        let no_in = self.no_in;
+        let error = match parse(self) {
+            Ok(node) => return Ok(node),
+            Err(error @ (Errors::ParseError(..) | Errors::TokenError(..))) => error,
+            Err(error) => return Err(error),
+        };
+        self.context = context;
+        self.no_in = no_in;
+        self.cover_initializer = None;
+
+        self.skip_to_boundary(start, boundary);
+        let lexed = self.tokens[start..self.index]
+            .iter()
+            .any(|token| matches!(token.kind, TokenKind::Error(_)));
+        if !lexed {
+            self.diagnostics.push(error);
+        }
+        Ok(error_node(self.finish(self.tokens[start].span)))
+    }
+
+    /**
+     * Skips from a failed statement or member to where the next one starts
+     *
+     * Brackets opened since `start` are skipped to their close. The skipping stops after a `;`,
+     * before a bracket closing one opened before `start`, the `}` of the enclosing block, and
+     * before a line that starts a statement or member, one outside the brackets or no more
+     * indented than the failed one. A line that starts with no keyword, an expression statement
+     * like `bar();`, counts when it is no more indented. At least one token is skipped, so the
+     * same error can't be hit again.
+     */
+    fn skip_to_boundary(&mut self, start: usize, boundary: Boundary) {
+        let column = self.tokens[start].span.column;
+        let mut open = Vec::new();
+        for token in &self.tokens[start..self.index] {
+            track_bracket(&token.kind, &mut open);
+        }
+        if self.index == start {
+            track_bracket(self.kind(), &mut open);
+            self.advance();
+        }
+
+        while !self.at_end() {
+            let token = self.current();
+            if opening_bracket(&token.kind).is_some_and(|opening| !open.contains(&opening)) {
+                return;
+            }
+            if token.newline_before
+                && (open.is_empty() || token.span.column <= column)
+                && self.starts_item(boundary)
+            {
+                return;
+            }
+            if token.newline_before
+                && token.span.column <= column
+                && boundary == Boundary::Statement
+                && self.starts_expression_statement()
+            {
+                return;
+            }
+            if open.is_empty()
+                && (self.at(Literal::SEMICOLON)
+                    || boundary == Boundary::TypeMember && self.at(Literal::COMMA))
+            {
+                self.advance();
+                return;
+            }
+            track_bracket(self.kind(), &mut open);
+            self.advance();
+        }
+    }
+
+    // Whether the current token, first on its line, starts what `boundary` is about
+    fn starts_item(&self, boundary: Boundary) -> bool {
+        match boundary {
+            // Members start with a name, modifier or bracket, any word will do
+            Boundary::ClassMember | Boundary::TypeMember => {
+                self.identifier_name().is_some()
+                    || matches!(
+                        self.kind(),
+                        TokenKind::PrivateName(_) | TokenKind::String(_)
+                    )
+                    || self.at(Literal::SQUARE_BRACKET_LEFT)
+                    || self.at(Literal::AT)
+            }
+            Boundary::Statement => {
+                matches!(
+                    self.kind(),
+                    TokenKind::Identifier(
+                        Keyword::VAR
+                            | Keyword::FUNCTION
+                            | Keyword::CLASS
+                            | Keyword::IF
+                            | Keyword::FOR
+                            | Keyword::WHILE
+                            | Keyword::DO
+                            | Keyword::RETURN
+                            | Keyword::THROW
+                            | Keyword::TRY
+                            | Keyword::SWITCH
+                            | Keyword::BREAK
+                            | Keyword::CONTINUE
+                            | Keyword::IMPORT
+                            | Keyword::EXPORT
+                            | Keyword::CONST
+                            | Keyword::STRICT_LET
+                    )
+                ) || [
+                    "async",
+                    "interface",
+                    "type",
+                    "enum",
+                    "declare",
+                    "namespace",
+                    "abstract",
+                ]
+                .iter()
+                .any(|word| self.at_word(word))
+            }
+        }
+    }
+
+    // A name, literal, `(`, `[` or unary operator, what an expression statement starts with
+    fn starts_expression_statement(&self) -> bool {
+        match self.kind() {
+            TokenKind::Name { .. }
+            | TokenKind::TsKeyword(_)
+            | TokenKind::Number(_)
+            | TokenKind::String(_)
+            | TokenKind::Template(_)
+            | TokenKind::Regex(_) => true,
+            TokenKind::Identifier(keyword) => matches!(
+                *keyword,
+                Keyword::THIS
+                    | Keyword::SUPER
+                    | Keyword::NEW
+                    | Keyword::TYPEOF
+                    | Keyword::VOID
+                    | Keyword::DELETE
+                    | Keyword::TRUE
+                    | Keyword::FALSE
+                    | Keyword::NULL
+                    | Keyword::STRICT_YIELD
+                    | Keyword::MODULE_ASYNC
+            ),
+            TokenKind::Literal(literal) => matches!(
+                *literal,
+                Literal::OPEN_BRACKET_LEFT
+                    | Literal::SQUARE_BRACKET_LEFT
+                    | Literal::EXCLAMATION_MARK
+                    | Literal::TILDE
+                    | Literal::PLUS
+                    | Literal::MINUS
+            ),
+            TokenKind::Punctuator(punctuator) => {
+                matches!(*punctuator, Punctuator::INCREMENT | Punctuator::DECREMENT)
+            }
+            _ => false,
+        }
+    }
+
+    // `>`, `>>`, `>=` and the other tokens a closing `>` can hide in
+    pub(super) fn at_greater_than(&self) -> bool {
+        self.current().split_greater_than().is_some()
+    }
+
+    /**
+     * The `>` closing type parameters or arguments
+     *
+     * `Array<Array<T>>` ends in one `>>` token, it is split and the second `>` left current.
+     */
+    pub(super) fn expect_greater_than(&mut self) -> Result<Span, Errors> {
+        match self.current().split_greater_than() {
+            Some((_, None)) => Ok(self.advance()),
+            Some((greater_than, Some(rest))) => {
+                let original = std::mem::replace(&mut self.tokens[self.index], greater_than);
+                self.tokens.insert(self.index + 1, rest);
+                self.split_tokens.push((self.index, original));
+                Ok(self.advance())
+            }
+            None => Err(self.error("Expected >")),
+        }
+    }
+}
+
+// Word spelled exactly like `word`, escaped names never count as contextual keywords
+pub(super) fn is_word(kind: &TokenKind, word: &str) -> bool {
+    match kind {
+        TokenKind::Name {
+            value,
+            escaped: false,
+        } => *value == word,
+        TokenKind::Identifier(keyword) => keyword.as_str() == word,
+        TokenKind::TsKeyword(keyword) => keyword.get_value() == word.as_bytes(),
+        _ => false,
+    }
+}
+
+// The first name spelled like an earlier one, names bound twice by parameters or a `let`
+pub(super) fn duplicate_name<'n>(names: &[&'n Identifier]) -> Option<&'n Identifier> {
+    names.iter().enumerate().find_map(|(index, name)| {
+        names[..index]
+            .iter()
+            .any(|earlier| earlier.name == name.name)
+            .then_some(*name)
+    })
+}
+
+// The bracket a closing bracket closes
+fn opening_bracket(kind: &TokenKind) -> Option<Literal> {
+    match kind {
+        TokenKind::Literal(Literal::OPEN_BRACKET_RIGHT) => Some(Literal::OPEN_BRACKET_LEFT),
+        TokenKind::Literal(Literal::SQUARE_BRACKET_RIGHT) => Some(Literal::SQUARE_BRACKET_LEFT),
+        TokenKind::Literal(Literal::CURLY_BRACKET_RIGHT) => Some(Literal::CURLY_BRACKET_LEFT),
+        _ => None,
+    }
+}
+
+// Brackets still open after a token, a close also drops the unclosed ones opened inside it
+fn track_bracket(kind: &TokenKind, open: &mut Vec<Literal>) {
+    if let Some(opening) = opening_bracket(kind) {
+        if let Some(position) = open.iter().rposition(|bracket| *bracket == opening) {
+            open.truncate(position);
+        }
+        return;
+    }
+    if let TokenKind::Literal(
+        literal @ (Literal::OPEN_BRACKET_LEFT
+        | Literal::SQUARE_BRACKET_LEFT
+        | Literal::CURLY_BRACKET_LEFT),
+    ) = kind
+    {
+        open.push(*literal);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::error::errors::Errors;
+use crate::read::punctuator::Punctuator;
+use crate::read::tokenizer::Literal;
+
+use super::ast::{
+    ArrayPattern, AssignmentOperator, AssignmentPattern, Expression, ExpressionOrSpread,
+    ObjectMember, ObjectPattern, ObjectPatternMember, Pattern, PatternProperty, PropertyKey,
+    PropertyKind, RestElement,
+};
+use super::parser::Parser;
+
+impl<'a> Parser<'a> {
+    // BindingPattern or BindingIdentifier, what `let`, parameters and catch declare
+    pub(super) fn parse_binding_pattern(&mut self) -> Result<Pattern, Errors> {
+        if self.at(Literal::CURLY_BRACKET_LEFT) {
+            self.parse_object_pattern()
+        } else if self.at(Literal::SQUARE_BRACKET_LEFT) {
+            self.parse_array_pattern()
+        } else {
+            Ok(Pattern::Identifier(self.parse_binding_identifier()?))
+        }
+    }
+
+    // A binding with an optional default, `a = 1` or `{ b } = {}`
+    pub(super) fn parse_binding_element(&mut self) -> Result<Pattern, Errors> {
+        let start = self.current().span;
+        let pattern = self.parse_binding_pattern()?;
+        if !self.eat(Literal::EQUAL) {
+            return Ok(pattern);
+        }
+
+        let right = self.with_in(|parser| parser.parse_assignment())?;
+        Ok(Pattern::Assignment(Box::new(AssignmentPattern {
+            left: pattern,
+            right,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_object_pattern(&mut self) -> Result<Pattern, Errors> {
+        let start = self.advance();
+        let mut properties = Vec::new();
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            let member_start = self.current().span;
+            if self.eat_punctuator(Punctuator::ELLIPSIS) {
+                let argument = Pattern::Identifier(self.parse_binding_identifier()?);
+                properties.push(ObjectPatternMember::Rest(RestElement {
+                    argument,
+                    span: self.finish(member_start),
+                }));
+                self.expect(Literal::CURLY_BRACKET_RIGHT, "A rest element must be last")?;
+                break;
+            }
+
+            let usable_as_name = self.at_identifier();
+            let key = self.parse_property_key(false)?;
+            let computed = matches!(key, PropertyKey::Computed(_));
+
+            let (value, shorthand) = if self.eat(Literal::COLON) {
+                (self.parse_binding_element()?, false)
+            } else {
+                // `{ a }` and `{ a = 1 }` bind the key itself
+                let identifier = match &key {
+                    PropertyKey::Identifier(identifier) if usable_as_name => identifier.clone(),
+                    _ => return Err(self.error("Expected :")),
+                };
+                self.check_binding_name(&identifier)?;
+                let mut value = Pattern::Identifier(identifier);
+                if self.eat(Literal::EQUAL) {
+                    let right = self.with_in(|parser| parser.parse_assignment())?;
+                    value = Pattern::Assignment(Box::new(AssignmentPattern {
+                        left: value,
+                        right,
+                        span: self.finish(member_start),
+                    }));
+                }
+                (value, true)
+            };
+
+            properties.push(ObjectPatternMember::Property(PatternProperty {
+                key,
+                value,
+                shorthand,
+                computed,
+                span: self.finish(member_start),
+            }));
+            if !self.at(Literal::CURLY_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or }")?;
+            }
+        }
+
+        Ok(Pattern::Object(Box::new(ObjectPattern {
+            properties,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_array_pattern(&mut self) -> Result<Pattern, Errors> {
+        let start = self.advance();
+        let mut elements = Vec::new();
+
+        while !self.eat(Literal::SQUARE_BRACKET_RIGHT) {
+            if self.eat(Literal::COMMA) {
+                elements.push(None);
+                continue;
+            }
+
+            let element_start = self.current().span;
+            if self.eat_punctuator(Punctuator::ELLIPSIS) {
+                let argument = self.parse_binding_pattern()?;
+                elements.push(Some(Pattern::Rest(Box::new(RestElement {
+                    argument,
+                    span: self.finish(element_start),
+                }))));
+                self.expect(Literal::SQUARE_BRACKET_RIGHT, "A rest element must be last")?;
+                break;
+            }
+
+            elements.push(Some(self.parse_binding_element()?));
+            if !self.at(Literal::SQUARE_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or ]")?;
+            }
+        }
+
+        Ok(Pattern::Array(Box::new(ArrayPattern {
+            elements,
+            span: self.finish(start),
+        })))
+    }
+}
+
+/**
+ * Reinterprets an expression as an assignment pattern
+ *
+ * Used once `=`, `in` or `of` shows that an array or object literal was a target all along.
+ */
+pub(super) fn expression_to_pattern(
+    parser: &Parser,
+    expression: Expression,
+) -> Result<Pattern, Errors> {
+    let span = expression.span();
+    match expression {
+        Expression::Identifier(identifier) => {
+            parser.check_binding_name(&identifier)?;
+            Ok(Pattern::Identifier(identifier))
+        }
+        Expression::Member(member) => Ok(Pattern::Member(member)),
+        Expression::TsNonNull(_) | Expression::TsAs(_) | Expression::TsTypeAssertion(_) => {
+            parser.to_simple_target(expression)
+        }
+        Expression::Assignment(assignment) if assignment.operator == AssignmentOperator::Assign => {
+            if matches!(assignment.left, Pattern::Rest(_)) {
+                return Err(Errors::ParseError("Invalid assignment target", span));
+            }
+            Ok(Pattern::Assignment(Box::new(AssignmentPattern {
+                left: assignment.left,
+                right: assignment.right,
+                span: assignment.span,
+            })))
+        }
+        Expression::Array(array) => {
+            let count = array.elements.len();
+            let mut elements = Vec::with_capacity(count);
+            for (index, element) in array.elements.into_iter().enumerate() {
+                elements.push(match element {
+                    None => None,
+                    Some(ExpressionOrSpread::Expression(expression)) => {
+                        Some(expression_to_pattern(parser, expression)?)
+                    }
+                    Some(ExpressionOrSpread::Spread(spread)) => {
+                        if index + 1 != count {
+                            return Err(Errors::ParseError(
+                                "A rest element must be last",
+                                spread.span,
+                            ));
+                        }
+                        Some(Pattern::Rest(Box::new(RestElement {
+                            argument: rest_target(parser, spread.argument)?,
+                            span: spread.span,
+                        })))
+                    }
+                });
+            }
+            Ok(Pattern::Array(Box::new(ArrayPattern {
+                elements,
+                span: array.span,
+            })))
+        }
+        Expression::Object(object) => {
+            let count = object.properties.len();
+            let mut properties = Vec::with_capacity(count);
+            for (index, member) in object.properties.into_iter().enumerate() {
+                properties.push(match member {
+                    ObjectMember::Property(property) => {
+                        if property.method || property.kind != PropertyKind::Init {
+                            return Err(Errors::ParseError(
+                                "Invalid destructuring target",
+                                property.span,
+                            ));
+                        }
+                        ObjectPatternMember::Property(PatternProperty {
+                            key: property.key,
+                            value: expression_to_pattern(parser, property.value)?,
+                            shorthand: property.shorthand,
+                            computed: property.computed,
+                            span: property.span,
+                        })
+                    }
+                    ObjectMember::Spread(spread) => {
+                        if index + 1 != count {
+                            return Err(Errors::ParseError(
+                                "A rest element must be last",
+                                spread.span,
+                            ));
+                        }
+                        ObjectPatternMember::Rest(RestElement {
+                            argument: rest_target(parser, spread.argument)?,
+                            span: spread.span,
+                        })
+                    }
+                });
+            }
+            Ok(Pattern::Object(Box::new(ObjectPattern {
+                properties,
+                span: object.span,
+            })))
+        }
+        _ => Err(Errors::ParseError("Invalid assignment target", span)),
+    }
+}
+
+// `...rest` in an assignment pattern can't have a default
+fn rest_target(parser: &Parser, argument: Expression) -> Result<Pattern, Errors> {
+    if let Expression::Assignment(assignment) = &argument {
+        return Err(Errors::ParseError(
+            "A rest element can't have a default",
+            assignment.span,
+        ));
+    }
+    expression_to_pattern(parser, argument)
+}
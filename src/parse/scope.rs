@@ -0,0 +1,1085 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use crate::read::interner::Symbol;
+use crate::read::tokenizer::Span;
+
+use super::ast::{
+    ArrowBody, ArrowFunction, AssignmentExpression, AssignmentOperator, BlockStatement,
+    CatchClause, Class, ExportDefault, ExportDefaultDeclaration, ExportNamedDeclaration,
+    Expression, ForHead, ForInStatement, ForInit, ForOfStatement, ForStatement, Function,
+    FunctionBody, Identifier, ImportDeclaration, ImportSpecifier, JsxClosingElement,
+    JsxElementName, ModuleExportName, ObjectPatternMember, Param, Pattern, Program, PropertyKey,
+    SourceType, Statement, StaticBlock, SwitchStatement, TsEntityName, TsHeritage,
+    TsIndexSignature, TsModifiers, TsModuleBlock, TsModuleKind, TsModuleName, TsType,
+    TsTypeAnnotation, TsTypeArguments, TsTypeParameters, UpdateExpression, VariableDeclaration,
+    VariableKind, WithStatement,
+};
+use super::visit::{
+    walk_block_statement, walk_expression, walk_for_in_statement, walk_for_of_statement,
+    walk_for_statement, walk_program, walk_statement, walk_static_block, walk_ts_module_block,
+    Ancestors, Visit,
+};
+
+/**
+ * Scopes of a program with what they declare and what every name refers to
+ *
+ * Scopes, bindings and references are stored flat and point at each other by id. The global
+ * scope is the root, a module adds a module scope under it that holds what the module declares.
+ *
+ * Only values are resolved, names in type annotations aren't references and types aren't
+ * walked. Function declarations inside blocks are scoped to the block, in sloppy scripts they
+ * are also a `var` of the enclosing function (Annex B) unless a `let` or a parameter of the same
+ * name is in the way.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct ScopeTree {
+    scopes: Vec<Scope>,
+    bindings: Vec<Binding>,
+    references: Vec<Reference>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScopeId(usize);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BindingId(usize);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ReferenceId(usize);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScopeKind {
+    Global,
+    Module,
+    Function,     // functions, arrows, methods and class static blocks
+    FunctionBody, // the body of a function with parameter defaults, apart from the parameters
+    Block,        // blocks, switch cases and the head of a for loop
+    Class,
+    Catch,    // the catch parameter, the body is a block inside it
+    TsModule, // `namespace A { }` and `declare module "a" { }`
+    With,     // the body of a with statement, its object's properties are in scope there
+}
+
+#[derive(Clone, Debug)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub parent: Option<ScopeId>,
+    pub children: Vec<ScopeId>,
+    pub bindings: Vec<BindingId>, // in declaration order
+    pub strict: bool,
+    pub span: Span, // of the function, block, class or catch clause, the source for the root
+    names: HashMap<Symbol, BindingId>,
+    arrow: bool, // arrows have no `arguments` of their own
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BindingKind {
+    Var,
+    Let,
+    Const,
+    Function,
+    Class,
+    Param,
+    CatchParam,
+    Import,
+    Enum,
+    Namespace,
+    Type,      // interfaces and type aliases, never referenced since types aren't walked
+    Arguments, // `arguments`, only added to functions that use it
+}
+
+#[derive(Clone, Debug)]
+pub struct Binding {
+    pub name: Symbol,
+    pub kind: BindingKind,
+    pub span: Span, // of the name where it is first declared
+    pub scope: ScopeId,
+    pub references: Vec<ReferenceId>,
+    property: bool, // a TypeScript parameter property, `constructor(private a)`
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,     // `a = 1`, a declaration's initialiser isn't a reference
+    ReadWrite, // `a += 1` and `a++`
+}
+
+#[derive(Clone, Debug)]
+pub struct Reference {
+    pub name: Symbol,
+    pub span: Span,
+    pub scope: ScopeId,
+    pub access: Access,
+    pub binding: Option<BindingId>, // None for globals and names declared nowhere
+    pub dynamic: bool, // inside a with body, the name may be a property of its object instead
+}
+
+impl Scope {
+    fn new(kind: ScopeKind, parent: Option<ScopeId>, strict: bool, span: Span) -> Self {
+        Self {
+            kind,
+            parent,
+            children: Vec::new(),
+            bindings: Vec::new(),
+            strict,
+            span,
+            names: HashMap::new(),
+            arrow: false,
+        }
+    }
+
+    // Scopes `var` and function declarations hoist to
+    pub fn is_var_scope(&self) -> bool {
+        matches!(
+            self.kind,
+            ScopeKind::Global
+                | ScopeKind::Module
+                | ScopeKind::Function
+                | ScopeKind::FunctionBody
+                | ScopeKind::TsModule
+        )
+    }
+}
+
+impl Access {
+    pub fn is_read(self) -> bool {
+        matches!(self, Access::Read | Access::ReadWrite)
+    }
+
+    pub fn is_write(self) -> bool {
+        matches!(self, Access::Write | Access::ReadWrite)
+    }
+}
+
+impl ScopeTree {
+    /**
+     * Builds the scopes of a program and resolves every reference in it
+     *
+     * @args program: &Program - the parsed program, a partial one from parse_recovering works
+     */
+    pub fn analyze(program: &Program) -> Self {
+        let mut analyzer = Analyzer {
+            tree: ScopeTree::default(),
+            scope: ScopeId(0),
+            block_functions: Vec::new(),
+        };
+        let _ = analyzer.visit_program(program, &mut Ancestors::default());
+
+        let mut tree = analyzer.tree;
+        // After the walk, a `let` declared below the block still keeps its function in it
+        for (block, binding) in analyzer.block_functions {
+            tree.hoist_block_function(block, binding);
+        }
+        for index in 0..tree.references.len() {
+            tree.resolve(ReferenceId(index));
+        }
+        tree
+    }
+
+    pub fn root(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    /**
+     * The scope top-level declarations of the program end up in
+     *
+     * The global scope of a script, `var a` there makes a global. Modules keep theirs to
+     * themselves in the module scope.
+     */
+    pub fn top_level(&self) -> ScopeId {
+        match self.scopes[0].children.first() {
+            Some(&child) if self.scope(child).kind == ScopeKind::Module => child,
+            _ => ScopeId(0),
+        }
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    pub fn binding(&self, id: BindingId) -> &Binding {
+        &self.bindings[id.0]
+    }
+
+    pub fn reference(&self, id: ReferenceId) -> &Reference {
+        &self.references[id.0]
+    }
+
+    pub fn scopes(&self) -> impl Iterator<Item = (ScopeId, &Scope)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .map(|(index, scope)| (ScopeId(index), scope))
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (BindingId, &Binding)> {
+        self.bindings
+            .iter()
+            .enumerate()
+            .map(|(index, binding)| (BindingId(index), binding))
+    }
+
+    pub fn references(&self) -> impl Iterator<Item = (ReferenceId, &Reference)> {
+        self.references
+            .iter()
+            .enumerate()
+            .map(|(index, reference)| (ReferenceId(index), reference))
+    }
+
+    /**
+     * The binding a name resolves to from a scope, looking out through the enclosing ones
+     *
+     * @returns None when the name is declared in none of them
+     */
+    pub fn lookup(&self, scope: ScopeId, name: Symbol) -> Option<BindingId> {
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let scope = self.scope(id);
+            if let Some(&binding) = scope.names.get(&name) {
+                return Some(binding);
+            }
+            current = scope.parent;
+        }
+        None
+    }
+
+    // References to names declared nowhere in the program, globals like `window` included
+    pub fn unresolved(&self) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(|reference| reference.binding.is_none())
+    }
+
+    /**
+     * Assignments to names declared nowhere, which create a global in sloppy mode code
+     *
+     * Strict code throws on them instead and isn't flagged. Neither are assignments inside a
+     * with body, they only reach the global object when the with object has no such property.
+     */
+    pub fn implicit_globals(&self) -> impl Iterator<Item = &Reference> {
+        self.unresolved().filter(|reference| {
+            reference.access.is_write() && !reference.dynamic && !self.scope(reference.scope).strict
+        })
+    }
+
+    /**
+     * Parameters the function never reads
+     *
+     * Parameter properties are kept, they are read as members of the class. Overloads and other
+     * functions without a body have no scope and no parameters here.
+     *
+     * @returns the parameters in source order, their scope is the function's
+     */
+    pub fn unused_parameters(&self) -> Vec<BindingId> {
+        self.bindings()
+            .filter(|(_, binding)| binding.kind == BindingKind::Param && !binding.property)
+            .filter(|(_, binding)| {
+                !binding
+                    .references
+                    .iter()
+                    .any(|&reference| self.reference(reference).access.is_read())
+            })
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    // The scope a `var` declared in `scope` goes to, the nearest function or the top level
+    fn var_scope(&self, mut scope: ScopeId) -> ScopeId {
+        while let (false, Some(parent)) =
+            (self.scope(scope).is_var_scope(), self.scope(scope).parent)
+        {
+            scope = parent;
+        }
+        scope
+    }
+
+    // The parameter a `var` in the body of `function f(a = 1)` redeclares, it starts as its value
+    fn parameter(&self, body: ScopeId, name: Symbol) -> Option<BindingId> {
+        let scope = self.scope(body);
+        if scope.kind != ScopeKind::FunctionBody {
+            return None;
+        }
+        let &binding = self.scope(scope.parent?).names.get(&name)?;
+        (self.binding(binding).kind == BindingKind::Param).then_some(binding)
+    }
+
+    /**
+     * Also declares a sloppy function of a block where a `var` of its name would go
+     *
+     * Skipped where that `var` would be an early error or another variable, with a `let`,
+     * `const`, class or block function of the name between the block and the function, or a
+     * parameter of the function of the name.
+     *
+     * @args block: ScopeId - the block the function is declared in
+     * @args binding: BindingId - the function's binding in the block
+     */
+    fn hoist_block_function(&mut self, block: ScopeId, binding: BindingId) {
+        let Binding { name, span, .. } = self.bindings[binding.0];
+        let target = self.var_scope(block);
+        let mut current = self.scope(block).parent;
+        while let Some(id) = current {
+            if let Some(&existing) = self.scope(id).names.get(&name) {
+                let kind = self.binding(existing).kind;
+                if id != target || !matches!(kind, BindingKind::Var | BindingKind::Function) {
+                    return;
+                }
+            }
+            if id == target {
+                break;
+            }
+            current = self.scope(id).parent;
+        }
+        if self.parameter(target, name).is_none() {
+            self.declare(target, name, BindingKind::Function, span);
+        }
+    }
+
+    fn resolve(&mut self, id: ReferenceId) {
+        let Reference { name, scope, .. } = self.references[id.0];
+        let binding = self
+            .lookup(scope, name)
+            .or_else(|| self.arguments_binding(scope, name));
+        self.references[id.0].binding = binding;
+        self.references[id.0].dynamic = self.through_with(scope, binding);
+        if let Some(binding) = binding {
+            self.bindings[binding.0].references.push(id);
+        }
+    }
+
+    // Whether a with body sits between the scope and the binding, or the top level for None
+    fn through_with(&self, scope: ScopeId, binding: Option<BindingId>) -> bool {
+        let declared = binding.map(|binding| self.binding(binding).scope);
+        let mut current = Some(scope);
+        while let Some(id) = current.filter(|&id| Some(id) != declared) {
+            if self.scope(id).kind == ScopeKind::With {
+                return true;
+            }
+            current = self.scope(id).parent;
+        }
+        false
+    }
+
+    // `arguments` of the nearest function that isn't an arrow, declared when first used
+    fn arguments_binding(&mut self, scope: ScopeId, name: Symbol) -> Option<BindingId> {
+        if name != "arguments" {
+            return None;
+        }
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let scope = self.scope(id);
+            if scope.kind == ScopeKind::Function && !scope.arrow {
+                let span = scope.span;
+                return Some(self.declare(id, name, BindingKind::Arguments, span));
+            }
+            current = scope.parent;
+        }
+        None
+    }
+
+    // Adds the binding, or returns the one already there for a redeclared `var` or function
+    fn declare(
+        &mut self,
+        scope: ScopeId,
+        name: Symbol,
+        kind: BindingKind,
+        span: Span,
+    ) -> BindingId {
+        if let Some(&existing) = self.scopes[scope.0].names.get(&name) {
+            return existing;
+        }
+        let id = BindingId(self.bindings.len());
+        self.bindings.push(Binding {
+            name,
+            kind,
+            span,
+            scope,
+            references: Vec::new(),
+            property: false,
+        });
+        self.scopes[scope.0].names.insert(name, id);
+        self.scopes[scope.0].bindings.push(id);
+        id
+    }
+}
+
+// How the identifiers of a pattern are taken, declared or assigned to
+#[derive(Copy, Clone, Debug)]
+enum Target {
+    Declare(BindingKind),
+    Assign(Access),
+}
+
+struct Analyzer {
+    tree: ScopeTree,
+    scope: ScopeId,
+    block_functions: Vec<(ScopeId, BindingId)>, // sloppy functions declared in blocks
+}
+
+impl Analyzer {
+    /**
+     * Runs `walk` inside a new scope under the current one
+     *
+     * @args kind: ScopeKind - the kind of the new scope
+     * @args span: Span - the node it is the scope of
+     * @args strict: bool - a "use strict" directive of its own, classes are always strict
+     */
+    fn in_scope(
+        &mut self,
+        kind: ScopeKind,
+        span: Span,
+        strict: bool,
+        walk: impl FnOnce(&mut Self) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let parent = (!self.tree.scopes.is_empty()).then_some(self.scope);
+        let strict = strict || parent.is_some_and(|parent| self.tree.scope(parent).strict);
+        let id = ScopeId(self.tree.scopes.len());
+        self.tree
+            .scopes
+            .push(Scope::new(kind, parent, strict, span));
+        if let Some(parent) = parent {
+            self.tree.scopes[parent.0].children.push(id);
+        }
+
+        let outer = std::mem::replace(&mut self.scope, id);
+        let flow = walk(self);
+        self.scope = outer;
+        flow
+    }
+
+    // `var` goes to the nearest function or the top level, everything else stays in its block
+    fn declare(&mut self, identifier: &Identifier, kind: BindingKind) -> BindingId {
+        let mut scope = self.scope;
+        if kind == BindingKind::Var {
+            scope = self.tree.var_scope(scope);
+            if let Some(param) = self.tree.parameter(scope, identifier.name) {
+                return param;
+            }
+        }
+        self.tree
+            .declare(scope, identifier.name, kind, identifier.span)
+    }
+
+    fn reference(&mut self, identifier: &Identifier, access: Access) {
+        self.tree.references.push(Reference {
+            name: identifier.name,
+            span: identifier.span,
+            scope: self.scope,
+            access,
+            binding: None,
+            dynamic: false,
+        });
+    }
+
+    // Declares or assigns the names a pattern binds, its defaults and computed keys are read
+    fn pattern<'ast>(
+        &mut self,
+        pattern: &'ast Pattern,
+        target: Target,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match pattern {
+            Pattern::Identifier(identifier) => match target {
+                // The `this` of `function f(this: T)` is a type, not a parameter
+                Target::Declare(BindingKind::Param) if identifier.name == "this" => {}
+                Target::Declare(kind) => {
+                    self.declare(identifier, kind);
+                }
+                Target::Assign(access) => self.reference(identifier, access),
+            },
+            Pattern::Object(object) => {
+                for member in &object.properties {
+                    match member {
+                        ObjectPatternMember::Property(property) => {
+                            if let PropertyKey::Computed(key) = &property.key {
+                                self.visit_expression(key, ancestors)?;
+                            }
+                            self.pattern(&property.value, target, ancestors)?;
+                        }
+                        ObjectPatternMember::Rest(rest) => {
+                            self.pattern(&rest.argument, target, ancestors)?
+                        }
+                    }
+                }
+            }
+            Pattern::Array(array) => {
+                for element in array.elements.iter().flatten() {
+                    self.pattern(element, target, ancestors)?;
+                }
+            }
+            Pattern::Rest(rest) => self.pattern(&rest.argument, target, ancestors)?,
+            Pattern::Assignment(assignment) => {
+                self.pattern(&assignment.left, target, ancestors)?;
+                self.visit_expression(&assignment.right, ancestors)?;
+            }
+            // `a.b = 1` writes a property, `a` itself is only read
+            Pattern::Member(member) => self.visit_member_expression(member, ancestors)?,
+        }
+        ControlFlow::Continue(())
+    }
+
+    /**
+     * A function with its parameters and body, `inner_name` binds its name inside it
+     *
+     * Named function expressions can call themselves by name, declarations bind theirs outside.
+     */
+    fn function<'ast>(
+        &mut self,
+        function: &'ast Function,
+        inner_name: bool,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        // Overloads and `declare function` have no body, nor anything to scope
+        let Some(body) = &function.body else {
+            return ControlFlow::Continue(());
+        };
+        let strict = has_use_strict(&body.body);
+        self.in_scope(ScopeKind::Function, function.span, strict, |analyzer| {
+            if let (true, Some(id)) = (inner_name, &function.id) {
+                analyzer.declare(id, BindingKind::Function);
+            }
+            analyzer.params_and_body(&function.params, body, ancestors)
+        })
+    }
+
+    /**
+     * The parameters of a function then its body, in the function's scope
+     *
+     * Parameters with a default or a computed key are code run before the body, the body then
+     * gets a scope of its own and a closure in a default can't see its `var`s.
+     */
+    fn params_and_body<'ast>(
+        &mut self,
+        params: &'ast [Param],
+        body: &'ast FunctionBody,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        for param in params {
+            self.visit_param(param, ancestors)?;
+        }
+        match params.iter().any(|param| has_expressions(&param.pattern)) {
+            true => self.in_scope(ScopeKind::FunctionBody, body.span, false, |analyzer| {
+                analyzer.visit_function_body(body, ancestors)
+            }),
+            false => self.visit_function_body(body, ancestors),
+        }
+    }
+
+    // A class, `inner_name` binds the name of a class expression inside it
+    fn class<'ast>(
+        &mut self,
+        class: &'ast Class,
+        inner_name: bool,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        for decorator in &class.decorators {
+            self.visit_decorator(decorator, ancestors)?;
+        }
+        self.in_scope(ScopeKind::Class, class.span, true, |analyzer| {
+            if let (true, Some(id)) = (inner_name, &class.id) {
+                analyzer.declare(id, BindingKind::Class);
+            }
+            if let Some(super_class) = &class.super_class {
+                analyzer.visit_expression(super_class, ancestors)?;
+            }
+            analyzer.visit_class_body(&class.body, ancestors)
+        })
+    }
+}
+
+impl<'ast> Visit<'ast> for Analyzer {
+    fn visit_program(
+        &mut self,
+        node: &'ast Program,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        let strict = has_use_strict(&node.body);
+        self.in_scope(
+            ScopeKind::Global,
+            node.span,
+            strict,
+            |analyzer| match node.source_type {
+                SourceType::Module => {
+                    analyzer.in_scope(ScopeKind::Module, node.span, true, |analyzer| {
+                        walk_program(analyzer, node, ancestors)
+                    })
+                }
+                SourceType::Script => walk_program(analyzer, node, ancestors),
+            },
+        )
+    }
+
+    fn visit_statement(
+        &mut self,
+        node: &'ast Statement,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match node {
+            Statement::Function(function) => {
+                if let Some(id) = &function.id {
+                    let binding = self.declare(id, BindingKind::Function);
+                    let scope = self.tree.scope(self.scope);
+                    if !scope.strict
+                        && !scope.is_var_scope()
+                        && !function.is_async
+                        && !function.is_generator
+                    {
+                        self.block_functions.push((self.scope, binding));
+                    }
+                }
+                self.function(function, false, ancestors)
+            }
+            Statement::Class(class) => {
+                if let Some(id) = &class.id {
+                    self.declare(id, BindingKind::Class);
+                }
+                self.class(class, false, ancestors)
+            }
+            Statement::TsInterface(interface) => {
+                self.declare(&interface.id, BindingKind::Type);
+                ControlFlow::Continue(())
+            }
+            Statement::TsTypeAlias(alias) => {
+                self.declare(&alias.id, BindingKind::Type);
+                ControlFlow::Continue(())
+            }
+            Statement::TsEnum(declaration) => {
+                self.declare(&declaration.id, BindingKind::Enum);
+                walk_statement(self, node, ancestors)
+            }
+            Statement::TsModule(declaration) => {
+                // `namespace A.B { }` declares A, `declare global` and `module "a"` nothing
+                if let (TsModuleName::Entity(name), false) =
+                    (&declaration.id, declaration.kind == TsModuleKind::Global)
+                {
+                    self.declare(leftmost(name), BindingKind::Namespace);
+                }
+                walk_statement(self, node, ancestors)
+            }
+            _ => walk_statement(self, node, ancestors),
+        }
+    }
+
+    fn visit_variable_declaration(
+        &mut self,
+        node: &'ast VariableDeclaration,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        let kind = match node.kind {
+            VariableKind::Var => BindingKind::Var,
+            VariableKind::Let => BindingKind::Let,
+            VariableKind::Const => BindingKind::Const,
+        };
+        for declarator in &node.declarations {
+            self.pattern(&declarator.id, Target::Declare(kind), ancestors)?;
+            if let Some(init) = &declarator.init {
+                self.visit_expression(init, ancestors)?;
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expression(
+        &mut self,
+        node: &'ast Expression,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match node {
+            Expression::Identifier(identifier) => {
+                self.reference(identifier, Access::Read);
+                ControlFlow::Continue(())
+            }
+            _ => walk_expression(self, node, ancestors),
+        }
+    }
+
+    fn visit_assignment_expression(
+        &mut self,
+        node: &'ast AssignmentExpression,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        let access = match node.operator {
+            AssignmentOperator::Assign => Access::Write,
+            _ => Access::ReadWrite,
+        };
+        self.pattern(&node.left, Target::Assign(access), ancestors)?;
+        self.visit_expression(&node.right, ancestors)
+    }
+
+    fn visit_update_expression(
+        &mut self,
+        node: &'ast UpdateExpression,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match &node.argument {
+            Expression::Identifier(identifier) => {
+                self.reference(identifier, Access::ReadWrite);
+                ControlFlow::Continue(())
+            }
+            argument => self.visit_expression(argument, ancestors),
+        }
+    }
+
+    fn visit_function(
+        &mut self,
+        node: &'ast Function,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.function(node, true, ancestors)
+    }
+
+    fn visit_arrow_function(
+        &mut self,
+        node: &'ast ArrowFunction,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        let strict = match &node.body {
+            ArrowBody::Block(body) => has_use_strict(&body.body),
+            ArrowBody::Expression(_) => false,
+        };
+        self.in_scope(ScopeKind::Function, node.span, strict, |analyzer| {
+            let scope = analyzer.scope.0;
+            analyzer.tree.scopes[scope].arrow = true;
+            match &node.body {
+                ArrowBody::Expression(expression) => {
+                    for param in &node.params {
+                        analyzer.visit_param(param, ancestors)?;
+                    }
+                    analyzer.visit_expression(expression, ancestors)
+                }
+                ArrowBody::Block(body) => analyzer.params_and_body(&node.params, body, ancestors),
+            }
+        })
+    }
+
+    fn visit_param(
+        &mut self,
+        node: &'ast Param,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        for decorator in &node.decorators {
+            self.visit_decorator(decorator, ancestors)?;
+        }
+        let declared = self.tree.bindings.len();
+        self.pattern(
+            &node.pattern,
+            Target::Declare(BindingKind::Param),
+            ancestors,
+        )?;
+        if node.modifiers != TsModifiers::default() {
+            for binding in &mut self.tree.bindings[declared..] {
+                binding.property = true;
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_class(
+        &mut self,
+        node: &'ast Class,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.class(node, true, ancestors)
+    }
+
+    fn visit_static_block(
+        &mut self,
+        node: &'ast StaticBlock,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.in_scope(ScopeKind::Function, node.span, false, |analyzer| {
+            walk_static_block(analyzer, node, ancestors)
+        })
+    }
+
+    fn visit_block_statement(
+        &mut self,
+        node: &'ast BlockStatement,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.in_scope(ScopeKind::Block, node.span, false, |analyzer| {
+            walk_block_statement(analyzer, node, ancestors)
+        })
+    }
+
+    // `for (let i ...)` scopes i to the loop, a `var` or an expression head needs no scope
+    fn visit_for_statement(
+        &mut self,
+        node: &'ast ForStatement,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match &node.init {
+            Some(ForInit::Variable(declaration)) if declaration.kind != VariableKind::Var => self
+                .in_scope(ScopeKind::Block, node.span, false, |analyzer| {
+                    walk_for_statement(analyzer, node, ancestors)
+                }),
+            _ => walk_for_statement(self, node, ancestors),
+        }
+    }
+
+    fn visit_for_in_statement(
+        &mut self,
+        node: &'ast ForInStatement,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match lexical_head(&node.left) {
+            true => self.in_scope(ScopeKind::Block, node.span, false, |analyzer| {
+                walk_for_in_statement(analyzer, node, ancestors)
+            }),
+            false => walk_for_in_statement(self, node, ancestors),
+        }
+    }
+
+    fn visit_for_of_statement(
+        &mut self,
+        node: &'ast ForOfStatement,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match lexical_head(&node.left) {
+            true => self.in_scope(ScopeKind::Block, node.span, false, |analyzer| {
+                walk_for_of_statement(analyzer, node, ancestors)
+            }),
+            false => walk_for_of_statement(self, node, ancestors),
+        }
+    }
+
+    fn visit_for_head(
+        &mut self,
+        node: &'ast ForHead,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match node {
+            ForHead::Variable(declaration) => {
+                self.visit_variable_declaration(declaration, ancestors)
+            }
+            ForHead::Pattern(pattern) => {
+                self.pattern(pattern, Target::Assign(Access::Write), ancestors)
+            }
+        }
+    }
+
+    // The discriminant is outside, the cases share one block
+    fn visit_switch_statement(
+        &mut self,
+        node: &'ast SwitchStatement,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.visit_expression(&node.discriminant, ancestors)?;
+        self.in_scope(ScopeKind::Block, node.span, false, |analyzer| {
+            for case in &node.cases {
+                analyzer.visit_switch_case(case, ancestors)?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    // The object is read outside, the body sees its properties as names
+    fn visit_with_statement(
+        &mut self,
+        node: &'ast WithStatement,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.visit_expression(&node.object, ancestors)?;
+        self.in_scope(ScopeKind::With, node.span, false, |analyzer| {
+            analyzer.visit_statement(&node.body, ancestors)
+        })
+    }
+
+    fn visit_catch_clause(
+        &mut self,
+        node: &'ast CatchClause,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.in_scope(ScopeKind::Catch, node.span, false, |analyzer| {
+            if let Some(param) = &node.param {
+                analyzer.pattern(param, Target::Declare(BindingKind::CatchParam), ancestors)?;
+            }
+            analyzer.visit_block_statement(&node.body, ancestors)
+        })
+    }
+
+    fn visit_ts_module_block(
+        &mut self,
+        node: &'ast TsModuleBlock,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        self.in_scope(ScopeKind::TsModule, node.span, false, |analyzer| {
+            walk_ts_module_block(analyzer, node, ancestors)
+        })
+    }
+
+    fn visit_import_declaration(
+        &mut self,
+        node: &'ast ImportDeclaration,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        for specifier in &node.specifiers {
+            let local = match specifier {
+                ImportSpecifier::Default(local) | ImportSpecifier::Namespace(local) => local,
+                ImportSpecifier::Named(named) => &named.local,
+            };
+            self.declare(local, BindingKind::Import);
+        }
+        ControlFlow::Continue(())
+    }
+
+    // `export { a }` reads the local a, `export { a } from "b"` is about b's a
+    fn visit_export_named_declaration(
+        &mut self,
+        node: &'ast ExportNamedDeclaration,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        if let Some(declaration) = &node.declaration {
+            self.visit_statement(declaration, ancestors)?;
+        }
+        if node.source.is_none() {
+            for specifier in &node.specifiers {
+                if let ModuleExportName::Identifier(local) = &specifier.local {
+                    self.reference(local, Access::Read);
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_export_default_declaration(
+        &mut self,
+        node: &'ast ExportDefaultDeclaration,
+        ancestors: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        match &node.declaration {
+            ExportDefault::Function(function) => {
+                if let Some(id) = &function.id {
+                    self.declare(id, BindingKind::Function);
+                }
+                self.function(function, false, ancestors)
+            }
+            ExportDefault::Class(class) => {
+                if let Some(id) = &class.id {
+                    self.declare(id, BindingKind::Class);
+                }
+                self.class(class, false, ancestors)
+            }
+            ExportDefault::TsInterface(interface) => {
+                self.declare(&interface.id, BindingKind::Type);
+                ControlFlow::Continue(())
+            }
+            ExportDefault::Expression(expression) => self.visit_expression(expression, ancestors),
+        }
+    }
+
+    // `<List />` reads List and `<ui.List />` reads ui, `<div />` is a tag and reads nothing
+    fn visit_jsx_element_name(
+        &mut self,
+        node: &'ast JsxElementName,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        let mut name = node;
+        while let JsxElementName::Member(member) = name {
+            name = &member.object;
+        }
+        if let JsxElementName::Identifier(identifier) = name {
+            let tag = identifier.name.with_str(|name| {
+                name.starts_with(|character: char| character.is_ascii_lowercase())
+            });
+            if !tag || !matches!(node, JsxElementName::Identifier(_)) {
+                self.reference(identifier, Access::Read);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    // The opening element already read the name
+    fn visit_jsx_closing_element(
+        &mut self,
+        _: &'ast JsxClosingElement,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    // Types name types, not values, and aren't walked
+    fn visit_ts_type(&mut self, _: &'ast TsType, _: &mut Ancestors<'ast>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_ts_type_annotation(
+        &mut self,
+        _: &'ast TsTypeAnnotation,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_ts_type_parameters(
+        &mut self,
+        _: &'ast TsTypeParameters,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_ts_type_arguments(
+        &mut self,
+        _: &'ast TsTypeArguments,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_ts_heritage(
+        &mut self,
+        _: &'ast TsHeritage,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_ts_index_signature(
+        &mut self,
+        _: &'ast TsIndexSignature,
+        _: &mut Ancestors<'ast>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+// Whether a body's directive prologue has "use strict"
+fn has_use_strict(body: &[Statement]) -> bool {
+    body.iter()
+        .map_while(|statement| match statement {
+            Statement::Expression(statement) => statement.directive.as_deref(),
+            _ => None,
+        })
+        .any(|directive| directive == "use strict")
+}
+
+// Whether binding the pattern runs code, a default value or a computed key
+fn has_expressions(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Identifier(_) | Pattern::Member(_) => false,
+        Pattern::Object(object) => object.properties.iter().any(|member| match member {
+            ObjectPatternMember::Property(property) => {
+                matches!(property.key, PropertyKey::Computed(_)) || has_expressions(&property.value)
+            }
+            ObjectPatternMember::Rest(rest) => has_expressions(&rest.argument),
+        }),
+        Pattern::Array(array) => array.elements.iter().flatten().any(has_expressions),
+        Pattern::Rest(rest) => has_expressions(&rest.argument),
+        Pattern::Assignment(_) => true,
+    }
+}
+
+// `let` and `const` heads of for-in and for-of loops get a scope of their own
+fn lexical_head(head: &ForHead) -> bool {
+    matches!(head, ForHead::Variable(declaration) if declaration.kind != VariableKind::Var)
+}
+
+// A of `A.B.C`
+fn leftmost(name: &TsEntityName) -> &Identifier {
+    match name {
+        TsEntityName::Identifier(identifier) => identifier,
+        TsEntityName::Qualified(qualified) => leftmost(&qualified.left),
+    }
+}
@@ -0,0 +1,835 @@
+use crate::error::errors::Errors;
+use crate::read::tokenizer::{Identifier as Keyword, Literal, TokenKind};
+
+use super::ast::{
+    BlockStatement, CatchClause, DoWhileStatement, Expression, ExpressionStatement, ForHead,
+    ForInStatement, ForInit, ForOfStatement, ForStatement, Identifier, IfStatement, JumpStatement,
+    LabeledStatement, Pattern, ReturnStatement, SequenceExpression, Statement, SwitchCase,
+    SwitchStatement, ThrowStatement, TryStatement, VariableDeclaration, VariableDeclarator,
+    VariableKind, WhileStatement, WithStatement,
+};
+use super::function::FunctionKind;
+use super::parser::{duplicate_name, is_word, Boundary, Parser};
+
+impl<'a> Parser<'a> {
+    /**
+     * Statements of the program or of a function body, up to the end or the closing `}`
+     *
+     * The directive prologue comes first, "use strict" makes the rest strict mode code.
+     *
+     * @args top_level: bool - the program itself, imports and exports are only allowed here
+     */
+    pub(super) fn parse_body(&mut self, top_level: bool) -> Result<Vec<Statement>, Errors> {
+        let mut body = Vec::new();
+        let mut prologue = true;
+        // A directive before "use strict" is checked once the rest turns strict
+        let mut octal_escape = None;
+
+        while !self.at_end() && (top_level || !self.at(Literal::CURLY_BRACKET_RIGHT)) {
+            let directive_token = matches!(self.kind(), TokenKind::String(_));
+            if prologue && matches!(self.kind(), TokenKind::String(string) if string.legacy_octal) {
+                octal_escape = octal_escape.or(Some(self.current().span));
+            }
+            let mut statement = if top_level {
+                self.recover(
+                    Boundary::Statement,
+                    Self::parse_module_item,
+                    Statement::Error,
+                )?
+            } else {
+                self.recover(
+                    Boundary::Statement,
+                    Self::parse_statement_list_item,
+                    Statement::Error,
+                )?
+            };
+
+            if prologue {
+                let strict = self.context.strict;
+                prologue = directive_token && self.mark_directive(&mut statement);
+                let turned_strict = !strict && self.context.strict;
+                if let Some(span) = octal_escape.filter(|_| turned_strict) {
+                    let error =
+                        Errors::ParseError("Octal escapes aren't allowed in strict mode", span);
+                    if !self.recovering {
+                        return Err(error);
+                    }
+                    self.diagnostics.push(error);
+                }
+            }
+            body.push(statement);
+        }
+
+        Ok(body)
+    }
+
+    // Sets the directive of a lone string statement, false when the statement isn't one
+    fn mark_directive(&mut self, statement: &mut Statement) -> bool {
+        let Statement::Expression(statement) = statement else {
+            return false;
+        };
+        let Expression::String(literal) = &statement.expression else {
+            return false;
+        };
+
+        let directive = literal.raw[1..literal.raw.len() - 1].to_owned();
+        if directive == "use strict" {
+            self.context.strict = true;
+        }
+        statement.directive = Some(directive);
+        true
+    }
+
+    /**
+     * StatementListItem, a statement or a declaration
+     *
+     * Declarations can't be the body of an `if` or a loop, those only take statements.
+     */
+    pub(super) fn parse_statement_list_item(&mut self) -> Result<Statement, Errors> {
+        if let Some(declaration) = self.parse_ts_declaration_opt()? {
+            return Ok(declaration);
+        }
+
+        match self.kind() {
+            TokenKind::Identifier(Keyword::FUNCTION) => {
+                let start = self.current().span;
+                Ok(Statement::Function(Box::new(self.parse_function(
+                    start,
+                    false,
+                    FunctionKind::Declaration,
+                )?)))
+            }
+            TokenKind::Identifier(Keyword::CLASS)
+            | TokenKind::Decorator(_)
+            | TokenKind::Literal(Literal::AT) => {
+                let decorators = self.parse_decorators()?;
+                Ok(Statement::Class(Box::new(
+                    self.parse_class(decorators, true)?,
+                )))
+            }
+            TokenKind::Identifier(Keyword::CONST) => self.parse_lexical_declaration(),
+            TokenKind::Identifier(Keyword::STRICT_LET) if self.at_let_declaration() => {
+                self.parse_lexical_declaration()
+            }
+            TokenKind::Identifier(Keyword::IMPORT | Keyword::EXPORT) if !self.at_import_call() => {
+                Err(self.error("Imports and exports are only allowed at the top level of a module"))
+            }
+            _ if self.at_async_function() => {
+                let start = self.advance();
+                Ok(Statement::Function(Box::new(self.parse_function(
+                    start,
+                    true,
+                    FunctionKind::Declaration,
+                )?)))
+            }
+            _ => self.parse_statement(),
+        }
+    }
+
+    pub(super) fn parse_statement(&mut self) -> Result<Statement, Errors> {
+        self.nested(|parser| {
+            let start = parser.current().span;
+
+            match parser.kind() {
+                TokenKind::Literal(Literal::CURLY_BRACKET_LEFT) => {
+                    Ok(Statement::Block(Box::new(parser.parse_block()?)))
+                }
+                TokenKind::Literal(Literal::SEMICOLON) => {
+                    parser.advance();
+                    Ok(Statement::Empty(start))
+                }
+                TokenKind::Identifier(keyword) => match keyword {
+                    Keyword::VAR => {
+                        let declaration =
+                            parser.parse_variable_declaration(VariableKind::Var, false)?;
+                        parser.consume_semicolon()?;
+                        Ok(Statement::Variable(Box::new(VariableDeclaration {
+                            span: parser.finish(start),
+                            ..declaration
+                        })))
+                    }
+                    Keyword::IF => parser.parse_if(),
+                    Keyword::FOR => parser.parse_for(),
+                    Keyword::WHILE => parser.parse_while(),
+                    Keyword::DO => parser.parse_do_while(),
+                    Keyword::CONTINUE | Keyword::BREAK => parser.parse_jump(),
+                    Keyword::RETURN => parser.parse_return(),
+                    Keyword::WITH => parser.parse_with(),
+                    Keyword::SWITCH => parser.parse_switch(),
+                    Keyword::THROW => parser.parse_throw(),
+                    Keyword::TRY => parser.parse_try(),
+                    Keyword::DEBUGGER => {
+                        parser.advance();
+                        parser.consume_semicolon()?;
+                        Ok(Statement::Debugger(parser.finish(start)))
+                    }
+                    // Annex B, `if (a) function b() {}` in sloppy mode
+                    Keyword::FUNCTION if !parser.context.strict => Ok(Statement::Function(
+                        Box::new(parser.parse_function(start, false, FunctionKind::Declaration)?),
+                    )),
+                    Keyword::FUNCTION | Keyword::CLASS | Keyword::CONST => {
+                        Err(parser.error("Declarations aren't allowed here"))
+                    }
+                    _ => parser.parse_expression_or_labeled_statement(),
+                },
+                _ => parser.parse_expression_or_labeled_statement(),
+            }
+        })
+    }
+
+    fn parse_expression_or_labeled_statement(&mut self) -> Result<Statement, Errors> {
+        let start = self.current().span;
+
+        if self.at_identifier() && matches!(self.peek(1).kind, TokenKind::Literal(Literal::COLON)) {
+            return self.parse_labeled();
+        }
+        if self.at_keyword(Keyword::STRICT_LET)
+            && matches!(
+                self.peek(1).kind,
+                TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT)
+            )
+        {
+            return Err(self.error("Lexical declarations aren't allowed here"));
+        }
+
+        let expression = self.parse_expression()?;
+        self.consume_semicolon()?;
+        Ok(Statement::Expression(Box::new(ExpressionStatement {
+            expression,
+            directive: None,
+            span: self.finish(start),
+        })))
+    }
+
+    pub(super) fn parse_block(&mut self) -> Result<BlockStatement, Errors> {
+        let start = self.expect(Literal::CURLY_BRACKET_LEFT, "Expected {")?;
+        let mut body = Vec::new();
+        while !self.at(Literal::CURLY_BRACKET_RIGHT) && !self.at_end() {
+            body.push(self.recover(
+                Boundary::Statement,
+                Self::parse_statement_list_item,
+                Statement::Error,
+            )?);
+        }
+        self.expect_closing_brace()?;
+
+        Ok(BlockStatement {
+            body,
+            span: self.finish(start),
+        })
+    }
+
+    /**
+     * `let` starts a declaration when a binding follows it, otherwise it is a name (`let = 1`)
+     */
+    pub(super) fn at_let_declaration(&self) -> bool {
+        if !self.at_keyword(Keyword::STRICT_LET) {
+            return false;
+        }
+        let next = self.peek(1);
+        match &next.kind {
+            TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT | Literal::CURLY_BRACKET_LEFT) => true,
+            // `let \n a = 1` is still a declaration, `let \n in x` isn't
+            TokenKind::Identifier(Keyword::IN | Keyword::INSTANCEOF) => false,
+            // `for (let of of x)` declares `of`, `for (let of x)` is an error either way
+            TokenKind::Identifier(_) | TokenKind::Name { .. } | TokenKind::TsKeyword(_) => true,
+            _ => false,
+        }
+    }
+
+    // `async function`, no line break is allowed between the two
+    pub(super) fn at_async_function(&self) -> bool {
+        let next = self.peek(1);
+        self.at_word("async")
+            && matches!(next.kind, TokenKind::Identifier(Keyword::FUNCTION))
+            && !next.newline_before
+    }
+
+    // `import(` and `import.meta` are expressions, not declarations
+    pub(super) fn at_import_call(&self) -> bool {
+        self.at_keyword(Keyword::IMPORT)
+            && matches!(
+                self.peek(1).kind,
+                TokenKind::Literal(Literal::OPEN_BRACKET_LEFT | Literal::PERIOD)
+            )
+    }
+
+    fn parse_lexical_declaration(&mut self) -> Result<Statement, Errors> {
+        let start = self.current().span;
+        let kind = if self.at_keyword(Keyword::CONST) {
+            VariableKind::Const
+        } else {
+            VariableKind::Let
+        };
+        let declaration = self.parse_variable_declaration(kind, false)?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Variable(Box::new(VariableDeclaration {
+            span: self.finish(start),
+            ..declaration
+        })))
+    }
+
+    /**
+     * `var`, `let` or `const` and its declarators, the caller ends the statement
+     *
+     * @args in_for: bool - the head of a for statement, declarators without an initialiser
+     *                      are checked once it's known whether `in` or `of` follows
+     */
+    pub(super) fn parse_variable_declaration(
+        &mut self,
+        kind: VariableKind,
+        in_for: bool,
+    ) -> Result<VariableDeclaration, Errors> {
+        let start = self.advance();
+        let mut declarations = Vec::new();
+
+        loop {
+            let declarator_start = self.current().span;
+            if kind != VariableKind::Var && self.at_keyword(Keyword::STRICT_LET) {
+                return Err(self.error("let can't be declared by let or const"));
+            }
+            let id = self.parse_binding_pattern()?;
+            // `let a!: T` promises the value is assigned before it's read
+            let definite = self.typescript()
+                && matches!(id, Pattern::Identifier(_))
+                && !self.current().newline_before
+                && self.eat(Literal::EXCLAMATION_MARK);
+            let type_annotation = self.parse_type_annotation_opt()?;
+
+            let init = if self.eat(Literal::EQUAL) {
+                Some(self.parse_assignment()?)
+            } else {
+                let destructuring = !matches!(id, Pattern::Identifier(_));
+                let head = in_for && (self.at_keyword(Keyword::IN) || self.at_word("of"));
+                let required = kind == VariableKind::Const || destructuring;
+                if !head && required && !self.context.in_ambient {
+                    return Err(self.error("Missing initializer in declaration"));
+                }
+                None
+            };
+
+            declarations.push(VariableDeclarator {
+                id,
+                type_annotation,
+                init,
+                definite,
+                span: self.finish(declarator_start),
+            });
+            if !self.eat(Literal::COMMA) {
+                break;
+            }
+        }
+
+        // `let [a, a] = b`, a redeclaration by another statement is left to the scope analysis
+        if kind != VariableKind::Var {
+            let names: Vec<&Identifier> = declarations
+                .iter()
+                .flat_map(|declarator| declarator.id.bound_names())
+                .collect();
+            if let Some(name) = duplicate_name(&names) {
+                return Err(Errors::ParseError("Duplicate lexical binding", name.span));
+            }
+        }
+
+        Ok(VariableDeclaration {
+            kind,
+            declarations,
+            declare: false,
+            span: self.finish(start),
+        })
+    }
+
+    fn parse_if(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        let test = self.parse_condition()?;
+        let consequent = self.parse_statement()?;
+        let alternate = if self.eat_keyword(Keyword::ELSE) {
+            Some(self.parse_statement()?)
+        } else {
+            None
+        };
+
+        Ok(Statement::If(Box::new(IfStatement {
+            test,
+            consequent,
+            alternate,
+            span: self.finish(start),
+        })))
+    }
+
+    // `(expression)` of if, while, do-while, with and switch
+    fn parse_condition(&mut self) -> Result<Expression, Errors> {
+        self.expect(Literal::OPEN_BRACKET_LEFT, "Expected (")?;
+        let expression = self.parse_expression()?;
+        self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+        Ok(expression)
+    }
+
+    // Body of a loop, `continue` and unlabelled `break` are allowed inside
+    fn parse_loop_body(&mut self) -> Result<Statement, Errors> {
+        let in_iteration = std::mem::replace(&mut self.context.in_iteration, true);
+        let body = self.parse_statement();
+        self.context.in_iteration = in_iteration;
+        body
+    }
+
+    fn parse_while(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        let test = self.parse_condition()?;
+        let body = self.parse_loop_body()?;
+
+        Ok(Statement::While(Box::new(WhileStatement {
+            test,
+            body,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_do_while(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        let body = self.parse_loop_body()?;
+        self.expect_keyword(Keyword::WHILE, "Expected while")?;
+        let test = self.parse_condition()?;
+        // A semicolon is always inserted after do-while, even without a line break
+        self.eat(Literal::SEMICOLON);
+
+        Ok(Statement::DoWhile(Box::new(DoWhileStatement {
+            body,
+            test,
+            span: self.finish(start),
+        })))
+    }
+
+    /**
+     * `for (;;)`, `for (a in b)`, `for (a of b)` and `for await (a of b)`
+     *
+     * The head is parsed with `in` turned off, then the token after the first part decides
+     * which of the three it is.
+     */
+    fn parse_for(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        let is_await = self.at_keyword(Keyword::MODULE_ASYNC);
+        if is_await {
+            if !self.context.in_async {
+                return Err(self.error("for await is only allowed in async functions"));
+            }
+            self.advance();
+        }
+        self.expect(Literal::OPEN_BRACKET_LEFT, "Expected (")?;
+
+        let no_in = std::mem::replace(&mut self.no_in, true);
+        let head = self.parse_for_head();
+        self.no_in = no_in;
+        let init = head?;
+
+        let head_start = self.current().span;
+        if self.at_word("of") || self.at_keyword(Keyword::IN) {
+            let of = self.at_word("of");
+            let left = match init {
+                Some(ForInit::Variable(declaration)) => {
+                    self.check_for_declaration(&declaration, of)?;
+                    ForHead::Variable(declaration)
+                }
+                Some(ForInit::Expression(expression)) => {
+                    self.cover_initializer = None;
+                    ForHead::Pattern(self.to_assignment_pattern(expression)?)
+                }
+                None => return Err(self.unexpected()),
+            };
+            self.advance();
+
+            let right = if of {
+                self.parse_assignment()?
+            } else {
+                self.parse_expression()?
+            };
+            self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+            let body = self.parse_loop_body()?;
+            let span = self.finish(start);
+
+            return Ok(if of {
+                Statement::ForOf(Box::new(ForOfStatement {
+                    is_await,
+                    left,
+                    right,
+                    body,
+                    span,
+                }))
+            } else if is_await {
+                return Err(Errors::ParseError("for await needs of", head_start));
+            } else {
+                Statement::ForIn(Box::new(ForInStatement {
+                    left,
+                    right,
+                    body,
+                    span,
+                }))
+            });
+        }
+
+        if is_await {
+            return Err(self.error("Expected of"));
+        }
+        if let Some(span) = self.cover_initializer.take() {
+            return Err(Errors::ParseError(
+                "Invalid shorthand property initializer",
+                span,
+            ));
+        }
+        if let Some(ForInit::Variable(declaration)) = &init {
+            for declarator in &declaration.declarations {
+                let destructuring = !matches!(declarator.id, Pattern::Identifier(_));
+                if declarator.init.is_none()
+                    && (declaration.kind == VariableKind::Const || destructuring)
+                {
+                    return Err(Errors::ParseError(
+                        "Missing initializer in declaration",
+                        declarator.span,
+                    ));
+                }
+            }
+        }
+
+        self.expect(Literal::SEMICOLON, "Expected ;")?;
+        let test = if self.at(Literal::SEMICOLON) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(Literal::SEMICOLON, "Expected ;")?;
+        let update = if self.at(Literal::OPEN_BRACKET_RIGHT) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+        let body = self.parse_loop_body()?;
+
+        Ok(Statement::For(Box::new(ForStatement {
+            init,
+            test,
+            update,
+            body,
+            span: self.finish(start),
+        })))
+    }
+
+    // What comes before the first `;`, `in` or `of` of a for head
+    fn parse_for_head(&mut self) -> Result<Option<ForInit>, Errors> {
+        let kind = match self.kind() {
+            TokenKind::Literal(Literal::SEMICOLON) => return Ok(None),
+            TokenKind::Identifier(Keyword::VAR) => Some(VariableKind::Var),
+            TokenKind::Identifier(Keyword::CONST) => Some(VariableKind::Const),
+            TokenKind::Identifier(Keyword::STRICT_LET) if self.at_let_declaration() => {
+                Some(VariableKind::Let)
+            }
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            return Ok(Some(ForInit::Variable(
+                self.parse_variable_declaration(kind, true)?,
+            )));
+        }
+
+        // `for (async of x)` would read as an async arrow function
+        if self.at_word("async") && is_word(&self.peek(1).kind, "of") {
+            return Err(self.error("for (async of is ambiguous"));
+        }
+
+        // The first expression may still turn into the pattern of a for-in or for-of
+        let start = self.current().span;
+        let outer = self.cover_initializer.take();
+        let first = self.parse_assignment_or_cover()?;
+        let cover = self.cover_initializer;
+        self.cover_initializer = outer.or(cover);
+
+        if !self.at(Literal::COMMA) {
+            return Ok(Some(ForInit::Expression(first)));
+        }
+        if let Some(span) = cover {
+            return Err(Errors::ParseError(
+                "Invalid shorthand property initializer",
+                span,
+            ));
+        }
+        let mut expressions = vec![first];
+        while self.eat(Literal::COMMA) {
+            expressions.push(self.parse_assignment()?);
+        }
+        Ok(Some(ForInit::Expression(Expression::Sequence(Box::new(
+            SequenceExpression {
+                expressions,
+                span: self.finish(start),
+            },
+        )))))
+    }
+
+    // A for-in or for-of declaration binds exactly one name and has no initializer
+    fn check_for_declaration(
+        &self,
+        declaration: &VariableDeclaration,
+        of: bool,
+    ) -> Result<(), Errors> {
+        if declaration.declarations.len() != 1 {
+            return Err(Errors::ParseError(
+                "Only one binding is allowed in a for-in or for-of head",
+                declaration.span,
+            ));
+        }
+        let declarator = &declaration.declarations[0];
+        // Annex B still allows `for (var a = 1 in b)` in sloppy mode
+        let legacy = !of
+            && !self.context.strict
+            && declaration.kind == VariableKind::Var
+            && matches!(declarator.id, Pattern::Identifier(_));
+        if declarator.init.is_some() && !legacy {
+            return Err(Errors::ParseError(
+                "A for-in or for-of binding can't have an initializer",
+                declarator.span,
+            ));
+        }
+        Ok(())
+    }
+
+    // `break` and `continue`, with an optional label on the same line
+    fn parse_jump(&mut self) -> Result<Statement, Errors> {
+        let is_break = self.at_keyword(Keyword::BREAK);
+        let start = self.advance();
+
+        let label = if self.at_identifier() && !self.current().newline_before {
+            let label = self.parse_identifier_name()?;
+            let target = self
+                .context
+                .labels
+                .iter()
+                .rev()
+                .find(|(name, _)| *name == label.name);
+            match target {
+                None => return Err(Errors::ParseError("Undefined label", label.span)),
+                Some((_, false)) if !is_break => {
+                    return Err(Errors::ParseError(
+                        "continue can only target a loop label",
+                        label.span,
+                    ))
+                }
+                _ => {}
+            }
+            Some(label)
+        } else {
+            None
+        };
+
+        if label.is_none() {
+            if is_break && !self.context.in_iteration && !self.context.in_switch {
+                return Err(Errors::ParseError(
+                    "break is only allowed in loops and switch statements",
+                    start,
+                ));
+            }
+            if !is_break && !self.context.in_iteration {
+                return Err(Errors::ParseError(
+                    "continue is only allowed in loops",
+                    start,
+                ));
+            }
+        }
+        self.consume_semicolon()?;
+
+        let statement = Box::new(JumpStatement {
+            label,
+            span: self.finish(start),
+        });
+        Ok(if is_break {
+            Statement::Break(statement)
+        } else {
+            Statement::Continue(statement)
+        })
+    }
+
+    fn parse_return(&mut self) -> Result<Statement, Errors> {
+        if !self.context.in_function {
+            return Err(self.error("return is only allowed in functions"));
+        }
+        let start = self.advance();
+        let argument = if self.at(Literal::SEMICOLON) || self.can_insert_semicolon() {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.consume_semicolon()?;
+
+        Ok(Statement::Return(Box::new(ReturnStatement {
+            argument,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_with(&mut self) -> Result<Statement, Errors> {
+        if self.context.strict {
+            return Err(self.error("with isn't allowed in strict mode"));
+        }
+        let start = self.advance();
+        let object = self.parse_condition()?;
+        let body = self.parse_statement()?;
+
+        Ok(Statement::With(Box::new(WithStatement {
+            object,
+            body,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_switch(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        let discriminant = self.parse_condition()?;
+        self.expect(Literal::CURLY_BRACKET_LEFT, "Expected {")?;
+
+        let in_switch = std::mem::replace(&mut self.context.in_switch, true);
+        let cases = self.parse_switch_cases();
+        self.context.in_switch = in_switch;
+        let cases = cases?;
+
+        Ok(Statement::Switch(Box::new(SwitchStatement {
+            discriminant,
+            cases,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_switch_cases(&mut self) -> Result<Vec<SwitchCase>, Errors> {
+        let mut cases = Vec::new();
+        let mut has_default = false;
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            let start = self.current().span;
+            let test = if self.eat_keyword(Keyword::CASE) {
+                Some(self.parse_expression()?)
+            } else if self.eat_keyword(Keyword::DEFAULT) {
+                if has_default {
+                    return Err(Errors::ParseError("More than one default in switch", start));
+                }
+                has_default = true;
+                None
+            } else {
+                return Err(self.error("Expected case or default"));
+            };
+            self.expect(Literal::COLON, "Expected :")?;
+
+            let mut consequent = Vec::new();
+            while !self.at_keyword(Keyword::CASE)
+                && !self.at_keyword(Keyword::DEFAULT)
+                && !self.at(Literal::CURLY_BRACKET_RIGHT)
+            {
+                if self.at_end() {
+                    return Err(self.error("Expected }"));
+                }
+                consequent.push(self.recover(
+                    Boundary::Statement,
+                    Self::parse_statement_list_item,
+                    Statement::Error,
+                )?);
+            }
+
+            cases.push(SwitchCase {
+                test,
+                consequent,
+                span: self.finish(start),
+            });
+        }
+
+        Ok(cases)
+    }
+
+    fn parse_labeled(&mut self) -> Result<Statement, Errors> {
+        let start = self.current().span;
+        let label = self.parse_identifier_name()?;
+        self.advance();
+
+        if self
+            .context
+            .labels
+            .iter()
+            .any(|(name, _)| *name == label.name)
+        {
+            return Err(Errors::ParseError("Label already declared", label.span));
+        }
+        let is_loop = matches!(
+            self.kind(),
+            TokenKind::Identifier(Keyword::FOR | Keyword::WHILE | Keyword::DO)
+        );
+
+        self.context.labels.push((label.name, is_loop));
+        let body = if self.at_keyword(Keyword::FUNCTION) && !self.context.strict {
+            // Annex B, a labelled function declaration
+            let function_start = self.current().span;
+            self.parse_function(function_start, false, FunctionKind::Declaration)
+                .map(|function| Statement::Function(Box::new(function)))
+        } else {
+            self.parse_statement()
+        };
+        self.context.labels.pop();
+
+        Ok(Statement::Labeled(Box::new(LabeledStatement {
+            label,
+            body: body?,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_throw(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        if self.current().newline_before {
+            return Err(self.error("No line break is allowed after throw"));
+        }
+        let argument = self.parse_expression()?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Throw(Box::new(ThrowStatement {
+            argument,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_try(&mut self) -> Result<Statement, Errors> {
+        let start = self.advance();
+        let block = self.parse_block()?;
+
+        let handler = if self.at_keyword(Keyword::CATCH) {
+            let catch_start = self.advance();
+            let (param, type_annotation) = if self.eat(Literal::OPEN_BRACKET_LEFT) {
+                let param = self.parse_binding_pattern()?;
+                let type_annotation = self.parse_type_annotation_opt()?;
+                self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+                (Some(param), type_annotation)
+            } else {
+                (None, None)
+            };
+            let body = self.parse_block()?;
+            Some(CatchClause {
+                param,
+                type_annotation,
+                body,
+                span: self.finish(catch_start),
+            })
+        } else {
+            None
+        };
+
+        let finalizer = if self.eat_keyword(Keyword::FINALLY) {
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+        if handler.is_none() && finalizer.is_none() {
+            return Err(self.error("Expected catch or finally"));
+        }
+
+        Ok(Statement::Try(Box::new(TryStatement {
+            block,
+            handler,
+            finalizer,
+            span: self.finish(start),
+        })))
+    }
+}
@@ -0,0 +1,1241 @@
+use crate::error::errors::Errors;
+use crate::read::punctuator::Punctuator;
+use crate::read::tokenizer::{Identifier as Keyword, Literal, SourceContext, Span, TokenKind};
+
+use super::ast::{
+    BooleanLiteral, Expression, MemberExpression, MemberProperty, NumberLiteral, Param, Pattern,
+    PropertyKey, PropertyKind, Statement, StringLiteral, TsArrayType, TsConditionalType,
+    TsEntityName, TsEnumDeclaration, TsEnumMember, TsFunctionType, TsHeritage, TsImportType,
+    TsIndexSignature, TsIndexedAccessType, TsInferType, TsInterfaceBody, TsInterfaceDeclaration,
+    TsIntersectionType, TsKeywordKind, TsKeywordType, TsLiteralType, TsMappedModifier,
+    TsMappedType, TsMethodSignature, TsModuleBlock, TsModuleDeclaration, TsModuleKind,
+    TsModuleName, TsPredicateName, TsPropertySignature, TsQualifiedName, TsSignature,
+    TsTemplateLiteralType, TsTupleElement, TsTupleType, TsType, TsTypeAliasDeclaration,
+    TsTypeAnnotation, TsTypeArguments, TsTypeLiteral, TsTypeMember, TsTypeOperator,
+    TsTypeOperatorKind, TsTypeParameter, TsTypeParameters, TsTypePredicate, TsTypeQuery,
+    TsTypeQueryName, TsTypeReference, TsUnionType, UnaryExpression, UnaryOperator,
+};
+use super::parser::{is_word, Boundary, Parser};
+
+impl<'a> Parser<'a> {
+    pub(super) fn typescript(&self) -> bool {
+        self.dialect.is_typescript()
+    }
+
+    // `: type` after a binding or a class field, only read in TypeScript
+    pub(super) fn parse_type_annotation_opt(&mut self) -> Result<Option<TsTypeAnnotation>, Errors> {
+        if !self.typescript() || !self.at(Literal::COLON) {
+            return Ok(None);
+        }
+        let start = self.advance();
+        let type_annotation = self.parse_type()?;
+        Ok(Some(TsTypeAnnotation {
+            type_annotation,
+            span: self.finish(start),
+        }))
+    }
+
+    // `: type` of a function, which can also be a type predicate
+    pub(super) fn parse_return_type(&mut self) -> Result<Option<TsTypeAnnotation>, Errors> {
+        if !self.typescript() || !self.at(Literal::COLON) {
+            return Ok(None);
+        }
+        let start = self.advance();
+        let type_annotation = self.parse_type_or_predicate()?;
+        Ok(Some(TsTypeAnnotation {
+            type_annotation,
+            span: self.finish(start),
+        }))
+    }
+
+    /**
+     * A type, `A | B`, `(a: A) => B` or `A extends B ? C : D` included
+     */
+    pub(super) fn parse_type(&mut self) -> Result<TsType, Errors> {
+        self.nested(|parser| parser.parse_type_with(true))
+    }
+
+    /**
+     * @args conditional: bool - `extends` starts a conditional type, not so in the extends
+     *                            clause of another conditional type
+     */
+    fn parse_type_with(&mut self, conditional: bool) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        if let Some(function) = self.parse_function_type_opt()? {
+            return Ok(function);
+        }
+
+        let check_type = self.parse_union_type()?;
+        if !conditional || !self.at_keyword(Keyword::EXTENDS) || self.current().newline_before {
+            return Ok(check_type);
+        }
+
+        self.advance();
+        let extends_type = self.parse_type_with(false)?;
+        self.expect(Literal::QUESTION_MARK, "Expected ?")?;
+        let true_type = self.parse_type()?;
+        self.expect(Literal::COLON, "Expected :")?;
+        let false_type = self.parse_type()?;
+
+        Ok(TsType::Conditional(Box::new(TsConditionalType {
+            check_type,
+            extends_type,
+            true_type,
+            false_type,
+            span: self.finish(start),
+        })))
+    }
+
+    // `x is T`, `asserts x` and `asserts this is T`, or an ordinary type
+    fn parse_type_or_predicate(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        let asserts =
+            self.at_word("asserts") && !self.peek(1).newline_before && self.predicate_name_at(1);
+        if asserts {
+            self.advance();
+        } else if !(self.predicate_name_at(0)
+            && is_word(&self.peek(1).kind, "is")
+            && !self.peek(1).newline_before)
+        {
+            return self.parse_type();
+        }
+
+        let parameter_name = if self.at_keyword(Keyword::THIS) {
+            TsPredicateName::This(self.advance())
+        } else {
+            TsPredicateName::Identifier(self.parse_identifier_name()?)
+        };
+        let type_annotation = if self.at_word("is") && !self.current().newline_before {
+            self.advance();
+            Some(self.parse_type()?)
+        } else if asserts {
+            None
+        } else {
+            return Err(self.error("Expected is"));
+        };
+
+        Ok(TsType::Predicate(Box::new(TsTypePredicate {
+            asserts,
+            parameter_name,
+            type_annotation,
+            span: self.finish(start),
+        })))
+    }
+
+    fn predicate_name_at(&self, offset: usize) -> bool {
+        match &self.peek(offset).kind {
+            TokenKind::Identifier(Keyword::THIS) => true,
+            kind => self.type_name(kind),
+        }
+    }
+
+    // Names that can be a type reference, reserved words can't
+    fn type_name(&self, kind: &TokenKind) -> bool {
+        match kind {
+            TokenKind::Name { .. } | TokenKind::TsKeyword(_) => true,
+            TokenKind::Identifier(keyword) => !keyword.is_reserved(SourceContext::Strict),
+            _ => false,
+        }
+    }
+
+    /**
+     * `(a: A) => B`, `<T>(a: T) => T` and `new () => A`
+     *
+     * `(` can also open a parenthesised type, it is a function type when `=>` follows the
+     * parameters.
+     */
+    fn parse_function_type_opt(&mut self) -> Result<Option<TsType>, Errors> {
+        let start = self.current().span;
+        let is_abstract = self.at_word("abstract") && is_word(&self.peek(1).kind, "new");
+        if is_abstract || self.at_keyword(Keyword::NEW) {
+            if is_abstract {
+                self.advance();
+            }
+            self.advance();
+            return Ok(Some(self.parse_function_type(start, true, is_abstract)?));
+        }
+
+        if self.at(Literal::LESS_THAN) {
+            return Ok(Some(self.parse_function_type(start, false, false)?));
+        }
+        if !self.at(Literal::OPEN_BRACKET_LEFT) {
+            return Ok(None);
+        }
+        // `()` and `(...` can only be parameters
+        if matches!(
+            self.peek(1).kind,
+            TokenKind::Literal(Literal::OPEN_BRACKET_RIGHT)
+                | TokenKind::Punctuator(Punctuator::ELLIPSIS)
+        ) {
+            return Ok(Some(self.parse_function_type(start, false, false)?));
+        }
+        Ok(self.try_parse(|parser| parser.parse_function_type(start, false, false)))
+    }
+
+    fn parse_function_type(
+        &mut self,
+        start: Span,
+        is_constructor: bool,
+        is_abstract: bool,
+    ) -> Result<TsType, Errors> {
+        let type_parameters = self.parse_type_parameters_opt()?;
+        let params = self.parse_formal_params()?;
+
+        let arrow = self.current().span;
+        if !self.eat_punctuator(Punctuator::ARROW) {
+            return Err(self.error("Expected =>"));
+        }
+        let type_annotation = self.parse_type_or_predicate()?;
+        let return_type = TsTypeAnnotation {
+            type_annotation,
+            span: self.finish(arrow),
+        };
+
+        Ok(TsType::Function(Box::new(TsFunctionType {
+            type_parameters,
+            params,
+            return_type,
+            is_constructor,
+            is_abstract,
+            span: self.finish(start),
+        })))
+    }
+
+    // `A | B`, a leading `|` is allowed
+    fn parse_union_type(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        self.eat(Literal::PIPE);
+        let first = self.parse_intersection_type()?;
+        if !self.at(Literal::PIPE) {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        while self.eat(Literal::PIPE) {
+            types.push(self.parse_intersection_type()?);
+        }
+        Ok(TsType::Union(Box::new(TsUnionType {
+            types,
+            span: self.finish(start),
+        })))
+    }
+
+    fn parse_intersection_type(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        self.eat(Literal::AMPERSAND);
+        let first = self.parse_type_operator()?;
+        if !self.at(Literal::AMPERSAND) {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        while self.eat(Literal::AMPERSAND) {
+            types.push(self.parse_type_operator()?);
+        }
+        Ok(TsType::Intersection(Box::new(TsIntersectionType {
+            types,
+            span: self.finish(start),
+        })))
+    }
+
+    // `keyof T`, `unique symbol`, `readonly T[]` and `infer U`
+    fn parse_type_operator(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        let operator = match self.kind() {
+            kind if is_word(kind, "keyof") => Some(TsTypeOperatorKind::Keyof),
+            kind if is_word(kind, "unique") => Some(TsTypeOperatorKind::Unique),
+            kind if is_word(kind, "readonly") => Some(TsTypeOperatorKind::Readonly),
+            _ => None,
+        };
+        // `keyof` alone is a type reference to a type named keyof
+        let operand_follows = !matches!(
+            self.peek(1).kind,
+            TokenKind::Literal(
+                Literal::COMMA
+                    | Literal::SEMICOLON
+                    | Literal::GREATER_THAN
+                    | Literal::OPEN_BRACKET_RIGHT
+                    | Literal::SQUARE_BRACKET_RIGHT
+                    | Literal::EQUAL
+            ) | TokenKind::EndOfFile
+        );
+
+        if let Some(operator) = operator.filter(|_| operand_follows) {
+            self.advance();
+            let type_annotation = self.nested(Self::parse_type_operator)?;
+            return Ok(TsType::Operator(Box::new(TsTypeOperator {
+                operator,
+                type_annotation,
+                span: self.finish(start),
+            })));
+        }
+
+        if self.at_word("infer") && self.type_name(&self.peek(1).kind) {
+            self.advance();
+            let name = self.parse_identifier_name()?;
+            // `infer U extends string ? A : B` leaves the extends to the conditional type
+            let constraint = if self.at_keyword(Keyword::EXTENDS) {
+                self.try_parse(|parser| {
+                    parser.advance();
+                    let constraint = parser.parse_type_with(false)?;
+                    if parser.at(Literal::QUESTION_MARK) {
+                        return Err(parser.unexpected());
+                    }
+                    Ok(constraint)
+                })
+            } else {
+                None
+            };
+            let span = self.finish(start);
+            return Ok(TsType::Infer(Box::new(TsInferType {
+                type_parameter: TsTypeParameter {
+                    span: self.finish(name.span),
+                    name,
+                    constraint,
+                    default: None,
+                    is_in: false,
+                    is_out: false,
+                    is_const: false,
+                },
+                span,
+            })));
+        }
+
+        self.parse_postfix_type()
+    }
+
+    // `T[]` and `T[K]`, the bracket has to be on the same line
+    fn parse_postfix_type(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        let mut element = self.parse_primary_type()?;
+
+        while self.at(Literal::SQUARE_BRACKET_LEFT) && !self.current().newline_before {
+            self.advance();
+            element = if self.eat(Literal::SQUARE_BRACKET_RIGHT) {
+                TsType::Array(Box::new(TsArrayType {
+                    element_type: element,
+                    span: self.finish(start),
+                }))
+            } else {
+                let index_type = self.parse_type()?;
+                self.expect(Literal::SQUARE_BRACKET_RIGHT, "Expected ]")?;
+                TsType::IndexedAccess(Box::new(TsIndexedAccessType {
+                    object_type: element,
+                    index_type,
+                    span: self.finish(start),
+                }))
+            };
+        }
+
+        Ok(element)
+    }
+
+    fn parse_primary_type(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+
+        let literal = match self.kind() {
+            TokenKind::Identifier(Keyword::THIS) => {
+                self.advance();
+                return Ok(TsType::This(start));
+            }
+            TokenKind::Identifier(Keyword::VOID) => {
+                return Ok(self.keyword_type(TsKeywordKind::Void));
+            }
+            TokenKind::Identifier(Keyword::NULL) => {
+                return Ok(self.keyword_type(TsKeywordKind::Null));
+            }
+            TokenKind::Identifier(Keyword::TYPEOF) => return self.parse_type_query(),
+            TokenKind::Identifier(Keyword::IMPORT) => {
+                return Ok(TsType::Import(Box::new(self.parse_import_type()?)));
+            }
+            TokenKind::Identifier(Keyword::TRUE | Keyword::FALSE) => {
+                Expression::Boolean(BooleanLiteral {
+                    value: self.at_keyword(Keyword::TRUE),
+                    span: start,
+                })
+            }
+            TokenKind::String(string) => Expression::String(StringLiteral {
+                value: string.value.clone(),
+                raw: self.text(start),
+                span: start,
+            }),
+            TokenKind::Number(number) => Expression::Number(NumberLiteral {
+                value: number.value.clone(),
+                raw: self.text(start),
+                span: start,
+            }),
+            // `-1`
+            TokenKind::Literal(Literal::MINUS)
+                if matches!(self.peek(1).kind, TokenKind::Number(_)) =>
+            {
+                self.advance();
+                let TokenKind::Number(number) = self.kind() else {
+                    unreachable!()
+                };
+                let argument = Expression::Number(NumberLiteral {
+                    value: number.value.clone(),
+                    raw: self.text(self.current().span),
+                    span: self.current().span,
+                });
+                self.advance();
+                let literal = Expression::Unary(Box::new(UnaryExpression {
+                    operator: UnaryOperator::Minus,
+                    argument,
+                    span: self.finish(start),
+                }));
+                return Ok(TsType::Literal(Box::new(TsLiteralType {
+                    literal,
+                    span: self.finish(start),
+                })));
+            }
+            TokenKind::Template(_) => return self.parse_template_literal_type(),
+            TokenKind::Literal(Literal::CURLY_BRACKET_LEFT) => {
+                return if self.at_mapped_type() {
+                    self.parse_mapped_type()
+                } else {
+                    let members = self.parse_type_members()?;
+                    Ok(TsType::TypeLiteral(Box::new(TsTypeLiteral {
+                        members,
+                        span: self.finish(start),
+                    })))
+                };
+            }
+            TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT) => return self.parse_tuple_type(),
+            TokenKind::Literal(Literal::OPEN_BRACKET_LEFT) => {
+                self.advance();
+                let inner = self.parse_type()?;
+                self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+                return Ok(inner);
+            }
+            kind if self.type_name(kind) => {
+                let keyword = keyword_kind(kind)
+                    .filter(|_| !matches!(self.peek(1).kind, TokenKind::Literal(Literal::PERIOD)));
+                if let Some(keyword) = keyword {
+                    return Ok(self.keyword_type(keyword));
+                }
+                return self.parse_type_reference();
+            }
+            _ => return Err(self.error("Expected a type")),
+        };
+
+        self.advance();
+        Ok(TsType::Literal(Box::new(TsLiteralType {
+            literal,
+            span: start,
+        })))
+    }
+
+    fn keyword_type(&mut self, kind: TsKeywordKind) -> TsType {
+        let span = self.advance();
+        TsType::Keyword(TsKeywordType { kind, span })
+    }
+
+    // `A.B<C>`, type arguments have to start on the same line
+    fn parse_type_reference(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        let type_name = self.parse_entity_name()?;
+        let type_arguments = self.parse_type_arguments_opt()?;
+
+        Ok(TsType::Reference(Box::new(TsTypeReference {
+            type_name,
+            type_arguments,
+            span: self.finish(start),
+        })))
+    }
+
+    pub(super) fn parse_type_arguments_opt(&mut self) -> Result<Option<TsTypeArguments>, Errors> {
+        if self.at(Literal::LESS_THAN) && !self.current().newline_before {
+            return Ok(Some(self.parse_type_arguments()?));
+        }
+        Ok(None)
+    }
+
+    // `A.B.C`, the names after a dot can be reserved words
+    pub(super) fn parse_entity_name(&mut self) -> Result<TsEntityName, Errors> {
+        let start = self.current().span;
+        let first = self.parse_identifier_name()?;
+        let mut name = TsEntityName::Identifier(first);
+
+        while self.at(Literal::PERIOD) && self.peek_is_name(1) {
+            self.advance();
+            let right = self.parse_identifier_name()?;
+            name = TsEntityName::Qualified(Box::new(TsQualifiedName {
+                left: name,
+                right,
+                span: self.finish(start),
+            }));
+        }
+        Ok(name)
+    }
+
+    pub(super) fn peek_is_name(&self, offset: usize) -> bool {
+        matches!(
+            self.peek(offset).kind,
+            TokenKind::Name { .. } | TokenKind::Identifier(_) | TokenKind::TsKeyword(_)
+        )
+    }
+
+    // `typeof a.b<C>`
+    fn parse_type_query(&mut self) -> Result<TsType, Errors> {
+        let start = self.advance();
+        let expr_name = if self.at_keyword(Keyword::IMPORT) {
+            TsTypeQueryName::Import(self.parse_import_type()?)
+        } else {
+            TsTypeQueryName::Entity(self.parse_entity_name()?)
+        };
+        let type_arguments = self.parse_type_arguments_opt()?;
+
+        Ok(TsType::Query(Box::new(TsTypeQuery {
+            expr_name,
+            type_arguments,
+            span: self.finish(start),
+        })))
+    }
+
+    // `import("a").B<C>`
+    fn parse_import_type(&mut self) -> Result<TsImportType, Errors> {
+        let start = self.advance();
+        self.expect(Literal::OPEN_BRACKET_LEFT, "Expected (")?;
+        let argument = self.parse_module_source()?;
+        self.expect(Literal::OPEN_BRACKET_RIGHT, "Expected )")?;
+
+        let qualifier = if self.eat(Literal::PERIOD) {
+            Some(self.parse_entity_name()?)
+        } else {
+            None
+        };
+        let type_arguments = self.parse_type_arguments_opt()?;
+
+        Ok(TsImportType {
+            argument,
+            qualifier,
+            type_arguments,
+            span: self.finish(start),
+        })
+    }
+
+    // `` `prefix-${T}` ``
+    fn parse_template_literal_type(&mut self) -> Result<TsType, Errors> {
+        let start = self.current().span;
+        let (quasis, types) = self.parse_template_parts(false, |parser| parser.parse_type())?;
+
+        Ok(TsType::TemplateLiteral(Box::new(TsTemplateLiteralType {
+            quasis,
+            types,
+            span: self.finish(start),
+        })))
+    }
+
+    // `[A, b?: B, ...C[]]`
+    fn parse_tuple_type(&mut self) -> Result<TsType, Errors> {
+        let start = self.advance();
+        let mut element_types = Vec::new();
+
+        while !self.eat(Literal::SQUARE_BRACKET_RIGHT) {
+            let element_start = self.current().span;
+            let rest = self.eat_punctuator(Punctuator::ELLIPSIS);
+
+            let labelled = self.peek_is_name(0)
+                && match self.peek(1).kind {
+                    TokenKind::Literal(Literal::COLON) => true,
+                    TokenKind::Literal(Literal::QUESTION_MARK) => {
+                        matches!(self.peek(2).kind, TokenKind::Literal(Literal::COLON))
+                    }
+                    _ => false,
+                };
+            let (label, mut optional) = if labelled {
+                let label = self.parse_identifier_name()?;
+                let optional = self.eat(Literal::QUESTION_MARK);
+                self.advance();
+                (Some(label), optional)
+            } else {
+                (None, false)
+            };
+
+            let element_type = self.parse_type()?;
+            if label.is_none() && self.eat(Literal::QUESTION_MARK) {
+                optional = true;
+            }
+            element_types.push(TsTupleElement {
+                label,
+                element_type,
+                optional,
+                rest,
+                span: self.finish(element_start),
+            });
+            if !self.at(Literal::SQUARE_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or ]")?;
+            }
+        }
+
+        Ok(TsType::Tuple(Box::new(TsTupleType {
+            element_types,
+            span: self.finish(start),
+        })))
+    }
+
+    // `{ [K in`, `{ readonly [K in` or `{ -readonly [K in`
+    fn at_mapped_type(&self) -> bool {
+        let mut offset = 1;
+        if matches!(
+            self.peek(offset).kind,
+            TokenKind::Literal(Literal::PLUS | Literal::MINUS)
+        ) {
+            offset += 1;
+        }
+        if is_word(&self.peek(offset).kind, "readonly") {
+            offset += 1;
+        }
+        matches!(
+            self.peek(offset).kind,
+            TokenKind::Literal(Literal::SQUARE_BRACKET_LEFT)
+        ) && self.peek_is_name(offset + 1)
+            && matches!(
+                self.peek(offset + 2).kind,
+                TokenKind::Identifier(Keyword::IN)
+            )
+    }
+
+    fn parse_mapped_type(&mut self) -> Result<TsType, Errors> {
+        let start = self.advance();
+
+        let readonly = self.parse_mapped_modifier("readonly")?;
+        self.expect(Literal::SQUARE_BRACKET_LEFT, "Expected [")?;
+        let key = self.parse_identifier_name()?;
+        self.expect_keyword(Keyword::IN, "Expected in")?;
+        let constraint = self.parse_type()?;
+        let name_type = if self.at_word("as") {
+            self.advance();
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        self.expect(Literal::SQUARE_BRACKET_RIGHT, "Expected ]")?;
+
+        let optional = self.parse_mapped_modifier("?")?;
+        let type_annotation = if self.eat(Literal::COLON) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        if !self.eat(Literal::SEMICOLON) {
+            self.eat(Literal::COMMA);
+        }
+        self.expect(Literal::CURLY_BRACKET_RIGHT, "Expected }")?;
+
+        Ok(TsType::Mapped(Box::new(TsMappedType {
+            key,
+            constraint,
+            name_type,
+            optional,
+            readonly,
+            type_annotation,
+            span: self.finish(start),
+        })))
+    }
+
+    // `readonly`, `+readonly` or `-readonly`, the same for `?`
+    fn parse_mapped_modifier(
+        &mut self,
+        modifier: &str,
+    ) -> Result<Option<TsMappedModifier>, Errors> {
+        let at_modifier = |parser: &Self| {
+            if modifier == "?" {
+                parser.at(Literal::QUESTION_MARK)
+            } else {
+                parser.at_word(modifier)
+            }
+        };
+
+        let sign = if self.eat(Literal::PLUS) {
+            TsMappedModifier::Plus
+        } else if self.eat(Literal::MINUS) {
+            TsMappedModifier::Minus
+        } else if at_modifier(self) {
+            TsMappedModifier::True
+        } else {
+            return Ok(None);
+        };
+        if !at_modifier(self) {
+            return Err(self.unexpected());
+        }
+        self.advance();
+        Ok(Some(sign))
+    }
+
+    /**
+     * `{ members }` of an interface or type literal, separated by `;`, `,` or line breaks
+     */
+    pub(super) fn parse_type_members(&mut self) -> Result<Vec<TsTypeMember>, Errors> {
+        self.expect(Literal::CURLY_BRACKET_LEFT, "Expected {")?;
+        let mut members = Vec::new();
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            if self.at_end() {
+                return Err(self.error("Expected }"));
+            }
+            let member = self.recover(
+                Boundary::TypeMember,
+                Self::parse_type_member,
+                TsTypeMember::Error,
+            )?;
+            // A recovered member already skipped past its separator
+            let recovered = matches!(member, TsTypeMember::Error(_));
+            members.push(member);
+            if !recovered
+                && !self.eat(Literal::SEMICOLON)
+                && !self.eat(Literal::COMMA)
+                && !self.at(Literal::CURLY_BRACKET_RIGHT)
+                && !self.current().newline_before
+            {
+                return Err(self.error("Expected ; or }"));
+            }
+        }
+
+        Ok(members)
+    }
+
+    fn parse_type_member(&mut self) -> Result<TsTypeMember, Errors> {
+        let start = self.current().span;
+
+        if self.at(Literal::OPEN_BRACKET_LEFT) || self.at(Literal::LESS_THAN) {
+            return Ok(TsTypeMember::Call(self.parse_signature(start)?));
+        }
+        if self.at_keyword(Keyword::NEW)
+            && matches!(
+                self.peek(1).kind,
+                TokenKind::Literal(Literal::OPEN_BRACKET_LEFT | Literal::LESS_THAN)
+            )
+        {
+            self.advance();
+            return Ok(TsTypeMember::Construct(self.parse_signature(start)?));
+        }
+
+        let readonly = self.at_word("readonly") && self.key_follows();
+        if readonly {
+            self.advance();
+        }
+        if self.at_index_signature() {
+            return Ok(TsTypeMember::Index(
+                self.parse_index_signature(start, readonly, false)?,
+            ));
+        }
+
+        let kind = if self.at_word("get") && self.key_follows() {
+            PropertyKind::Get
+        } else if self.at_word("set") && self.key_follows() {
+            PropertyKind::Set
+        } else {
+            PropertyKind::Init
+        };
+        if kind != PropertyKind::Init {
+            self.advance();
+        }
+
+        let key = self.parse_property_key(false)?;
+        let computed = matches!(key, PropertyKey::Computed(_));
+        let optional = self.eat(Literal::QUESTION_MARK);
+
+        if kind != PropertyKind::Init
+            || self.at(Literal::OPEN_BRACKET_LEFT)
+            || self.at(Literal::LESS_THAN)
+        {
+            let signature = self.parse_signature(start)?;
+            return Ok(TsTypeMember::Method(TsMethodSignature {
+                key,
+                computed,
+                optional,
+                kind,
+                type_parameters: signature.type_parameters,
+                params: signature.params,
+                return_type: signature.return_type,
+                span: signature.span,
+            }));
+        }
+
+        let type_annotation = self.parse_type_annotation_opt()?;
+        Ok(TsTypeMember::Property(TsPropertySignature {
+            key,
+            computed,
+            optional,
+            readonly,
+            type_annotation,
+            span: self.finish(start),
+        }))
+    }
+
+    // `<T>(params): R` of call, construct and method signatures
+    fn parse_signature(&mut self, start: Span) -> Result<TsSignature, Errors> {
+        let type_parameters = self.parse_type_parameters_opt()?;
+        let params = self.parse_formal_params()?;
+        let return_type = self.parse_return_type()?;
+
+        Ok(TsSignature {
+            type_parameters,
+            params,
+            return_type,
+            span: self.finish(start),
+        })
+    }
+
+    // `[name:` opens an index signature, `[expression]` a computed key
+    pub(super) fn at_index_signature(&self) -> bool {
+        self.at(Literal::SQUARE_BRACKET_LEFT)
+            && self.peek_is_name(1)
+            && matches!(self.peek(2).kind, TokenKind::Literal(Literal::COLON))
+    }
+
+    /**
+     * `[key: string]: T`, the current token is the `[`
+     */
+    pub(super) fn parse_index_signature(
+        &mut self,
+        start: Span,
+        readonly: bool,
+        is_static: bool,
+    ) -> Result<TsIndexSignature, Errors> {
+        self.advance();
+        let mut parameters = Vec::new();
+
+        while !self.eat(Literal::SQUARE_BRACKET_RIGHT) {
+            let param_start = self.current().span;
+            let name = self.parse_identifier_name()?;
+            let type_annotation = self.parse_type_annotation_opt()?;
+            parameters.push(Param {
+                decorators: Vec::new(),
+                modifiers: Default::default(),
+                pattern: Pattern::Identifier(name),
+                optional: false,
+                type_annotation,
+                span: self.finish(param_start),
+            });
+            if !self.at(Literal::SQUARE_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or ]")?;
+            }
+        }
+
+        let type_annotation = self.parse_type_annotation_opt()?;
+        Ok(TsIndexSignature {
+            parameters,
+            type_annotation,
+            readonly,
+            is_static,
+            span: self.finish(start),
+        })
+    }
+
+    /**
+     * `<in out T extends U = V>` after the name of a declaration
+     *
+     * @returns None when no `<` follows, which is also the case outside TypeScript
+     */
+    pub(super) fn parse_type_parameters_opt(&mut self) -> Result<Option<TsTypeParameters>, Errors> {
+        if !self.typescript() || !self.at(Literal::LESS_THAN) {
+            return Ok(None);
+        }
+        let start = self.advance();
+        let mut params = Vec::new();
+
+        while !self.at_greater_than() {
+            let param_start = self.current().span;
+            let is_const = self.at_keyword(Keyword::CONST) && self.type_name(&self.peek(1).kind);
+            if is_const {
+                self.advance();
+            }
+            let is_in = self.at_keyword(Keyword::IN) && self.type_name(&self.peek(1).kind);
+            if is_in {
+                self.advance();
+            }
+            let is_out = self.at_word("out") && self.type_name(&self.peek(1).kind);
+            if is_out {
+                self.advance();
+            }
+
+            let name = self.parse_identifier()?;
+            let constraint = if self.eat_keyword(Keyword::EXTENDS) {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            let default = if self.eat(Literal::EQUAL) {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+
+            params.push(TsTypeParameter {
+                name,
+                constraint,
+                default,
+                is_in,
+                is_out,
+                is_const,
+                span: self.finish(param_start),
+            });
+            if !self.at_greater_than() {
+                self.expect(Literal::COMMA, "Expected , or >")?;
+            }
+        }
+        if params.is_empty() {
+            return Err(self.error("A type parameter list can't be empty"));
+        }
+        self.expect_greater_than()?;
+
+        Ok(Some(TsTypeParameters {
+            params,
+            span: self.finish(start),
+        }))
+    }
+
+    // `<A, B>`, the current token is the `<`
+    pub(super) fn parse_type_arguments(&mut self) -> Result<TsTypeArguments, Errors> {
+        let start = self.expect(Literal::LESS_THAN, "Expected <")?;
+        let mut params = Vec::new();
+
+        while !self.at_greater_than() {
+            params.push(self.parse_type()?);
+            if !self.at_greater_than() {
+                self.expect(Literal::COMMA, "Expected , or >")?;
+            }
+        }
+        if params.is_empty() {
+            return Err(self.error("A type argument list can't be empty"));
+        }
+        self.expect_greater_than()?;
+
+        Ok(TsTypeArguments {
+            params,
+            span: self.finish(start),
+        })
+    }
+
+    /**
+     * `interface`, `type`, `enum`, `namespace`, `declare` or `abstract class` at the current
+     * token
+     *
+     * Only `enum` is reserved, `type = 1` assigns to a variable named type. The token after
+     * the word decides, and it has to be on the same line.
+     *
+     * Out of scope, and syntax errors for now: the CommonJS forms `export = x` and
+     * `import x = require("y")`, and instantiation expressions `f<T>;` without a call. None of
+     * the documented targets is written with them.
+     *
+     * @returns None when no TypeScript declaration starts here
+     */
+    pub(super) fn parse_ts_declaration_opt(&mut self) -> Result<Option<Statement>, Errors> {
+        if !self.typescript() {
+            return Ok(None);
+        }
+        let start = self.current().span;
+        let next = self.peek(1);
+        let same_line = !next.newline_before;
+        let name_follows = same_line && self.type_name(&next.kind);
+        let string_follows = same_line && matches!(next.kind, TokenKind::String(_));
+        let enum_follows = is_word(&next.kind, "enum");
+        let declaration_follows = same_line && self.declaration_at(1);
+
+        let declaration = match self.kind() {
+            TokenKind::Identifier(Keyword::STRICT_INTERFACE) if name_follows => {
+                Statement::TsInterface(Box::new(self.parse_interface(start)?))
+            }
+            TokenKind::Identifier(Keyword::FUTURE_ENUM) => self.parse_enum(start, false)?,
+            TokenKind::Identifier(Keyword::CONST) if enum_follows => {
+                self.advance();
+                self.parse_enum(start, true)?
+            }
+            _ if self.at_word("type") && name_follows => self.parse_type_alias(start)?,
+            _ if self.at_word("namespace") && name_follows => {
+                self.parse_module_declaration(start)?
+            }
+            _ if self.at_word("module") && (name_follows || string_follows) => {
+                self.parse_module_declaration(start)?
+            }
+            _ if self.at_abstract_class() => {
+                Statement::Class(Box::new(self.parse_class(Vec::new(), true)?))
+            }
+            _ if self.at_word("declare") && declaration_follows => self.parse_declare(start)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(declaration))
+    }
+
+    // Whether the token `offset` places ahead can start what `declare` applies to
+    fn declaration_at(&self, offset: usize) -> bool {
+        let kind = &self.peek(offset).kind;
+        matches!(
+            kind,
+            TokenKind::Identifier(
+                Keyword::VAR
+                    | Keyword::CONST
+                    | Keyword::STRICT_LET
+                    | Keyword::FUNCTION
+                    | Keyword::CLASS
+                    | Keyword::FUTURE_ENUM
+                    | Keyword::STRICT_INTERFACE
+            )
+        ) || ["abstract", "async", "global", "module", "namespace", "type"]
+            .iter()
+            .any(|word| is_word(kind, word))
+    }
+
+    /**
+     * `declare` in front of a declaration, nothing after it has an implementation
+     *
+     * `declare const a: number;` needs no initialiser and `declare function` no body.
+     */
+    fn parse_declare(&mut self, start: Span) -> Result<Statement, Errors> {
+        self.advance();
+        let ambient = std::mem::replace(&mut self.context.in_ambient, true);
+        let declaration = if self.at_word("global") {
+            let global_start = self.current().span;
+            self.parse_module_declaration(global_start)
+        } else {
+            self.parse_statement_list_item()
+        };
+        self.context.in_ambient = ambient;
+
+        let span = self.finish(start);
+        let mut declaration = declaration?;
+        match &mut declaration {
+            Statement::Variable(node) => (node.declare, node.span) = (true, span),
+            Statement::Function(node) => (node.declare, node.span) = (true, span),
+            Statement::Class(node) => (node.declare, node.span) = (true, span),
+            Statement::TsInterface(node) => (node.declare, node.span) = (true, span),
+            Statement::TsTypeAlias(node) => (node.declare, node.span) = (true, span),
+            Statement::TsEnum(node) => (node.declare, node.span) = (true, span),
+            Statement::TsModule(node) => (node.declare, node.span) = (true, span),
+            _ => {
+                return Err(Errors::ParseError(
+                    "Expected a declaration after declare",
+                    declaration.span(),
+                ))
+            }
+        }
+        Ok(declaration)
+    }
+
+    // Whether `interface` here starts an interface declaration, `export default interface A {}`
+    pub(super) fn at_interface(&self) -> bool {
+        let next = self.peek(1);
+        self.typescript()
+            && self.at_keyword(Keyword::STRICT_INTERFACE)
+            && !next.newline_before
+            && self.type_name(&next.kind)
+    }
+
+    // `interface A<T> extends B { members }`
+    pub(super) fn parse_interface(
+        &mut self,
+        start: Span,
+    ) -> Result<TsInterfaceDeclaration, Errors> {
+        self.advance();
+        let id = self.parse_binding_identifier()?;
+        let type_parameters = self.parse_type_parameters_opt()?;
+        let extends = if self.eat_keyword(Keyword::EXTENDS) {
+            self.parse_heritage_list()?
+        } else {
+            Vec::new()
+        };
+
+        let body_start = self.current().span;
+        let members = self.parse_type_members()?;
+        let body = TsInterfaceBody {
+            body: members,
+            span: self.finish(body_start),
+        };
+
+        Ok(TsInterfaceDeclaration {
+            id,
+            type_parameters,
+            extends,
+            body,
+            declare: false,
+            span: self.finish(start),
+        })
+    }
+
+    // `A, B.C<T>`, what an interface extends or a class implements
+    pub(super) fn parse_heritage_list(&mut self) -> Result<Vec<TsHeritage>, Errors> {
+        let mut heritage = Vec::new();
+
+        loop {
+            let start = self.current().span;
+            let mut expression = Expression::Identifier(self.parse_identifier()?);
+            while self.eat(Literal::PERIOD) {
+                let property = self.parse_identifier_name()?;
+                expression = Expression::Member(Box::new(MemberExpression {
+                    object: expression,
+                    property: MemberProperty::Identifier(property),
+                    optional: false,
+                    span: self.finish(start),
+                }));
+            }
+            let type_arguments = self.parse_type_arguments_opt()?;
+
+            heritage.push(TsHeritage {
+                expression,
+                type_arguments,
+                span: self.finish(start),
+            });
+            if !self.eat(Literal::COMMA) {
+                return Ok(heritage);
+            }
+        }
+    }
+
+    // `type A<T> = B;`
+    fn parse_type_alias(&mut self, start: Span) -> Result<Statement, Errors> {
+        self.advance();
+        let id = self.parse_binding_identifier()?;
+        let type_parameters = self.parse_type_parameters_opt()?;
+        self.expect(Literal::EQUAL, "Expected =")?;
+        let type_annotation = self.parse_type()?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::TsTypeAlias(Box::new(TsTypeAliasDeclaration {
+            id,
+            type_parameters,
+            type_annotation,
+            declare: false,
+            span: self.finish(start),
+        })))
+    }
+
+    /**
+     * `enum A { B, C = 1, "d-e" = C }`, the current token is `enum`
+     *
+     * @args is_const: bool - `const` was read in front of it
+     */
+    fn parse_enum(&mut self, start: Span, is_const: bool) -> Result<Statement, Errors> {
+        self.expect_keyword(Keyword::FUTURE_ENUM, "Expected enum")?;
+        let id = self.parse_binding_identifier()?;
+        self.expect(Literal::CURLY_BRACKET_LEFT, "Expected {")?;
+        let mut members = Vec::new();
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            let member_start = self.current().span;
+            let id = match self.kind() {
+                TokenKind::String(_) => PropertyKey::String(self.parse_string_literal()?),
+                _ => PropertyKey::Identifier(self.parse_identifier_name()?),
+            };
+            let initializer = if self.eat(Literal::EQUAL) {
+                Some(self.with_in(|parser| parser.parse_assignment())?)
+            } else {
+                None
+            };
+
+            members.push(TsEnumMember {
+                id,
+                initializer,
+                span: self.finish(member_start),
+            });
+            if !self.at(Literal::CURLY_BRACKET_RIGHT) {
+                self.expect(Literal::COMMA, "Expected , or }")?;
+            }
+        }
+
+        Ok(Statement::TsEnum(Box::new(TsEnumDeclaration {
+            id,
+            members,
+            is_const,
+            declare: false,
+            span: self.finish(start),
+        })))
+    }
+
+    /**
+     * `namespace A.B { }`, `module "a" { }` or `global { }`
+     *
+     * Only an ambient module can leave out its body, `declare module "a";`.
+     */
+    fn parse_module_declaration(&mut self, start: Span) -> Result<Statement, Errors> {
+        let kind = if self.at_word("namespace") {
+            TsModuleKind::Namespace
+        } else if self.at_word("global") {
+            TsModuleKind::Global
+        } else {
+            TsModuleKind::Module
+        };
+        // `global` is the name as well
+        if kind != TsModuleKind::Global {
+            self.advance();
+        }
+
+        let id = match self.kind() {
+            TokenKind::String(_) if kind == TsModuleKind::Module => {
+                TsModuleName::String(self.parse_string_literal()?)
+            }
+            _ => TsModuleName::Entity(self.parse_entity_name()?),
+        };
+        let body = if self.at(Literal::CURLY_BRACKET_LEFT) {
+            Some(self.parse_module_block()?)
+        } else if matches!(id, TsModuleName::String(_)) {
+            self.consume_semicolon()?;
+            None
+        } else {
+            return Err(self.error("Expected {"));
+        };
+
+        Ok(Statement::TsModule(Box::new(TsModuleDeclaration {
+            id,
+            body,
+            kind,
+            declare: false,
+            span: self.finish(start),
+        })))
+    }
+
+    // Statements of a namespace, which can export what it declares
+    fn parse_module_block(&mut self) -> Result<TsModuleBlock, Errors> {
+        let start = self.advance();
+        let mut body = Vec::new();
+
+        while !self.eat(Literal::CURLY_BRACKET_RIGHT) {
+            if self.at_end() {
+                return Err(self.error("Expected }"));
+            }
+            let statement = self.recover(
+                Boundary::Statement,
+                |parser| match parser.kind() {
+                    TokenKind::Identifier(Keyword::EXPORT) => parser.parse_export(Vec::new()),
+                    TokenKind::Identifier(Keyword::IMPORT) if !parser.at_import_call() => {
+                        parser.parse_import()
+                    }
+                    _ => parser.parse_statement_list_item(),
+                },
+                Statement::Error,
+            )?;
+            body.push(statement);
+        }
+
+        Ok(TsModuleBlock {
+            body,
+            span: self.finish(start),
+        })
+    }
+}
+
+// Built in types, `string` unless it names a namespace as in `string.A`
+fn keyword_kind(kind: &TokenKind) -> Option<TsKeywordKind> {
+    let TokenKind::Name {
+        value,
+        escaped: false,
+    } = kind
+    else {
+        return None;
+    };
+    let kind = match &*value.resolve() {
+        "any" => TsKeywordKind::Any,
+        "unknown" => TsKeywordKind::Unknown,
+        "number" => TsKeywordKind::Number,
+        "bigint" => TsKeywordKind::BigInt,
+        "boolean" => TsKeywordKind::Boolean,
+        "string" => TsKeywordKind::String,
+        "symbol" => TsKeywordKind::Symbol,
+        "object" => TsKeywordKind::Object,
+        "never" => TsKeywordKind::Never,
+        "undefined" => TsKeywordKind::Undefined,
+        _ => return None,
+    };
+    Some(kind)
+}
@@ -0,0 +1,1105 @@
+use std::ops::ControlFlow;
+
+/**
+ * Generates the node enums, NodeKind, Visit and VisitMut with their walk functions from the
+ * table below
+ *
+ * Enums that only choose between nodes are `Enum => visit, walk { Variant => visit, }`, their
+ * walk hands what the variant holds to its visit. Nodes are `Variant(Type) => visit, walk { }`,
+ * the type left out when it is the variant's name, and list their children in source order:
+ *
+ * - `one field => visit` for a node
+ * - `opt field => visit` for an Option
+ * - `each field => visit` for a Vec
+ * - `each_some field => visit` for a Vec of Options, the holes left out
+ * - `interleave (field => visit, field => visit)` for the quasis of a template and the parts
+ *   between them, one of each in turn
+ *
+ * `visitor!(@kinds [...])` makes NodeKind, `visitor!(@visitor [...])` the node enum and trait
+ * named in the brackets, what differs between Visit and VisitMut comes from `flavour!`.
+ */
+macro_rules! visitor {
+    (
+        @kinds [$(#[$attribute:meta])* pub enum $kind:ident]
+        enums { $($enums:tt)* }
+        nodes {
+            $($variant:ident $(($ty:ident))? => $visit:ident, $walk:ident { $($children:tt)* })*
+        }
+    ) => {
+        $(#[$attribute])*
+        pub enum $kind {
+            $($variant,)*
+        }
+    };
+    (
+        @visitor [
+            $(#[$node_attribute:meta])* pub enum $node:ident;
+            $(#[$trait_attribute:meta])* pub trait $trait:ident $(<$lifetime:lifetime>)?
+        ]
+        enums {
+            $($enum:ident => $enum_visit:ident, $enum_walk:ident {
+                $($choice:ident => $choice_visit:ident,)*
+            })*
+        }
+        nodes {
+            $($variant:ident $(($ty:ident))? => $visit:ident, $walk:ident { $($children:tt)* })*
+        }
+    ) => {
+        use $crate::parse::ast::{
+            ArrayExpression, ArrayPattern, ArrowBody, ArrowFunction, AssignmentExpression,
+            AssignmentPattern, AwaitExpression, BinaryExpression, BlockStatement, BooleanLiteral,
+            CallExpression, CatchClause, ChainExpression, Class, ClassBody, ClassMember,
+            ConditionalExpression, Decorator, DoWhileStatement, ExportAllDeclaration, ExportDefault,
+            ExportDefaultDeclaration, ExportNamedDeclaration, ExportSpecifier, Expression,
+            ExpressionOrSpread, ExpressionStatement, ForHead, ForInStatement, ForInit,
+            ForOfStatement, ForStatement, Function, FunctionBody, Identifier, IfStatement,
+            ImportAttribute, ImportDeclaration, ImportExpression, ImportSpecifier, JsxAttribute,
+            JsxAttributeItem, JsxAttributeName, JsxAttributeValue, JsxChild, JsxClosingElement,
+            JsxElement, JsxElementName, JsxExpressionContainer, JsxFragment, JsxMemberExpression,
+            JsxNamespacedName, JsxOpeningElement, JsxText, JumpStatement, LabeledStatement,
+            LogicalExpression, MemberExpression, MemberProperty, MetaProperty, MethodDefinition,
+            ModuleExportName, NamedImport, NewExpression, NumberLiteral, ObjectExpression,
+            ObjectMember, ObjectPattern, ObjectPatternMember, Param, Pattern, PatternProperty,
+            PrivateName, Program, Property, PropertyDefinition, PropertyKey, RegExpLiteral,
+            RestElement, ReturnStatement, SequenceExpression, SpreadElement, Statement, StaticBlock,
+            StringLiteral, SwitchCase, SwitchStatement, TaggedTemplateExpression, TemplateElement,
+            TemplateLiteral, ThrowStatement, TryStatement, TsArrayType, TsAsExpression,
+            TsConditionalType, TsEntityName, TsEnumDeclaration, TsEnumMember, TsFunctionType,
+            TsHeritage, TsImportType, TsIndexSignature, TsIndexedAccessType, TsInferType,
+            TsInterfaceBody, TsInterfaceDeclaration, TsIntersectionType, TsKeywordType,
+            TsLiteralType, TsMappedType, TsMethodSignature, TsModuleBlock, TsModuleDeclaration,
+            TsModuleName, TsNonNullExpression, TsPredicateName, TsPropertySignature,
+            TsQualifiedName, TsSignature, TsTemplateLiteralType, TsTupleElement, TsTupleType,
+            TsType, TsTypeAliasDeclaration, TsTypeAnnotation, TsTypeArguments, TsTypeLiteral,
+            TsTypeMember, TsTypeOperator, TsTypeParameter, TsTypeParameters, TsTypePredicate,
+            TsTypeQuery, TsTypeQueryName, TsTypeReference, TsUnionType, UnaryExpression,
+            UpdateExpression, VariableDeclaration, VariableDeclarator, WhileStatement,
+            WithStatement, YieldExpression,
+        };
+        use $crate::read::tokenizer::Span;
+
+        $(#[$node_attribute])*
+        pub enum $node<'ast> {
+            $($variant($crate::parse::visit::flavour!(
+                $trait, field $crate::parse::visit::node_type!($variant $(($ty))?)
+            )),)*
+        }
+
+        impl $node<'_> {
+            pub fn kind(&self) -> NodeKind {
+                match self {
+                    $($node::$variant(_) => NodeKind::$variant,)*
+                }
+            }
+
+            pub fn span(&self) -> Span {
+                match self {
+                    $($node::$variant(node) => {
+                        $crate::parse::visit::span_of!(node: $variant $(($ty))?)
+                    })*
+                }
+            }
+        }
+
+        $(#[$trait_attribute])*
+        pub trait $trait $(<$lifetime>)? {
+            fn enter(
+                &mut self,
+                _node: $crate::parse::visit::flavour!($trait, node $node),
+                _ancestors: &$crate::parse::visit::flavour!($trait, ancestors),
+            ) -> Flow {
+                Flow::Continue
+            }
+
+            fn exit(
+                &mut self,
+                _node: $crate::parse::visit::flavour!($trait, node $node),
+                _ancestors: &$crate::parse::visit::flavour!($trait, ancestors),
+            ) -> ControlFlow<()> {
+                ControlFlow::Continue(())
+            }
+
+            $(
+                fn $enum_visit(
+                    &mut self,
+                    node: $crate::parse::visit::flavour!($trait, ref $enum),
+                    ancestors: &mut $crate::parse::visit::flavour!($trait, ancestors),
+                ) -> ControlFlow<()> {
+                    $enum_walk(self, node, ancestors)
+                }
+            )*
+
+            $(
+                fn $visit(
+                    &mut self,
+                    node: $crate::parse::visit::flavour!(
+                        $trait, ref $crate::parse::visit::node_type!($variant $(($ty))?)
+                    ),
+                    ancestors: &mut $crate::parse::visit::flavour!($trait, ancestors),
+                ) -> ControlFlow<()> {
+                    $walk(self, node, ancestors)
+                }
+            )*
+        }
+
+        $(
+            $crate::parse::visit::walk_fn!($trait, $enum_walk, $enum, |visitor, node, ancestors| {
+                match node {
+                    $($enum::$choice(node) => visitor.$choice_visit(node, ancestors),)*
+                }
+            });
+        )*
+
+        $(
+            $crate::parse::visit::walk_fn!(
+                $trait,
+                $walk,
+                $crate::parse::visit::node_type!($variant $(($ty))?),
+                |visitor, node, ancestors| {
+                    walk_node(
+                        visitor,
+                        node,
+                        ancestors,
+                        $crate::parse::visit::flavour!($trait, wrap $node::$variant),
+                        $crate::parse::visit::walk_children!($trait; $($children)*),
+                    )
+                }
+            );
+        )*
+    };
+    ($($generate:tt)*) => {
+        $crate::parse::visit::visitor! {
+            $($generate)*
+            enums {
+                Statement => visit_statement, walk_statement {
+                    Block => visit_block_statement,
+                    Variable => visit_variable_declaration,
+                    Function => visit_function,
+                    Class => visit_class,
+                    Empty => visit_empty_statement,
+                    Expression => visit_expression_statement,
+                    If => visit_if_statement,
+                    DoWhile => visit_do_while_statement,
+                    While => visit_while_statement,
+                    For => visit_for_statement,
+                    ForIn => visit_for_in_statement,
+                    ForOf => visit_for_of_statement,
+                    Continue => visit_continue_statement,
+                    Break => visit_break_statement,
+                    Return => visit_return_statement,
+                    With => visit_with_statement,
+                    Switch => visit_switch_statement,
+                    Labeled => visit_labeled_statement,
+                    Throw => visit_throw_statement,
+                    Try => visit_try_statement,
+                    Debugger => visit_debugger_statement,
+                    Import => visit_import_declaration,
+                    ExportNamed => visit_export_named_declaration,
+                    ExportDefault => visit_export_default_declaration,
+                    ExportAll => visit_export_all_declaration,
+                    TsInterface => visit_ts_interface_declaration,
+                    TsTypeAlias => visit_ts_type_alias_declaration,
+                    TsEnum => visit_ts_enum_declaration,
+                    TsModule => visit_ts_module_declaration,
+                    Error => visit_error,
+                }
+                ForInit => visit_for_init, walk_for_init {
+                    Variable => visit_variable_declaration,
+                    Expression => visit_expression,
+                }
+                ForHead => visit_for_head, walk_for_head {
+                    Variable => visit_variable_declaration,
+                    Pattern => visit_pattern,
+                }
+                Expression => visit_expression, walk_expression {
+                    Identifier => visit_identifier,
+                    PrivateName => visit_private_name,
+                    This => visit_this_expression,
+                    Super => visit_super,
+                    Null => visit_null_literal,
+                    Boolean => visit_boolean_literal,
+                    Number => visit_number_literal,
+                    String => visit_string_literal,
+                    RegExp => visit_reg_exp_literal,
+                    Template => visit_template_literal,
+                    TaggedTemplate => visit_tagged_template_expression,
+                    Array => visit_array_expression,
+                    Object => visit_object_expression,
+                    Function => visit_function,
+                    Arrow => visit_arrow_function,
+                    Class => visit_class,
+                    Unary => visit_unary_expression,
+                    Update => visit_update_expression,
+                    Binary => visit_binary_expression,
+                    Logical => visit_logical_expression,
+                    Conditional => visit_conditional_expression,
+                    Assignment => visit_assignment_expression,
+                    Sequence => visit_sequence_expression,
+                    Member => visit_member_expression,
+                    Call => visit_call_expression,
+                    New => visit_new_expression,
+                    Chain => visit_chain_expression,
+                    Yield => visit_yield_expression,
+                    Await => visit_await_expression,
+                    MetaProperty => visit_meta_property,
+                    Import => visit_import_expression,
+                    TsAs => visit_ts_as_expression,
+                    TsSatisfies => visit_ts_satisfies_expression,
+                    TsTypeAssertion => visit_ts_type_assertion,
+                    TsNonNull => visit_ts_non_null_expression,
+                    JsxElement => visit_jsx_element,
+                    JsxFragment => visit_jsx_fragment,
+                }
+                ExpressionOrSpread => visit_expression_or_spread, walk_expression_or_spread {
+                    Expression => visit_expression,
+                    Spread => visit_spread_element,
+                }
+                ObjectMember => visit_object_member, walk_object_member {
+                    Property => visit_property,
+                    Spread => visit_spread_element,
+                }
+                PropertyKey => visit_property_key, walk_property_key {
+                    Identifier => visit_identifier,
+                    PrivateName => visit_private_name,
+                    String => visit_string_literal,
+                    Number => visit_number_literal,
+                    Computed => visit_expression,
+                }
+                MemberProperty => visit_member_property, walk_member_property {
+                    Identifier => visit_identifier,
+                    PrivateName => visit_private_name,
+                    Computed => visit_expression,
+                }
+                Pattern => visit_pattern, walk_pattern {
+                    Identifier => visit_identifier,
+                    Object => visit_object_pattern,
+                    Array => visit_array_pattern,
+                    Rest => visit_rest_element,
+                    Assignment => visit_assignment_pattern,
+                    Member => visit_member_expression,
+                }
+                ObjectPatternMember => visit_object_pattern_member, walk_object_pattern_member {
+                    Property => visit_pattern_property,
+                    Rest => visit_rest_element,
+                }
+                ArrowBody => visit_arrow_body, walk_arrow_body {
+                    Expression => visit_expression,
+                    Block => visit_function_body,
+                }
+                ClassMember => visit_class_member, walk_class_member {
+                    Method => visit_method_definition,
+                    Property => visit_property_definition,
+                    StaticBlock => visit_static_block,
+                    IndexSignature => visit_ts_index_signature,
+                    Error => visit_error,
+                }
+                ImportSpecifier => visit_import_specifier, walk_import_specifier {
+                    Default => visit_identifier,
+                    Namespace => visit_identifier,
+                    Named => visit_named_import,
+                }
+                ModuleExportName => visit_module_export_name, walk_module_export_name {
+                    Identifier => visit_identifier,
+                    String => visit_string_literal,
+                }
+                ExportDefault => visit_export_default, walk_export_default {
+                    Function => visit_function,
+                    Class => visit_class,
+                    TsInterface => visit_ts_interface_declaration,
+                    Expression => visit_expression,
+                }
+                TsType => visit_ts_type, walk_ts_type {
+                    Keyword => visit_ts_keyword_type,
+                    This => visit_ts_this_type,
+                    Reference => visit_ts_type_reference,
+                    Literal => visit_ts_literal_type,
+                    TemplateLiteral => visit_ts_template_literal_type,
+                    Array => visit_ts_array_type,
+                    Tuple => visit_ts_tuple_type,
+                    Union => visit_ts_union_type,
+                    Intersection => visit_ts_intersection_type,
+                    Function => visit_ts_function_type,
+                    TypeLiteral => visit_ts_type_literal,
+                    Mapped => visit_ts_mapped_type,
+                    Conditional => visit_ts_conditional_type,
+                    Infer => visit_ts_infer_type,
+                    Operator => visit_ts_type_operator,
+                    IndexedAccess => visit_ts_indexed_access_type,
+                    Query => visit_ts_type_query,
+                    Import => visit_ts_import_type,
+                    Predicate => visit_ts_type_predicate,
+                }
+                TsEntityName => visit_ts_entity_name, walk_ts_entity_name {
+                    Identifier => visit_identifier,
+                    Qualified => visit_ts_qualified_name,
+                }
+                TsTypeQueryName => visit_ts_type_query_name, walk_ts_type_query_name {
+                    Entity => visit_ts_entity_name,
+                    Import => visit_ts_import_type,
+                }
+                TsPredicateName => visit_ts_predicate_name, walk_ts_predicate_name {
+                    Identifier => visit_identifier,
+                    This => visit_ts_this_type,
+                }
+                TsTypeMember => visit_ts_type_member, walk_ts_type_member {
+                    Property => visit_ts_property_signature,
+                    Method => visit_ts_method_signature,
+                    Call => visit_ts_call_signature,
+                    Construct => visit_ts_construct_signature,
+                    Index => visit_ts_index_signature,
+                    Error => visit_error,
+                }
+                TsModuleName => visit_ts_module_name, walk_ts_module_name {
+                    Entity => visit_ts_entity_name,
+                    String => visit_string_literal,
+                }
+                JsxElementName => visit_jsx_element_name, walk_jsx_element_name {
+                    Identifier => visit_identifier,
+                    Namespaced => visit_jsx_namespaced_name,
+                    Member => visit_jsx_member_expression,
+                }
+                JsxAttributeItem => visit_jsx_attribute_item, walk_jsx_attribute_item {
+                    Attribute => visit_jsx_attribute,
+                    Spread => visit_spread_element,
+                }
+                JsxAttributeName => visit_jsx_attribute_name, walk_jsx_attribute_name {
+                    Identifier => visit_identifier,
+                    Namespaced => visit_jsx_namespaced_name,
+                }
+                JsxAttributeValue => visit_jsx_attribute_value, walk_jsx_attribute_value {
+                    String => visit_string_literal,
+                    ExpressionContainer => visit_jsx_expression_container,
+                    Element => visit_jsx_element,
+                    Fragment => visit_jsx_fragment,
+                }
+                JsxChild => visit_jsx_child, walk_jsx_child {
+                    Text => visit_jsx_text,
+                    Element => visit_jsx_element,
+                    Fragment => visit_jsx_fragment,
+                    ExpressionContainer => visit_jsx_expression_container,
+                    Spread => visit_spread_element,
+                }
+            }
+            nodes {
+                Program => visit_program, walk_program {
+                    each body => visit_statement,
+                }
+                Identifier => visit_identifier, walk_identifier {}
+                PrivateName => visit_private_name, walk_private_name {}
+                BlockStatement => visit_block_statement, walk_block_statement {
+                    each body => visit_statement,
+                }
+                VariableDeclaration => visit_variable_declaration, walk_variable_declaration {
+                    each declarations => visit_variable_declarator,
+                }
+                VariableDeclarator => visit_variable_declarator, walk_variable_declarator {
+                    one id => visit_pattern,
+                    opt type_annotation => visit_ts_type_annotation,
+                    opt init => visit_expression,
+                }
+                EmptyStatement(Span) => visit_empty_statement, walk_empty_statement {}
+                ExpressionStatement => visit_expression_statement, walk_expression_statement {
+                    one expression => visit_expression,
+                }
+                IfStatement => visit_if_statement, walk_if_statement {
+                    one test => visit_expression,
+                    one consequent => visit_statement,
+                    opt alternate => visit_statement,
+                }
+                DoWhileStatement => visit_do_while_statement, walk_do_while_statement {
+                    one body => visit_statement,
+                    one test => visit_expression,
+                }
+                WhileStatement => visit_while_statement, walk_while_statement {
+                    one test => visit_expression,
+                    one body => visit_statement,
+                }
+                ForStatement => visit_for_statement, walk_for_statement {
+                    opt init => visit_for_init,
+                    opt test => visit_expression,
+                    opt update => visit_expression,
+                    one body => visit_statement,
+                }
+                ForInStatement => visit_for_in_statement, walk_for_in_statement {
+                    one left => visit_for_head,
+                    one right => visit_expression,
+                    one body => visit_statement,
+                }
+                ForOfStatement => visit_for_of_statement, walk_for_of_statement {
+                    one left => visit_for_head,
+                    one right => visit_expression,
+                    one body => visit_statement,
+                }
+                ContinueStatement(JumpStatement)
+                    => visit_continue_statement, walk_continue_statement {
+                    opt label => visit_identifier,
+                }
+                BreakStatement(JumpStatement) => visit_break_statement, walk_break_statement {
+                    opt label => visit_identifier,
+                }
+                ReturnStatement => visit_return_statement, walk_return_statement {
+                    opt argument => visit_expression,
+                }
+                WithStatement => visit_with_statement, walk_with_statement {
+                    one object => visit_expression,
+                    one body => visit_statement,
+                }
+                SwitchStatement => visit_switch_statement, walk_switch_statement {
+                    one discriminant => visit_expression,
+                    each cases => visit_switch_case,
+                }
+                SwitchCase => visit_switch_case, walk_switch_case {
+                    opt test => visit_expression,
+                    each consequent => visit_statement,
+                }
+                LabeledStatement => visit_labeled_statement, walk_labeled_statement {
+                    one label => visit_identifier,
+                    one body => visit_statement,
+                }
+                ThrowStatement => visit_throw_statement, walk_throw_statement {
+                    one argument => visit_expression,
+                }
+                TryStatement => visit_try_statement, walk_try_statement {
+                    one block => visit_block_statement,
+                    opt handler => visit_catch_clause,
+                    opt finalizer => visit_block_statement,
+                }
+                CatchClause => visit_catch_clause, walk_catch_clause {
+                    opt param => visit_pattern,
+                    opt type_annotation => visit_ts_type_annotation,
+                    one body => visit_block_statement,
+                }
+                DebuggerStatement(Span) => visit_debugger_statement, walk_debugger_statement {}
+                ThisExpression(Span) => visit_this_expression, walk_this_expression {}
+                Super(Span) => visit_super, walk_super {}
+                NullLiteral(Span) => visit_null_literal, walk_null_literal {}
+                BooleanLiteral => visit_boolean_literal, walk_boolean_literal {}
+                NumberLiteral => visit_number_literal, walk_number_literal {}
+                StringLiteral => visit_string_literal, walk_string_literal {}
+                RegExpLiteral => visit_reg_exp_literal, walk_reg_exp_literal {}
+                TemplateLiteral => visit_template_literal, walk_template_literal {
+                    interleave (quasis => visit_template_element, expressions => visit_expression),
+                }
+                TemplateElement => visit_template_element, walk_template_element {}
+                TaggedTemplateExpression
+                    => visit_tagged_template_expression, walk_tagged_template_expression {
+                    one tag => visit_expression,
+                    opt type_arguments => visit_ts_type_arguments,
+                    one quasi => visit_template_literal,
+                }
+                ArrayExpression => visit_array_expression, walk_array_expression {
+                    each_some elements => visit_expression_or_spread,
+                }
+                SpreadElement => visit_spread_element, walk_spread_element {
+                    one argument => visit_expression,
+                }
+                ObjectExpression => visit_object_expression, walk_object_expression {
+                    each properties => visit_object_member,
+                }
+                Property => visit_property, walk_property {
+                    one key => visit_property_key,
+                    one value => visit_expression,
+                }
+                UnaryExpression => visit_unary_expression, walk_unary_expression {
+                    one argument => visit_expression,
+                }
+                UpdateExpression => visit_update_expression, walk_update_expression {
+                    one argument => visit_expression,
+                }
+                BinaryExpression => visit_binary_expression, walk_binary_expression {
+                    one left => visit_expression,
+                    one right => visit_expression,
+                }
+                LogicalExpression => visit_logical_expression, walk_logical_expression {
+                    one left => visit_expression,
+                    one right => visit_expression,
+                }
+                ConditionalExpression => visit_conditional_expression, walk_conditional_expression {
+                    one test => visit_expression,
+                    one consequent => visit_expression,
+                    one alternate => visit_expression,
+                }
+                AssignmentExpression => visit_assignment_expression, walk_assignment_expression {
+                    one left => visit_pattern,
+                    one right => visit_expression,
+                }
+                SequenceExpression => visit_sequence_expression, walk_sequence_expression {
+                    each expressions => visit_expression,
+                }
+                MemberExpression => visit_member_expression, walk_member_expression {
+                    one object => visit_expression,
+                    one property => visit_member_property,
+                }
+                CallExpression => visit_call_expression, walk_call_expression {
+                    one callee => visit_expression,
+                    opt type_arguments => visit_ts_type_arguments,
+                    each arguments => visit_expression_or_spread,
+                }
+                NewExpression => visit_new_expression, walk_new_expression {
+                    one callee => visit_expression,
+                    opt type_arguments => visit_ts_type_arguments,
+                    each arguments => visit_expression_or_spread,
+                }
+                ChainExpression => visit_chain_expression, walk_chain_expression {
+                    one expression => visit_expression,
+                }
+                YieldExpression => visit_yield_expression, walk_yield_expression {
+                    opt argument => visit_expression,
+                }
+                AwaitExpression => visit_await_expression, walk_await_expression {
+                    one argument => visit_expression,
+                }
+                MetaProperty => visit_meta_property, walk_meta_property {
+                    one meta => visit_identifier,
+                    one property => visit_identifier,
+                }
+                ImportExpression => visit_import_expression, walk_import_expression {
+                    one source => visit_expression,
+                    opt options => visit_expression,
+                }
+                ObjectPattern => visit_object_pattern, walk_object_pattern {
+                    each properties => visit_object_pattern_member,
+                }
+                PatternProperty => visit_pattern_property, walk_pattern_property {
+                    one key => visit_property_key,
+                    one value => visit_pattern,
+                }
+                ArrayPattern => visit_array_pattern, walk_array_pattern {
+                    each_some elements => visit_pattern,
+                }
+                RestElement => visit_rest_element, walk_rest_element {
+                    one argument => visit_pattern,
+                }
+                AssignmentPattern => visit_assignment_pattern, walk_assignment_pattern {
+                    one left => visit_pattern,
+                    one right => visit_expression,
+                }
+                Function => visit_function, walk_function {
+                    opt id => visit_identifier,
+                    opt type_parameters => visit_ts_type_parameters,
+                    each params => visit_param,
+                    opt return_type => visit_ts_type_annotation,
+                    opt body => visit_function_body,
+                }
+                Param => visit_param, walk_param {
+                    each decorators => visit_decorator,
+                    one pattern => visit_pattern,
+                    opt type_annotation => visit_ts_type_annotation,
+                }
+                FunctionBody => visit_function_body, walk_function_body {
+                    each body => visit_statement,
+                }
+                ArrowFunction => visit_arrow_function, walk_arrow_function {
+                    opt type_parameters => visit_ts_type_parameters,
+                    each params => visit_param,
+                    opt return_type => visit_ts_type_annotation,
+                    one body => visit_arrow_body,
+                }
+                Class => visit_class, walk_class {
+                    each decorators => visit_decorator,
+                    opt id => visit_identifier,
+                    opt type_parameters => visit_ts_type_parameters,
+                    opt super_class => visit_expression,
+                    opt super_type_arguments => visit_ts_type_arguments,
+                    each implements => visit_ts_heritage,
+                    one body => visit_class_body,
+                }
+                ClassBody => visit_class_body, walk_class_body {
+                    each body => visit_class_member,
+                }
+                MethodDefinition => visit_method_definition, walk_method_definition {
+                    each decorators => visit_decorator,
+                    one key => visit_property_key,
+                    one value => visit_function,
+                }
+                PropertyDefinition => visit_property_definition, walk_property_definition {
+                    each decorators => visit_decorator,
+                    one key => visit_property_key,
+                    opt type_annotation => visit_ts_type_annotation,
+                    opt value => visit_expression,
+                }
+                StaticBlock => visit_static_block, walk_static_block {
+                    each body => visit_statement,
+                }
+                Decorator => visit_decorator, walk_decorator {
+                    one expression => visit_expression,
+                }
+                ImportDeclaration => visit_import_declaration, walk_import_declaration {
+                    each specifiers => visit_import_specifier,
+                    one source => visit_string_literal,
+                    each attributes => visit_import_attribute,
+                }
+                NamedImport => visit_named_import, walk_named_import {
+                    one imported => visit_module_export_name,
+                    one local => visit_identifier,
+                }
+                ImportAttribute => visit_import_attribute, walk_import_attribute {
+                    one key => visit_module_export_name,
+                    one value => visit_string_literal,
+                }
+                ExportNamedDeclaration
+                    => visit_export_named_declaration, walk_export_named_declaration {
+                    opt declaration => visit_statement,
+                    each specifiers => visit_export_specifier,
+                    opt source => visit_string_literal,
+                    each attributes => visit_import_attribute,
+                }
+                ExportSpecifier => visit_export_specifier, walk_export_specifier {
+                    one local => visit_module_export_name,
+                    one exported => visit_module_export_name,
+                }
+                ExportDefaultDeclaration
+                    => visit_export_default_declaration, walk_export_default_declaration {
+                    one declaration => visit_export_default,
+                }
+                ExportAllDeclaration => visit_export_all_declaration, walk_export_all_declaration {
+                    opt exported => visit_module_export_name,
+                    one source => visit_string_literal,
+                    each attributes => visit_import_attribute,
+                }
+                TsTypeAnnotation => visit_ts_type_annotation, walk_ts_type_annotation {
+                    one type_annotation => visit_ts_type,
+                }
+                TsKeywordType => visit_ts_keyword_type, walk_ts_keyword_type {}
+                TsThisType(Span) => visit_ts_this_type, walk_ts_this_type {}
+                TsQualifiedName => visit_ts_qualified_name, walk_ts_qualified_name {
+                    one left => visit_ts_entity_name,
+                    one right => visit_identifier,
+                }
+                TsTypeReference => visit_ts_type_reference, walk_ts_type_reference {
+                    one type_name => visit_ts_entity_name,
+                    opt type_arguments => visit_ts_type_arguments,
+                }
+                TsLiteralType => visit_ts_literal_type, walk_ts_literal_type {
+                    one literal => visit_expression,
+                }
+                TsTemplateLiteralType
+                    => visit_ts_template_literal_type, walk_ts_template_literal_type {
+                    interleave (quasis => visit_template_element, types => visit_ts_type),
+                }
+                TsArrayType => visit_ts_array_type, walk_ts_array_type {
+                    one element_type => visit_ts_type,
+                }
+                TsTupleType => visit_ts_tuple_type, walk_ts_tuple_type {
+                    each element_types => visit_ts_tuple_element,
+                }
+                TsTupleElement => visit_ts_tuple_element, walk_ts_tuple_element {
+                    opt label => visit_identifier,
+                    one element_type => visit_ts_type,
+                }
+                TsUnionType => visit_ts_union_type, walk_ts_union_type {
+                    each types => visit_ts_type,
+                }
+                TsIntersectionType => visit_ts_intersection_type, walk_ts_intersection_type {
+                    each types => visit_ts_type,
+                }
+                TsFunctionType => visit_ts_function_type, walk_ts_function_type {
+                    opt type_parameters => visit_ts_type_parameters,
+                    each params => visit_param,
+                    one return_type => visit_ts_type_annotation,
+                }
+                TsTypeLiteral => visit_ts_type_literal, walk_ts_type_literal {
+                    each members => visit_ts_type_member,
+                }
+                TsMappedType => visit_ts_mapped_type, walk_ts_mapped_type {
+                    one key => visit_identifier,
+                    one constraint => visit_ts_type,
+                    opt name_type => visit_ts_type,
+                    opt type_annotation => visit_ts_type,
+                }
+                TsConditionalType => visit_ts_conditional_type, walk_ts_conditional_type {
+                    one check_type => visit_ts_type,
+                    one extends_type => visit_ts_type,
+                    one true_type => visit_ts_type,
+                    one false_type => visit_ts_type,
+                }
+                TsInferType => visit_ts_infer_type, walk_ts_infer_type {
+                    one type_parameter => visit_ts_type_parameter,
+                }
+                TsTypeOperator => visit_ts_type_operator, walk_ts_type_operator {
+                    one type_annotation => visit_ts_type,
+                }
+                TsIndexedAccessType => visit_ts_indexed_access_type, walk_ts_indexed_access_type {
+                    one object_type => visit_ts_type,
+                    one index_type => visit_ts_type,
+                }
+                TsTypeQuery => visit_ts_type_query, walk_ts_type_query {
+                    one expr_name => visit_ts_type_query_name,
+                    opt type_arguments => visit_ts_type_arguments,
+                }
+                TsImportType => visit_ts_import_type, walk_ts_import_type {
+                    one argument => visit_string_literal,
+                    opt qualifier => visit_ts_entity_name,
+                    opt type_arguments => visit_ts_type_arguments,
+                }
+                TsTypePredicate => visit_ts_type_predicate, walk_ts_type_predicate {
+                    one parameter_name => visit_ts_predicate_name,
+                    opt type_annotation => visit_ts_type,
+                }
+                TsTypeParameter => visit_ts_type_parameter, walk_ts_type_parameter {
+                    one name => visit_identifier,
+                    opt constraint => visit_ts_type,
+                    opt default => visit_ts_type,
+                }
+                TsTypeParameters => visit_ts_type_parameters, walk_ts_type_parameters {
+                    each params => visit_ts_type_parameter,
+                }
+                TsTypeArguments => visit_ts_type_arguments, walk_ts_type_arguments {
+                    each params => visit_ts_type,
+                }
+                TsPropertySignature => visit_ts_property_signature, walk_ts_property_signature {
+                    one key => visit_property_key,
+                    opt type_annotation => visit_ts_type_annotation,
+                }
+                TsMethodSignature => visit_ts_method_signature, walk_ts_method_signature {
+                    one key => visit_property_key,
+                    opt type_parameters => visit_ts_type_parameters,
+                    each params => visit_param,
+                    opt return_type => visit_ts_type_annotation,
+                }
+                TsCallSignature(TsSignature) => visit_ts_call_signature, walk_ts_call_signature {
+                    opt type_parameters => visit_ts_type_parameters,
+                    each params => visit_param,
+                    opt return_type => visit_ts_type_annotation,
+                }
+                TsConstructSignature(TsSignature)
+                    => visit_ts_construct_signature, walk_ts_construct_signature {
+                    opt type_parameters => visit_ts_type_parameters,
+                    each params => visit_param,
+                    opt return_type => visit_ts_type_annotation,
+                }
+                TsIndexSignature => visit_ts_index_signature, walk_ts_index_signature {
+                    each parameters => visit_param,
+                    opt type_annotation => visit_ts_type_annotation,
+                }
+                TsAsExpression => visit_ts_as_expression, walk_ts_as_expression {
+                    one expression => visit_expression,
+                    one type_annotation => visit_ts_type,
+                }
+                TsSatisfiesExpression(TsAsExpression)
+                    => visit_ts_satisfies_expression, walk_ts_satisfies_expression {
+                    one expression => visit_expression,
+                    one type_annotation => visit_ts_type,
+                }
+                TsTypeAssertion(TsAsExpression) => visit_ts_type_assertion, walk_ts_type_assertion {
+                    one type_annotation => visit_ts_type,
+                    one expression => visit_expression,
+                }
+                TsNonNullExpression => visit_ts_non_null_expression, walk_ts_non_null_expression {
+                    one expression => visit_expression,
+                }
+                TsInterfaceDeclaration
+                    => visit_ts_interface_declaration, walk_ts_interface_declaration {
+                    one id => visit_identifier,
+                    opt type_parameters => visit_ts_type_parameters,
+                    each extends => visit_ts_heritage,
+                    one body => visit_ts_interface_body,
+                }
+                TsInterfaceBody => visit_ts_interface_body, walk_ts_interface_body {
+                    each body => visit_ts_type_member,
+                }
+                TsHeritage => visit_ts_heritage, walk_ts_heritage {
+                    one expression => visit_expression,
+                    opt type_arguments => visit_ts_type_arguments,
+                }
+                TsTypeAliasDeclaration
+                    => visit_ts_type_alias_declaration, walk_ts_type_alias_declaration {
+                    one id => visit_identifier,
+                    opt type_parameters => visit_ts_type_parameters,
+                    one type_annotation => visit_ts_type,
+                }
+                TsEnumDeclaration => visit_ts_enum_declaration, walk_ts_enum_declaration {
+                    one id => visit_identifier,
+                    each members => visit_ts_enum_member,
+                }
+                TsEnumMember => visit_ts_enum_member, walk_ts_enum_member {
+                    one id => visit_property_key,
+                    opt initializer => visit_expression,
+                }
+                TsModuleDeclaration => visit_ts_module_declaration, walk_ts_module_declaration {
+                    one id => visit_ts_module_name,
+                    opt body => visit_ts_module_block,
+                }
+                TsModuleBlock => visit_ts_module_block, walk_ts_module_block {
+                    each body => visit_statement,
+                }
+                JsxElement => visit_jsx_element, walk_jsx_element {
+                    one opening_element => visit_jsx_opening_element,
+                    each children => visit_jsx_child,
+                    opt closing_element => visit_jsx_closing_element,
+                }
+                JsxOpeningElement => visit_jsx_opening_element, walk_jsx_opening_element {
+                    one name => visit_jsx_element_name,
+                    each attributes => visit_jsx_attribute_item,
+                }
+                JsxClosingElement => visit_jsx_closing_element, walk_jsx_closing_element {
+                    one name => visit_jsx_element_name,
+                }
+                JsxFragment => visit_jsx_fragment, walk_jsx_fragment {
+                    each children => visit_jsx_child,
+                }
+                JsxNamespacedName => visit_jsx_namespaced_name, walk_jsx_namespaced_name {
+                    one namespace => visit_identifier,
+                    one name => visit_identifier,
+                }
+                JsxMemberExpression => visit_jsx_member_expression, walk_jsx_member_expression {
+                    one object => visit_jsx_element_name,
+                    one property => visit_identifier,
+                }
+                JsxAttribute => visit_jsx_attribute, walk_jsx_attribute {
+                    one name => visit_jsx_attribute_name,
+                    opt value => visit_jsx_attribute_value,
+                }
+                JsxExpressionContainer
+                    => visit_jsx_expression_container, walk_jsx_expression_container {
+                    opt expression => visit_expression,
+                }
+                JsxText => visit_jsx_text, walk_jsx_text {}
+                // A statement or member that didn't parse, see parse_recovering
+                Error(Span) => visit_error, walk_error {}
+            }
+        }
+    };
+}
+
+// The parts of the generated code that differ between Visit and VisitMut
+macro_rules! flavour {
+    (Visit, node $node:ident) => { $node<'ast> };
+    (VisitMut, node $node:ident) => { $node<'_> };
+    (Visit, ancestors) => { Ancestors<'ast> };
+    (VisitMut, ancestors) => { AncestorKinds };
+    (Visit, ref $ty:ty) => { &'ast $ty };
+    (VisitMut, ref $ty:ty) => { &mut $ty };
+    (Visit, field $ty:ty) => { &'ast $ty };
+    (VisitMut, field $ty:ty) => { &'ast mut $ty };
+    (Visit, wrap $node:ident::$variant:ident) => { $node::$variant };
+    (VisitMut, wrap $node:ident::$variant:ident) => { |node| $node::$variant(node) };
+    (Visit, borrow $place:expr) => { &$place };
+    (VisitMut, borrow $place:expr) => { &mut $place };
+    (Visit, iter $place:expr) => { $place.iter() };
+    (VisitMut, iter $place:expr) => { $place.iter_mut() };
+    (Visit, get $place:expr, $index:expr) => { $place.get($index) };
+    (VisitMut, get $place:expr, $index:expr) => { $place.get_mut($index) };
+}
+
+// A walk function, the body written once for both traits
+macro_rules! walk_fn {
+    (
+        Visit, $walk:ident, $ty:ty,
+        |$visitor:ident, $node:ident, $ancestors:ident| $body:block
+    ) => {
+        pub fn $walk<'ast, V: Visit<'ast> + ?Sized>(
+            $visitor: &mut V,
+            $node: &'ast $ty,
+            $ancestors: &mut Ancestors<'ast>,
+        ) -> ControlFlow<()> $body
+    };
+    (
+        VisitMut, $walk:ident, $ty:ty,
+        |$visitor:ident, $node:ident, $ancestors:ident| $body:block
+    ) => {
+        pub fn $walk<V: VisitMut + ?Sized>(
+            $visitor: &mut V,
+            $node: &mut $ty,
+            $ancestors: &mut AncestorKinds,
+        ) -> ControlFlow<()> $body
+    };
+}
+
+// The closure walking the children of a node, see children
+macro_rules! walk_children {
+    ($trait:ident;) => {
+        |_, _, _| ControlFlow::Continue(())
+    };
+    ($trait:ident; $($children:tt)+) => {
+        |visitor, node, ancestors| {
+            $crate::parse::visit::children!($trait, visitor, node, ancestors; $($children)+);
+            ControlFlow::Continue(())
+        }
+    };
+}
+
+// Visits the children of a node one row of the table at a time
+macro_rules! children {
+    ($trait:ident, $visitor:ident, $node:ident, $ancestors:ident;) => {};
+    (
+        $trait:ident, $visitor:ident, $node:ident, $ancestors:ident;
+        one $field:ident => $visit:ident, $($rest:tt)*
+    ) => {
+        $visitor.$visit($crate::parse::visit::flavour!($trait, borrow $node.$field), $ancestors)?;
+        $crate::parse::visit::children!($trait, $visitor, $node, $ancestors; $($rest)*);
+    };
+    (
+        $trait:ident, $visitor:ident, $node:ident, $ancestors:ident;
+        opt $field:ident => $visit:ident, $($rest:tt)*
+    ) => {
+        if let Some(child) = $crate::parse::visit::flavour!($trait, borrow $node.$field) {
+            $visitor.$visit(child, $ancestors)?;
+        }
+        $crate::parse::visit::children!($trait, $visitor, $node, $ancestors; $($rest)*);
+    };
+    (
+        $trait:ident, $visitor:ident, $node:ident, $ancestors:ident;
+        each $field:ident => $visit:ident, $($rest:tt)*
+    ) => {
+        for child in $crate::parse::visit::flavour!($trait, borrow $node.$field) {
+            $visitor.$visit(child, $ancestors)?;
+        }
+        $crate::parse::visit::children!($trait, $visitor, $node, $ancestors; $($rest)*);
+    };
+    (
+        $trait:ident, $visitor:ident, $node:ident, $ancestors:ident;
+        each_some $field:ident => $visit:ident, $($rest:tt)*
+    ) => {
+        for child in $crate::parse::visit::flavour!($trait, iter $node.$field).flatten() {
+            $visitor.$visit(child, $ancestors)?;
+        }
+        $crate::parse::visit::children!($trait, $visitor, $node, $ancestors; $($rest)*);
+    };
+    (
+        $trait:ident, $visitor:ident, $node:ident, $ancestors:ident;
+        interleave ($field:ident => $visit:ident, $between:ident => $between_visit:ident),
+        $($rest:tt)*
+    ) => {
+        for (index, child) in
+            $crate::parse::visit::flavour!($trait, iter $node.$field).enumerate()
+        {
+            $visitor.$visit(child, $ancestors)?;
+            if let Some(between) =
+                $crate::parse::visit::flavour!($trait, get $node.$between, index)
+            {
+                $visitor.$between_visit(between, $ancestors)?;
+            }
+        }
+        $crate::parse::visit::children!($trait, $visitor, $node, $ancestors; $($rest)*);
+    };
+}
+
+// The type a node row holds, its variant unless one is given
+macro_rules! node_type {
+    ($variant:ident ($ty:ident)) => {
+        $ty
+    };
+    ($variant:ident) => {
+        $variant
+    };
+}
+
+// Nodes that are nothing but their span hold it
+macro_rules! span_of {
+    ($node:ident: $variant:ident (Span)) => {
+        **$node
+    };
+    ($node:ident: $variant:ident $(($ty:ident))?) => {
+        $node.span
+    };
+}
+
+pub(crate) use {children, flavour, node_type, span_of, visitor, walk_children, walk_fn};
+
+visitor! {
+    @kinds [
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum NodeKind
+    ]
+}
+
+visitor! {
+    @visitor [
+        /**
+         * A node of the syntax tree handed to `Visit::enter` and `Visit::exit`
+         *
+         * Enums that only choose between nodes, Statement, Expression, Pattern and the like, have
+         * no variant, the node they hold is what gets entered. Nodes sharing a struct get a variant
+         * each, `continue` and `break` are both a JumpStatement but enter as ContinueStatement and
+         * BreakStatement. Nodes that are nothing but their span, `this` or `debugger`, hold the
+         * span.
+         */
+        #[derive(Copy, Clone, Debug)]
+        pub enum Node;
+
+        /**
+         * Walks the syntax tree, calling a method for every node on the way
+         *
+         * Rules that only care about a few kinds of node implement `enter`, which every node passes
+         * through, and match on the kinds they want. Overriding `visit_*` replaces the walk of that
+         * node type: call the `walk_*` of the same name to carry on into its children, or don't to
+         * leave them out. Either way `ControlFlow::Break` stops the whole traversal.
+         *
+         * Children are visited in source order.
+         */
+        pub trait Visit<'ast>
+    ]
+}
+
+/**
+ * What the traversal does after `Visit::enter`
+ *
+ * Skip leaves out the children of the node, its `exit` still runs. Stop ends the traversal
+ * there, nothing else is entered or exited.
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Skip,
+    Stop,
+}
+
+/**
+ * The nodes above the one being visited, from the program down to its parent
+ */
+#[derive(Clone, Debug, Default)]
+pub struct Ancestors<'ast> {
+    nodes: Vec<Node<'ast>>,
+}
+
+impl<'ast> Ancestors<'ast> {
+    pub fn parent(&self) -> Option<Node<'ast>> {
+        self.nodes.last().copied()
+    }
+
+    // Nearest first, the parent then its parent up to the program
+    pub fn iter(&self) -> impl Iterator<Item = Node<'ast>> + '_ {
+        self.nodes.iter().rev().copied()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+/**
+ * Visits every node of the program
+ *
+ * @args visitor: &mut V - the visitor to call
+ * @args program: &Program - the program to walk
+ * @returns Break if the visitor stopped the traversal early
+ */
+pub fn traverse<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    program: &'ast Program,
+) -> ControlFlow<()> {
+    visitor.visit_program(program, &mut Ancestors::default())
+}
+
+// Enters the node, walks its children with it as their parent, then exits it
+fn walk_node<'ast, V: Visit<'ast> + ?Sized, T: ?Sized>(
+    visitor: &mut V,
+    node: &'ast T,
+    ancestors: &mut Ancestors<'ast>,
+    wrap: fn(&'ast T) -> Node<'ast>,
+    children: impl FnOnce(&mut V, &'ast T, &mut Ancestors<'ast>) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let wrapped = wrap(node);
+    match visitor.enter(wrapped, ancestors) {
+        Flow::Stop => return ControlFlow::Break(()),
+        Flow::Skip => {}
+        Flow::Continue => {
+            ancestors.nodes.push(wrapped);
+            let flow = children(visitor, node, ancestors);
+            ancestors.nodes.pop();
+            flow?;
+        }
+    }
+    visitor.exit(wrapped, ancestors)
+}
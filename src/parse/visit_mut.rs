@@ -0,0 +1,82 @@
+use std::ops::ControlFlow;
+
+use super::visit::{visitor, Flow, NodeKind};
+
+visitor! {
+    @visitor [
+        /**
+         * A node handed to `VisitMut::enter` and `VisitMut::exit`, Node with mutable references
+         */
+        #[derive(Debug)]
+        pub enum NodeMut;
+
+        /**
+         * Visit for rewriting the tree in place, same methods and order with `&mut` nodes
+         *
+         * Children are read after `enter` and the `visit_*` overrides run, so what they change or
+         * replace is what gets walked.
+         */
+        pub trait VisitMut
+    ]
+}
+
+/**
+ * The kinds of the nodes above the one being visited, from the program down to its parent
+ *
+ * Only kinds, the nodes themselves are borrowed by whoever is changing their children.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct AncestorKinds {
+    kinds: Vec<NodeKind>,
+}
+
+impl AncestorKinds {
+    pub fn parent(&self) -> Option<NodeKind> {
+        self.kinds.last().copied()
+    }
+
+    // Nearest first, the parent then its parent up to the program
+    pub fn iter(&self) -> impl Iterator<Item = NodeKind> + '_ {
+        self.kinds.iter().rev().copied()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.kinds.len()
+    }
+}
+
+/**
+ * Visits every node of the program, letting the visitor change them
+ *
+ * @args visitor: &mut V - the visitor to call
+ * @args program: &mut Program - the program to walk
+ * @returns Break if the visitor stopped the traversal early
+ */
+pub fn traverse_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    program: &mut Program,
+) -> ControlFlow<()> {
+    visitor.visit_program(program, &mut AncestorKinds::default())
+}
+
+// Enters the node, walks its children with it as their parent, then exits it
+fn walk_node<V: VisitMut + ?Sized, T: ?Sized>(
+    visitor: &mut V,
+    node: &mut T,
+    ancestors: &mut AncestorKinds,
+    wrap: fn(&mut T) -> NodeMut<'_>,
+    children: impl FnOnce(&mut V, &mut T, &mut AncestorKinds) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let kind = wrap(node).kind();
+    match visitor.enter(wrap(node), ancestors) {
+        Flow::Stop => return ControlFlow::Break(()),
+        Flow::Skip => {}
+        Flow::Continue => {
+            ancestors.kinds.push(kind);
+            let flow = children(visitor, node, ancestors);
+            ancestors.kinds.pop();
+            flow?;
+        }
+    }
+    visitor.exit(wrap(node), ancestors)
+}
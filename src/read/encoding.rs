@@ -0,0 +1,171 @@
+use crate::error::errors::Errors;
+
+use super::line_index::{ColumnUnit, LineIndex};
+use super::tokenizer::Span;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16_LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16_BE_BOM: &[u8] = b"\xFE\xFF";
+
+/**
+ * Encoding a source file was saved in
+ *
+ * UTF-16 is only recognised by its byte order mark, Windows editors always write one.
+ */
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl Encoding {
+    /**
+     * @returns the encoding and whether the bytes start with a byte order mark
+     */
+    pub fn detect(bytes: &[u8]) -> (Encoding, bool) {
+        if bytes.starts_with(UTF8_BOM) {
+            (Encoding::Utf8, true)
+        } else if bytes.starts_with(UTF16_LE_BOM) {
+            (Encoding::Utf16Le, true)
+        } else if bytes.starts_with(UTF16_BE_BOM) {
+            (Encoding::Utf16Be, true)
+        } else {
+            (Encoding::Utf8, false)
+        }
+    }
+
+    fn bom(&self) -> &'static [u8] {
+        match self {
+            Encoding::Utf8 => UTF8_BOM,
+            Encoding::Utf16Le => UTF16_LE_BOM,
+            Encoding::Utf16Be => UTF16_BE_BOM,
+        }
+    }
+}
+
+/**
+ * A source file decoded to UTF-8, the tokenizer works on text
+ *
+ * The byte order mark is stripped and remembered, encode writes the file back the way it was.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceText {
+    pub text: String,
+    pub encoding: Encoding,
+    pub bom: bool,
+}
+
+impl SourceText {
+    /**
+     * @args bytes: &[u8] - the file as read from disk
+     *
+     * @returns the text without the byte order mark, an EncodingError at the first byte that
+     *          isn't valid in the detected encoding
+     */
+    pub fn decode(bytes: &[u8]) -> Result<SourceText, Errors> {
+        let (encoding, bom) = Encoding::detect(bytes);
+        let skipped = if bom { encoding.bom().len() } else { 0 };
+        let content = &bytes[skipped..];
+
+        let text = match encoding {
+            Encoding::Utf8 => match std::str::from_utf8(content) {
+                Ok(text) => text.to_owned(),
+                Err(error) => {
+                    let start = error.valid_up_to();
+                    let length = error.error_len().unwrap_or(content.len() - start);
+                    return Err(invalid_utf8(content, start, length, skipped));
+                }
+            },
+            Encoding::Utf16Le | Encoding::Utf16Be => decode_utf16(content, encoding, skipped)?,
+        };
+
+        Ok(SourceText {
+            text,
+            encoding,
+            bom,
+        })
+    }
+
+    // The bytes to write back, in the original encoding and with the original byte order mark
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.text.len() + 3);
+        if self.bom {
+            bytes.extend_from_slice(self.encoding.bom());
+        }
+        match self.encoding {
+            Encoding::Utf8 => bytes.extend_from_slice(self.text.as_bytes()),
+            Encoding::Utf16Le => {
+                for unit in self.text.encode_utf16() {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                }
+            }
+            Encoding::Utf16Be => {
+                for unit in self.text.encode_utf16() {
+                    bytes.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+        }
+        bytes
+    }
+}
+
+/**
+ * EncodingError for the invalid bytes at start..start + length of the text
+ *
+ * @args skipped: usize - bytes in front of the text, added to the offsets but not the column
+ */
+pub(super) fn invalid_utf8(text: &[u8], start: usize, length: usize, skipped: usize) -> Errors {
+    let (line, column) = end_position(&text[..start], ColumnUnit::Utf8);
+    Errors::EncodingError(
+        "Invalid UTF-8",
+        Span {
+            start: skipped + start,
+            end: skipped + start + length,
+            line,
+            column,
+        },
+    )
+}
+
+// Offsets in errors are file offsets, line and column are those of the text decoded so far
+fn decode_utf16(content: &[u8], encoding: Encoding, skipped: usize) -> Result<String, Errors> {
+    let units = content.chunks(2).map(|pair| match (pair, encoding) {
+        ([low, high], Encoding::Utf16Le) => u16::from_le_bytes([*low, *high]),
+        ([high, low], _) => u16::from_be_bytes([*high, *low]),
+        // An odd byte at the end, never a valid unit on its own
+        _ => 0xDC00,
+    });
+
+    let mut text = String::with_capacity(content.len() / 2);
+    let mut offset = 0;
+    for decoded in char::decode_utf16(units) {
+        match decoded {
+            Ok(character) => {
+                text.push(character);
+                offset += character.len_utf16() * 2;
+            }
+            Err(_) => {
+                let (line, column) = end_position(text.as_bytes(), ColumnUnit::Utf16);
+                return Err(Errors::EncodingError(
+                    "Invalid UTF-16",
+                    Span {
+                        start: skipped + offset,
+                        end: skipped + (offset + 2).min(content.len()),
+                        line,
+                        column,
+                    },
+                ));
+            }
+        }
+    }
+    Ok(text)
+}
+
+// Line and column right after the text
+fn end_position(text: &[u8], unit: ColumnUnit) -> (usize, usize) {
+    LineIndex::new(text)
+        .position(text.len(), unit)
+        .map_or((1, 0), |position| (position.line, position.column))
+}
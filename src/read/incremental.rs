@@ -0,0 +1,250 @@
+use crate::error::errors::Errors;
+
+use super::jsx::jsx_element_start;
+use super::lexer::LOOKAHEAD;
+use super::tokenizer::{is_line_terminator, Span, Token, TokenState, TokenTrait};
+use super::typescript::Dialect;
+
+/**
+ * Replacement of the bytes start..end of a source with text
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(start: usize, end: usize, text: &str) -> Self {
+        Self {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    /**
+     * @args source: &[u8] - the source before the edit
+     *
+     * @returns the source after the edit
+     */
+    pub fn apply(&self, source: &[u8]) -> Vec<u8> {
+        let mut edited = Vec::with_capacity(source.len() + self.text.len());
+        edited.extend_from_slice(&source[..self.start]);
+        edited.extend_from_slice(self.text.as_bytes());
+        edited.extend_from_slice(&source[self.end..]);
+        edited
+    }
+}
+
+impl Token {
+    /**
+     * Updates the tokens to the edited source, lexing only the part the edit can have changed
+     *
+     * Tokens are kept up to the last one the lexer finished without looking at the edit.
+     * Lexing restarts after it and stops once a new token lines up with an old token past
+     * the edit and the lexer is in the same state after both. The new tokens are spliced in
+     * between and the rest of the old tokens are moved by the length difference in place.
+     *
+     * @args tokens: &mut Vec<Token> - every token of the source before the edit, EndOfFile
+     *       included, afterwards the same as tokenizing the edited source from scratch.
+     *       Left as they were when that fails.
+     * @args edit: &TextEdit - offsets into the source before the edit
+     * @args source: &[u8] - the source after the edit
+     * @args dialect: Dialect - the dialect the tokens were lexed with
+     */
+    pub fn relex(
+        tokens: &mut Vec<Token>,
+        edit: &TextEdit,
+        source: &[u8],
+        dialect: Dialect,
+    ) -> Result<(), Errors> {
+        let old_length = match tokens.last() {
+            Some(token) => token.span.end,
+            None => return Err(Errors::DeveloperError("No tokens to re-lex")),
+        };
+        if edit.start > edit.end
+            || edit.end > old_length
+            || source.len() + edit.end - edit.start != old_length + edit.text.len()
+        {
+            return Err(Errors::DeveloperError("Edit doesn't match the tokens"));
+        }
+        // Where the encoding error goes depends on the whole source, not only the edit
+        if std::str::from_utf8(source).is_err() {
+            *tokens = Token::tokenize_with_dialect(source, dialect)?;
+            return Ok(());
+        }
+
+        // The lexer may have peeked LOOKAHEAD bytes past a token, only those before are safe.
+        // A `<` in Jsx can read further, the bytes before the edit tell how far it did.
+        let restart = tokens
+            .iter()
+            .position(|token| {
+                extent(token).end + LOOKAHEAD > edit.start
+                    || dialect.is_jsx()
+                        && source[token.span.start] == b'<'
+                        && token.span.start
+                            + jsx_element_start(&source[token.span.start..], dialect).1
+                            > edit.start
+            })
+            .unwrap_or(tokens.len() - 1);
+
+        // Only the lexer state is rebuilt from the tokens before, they stay where they are
+        let mut state = TokenState::new(source, dialect);
+        for token in &tokens[..restart] {
+            state.replay(token);
+        }
+        let mut old_state = TokenState::new(&[], dialect);
+        old_state.rewind(state.checkpoint());
+        state.resume_at(&tokens[restart], restart.checked_sub(1).map(|i| &tokens[i]));
+
+        let mut relexed = Vec::new();
+        let mut old_index = restart;
+        let edited_end = edit.start + edit.text.len();
+
+        while let Some(token) = state.next_token()? {
+            let start = extent(&token).start;
+
+            // Old tokens the new one has moved past can't line up anymore
+            while old_index < tokens.len()
+                && shift_offset(extent(&tokens[old_index]).start, edit) < start
+            {
+                old_state.replay(&tokens[old_index]);
+                old_index += 1;
+            }
+
+            if start >= edited_end
+                && old_index < tokens.len()
+                && extent(&tokens[old_index]).start >= edit.end
+                && shift_offset(extent(&tokens[old_index]).start, edit) == start
+            {
+                let old = &tokens[old_index];
+                old_state.replay(old);
+                old_index += 1;
+
+                let shift = Shift::between(old, &token, edit);
+                let mut moved = old.clone();
+                shift.token(&mut moved);
+                if moved == token && state.same_position(&old_state) {
+                    relexed.push(token);
+                    let kept = restart + relexed.len();
+                    tokens.splice(restart..old_index, relexed);
+                    for old in &mut tokens[kept..] {
+                        shift.token(old);
+                    }
+                    return Ok(());
+                }
+            }
+
+            relexed.push(token);
+        }
+
+        tokens.splice(restart.., relexed);
+        Ok(())
+    }
+}
+
+// Offsets of the token with its trivia
+fn extent(token: &Token) -> Span {
+    let start = token
+        .leading_trivia
+        .first()
+        .map_or(token.span, |trivia| trivia.span);
+    let end = token
+        .trailing_trivia
+        .last()
+        .map_or(token.span, |trivia| trivia.span);
+    Span {
+        end: end.end,
+        ..start
+    }
+}
+
+// Where an old offset ends up after the edit, offsets inside it move to its start
+fn shift_offset(offset: usize, edit: &TextEdit) -> usize {
+    if offset >= edit.end {
+        offset - edit.end + edit.start + edit.text.len()
+    } else {
+        offset.min(edit.start)
+    }
+}
+
+// Moves old spans behind the edit to where they are in the edited source
+struct Shift {
+    offset: isize,
+    line: isize,
+    column: isize,
+    edit_line: usize, // old line of the first moved token, only spans on it change columns
+}
+
+impl Shift {
+    fn between(old: &Token, new: &Token, edit: &TextEdit) -> Self {
+        let (old, new) = (extent(old), extent(new));
+        Self {
+            offset: edit.text.len() as isize - (edit.end - edit.start) as isize,
+            line: new.line as isize - old.line as isize,
+            column: new.column as isize - old.column as isize,
+            edit_line: old.line,
+        }
+    }
+
+    fn span(&self, span: Span) -> Span {
+        let column = if span.line == self.edit_line {
+            (span.column as isize + self.column) as usize
+        } else {
+            span.column
+        };
+        Span {
+            start: (span.start as isize + self.offset) as usize,
+            end: (span.end as isize + self.offset) as usize,
+            line: (span.line as isize + self.line) as usize,
+            column,
+        }
+    }
+
+    fn token(&self, token: &mut Token) {
+        token.span = self.span(token.span);
+        for trivia in token
+            .leading_trivia
+            .iter_mut()
+            .chain(token.trailing_trivia.iter_mut())
+        {
+            trivia.span = self.span(trivia.span);
+        }
+    }
+}
+
+impl<'a> TokenState<'a> {
+    // Updates the state as if the token had just been lexed
+    fn replay(&mut self, token: &Token) {
+        self.track_expression_position(&token.kind);
+        self.started = true;
+    }
+
+    // Moves to the first byte of the token, the tokens before it were replayed
+    fn resume_at(&mut self, token: &Token, previous: Option<&Token>) {
+        let start = extent(token);
+        self.index = start.start;
+        self.line = start.line;
+        self.line_start = start.start - start.column;
+
+        // A comment spanning lines after the previous token counts as a line break
+        self.newline_before = previous.is_some_and(|previous| {
+            previous.trailing_trivia.iter().any(|trivia| {
+                String::from_utf8_lossy(&self.buffer[trivia.span.start..trivia.span.end])
+                    .chars()
+                    .any(is_line_terminator)
+            })
+        });
+    }
+
+    // Whether the next token would be lexed the same after either state
+    fn same_position(&self, other: &TokenState) -> bool {
+        self.nesting == other.nesting
+            && self.parens == other.parens
+            && self.expression_allowed == other.expression_allowed
+            && self.brace_is_block == other.brace_is_block
+            && self.condition_keyword == other.condition_keyword
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, RwLock, RwLockReadGuard, Weak,
+    },
+};
+
+use lazy_static::lazy_static;
+
+/**
+ * A name stored once in an interner, comparing two symbols compares two integers
+ *
+ * Symbols are made through the interner entered on the current thread, the global one when none
+ * is, so the same name gets the same symbol in every file and thread that share the interner.
+ * A symbol remembers the interner that made it and resolves there wherever it's used, symbols
+ * of two interners are never equal.
+ */
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol {
+    interner: u32, // the id of the interner that made it
+    index: u32,    // into its names
+}
+
+/**
+ * Thread-safe table of interned names
+ *
+ * Names are never removed, they are freed with the interner. A long-running caller like a
+ * watch mode, or an editor re-lexing every keystroke, owns one per session or project and
+ * enters it around lexing and parsing, half-typed names then go away with the session.
+ * Lookups of known names only take the read lock.
+ */
+pub struct Interner {
+    id: u32,
+    table: Arc<RwLock<Table>>,
+}
+
+#[derive(Default)]
+struct Table {
+    symbols: HashMap<Arc<str>, Symbol>,
+    names: Vec<Arc<str>>, // indexed by symbol
+}
+
+lazy_static! {
+    // The default when no interner is entered, it lives as long as the process and every name
+    // interned into it does too, so it grows without limit
+    static ref GLOBAL: Interner = Interner::new();
+
+    // The tables of the interners alive by id, for symbols used where their interner isn't
+    // entered
+    static ref INTERNERS: RwLock<HashMap<u32, Weak<RwLock<Table>>>> = RwLock::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+const DROPPED: &str = "the interner of the symbol was dropped";
+
+thread_local! {
+    // Interners entered on this thread, the innermost last
+    static ENTERED: RefCell<Vec<Arc<Interner>>> = const { RefCell::new(Vec::new()) };
+}
+
+// Leaves the interner entered last, also when the work inside panics
+struct Entered;
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        ENTERED.with(|entered| entered.borrow_mut().pop());
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Interner {
+    fn drop(&mut self) {
+        write(&INTERNERS).remove(&self.id);
+    }
+}
+
+impl Interner {
+    // An empty interner of its own, see enter
+    pub fn new() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let table = Arc::new(RwLock::new(Table::default()));
+        write(&INTERNERS).insert(id, Arc::downgrade(&table));
+        Self { id, table }
+    }
+
+    // The interner symbols use when none is entered
+    pub fn global() -> &'static Interner {
+        &GLOBAL
+    }
+
+    /**
+     * Runs `work` with this interner entered on the current thread
+     *
+     * Symbols made inside, by the lexer, the parser or Symbol itself, come from this interner.
+     * They still resolve after it, for as long as the interner is alive.
+     *
+     * @args work: FnOnce() -> T - the lexing, parsing and everything reading their names
+     */
+    pub fn enter<T>(self: &Arc<Self>, work: impl FnOnce() -> T) -> T {
+        ENTERED.with(|entered| entered.borrow_mut().push(Arc::clone(self)));
+        let _entered = Entered;
+        work()
+    }
+
+    /**
+     * @args name: &str - the name, copied the first time it's seen
+     *
+     * @returns the symbol of the name, the same one on every call
+     */
+    pub fn intern(&self, name: &str) -> Symbol {
+        if let Some(symbol) = self.read().symbols.get(name) {
+            return *symbol;
+        }
+
+        let mut table = write(&self.table);
+        // Another thread may have added it between the two locks
+        if let Some(symbol) = table.symbols.get(name) {
+            return *symbol;
+        }
+        let symbol = Symbol {
+            interner: self.id,
+            index: table.names.len() as u32,
+        };
+        let name: Arc<str> = Arc::from(name);
+        table.names.push(Arc::clone(&name));
+        table.symbols.insert(name, symbol);
+        symbol
+    }
+
+    /**
+     * @returns the name of a symbol this interner made, None for a symbol of another interner
+     */
+    pub fn resolve(&self, symbol: Symbol) -> Option<Arc<str>> {
+        (symbol.interner == self.id).then(|| Arc::clone(&self.read().names[symbol.index as usize]))
+    }
+
+    // Number of distinct names
+    pub fn len(&self) -> usize {
+        self.read().names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Table> {
+        read(&self.table)
+    }
+}
+
+// A panic while holding a lock can't leave the table half written
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|error| error.into_inner())
+}
+
+fn write<T>(lock: &RwLock<T>) -> std::sync::RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|error| error.into_inner())
+}
+
+// Runs `use_interner` with the interner entered on this thread, or the global one
+fn with_current<T>(use_interner: impl FnOnce(&Interner) -> T) -> T {
+    ENTERED.with(|entered| match entered.borrow().last() {
+        Some(interner) => use_interner(interner),
+        None => use_interner(&GLOBAL),
+    })
+}
+
+/**
+ * Runs `read_name` with the name of the symbol, from the interner that made it
+ *
+ * The interners entered on this thread and the global one are tried first, they need no lookup.
+ * None when that interner has been dropped.
+ */
+fn with_name<T>(symbol: Symbol, read_name: impl FnOnce(Option<&Arc<str>>) -> T) -> T {
+    let local = ENTERED.with(|entered| {
+        entered
+            .borrow()
+            .iter()
+            .rev()
+            .find(|interner| interner.id == symbol.interner)
+            .map(|interner| Arc::clone(&interner.table))
+    });
+    let table = match local {
+        Some(table) => Some(table),
+        None if symbol.interner == GLOBAL.id => Some(Arc::clone(&GLOBAL.table)),
+        None => read(&INTERNERS)
+            .get(&symbol.interner)
+            .and_then(Weak::upgrade),
+    };
+    match table {
+        Some(table) => read_name(Some(&read(&table).names[symbol.index as usize])),
+        None => read_name(None),
+    }
+}
+
+impl Symbol {
+    pub fn intern(name: &str) -> Symbol {
+        with_current(|interner| interner.intern(name))
+    }
+
+    // The name, panics once the interner that made the symbol is dropped
+    pub fn resolve(&self) -> Arc<str> {
+        with_name(*self, |name| Arc::clone(name.expect(DROPPED)))
+    }
+
+    // The name borrowed for as long as `read` runs, panics like resolve
+    pub fn with_str<T>(&self, read: impl FnOnce(&str) -> T) -> T {
+        with_name(*self, |name| read(name.expect(DROPPED)))
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.index
+    }
+}
+
+// `Symbol(3)` when its interner is gone
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        with_name(*self, |name| match name {
+            Some(name) => f.write_str(name),
+            None => write!(f, "Symbol({})", self.index),
+        })
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        with_name(*self, |name| match name {
+            Some(name) => write!(f, "{:?}", name),
+            None => write!(f, "Symbol({})", self.index),
+        })
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        with_name(*self, |name| name.is_some_and(|name| **name == *other))
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        with_name(*self, |name| name.is_some_and(|name| **name == **other))
+    }
+}
@@ -0,0 +1,321 @@
+use crate::error::errors::Errors;
+
+use super::interner::Symbol;
+use super::tokenizer::{
+    is_id_continue, is_id_start, is_line_terminator, Literal, TokenKind, TokenState,
+};
+use super::typescript::Dialect;
+
+/**
+ * Tokens of a JSX element, `<a b="c">d {e}</a>` lexes as
+ * TagOpen, Name(a), Name(b), `=`, String(c), TagEnd, Text(d ), ExpressionStart, e,
+ * ExpressionEnd, TagClose, Name(a), TagEnd
+ *
+ * `.` and `:` in `<a.b>` and `<svg:rect>` stay Literals. Text keeps its whitespace as
+ * written, entities between text are their own tokens.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxToken {
+    TagOpen,         // `<` of an opening tag or fragment
+    TagClose,        // `</` of a closing tag or fragment
+    TagEnd,          // `>` ending either tag
+    SelfClosingEnd,  // `/>`
+    Name(Symbol),    // element or attribute name, can contain `-`
+    String(String),  // attribute value, entities decoded
+    Text(String),    // text between tags as written
+    Entity(String),  // `&amp;`, `&#123;` or `&#x7B;` decoded
+    ExpressionStart, // `{` in a tag or between children
+    ExpressionEnd,   // `}` closing it
+}
+
+/**
+ * Whether the `<` at the start of source begins an element, and how far deciding that read
+ *
+ * Only asked where an expression can start. In Tsx `<T,>() => {}` is a generic arrow
+ * function, same as TypeScript decides it. That reads the whole name, which can go further
+ * than LOOKAHEAD past the `<`.
+ *
+ * @returns the answer and the offset one past the last byte looked at, reaching the end of
+ *          the source counts as looking at one more byte
+ */
+pub(super) fn jsx_element_start(source: &[u8], dialect: Dialect) -> (bool, usize) {
+    let rest = &source[1..];
+    match rest.first() {
+        Some(b'>') => return (true, 2),
+        Some(byte) if byte.is_ascii_alphabetic() || matches!(byte, b'$' | b'_') => {}
+        Some(0x80..) => {}
+        _ => return (false, 2),
+    }
+    if !dialect.is_typescript() {
+        return (true, 2);
+    }
+
+    let name_length = rest
+        .iter()
+        .position(|byte| !byte.is_ascii_alphanumeric() && !matches!(byte, b'$' | b'_'))
+        .unwrap_or(rest.len());
+    let after_name = &rest[name_length..];
+    let after_spaces = after_name.trim_ascii_start();
+    // The `<`, the name, the spaces and up to `extends` and the byte after it
+    let mut read = 1 + name_length + (after_name.len() - after_spaces.len()) + b"extends".len() + 1;
+
+    if after_spaces.starts_with(b",") {
+        return (false, read);
+    }
+    if after_spaces.len() < after_name.len() && after_spaces.starts_with(b"extends") {
+        let after_extends = &after_spaces[b"extends".len()..];
+        let after_spaces = after_extends.trim_ascii_start();
+        read += after_extends.len() - after_spaces.len();
+        if !after_spaces.starts_with(b"=") {
+            return (false, read);
+        }
+    }
+    (true, read)
+}
+
+impl<'a> TokenState<'a> {
+    // Whether the `<` under the current index starts an element, see jsx_element_start
+    pub(super) fn starts_jsx_element(&mut self) -> bool {
+        if self.peek(0) != Some(b'<') {
+            return false;
+        }
+        let (starts, read) = jsx_element_start(&self.buffer[self.index..], self.dialect);
+        self.read_to = self.read_to.max(self.index + read);
+        starts
+    }
+
+    // `<` starting an element in expression position
+    pub(super) fn read_jsx_element_start(&mut self) -> TokenKind {
+        self.index += 1;
+        TokenKind::Jsx(JsxToken::TagOpen)
+    }
+
+    /**
+     * Next token inside `<...>`, whitespace and comments were already read as trivia
+     *
+     * Like every JSX token it only moves the index, track_expression_position updates the
+     * nesting so replaying the tokens rebuilds it.
+     */
+    pub(super) fn read_jsx_tag_token(&mut self, closing: bool) -> Result<TokenKind, Errors> {
+        let start = self.index;
+        let byte = match self.peek(0) {
+            Some(byte) => byte,
+            None => return Err(self.error("Unterminated JSX element", start)),
+        };
+
+        let token = match byte {
+            b'>' => {
+                self.index += 1;
+                JsxToken::TagEnd
+            }
+            b'/' if self.peek(1) == Some(b'>') && !closing => {
+                self.index += 2;
+                JsxToken::SelfClosingEnd
+            }
+            b'{' => {
+                self.index += 1;
+                JsxToken::ExpressionStart
+            }
+            b'<' => {
+                // `<a b=<c /> />`, an element as attribute value
+                self.index += 1;
+                JsxToken::TagOpen
+            }
+            b'"' | b'\'' => JsxToken::String(self.read_jsx_string()?),
+            b'=' | b'.' | b':' => {
+                self.index += 1;
+                let literal = match byte {
+                    b'=' => Literal::EQUAL,
+                    b'.' => Literal::PERIOD,
+                    _ => Literal::COLON,
+                };
+                return Ok(TokenKind::Literal(literal));
+            }
+            _ => match self.peek_char() {
+                Some((character, _)) if is_id_start(character) => {
+                    JsxToken::Name(self.read_jsx_name())
+                }
+                _ => {
+                    self.index += 1;
+                    return Err(self.error("Unexpected character in JSX tag", start));
+                }
+            },
+        };
+
+        Ok(TokenKind::Jsx(token))
+    }
+
+    /**
+     * Next token between an opening and a closing tag, no trivia is read there
+     */
+    pub(super) fn read_jsx_child(&mut self) -> Result<TokenKind, Errors> {
+        let start = self.index;
+        if let Some((value, length)) = entity_at(&self.buffer[start..]) {
+            self.index += length;
+            return Ok(TokenKind::Jsx(JsxToken::Entity(value)));
+        }
+
+        let token = match self.peek(0) {
+            None => return Err(self.error("Unterminated JSX element", start)),
+            Some(b'<') if self.peek(1) == Some(b'/') => {
+                self.index += 2;
+                JsxToken::TagClose
+            }
+            Some(b'<') => {
+                self.index += 1;
+                JsxToken::TagOpen
+            }
+            Some(b'{') => {
+                self.index += 1;
+                JsxToken::ExpressionStart
+            }
+            Some(_) => {
+                while let Some(byte) = self.peek(0) {
+                    match byte {
+                        b'<' | b'{' => break,
+                        b'&' if entity_at(&self.buffer[self.index..]).is_some() => break,
+                        b'\r' => {
+                            self.index += if self.peek(1) == Some(b'\n') { 2 } else { 1 };
+                            self.new_line();
+                        }
+                        _ => {
+                            let (character, length) =
+                                self.peek_char().unwrap_or((char::REPLACEMENT_CHARACTER, 1));
+                            self.index += length;
+                            if is_line_terminator(character) {
+                                self.new_line();
+                            }
+                        }
+                    }
+                }
+                let text = String::from_utf8_lossy(&self.buffer[start..self.index]);
+                JsxToken::Text(text.into_owned())
+            }
+        };
+
+        Ok(TokenKind::Jsx(token))
+    }
+
+    // JSXIdentifier, an identifier that can also contain `-`
+    fn read_jsx_name(&mut self) -> Symbol {
+        let start = self.index;
+        while let Some((character, length)) = self.peek_char() {
+            if character != '-' && !is_id_continue(character) {
+                break;
+            }
+            self.index += length;
+        }
+        Symbol::intern(&String::from_utf8_lossy(&self.buffer[start..self.index]))
+    }
+
+    // Attribute strings have no escapes, they can span lines and contain entities
+    fn read_jsx_string(&mut self) -> Result<String, Errors> {
+        let start = self.index;
+        let quote = self.buffer[start];
+        self.index += 1;
+
+        let mut value = String::new();
+        loop {
+            if let Some((decoded, length)) = entity_at(&self.buffer[self.index..]) {
+                value.push_str(&decoded);
+                self.index += length;
+                continue;
+            }
+
+            match self.peek(0) {
+                None => return Err(self.error("Unterminated JSX string", start)),
+                Some(byte) if byte == quote => {
+                    self.index += 1;
+                    return Ok(value);
+                }
+                Some(b'\r') if self.peek(1) == Some(b'\n') => {
+                    value.push_str("\r\n");
+                    self.index += 2;
+                    self.new_line();
+                }
+                Some(_) => {
+                    let (character, length) =
+                        self.peek_char().unwrap_or((char::REPLACEMENT_CHARACTER, 1));
+                    value.push(character);
+                    self.index += length;
+                    if is_line_terminator(character) {
+                        self.new_line();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/**
+ * Decodes the entity reference at the start of the bytes
+ *
+ * @returns the decoded text and the length of the reference, None for an unknown
+ *          name or a reference without the closing `;` (it is plain text then)
+ */
+fn entity_at(bytes: &[u8]) -> Option<(String, usize)> {
+    if bytes.first() != Some(&b'&') {
+        return None;
+    }
+    let end = bytes.iter().take(12).position(|byte| *byte == b';')?;
+    let name = std::str::from_utf8(&bytes[1..end]).ok()?;
+
+    let character = if let Some(number) = name.strip_prefix('#') {
+        let code_point = match number.strip_prefix(['x', 'X']) {
+            Some(hexadecimal) => u32::from_str_radix(hexadecimal, 16).ok()?,
+            None => number.parse::<u32>().ok()?,
+        };
+        char::from_u32(code_point)?
+    } else {
+        ENTITIES
+            .iter()
+            .find(|(entity, _)| *entity == name)
+            .map(|(_, character)| *character)?
+    };
+
+    Some((character.to_string(), end + 1))
+}
+
+// Named references that show up in component markup, the full HTML list is much longer
+const ENTITIES: [(&str, char); 40] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{A0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("hellip", '…'),
+    ("mdash", '—'),
+    ("ndash", '–'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("bull", '•'),
+    ("middot", '·'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("deg", '°'),
+    ("plusmn", '±'),
+    ("para", '¶'),
+    ("sect", '§'),
+    ("cent", '¢'),
+    ("pound", '£'),
+    ("yen", '¥'),
+    ("euro", '€'),
+    ("larr", '←'),
+    ("rarr", '→'),
+    ("uarr", '↑'),
+    ("darr", '↓'),
+    ("harr", '↔'),
+    ("hearts", '♥'),
+    ("le", '≤'),
+    ("ge", '≥'),
+    ("ne", '≠'),
+    ("zwj", '\u{200D}'),
+];
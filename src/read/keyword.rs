@@ -0,0 +1,69 @@
+use super::tokenizer::Identifier;
+use super::typescript::TsKeyword;
+
+// Slots of the keyword table, a power of two so the hash only needs a mask
+const TABLE_SIZE: usize = 256;
+
+// Longest reserved word, `synchronized`
+const LONGEST: usize = 12;
+
+/**
+ * Fills a perfect hash table with every word of a generated keyword enum
+ *
+ * Runs at compile time on the enum's ALL. A word that lands on an occupied slot fails the
+ * build, the multipliers in keyword_hash have to be changed then.
+ */
+macro_rules! keyword_table {
+    ($words:ident) => {{
+        let mut table = [None; TABLE_SIZE];
+        let mut index = 0;
+        while index < $words::ALL.len() {
+            let bytes = $words::ALL[index].as_bytes();
+            assert!(bytes.len() >= 2 && bytes.len() <= LONGEST);
+
+            let slot = keyword_hash(bytes);
+            if table[slot].is_some() {
+                panic!("Two keywords share a slot of the keyword table");
+            }
+            table[slot] = Some($words::ALL[index]);
+            index += 1;
+        }
+        table
+    }};
+}
+
+// Perfect hash of the reserved words, every word gets a slot of its own
+const KEYWORD_TABLE: [Option<Identifier>; TABLE_SIZE] = keyword_table!(Identifier);
+
+// The same hash over the TypeScript keywords, looked up before the reserved words
+const TS_KEYWORD_TABLE: [Option<TsKeyword>; TABLE_SIZE] = keyword_table!(TsKeyword);
+
+// First two bytes and the length tell every reserved word apart
+const fn keyword_hash(bytes: &[u8]) -> usize {
+    let mixed = (bytes[0] as usize).wrapping_mul(172) ^ (bytes[1] as usize).wrapping_mul(12);
+    mixed.wrapping_add(bytes.len()) & (TABLE_SIZE - 1)
+}
+
+impl Identifier {
+    /**
+     * Looks a word up in the keyword table, one hash and one comparison
+     *
+     * @returns the reserved word spelled exactly like the bytes, None for anything else
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Option<Identifier> {
+        if bytes.len() < 2 || bytes.len() > LONGEST {
+            return None;
+        }
+        KEYWORD_TABLE[keyword_hash(bytes)].filter(|identifier| identifier.as_bytes() == bytes)
+    }
+}
+
+impl TsKeyword {
+    // Same as Identifier::from_bytes, over the TypeScript keywords
+    pub fn from_bytes(bytes: &[u8]) -> Option<TsKeyword> {
+        if bytes.len() < 2 || bytes.len() > LONGEST {
+            return None;
+        }
+        TS_KEYWORD_TABLE[keyword_hash(bytes)].filter(|keyword| keyword.as_bytes() == bytes)
+    }
+}
@@ -0,0 +1,283 @@
+use std::io::{self, Read};
+
+use crate::error::errors::Errors;
+
+use super::encoding::invalid_utf8;
+use super::tokenizer::{Nesting, Token, TokenState};
+use super::typescript::Dialect;
+
+// Bytes read from a stream at a time
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+// Furthest the lexer looks past the end of a token, a JSX entity like `&hellip;`
+pub(super) const LOOKAHEAD: usize = 16;
+
+/**
+ * Public tokenizer, yields the tokens of a slice or a stream one at a time
+ *
+ * Streams are read in chunks, a token that crosses the end of a chunk is lexed again once
+ * the next chunk arrived. Bytes before the current token are dropped so only about one
+ * chunk is held in memory. Spans are offsets into the whole source either way.
+ *
+ * The last item is the EndOfFile token, or the first error. Bytes that aren't UTF-8 are an
+ * EncodingError once lexing reaches them, when recovering they are lexed as U+FFFD.
+ */
+pub struct Lexer<'a> {
+    state: TokenState<'a>,
+    reader: Option<Box<dyn Read + 'a>>, // None for slices and once the stream is exhausted
+    chunk_size: usize,
+    done: bool,
+    validated: usize, // source offset up to which the bytes are known to be UTF-8
+    invalid: Option<(usize, Errors)>, // first invalid byte and its error
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(buffer: &'a [u8], dialect: Dialect) -> Self {
+        let mut lexer = Self {
+            state: TokenState::new(buffer, dialect),
+            reader: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            done: false,
+            validated: 0,
+            invalid: None,
+        };
+        lexer.validate(true);
+        lexer
+    }
+
+    /**
+     * Lexer over anything readable, chunk_size bytes are requested at a time
+     *
+     * @args reader: R - read until it returns 0 bytes
+     * @args dialect: Dialect - see `Dialect::from_path`
+     */
+    pub fn from_reader<R: Read + 'a>(reader: R, dialect: Dialect) -> Self {
+        Self {
+            state: TokenState::new(&[], dialect),
+            reader: Some(Box::new(reader)),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            done: false,
+            validated: 0,
+            invalid: None,
+        }
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /**
+     * Keeps lexing after bad input, it comes out as a TokenKind::Error token
+     *
+     * Only reading the stream can still fail then.
+     */
+    pub fn with_recovery(mut self) -> Self {
+        self.state.recovering = true;
+        self.invalid = None;
+        self
+    }
+
+    /**
+     * Drops the bytes before the current index and appends the next chunk of the stream
+     *
+     * @returns Ok(false) when the stream has nothing left
+     */
+    fn fill(&mut self) -> Result<bool, Errors> {
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => return Ok(false),
+        };
+
+        self.state.compact();
+        let buffer = self.state.buffer.to_mut();
+        let length = buffer.len();
+        buffer.resize(length + self.chunk_size, 0);
+
+        let read = loop {
+            match reader.read(&mut buffer[length..]) {
+                Ok(read) => break read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    buffer.truncate(length);
+                    return Err(Errors::IoError(error));
+                }
+            }
+        };
+        buffer.truncate(length + read);
+
+        if read == 0 {
+            self.reader = None;
+        }
+        self.validate(self.reader.is_none());
+        Ok(read > 0)
+    }
+
+    /**
+     * Checks the bytes that arrived since the last call are UTF-8
+     *
+     * @args complete: bool - nothing follows the buffer, a sequence cut off at its end is invalid
+     */
+    fn validate(&mut self, complete: bool) {
+        if self.invalid.is_some() || self.state.recovering {
+            return;
+        }
+
+        let state = &self.state;
+        let from = self.validated.saturating_sub(state.base);
+        let error = match std::str::from_utf8(&state.buffer[from..]) {
+            Ok(_) => {
+                self.validated = state.base + state.buffer.len();
+                return;
+            }
+            Err(error) => error,
+        };
+
+        let start = from + error.valid_up_to();
+        let length = match error.error_len() {
+            Some(length) => length,
+            None if !complete => {
+                self.validated = state.base + start;
+                return;
+            }
+            None => state.buffer.len() - start,
+        };
+
+        // Located from the current index, the lexer's position is only known up to there
+        let mut error = invalid_utf8(&state.buffer[state.index..], start - state.index, length, 0);
+        if let Errors::EncodingError(_, span) = &mut error {
+            if span.line == 1 {
+                span.column += state.base + state.index - state.line_start;
+            }
+            span.line += state.line - 1;
+            span.start += state.base + state.index;
+            span.end += state.base + state.index;
+        }
+        self.invalid = Some((state.base + start, error));
+    }
+
+    // The encoding error replaces whatever touched the invalid byte
+    fn reached_invalid(&self, result: &Result<Option<Token>, Errors>) -> bool {
+        let invalid = match &self.invalid {
+            Some((invalid, _)) => *invalid,
+            None => return false,
+        };
+        match result {
+            Ok(Some(token)) => {
+                let end = token
+                    .trailing_trivia
+                    .last()
+                    .map_or(token.span, |trivia| trivia.span);
+                end.end > invalid
+            }
+            Err(Errors::TokenError(_, span)) => span.end > invalid || span.start >= invalid,
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token, Errors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let checkpoint = self.reader.is_some().then(|| self.state.checkpoint());
+            let result = self.state.next_token();
+
+            if let Some(checkpoint) = checkpoint {
+                // Errors and tokens near the end may only be cut off by the chunk
+                let read = self.state.index.max(self.state.read_to);
+                let complete =
+                    matches!(result, Ok(Some(_))) && read + LOOKAHEAD < self.state.buffer.len();
+                if !complete {
+                    self.state.rewind(checkpoint);
+                    if let Err(error) = self.fill() {
+                        self.done = true;
+                        return Some(Err(error));
+                    }
+                    continue;
+                }
+            }
+
+            if self.reached_invalid(&result) {
+                self.done = true;
+                return self.invalid.take().map(|(_, error)| Err(error));
+            }
+
+            return match result {
+                Ok(Some(token)) => Some(Ok(token)),
+                Ok(None) => {
+                    self.done = true;
+                    None
+                }
+                Err(error) => {
+                    self.done = true;
+                    Some(Err(error))
+                }
+            };
+        }
+    }
+}
+
+// Everything next_token changes, restored when a token has to be lexed again
+pub(super) struct Checkpoint {
+    index: usize,
+    line: usize,
+    line_start: usize,
+    newline_before: bool,
+    started: bool,
+    finished: bool,
+    anchor: (usize, usize, usize),
+    nesting: Vec<Nesting>,
+    parens: Vec<bool>,
+    expression_allowed: bool,
+    brace_is_block: bool,
+    condition_keyword: bool,
+}
+
+impl<'a> TokenState<'a> {
+    pub(super) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            index: self.index,
+            line: self.line,
+            line_start: self.line_start,
+            newline_before: self.newline_before,
+            started: self.started,
+            finished: self.finished,
+            anchor: self.anchor,
+            nesting: self.nesting.clone(),
+            parens: self.parens.clone(),
+            expression_allowed: self.expression_allowed,
+            brace_is_block: self.brace_is_block,
+            condition_keyword: self.condition_keyword,
+        }
+    }
+
+    pub(super) fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.index = checkpoint.index;
+        self.line = checkpoint.line;
+        self.line_start = checkpoint.line_start;
+        self.newline_before = checkpoint.newline_before;
+        self.started = checkpoint.started;
+        self.finished = checkpoint.finished;
+        self.anchor = checkpoint.anchor;
+        self.nesting = checkpoint.nesting;
+        self.parens = checkpoint.parens;
+        self.expression_allowed = checkpoint.expression_allowed;
+        self.brace_is_block = checkpoint.brace_is_block;
+        self.condition_keyword = checkpoint.condition_keyword;
+    }
+
+    // Forgets the bytes before the current index, only called between tokens
+    fn compact(&mut self) {
+        let consumed = self.index;
+        self.buffer.to_mut().drain(..consumed);
+        self.base += consumed;
+        self.index = 0;
+        self.anchor.0 = self.anchor.0.saturating_sub(consumed);
+    }
+}
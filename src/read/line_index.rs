@@ -0,0 +1,228 @@
+/**
+ * What a column counts, editors speaking LSP usually want Utf16
+ */
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ColumnUnit {
+    #[default]
+    Utf8, // bytes, the columns of Span
+    Utf16, // UTF-16 code units, characters outside the BMP count twice
+    Char,  // Unicode scalar values
+}
+
+/**
+ * Line and column of an offset, line is 1-based and column 0-based like in Span
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+// A character longer than one byte, the only ones that make the units differ
+#[derive(Copy, Clone, Debug)]
+struct WideChar {
+    offset: usize,
+    length: u8,          // UTF-8 bytes
+    utf16_length: u8,    // UTF-16 code units
+    utf16_offset: usize, // where it starts in UTF-16 code units
+    char_offset: usize,  // where it starts in Unicode scalar values
+}
+
+/**
+ * Maps byte offsets of a source to line/column positions and back
+ *
+ * Lines end at LF, CRLF, a lone CR, U+2028 and U+2029, like ECMAScript LineTerminatorSequence.
+ * Invalid UTF-8 bytes count as one U+FFFD each.
+ */
+#[derive(Clone, Debug)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+    line_ends: Vec<usize>,     // where the terminator of each line starts
+    wide_chars: Vec<WideChar>, // sorted by offset
+    length: usize,
+}
+
+impl LineIndex {
+    pub fn new(source: &[u8]) -> Self {
+        let mut line_starts = vec![0];
+        let mut line_ends = Vec::new();
+        let mut wide_chars = Vec::new();
+        // Bytes beyond the first of each wide character so far, and UTF-16 units beyond it
+        let (mut extra_bytes, mut extra_utf16) = (0, 0);
+
+        // Chunks only split at invalid bytes, so a CRLF is never split between two
+        let mut offset = 0;
+        for chunk in source.utf8_chunks() {
+            let valid = chunk.valid();
+            let mut characters = valid.char_indices().peekable();
+            while let Some((index, character)) = characters.next() {
+                let start = offset + index;
+                let length = character.len_utf8();
+                match character {
+                    '\r' if characters.peek().is_some_and(|(_, next)| *next == '\n') => {
+                        characters.next();
+                        line_ends.push(start);
+                        line_starts.push(start + 2);
+                    }
+                    '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                        line_ends.push(start);
+                        line_starts.push(start + length);
+                    }
+                    _ => {}
+                }
+                if length > 1 {
+                    wide_chars.push(WideChar {
+                        offset: start,
+                        length: length as u8,
+                        utf16_length: character.len_utf16() as u8,
+                        utf16_offset: start - extra_bytes + extra_utf16,
+                        char_offset: start - extra_bytes,
+                    });
+                    extra_bytes += length - 1;
+                    extra_utf16 += character.len_utf16() - 1;
+                }
+            }
+            offset += valid.len() + chunk.invalid().len();
+        }
+        line_ends.push(source.len());
+
+        Self {
+            line_starts,
+            line_ends,
+            wide_chars,
+            length: source.len(),
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /**
+     * @args line: usize - 1-based
+     *
+     * @returns the offsets of the line without its terminator
+     */
+    pub fn line_range(&self, line: usize) -> Option<std::ops::Range<usize>> {
+        let index = line.checked_sub(1)?;
+        let start = *self.line_starts.get(index)?;
+        let end = self.line_ends[index];
+        Some(start..end)
+    }
+
+    /**
+     * @args offset: usize - byte offset, an offset inside a character counts from its start
+     * @args unit: ColumnUnit - what the column counts
+     *
+     * @returns None past the end of the source
+     */
+    pub fn position(&self, offset: usize, unit: ColumnUnit) -> Option<Position> {
+        if offset > self.length {
+            return None;
+        }
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+
+        let mut column = offset - line_start;
+        for wide in self.wide_chars_between(line_start, offset) {
+            let counted = match unit {
+                ColumnUnit::Utf8 => continue,
+                ColumnUnit::Utf16 => wide.utf16_length as usize,
+                ColumnUnit::Char => 1,
+            };
+            // A partly covered character isn't counted at all
+            column -= (offset - wide.offset).min(wide.length as usize);
+            if offset >= wide.offset + wide.length as usize {
+                column += counted;
+            }
+        }
+
+        Some(Position { line, column })
+    }
+
+    /**
+     * Inverse of position
+     *
+     * @returns None for a line that doesn't exist or a column past the end of the line
+     */
+    pub fn offset(&self, position: Position, unit: ColumnUnit) -> Option<usize> {
+        let range = self.line_range(position.line)?;
+        if unit == ColumnUnit::Utf8 {
+            let offset = range.start + position.column;
+            return (offset <= range.end).then_some(offset);
+        }
+
+        let mut offset = range.start;
+        let mut column = 0;
+
+        let mut wide_chars = self
+            .wide_chars_between(range.start, range.end)
+            .iter()
+            .peekable();
+        while column < position.column {
+            if offset >= range.end {
+                return None;
+            }
+            match wide_chars.peek() {
+                Some(wide) if wide.offset == offset => {
+                    offset += wide.length as usize;
+                    column += match unit {
+                        ColumnUnit::Utf16 => wide.utf16_length as usize,
+                        _ => 1,
+                    };
+                    wide_chars.next();
+                }
+                // Until the next wide character every byte is one unit
+                Some(wide) => {
+                    let step = (wide.offset - offset).min(position.column - column);
+                    offset += step;
+                    column += step;
+                }
+                None => {
+                    let step = (range.end - offset).min(position.column - column);
+                    offset += step;
+                    column += step;
+                }
+            }
+        }
+
+        // A column inside a character (half a surrogate pair) doesn't have an offset
+        (column == position.column).then_some(offset)
+    }
+
+    /**
+     * Offset from the start of the source counted in unit, a JavaScript string index for Utf16
+     *
+     * @args offset: usize - byte offset, an offset inside a character counts from its start
+     *
+     * @returns None past the end of the source
+     */
+    pub fn offset_in(&self, offset: usize, unit: ColumnUnit) -> Option<usize> {
+        if offset > self.length {
+            return None;
+        }
+        let before = self.wide_chars.partition_point(|wide| wide.offset < offset);
+        let Some(wide) = before.checked_sub(1).map(|index| &self.wide_chars[index]) else {
+            return Some(offset);
+        };
+
+        let (start, length) = match unit {
+            ColumnUnit::Utf8 => return Some(offset),
+            ColumnUnit::Utf16 => (wide.utf16_offset, wide.utf16_length as usize),
+            ColumnUnit::Char => (wide.char_offset, 1),
+        };
+        let end = wide.offset + wide.length as usize;
+        Some(if offset < end {
+            start
+        } else {
+            start + length + offset - end
+        })
+    }
+
+    // Wide characters starting in start..end
+    fn wide_chars_between(&self, start: usize, end: usize) -> &[WideChar] {
+        let first = self.wide_chars.partition_point(|wide| wide.offset < start);
+        let last = self.wide_chars.partition_point(|wide| wide.offset < end);
+        &self.wide_chars[first..last]
+    }
+}
@@ -1,3 +1,18 @@
+pub mod encoding;
+pub mod incremental;
+pub mod interner;
+pub mod jsx;
+pub mod keyword;
+pub mod line_index;
+pub mod lexer;
+pub mod number;
+pub mod punctuator;
+#[allow(clippy::module_inception)]
 pub mod read;
+pub mod regex;
+pub mod string;
+pub mod template;
 pub mod tokenizer;
-pub mod tokens;
\ No newline at end of file
+pub mod tokens;
+pub mod trivia;
+pub mod typescript;
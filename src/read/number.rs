@@ -0,0 +1,317 @@
+use std::fmt;
+
+use crate::error::errors::Errors;
+
+use super::tokenizer::{is_id_start, TokenState};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NumberBase {
+    Decimal,
+    Hexadecimal,
+    Octal,
+    Binary,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NumberValue {
+    Number(f64),
+    BigInt(String), // decimal digits without sign, `0x1Fn` is stored as "31"
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumericLiteral {
+    pub value: NumberValue,
+    pub base: NumberBase,
+    pub legacy: bool, // `017` and `089`, both rejected in strict mode
+}
+
+impl<'a> TokenState<'a> {
+    /**
+     * NumericLiteral starting at the current index, either a digit or a `.` followed by one
+     *
+     * @returns the literal with its parsed value, malformed input is a located TokenError
+     */
+    pub(super) fn read_number(&mut self) -> Result<NumericLiteral, Errors> {
+        let start = self.index;
+
+        let prefixed_base = match (
+            self.peek(0),
+            self.peek(1).map(|byte| byte.to_ascii_lowercase()),
+        ) {
+            (Some(b'0'), Some(b'x')) => Some(NumberBase::Hexadecimal),
+            (Some(b'0'), Some(b'o')) => Some(NumberBase::Octal),
+            (Some(b'0'), Some(b'b')) => Some(NumberBase::Binary),
+            _ => None,
+        };
+
+        let literal = if let Some(base) = prefixed_base {
+            self.index += 2;
+            let digits = self.read_digits(radix(base), start)?;
+            if digits.is_empty() {
+                return Err(self.error(missing_digits(base), start));
+            }
+            let bigint = self.read_bigint_suffix();
+            NumericLiteral {
+                value: integer_value(&digits, radix(base), bigint),
+                base,
+                legacy: false,
+            }
+        } else if self.peek(0) == Some(b'0')
+            && self.peek(1).is_some_and(|byte| byte.is_ascii_digit())
+        {
+            self.read_legacy_number(start)?
+        } else {
+            self.read_decimal(start)?
+        };
+
+        // `3in` or `0b12` - the literal has to end at a token boundary
+        if let Some(byte) = self.peek(0) {
+            let touching = byte.is_ascii_digit()
+                || byte == b'\\'
+                || self
+                    .peek_char()
+                    .is_some_and(|(character, _)| is_id_start(character));
+            if touching {
+                self.index += 1;
+                return Err(self.error(
+                    "Identifier or digit starts immediately after numeric literal",
+                    start,
+                ));
+            }
+        }
+
+        Ok(literal)
+    }
+
+    /**
+     * Digits of the given radix with `_` separators, a separator must sit between two digits
+     *
+     * @returns the digits with the separators removed
+     */
+    fn read_digits(&mut self, radix: u32, start: usize) -> Result<String, Errors> {
+        let mut digits = String::new();
+
+        while let Some(byte) = self.peek(0) {
+            if byte == b'_' {
+                let previous_is_digit = !digits.is_empty() && self.buffer[self.index - 1] != b'_';
+                let next_is_digit = self
+                    .peek(1)
+                    .is_some_and(|next| (next as char).is_digit(radix));
+                if !previous_is_digit || !next_is_digit {
+                    self.index += 1;
+                    return Err(self.error("Numeric separators are not allowed here", start));
+                }
+                self.index += 1;
+            } else if (byte as char).is_digit(radix) {
+                digits.push(byte as char);
+                self.index += 1;
+            } else {
+                break;
+            }
+        }
+
+        Ok(digits)
+    }
+
+    fn read_bigint_suffix(&mut self) -> bool {
+        if self.peek(0) == Some(b'n') {
+            self.index += 1;
+            return true;
+        }
+        false
+    }
+
+    // `017` is octal, `089` decimal - neither allows separators, fractions on `017` or BigInt
+    fn read_legacy_number(&mut self, start: usize) -> Result<NumericLiteral, Errors> {
+        while self.peek(0).is_some_and(|byte| byte.is_ascii_digit()) {
+            self.index += 1;
+        }
+        let digits = std::str::from_utf8(&self.buffer[start..self.index]).unwrap_or("0");
+
+        if self.peek(0) == Some(b'_') {
+            self.index += 1;
+            return Err(self.error("Numeric separators are not allowed here", start));
+        }
+        if self.peek(0) == Some(b'n') {
+            self.index += 1;
+            return Err(self.error("BigInt literals can't have a leading zero", start));
+        }
+
+        if digits.bytes().all(|byte| byte < b'8') {
+            return Ok(NumericLiteral {
+                value: integer_value(digits, 8, false),
+                base: NumberBase::Octal,
+                legacy: true,
+            });
+        }
+
+        let mut literal = self.read_decimal_tail(start, digits.to_string())?;
+        literal.legacy = true;
+        Ok(literal)
+    }
+
+    fn read_decimal(&mut self, start: usize) -> Result<NumericLiteral, Errors> {
+        let integer = self.read_digits(10, start)?;
+        if integer.len() > 1 && integer.starts_with('0') {
+            // `0_1` - a zero followed by a separator is the only way to get here
+            return Err(self.error("Numeric separators are not allowed here", start));
+        }
+        self.read_decimal_tail(start, integer)
+    }
+
+    // Fraction, exponent and BigInt suffix following the integer part
+    fn read_decimal_tail(
+        &mut self,
+        start: usize,
+        integer: String,
+    ) -> Result<NumericLiteral, Errors> {
+        let mut text = integer;
+        let mut is_integer = true;
+
+        if self.peek(0) == Some(b'.') {
+            self.index += 1;
+            is_integer = false;
+            text.push('.');
+            if self.peek(0) == Some(b'_') {
+                self.index += 1;
+                return Err(self.error("Numeric separators are not allowed here", start));
+            }
+            text.push_str(&self.read_digits(10, start)?);
+        }
+
+        if matches!(self.peek(0), Some(b'e' | b'E')) {
+            self.index += 1;
+            is_integer = false;
+            text.push('e');
+            if let Some(sign @ (b'+' | b'-')) = self.peek(0) {
+                text.push(sign as char);
+                self.index += 1;
+            }
+            let exponent = self.read_digits(10, start)?;
+            if exponent.is_empty() {
+                return Err(self.error("Missing exponent in numeric literal", start));
+            }
+            text.push_str(&exponent);
+        }
+
+        if self.peek(0) == Some(b'n') {
+            self.index += 1;
+            if !is_integer || text.starts_with('0') && text.len() > 1 {
+                return Err(self.error("Invalid BigInt literal", start));
+            }
+            return Ok(NumericLiteral {
+                value: integer_value(&text, 10, true),
+                base: NumberBase::Decimal,
+                legacy: false,
+            });
+        }
+
+        Ok(NumericLiteral {
+            value: NumberValue::Number(text.parse::<f64>().unwrap_or(f64::NAN)),
+            base: NumberBase::Decimal,
+            legacy: false,
+        })
+    }
+}
+
+fn radix(base: NumberBase) -> u32 {
+    match base {
+        NumberBase::Decimal => 10,
+        NumberBase::Hexadecimal => 16,
+        NumberBase::Octal => 8,
+        NumberBase::Binary => 2,
+    }
+}
+
+fn missing_digits(base: NumberBase) -> &'static str {
+    match base {
+        NumberBase::Hexadecimal => "Expected hexadecimal digits after 0x",
+        NumberBase::Octal => "Expected octal digits after 0o",
+        _ => "Expected binary digits after 0b",
+    }
+}
+
+/**
+ * Value of an integer written in the given radix
+ *
+ * @args digits: &str - digits without separators or prefix
+ * @args radix: u32
+ * @args bigint: bool - keep every digit as a BigInt instead of rounding to a double
+ */
+fn integer_value(digits: &str, radix: u32, bigint: bool) -> NumberValue {
+    if !bigint {
+        let value = digits.chars().fold(0f64, |value, digit| {
+            value * radix as f64 + digit.to_digit(radix).unwrap_or(0) as f64
+        });
+        return NumberValue::Number(value);
+    }
+
+    // Little endian limbs in base 10^9 so the decimal string falls out directly
+    const LIMB: u64 = 1_000_000_000;
+    let mut limbs: Vec<u64> = vec![0];
+    for digit in digits.chars() {
+        let mut carry = digit.to_digit(radix).unwrap_or(0) as u64;
+        for limb in limbs.iter_mut() {
+            let next = *limb * radix as u64 + carry;
+            *limb = next % LIMB;
+            carry = next / LIMB;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+
+    let mut decimal = limbs.last().copied().unwrap_or(0).to_string();
+    for limb in limbs.iter().rev().skip(1) {
+        decimal.push_str(&format!("{:09}", limb));
+    }
+    NumberValue::BigInt(decimal)
+}
+
+impl fmt::Display for NumberValue {
+    // Same output as Number.prototype.toString() / BigInt.prototype.toString() with an `n`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            NumberValue::BigInt(digits) => return write!(f, "{}n", digits),
+            NumberValue::Number(value) => *value,
+        };
+
+        if value.is_nan() {
+            return write!(f, "NaN");
+        }
+        if value == 0.0 {
+            return write!(f, "0");
+        }
+        if value.is_infinite() {
+            return write!(f, "{}Infinity", if value < 0.0 { "-" } else { "" });
+        }
+        if value < 0.0 {
+            write!(f, "-")?;
+        }
+
+        // Shortest round-trip digits, value = 0.digits * 10^point
+        let scientific = format!("{:e}", value.abs());
+        let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+        let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+        let point = exponent.parse::<i32>().unwrap_or(0) + 1;
+        let length = digits.len() as i32;
+
+        if length <= point && point <= 21 {
+            write!(f, "{}{}", digits, "0".repeat((point - length) as usize))
+        } else if 0 < point && point <= 21 {
+            let (integer, fraction) = digits.split_at(point as usize);
+            write!(f, "{}.{}", integer, fraction)
+        } else if -6 < point && point <= 0 {
+            write!(f, "0.{}{}", "0".repeat(-point as usize), digits)
+        } else {
+            let exponent = point - 1;
+            let sign = if exponent < 0 { '-' } else { '+' };
+            let (first, rest) = digits.split_at(1);
+            if rest.is_empty() {
+                write!(f, "{}e{}{}", first, sign, exponent.abs())
+            } else {
+                write!(f, "{}.{}e{}{}", first, rest, sign, exponent.abs())
+            }
+        }
+    }
+}
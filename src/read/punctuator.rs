@@ -0,0 +1,308 @@
+use crate::error::errors::Errors;
+
+use super::interner::Symbol;
+use super::tokenizer::{Identifier, Literal, SharedToken, TokenKind, TokenState};
+
+/**
+ * Punctuators longer than one byte, single byte ones stay a `Literal`
+ *
+ * Lexed by maximal munch, `a>>>=b` is `a`, `>>>=`, `b`.
+ */
+#[derive(PartialEq, Copy, Clone, Debug)]
+#[allow(non_camel_case_types, unused)]
+pub enum Punctuator {
+    ARROW,
+    ELLIPSIS,
+    OPTIONAL_CHAIN,
+    INCREMENT,
+    DECREMENT,
+    EXPONENT,
+    EQUAL_EQUAL,
+    STRICT_EQUAL,
+    NOT_EQUAL,
+    STRICT_NOT_EQUAL,
+    LESS_EQUAL,
+    GREATER_EQUAL,
+    SHIFT_LEFT,
+    SHIFT_RIGHT,
+    UNSIGNED_SHIFT_RIGHT,
+    LOGICAL_AND,
+    LOGICAL_OR,
+    NULLISH_COALESCING,
+    PLUS_ASSIGN,
+    MINUS_ASSIGN,
+    MULTIPLY_ASSIGN,
+    DIVIDE_ASSIGN,
+    REMAINDER_ASSIGN,
+    EXPONENT_ASSIGN,
+    SHIFT_LEFT_ASSIGN,
+    SHIFT_RIGHT_ASSIGN,
+    UNSIGNED_SHIFT_RIGHT_ASSIGN,
+    BITWISE_AND_ASSIGN,
+    BITWISE_OR_ASSIGN,
+    BITWISE_XOR_ASSIGN,
+    LOGICAL_AND_ASSIGN,
+    LOGICAL_OR_ASSIGN,
+    NULLISH_ASSIGN,
+}
+
+// Longest first so the first match is the maximal munch
+const PUNCTUATORS: [Punctuator; 33] = [
+    Punctuator::UNSIGNED_SHIFT_RIGHT_ASSIGN,
+    Punctuator::ELLIPSIS,
+    Punctuator::STRICT_EQUAL,
+    Punctuator::STRICT_NOT_EQUAL,
+    Punctuator::UNSIGNED_SHIFT_RIGHT,
+    Punctuator::EXPONENT_ASSIGN,
+    Punctuator::SHIFT_LEFT_ASSIGN,
+    Punctuator::SHIFT_RIGHT_ASSIGN,
+    Punctuator::LOGICAL_AND_ASSIGN,
+    Punctuator::LOGICAL_OR_ASSIGN,
+    Punctuator::NULLISH_ASSIGN,
+    Punctuator::ARROW,
+    Punctuator::OPTIONAL_CHAIN,
+    Punctuator::INCREMENT,
+    Punctuator::DECREMENT,
+    Punctuator::EXPONENT,
+    Punctuator::EQUAL_EQUAL,
+    Punctuator::NOT_EQUAL,
+    Punctuator::LESS_EQUAL,
+    Punctuator::GREATER_EQUAL,
+    Punctuator::SHIFT_LEFT,
+    Punctuator::SHIFT_RIGHT,
+    Punctuator::LOGICAL_AND,
+    Punctuator::LOGICAL_OR,
+    Punctuator::NULLISH_COALESCING,
+    Punctuator::PLUS_ASSIGN,
+    Punctuator::MINUS_ASSIGN,
+    Punctuator::MULTIPLY_ASSIGN,
+    Punctuator::DIVIDE_ASSIGN,
+    Punctuator::REMAINDER_ASSIGN,
+    Punctuator::BITWISE_AND_ASSIGN,
+    Punctuator::BITWISE_OR_ASSIGN,
+    Punctuator::BITWISE_XOR_ASSIGN,
+];
+
+impl SharedToken for Punctuator {
+    fn get_value(&self) -> &[u8] {
+        match self {
+            Punctuator::ARROW => b"=>",
+            Punctuator::ELLIPSIS => b"...",
+            Punctuator::OPTIONAL_CHAIN => b"?.",
+            Punctuator::INCREMENT => b"++",
+            Punctuator::DECREMENT => b"--",
+            Punctuator::EXPONENT => b"**",
+            Punctuator::EQUAL_EQUAL => b"==",
+            Punctuator::STRICT_EQUAL => b"===",
+            Punctuator::NOT_EQUAL => b"!=",
+            Punctuator::STRICT_NOT_EQUAL => b"!==",
+            Punctuator::LESS_EQUAL => b"<=",
+            Punctuator::GREATER_EQUAL => b">=",
+            Punctuator::SHIFT_LEFT => b"<<",
+            Punctuator::SHIFT_RIGHT => b">>",
+            Punctuator::UNSIGNED_SHIFT_RIGHT => b">>>",
+            Punctuator::LOGICAL_AND => b"&&",
+            Punctuator::LOGICAL_OR => b"||",
+            Punctuator::NULLISH_COALESCING => b"??",
+            Punctuator::PLUS_ASSIGN => b"+=",
+            Punctuator::MINUS_ASSIGN => b"-=",
+            Punctuator::MULTIPLY_ASSIGN => b"*=",
+            Punctuator::DIVIDE_ASSIGN => b"/=",
+            Punctuator::REMAINDER_ASSIGN => b"%=",
+            Punctuator::EXPONENT_ASSIGN => b"**=",
+            Punctuator::SHIFT_LEFT_ASSIGN => b"<<=",
+            Punctuator::SHIFT_RIGHT_ASSIGN => b">>=",
+            Punctuator::UNSIGNED_SHIFT_RIGHT_ASSIGN => b">>>=",
+            Punctuator::BITWISE_AND_ASSIGN => b"&=",
+            Punctuator::BITWISE_OR_ASSIGN => b"|=",
+            Punctuator::BITWISE_XOR_ASSIGN => b"^=",
+            Punctuator::LOGICAL_AND_ASSIGN => b"&&=",
+            Punctuator::LOGICAL_OR_ASSIGN => b"||=",
+            Punctuator::NULLISH_ASSIGN => b"??=",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OperatorCategory {
+    Assignment,     // `=` and every compound assignment
+    Conditional,    // `?` of `a ? b : c`
+    Coalesce,       // `??`
+    Logical,        // `||` `&&`
+    Bitwise,        // `|` `^` `&`
+    Equality,       // `==` `!=` `===` `!==`
+    Relational,     // `<` `>` `<=` `>=` `instanceof` `in`
+    Shift,          // `<<` `>>` `>>>`
+    Additive,       // `+` `-`, also prefix operators
+    Multiplicative, // `*` `/` `%`
+    Exponent,       // `**`
+    Unary,          // `!` `~` `typeof` `void` `delete`
+    Update,         // `++` `--`
+    Arrow,          // `=>`
+    Spread,         // `...`
+    OptionalChain,  // `?.`
+    Sequence,       // `,`
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+impl TokenKind {
+    /**
+     * What kind of operator the token is
+     *
+     * @returns None for tokens that are never operators (names, brackets, `;` ...)
+     */
+    pub fn operator_category(&self) -> Option<OperatorCategory> {
+        let category = match self {
+            TokenKind::Punctuator(punctuator) => match punctuator {
+                Punctuator::ARROW => OperatorCategory::Arrow,
+                Punctuator::ELLIPSIS => OperatorCategory::Spread,
+                Punctuator::OPTIONAL_CHAIN => OperatorCategory::OptionalChain,
+                Punctuator::INCREMENT | Punctuator::DECREMENT => OperatorCategory::Update,
+                Punctuator::EXPONENT => OperatorCategory::Exponent,
+                Punctuator::EQUAL_EQUAL
+                | Punctuator::STRICT_EQUAL
+                | Punctuator::NOT_EQUAL
+                | Punctuator::STRICT_NOT_EQUAL => OperatorCategory::Equality,
+                Punctuator::LESS_EQUAL | Punctuator::GREATER_EQUAL => OperatorCategory::Relational,
+                Punctuator::SHIFT_LEFT
+                | Punctuator::SHIFT_RIGHT
+                | Punctuator::UNSIGNED_SHIFT_RIGHT => OperatorCategory::Shift,
+                Punctuator::LOGICAL_AND | Punctuator::LOGICAL_OR => OperatorCategory::Logical,
+                Punctuator::NULLISH_COALESCING => OperatorCategory::Coalesce,
+                _ => OperatorCategory::Assignment,
+            },
+            TokenKind::Literal(literal) => match literal {
+                Literal::EQUAL => OperatorCategory::Assignment,
+                Literal::QUESTION_MARK => OperatorCategory::Conditional,
+                Literal::PIPE | Literal::CARET | Literal::AMPERSAND => OperatorCategory::Bitwise,
+                Literal::LESS_THAN | Literal::GREATER_THAN => OperatorCategory::Relational,
+                Literal::PLUS | Literal::MINUS => OperatorCategory::Additive,
+                Literal::ASTERISK | Literal::SLASH | Literal::PERCENT => {
+                    OperatorCategory::Multiplicative
+                }
+                Literal::EXCLAMATION_MARK | Literal::TILDE => OperatorCategory::Unary,
+                Literal::COMMA => OperatorCategory::Sequence,
+                _ => return None,
+            },
+            TokenKind::Identifier(identifier) => match identifier {
+                Identifier::INSTANCEOF | Identifier::IN => OperatorCategory::Relational,
+                Identifier::TYPEOF | Identifier::VOID | Identifier::DELETE => {
+                    OperatorCategory::Unary
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(category)
+    }
+
+    /**
+     * Binding power of the token as a binary operator, higher binds tighter
+     *
+     * `??` sits below `||` and `&&`, mixing them without parentheses is a syntax error anyway.
+     *
+     * @returns None when the token isn't a binary operator
+     */
+    pub fn binary_precedence(&self) -> Option<u8> {
+        let precedence = match self {
+            TokenKind::Punctuator(Punctuator::NULLISH_COALESCING) => 1,
+            TokenKind::Punctuator(Punctuator::LOGICAL_OR) => 2,
+            TokenKind::Punctuator(Punctuator::LOGICAL_AND) => 3,
+            TokenKind::Literal(Literal::PIPE) => 4,
+            TokenKind::Literal(Literal::CARET) => 5,
+            TokenKind::Literal(Literal::AMPERSAND) => 6,
+            _ => match self.operator_category()? {
+                OperatorCategory::Equality => 7,
+                OperatorCategory::Relational => 8,
+                OperatorCategory::Shift => 9,
+                OperatorCategory::Additive => 10,
+                OperatorCategory::Multiplicative => 11,
+                OperatorCategory::Exponent => 12,
+                _ => return None,
+            },
+        };
+
+        Some(precedence)
+    }
+
+    /**
+     * How a chain of the same operator groups
+     *
+     * @returns Right for `**`, assignments and `?`, Left for the other binary operators and `,`
+     */
+    pub fn associativity(&self) -> Option<Associativity> {
+        match self.operator_category()? {
+            OperatorCategory::Exponent
+            | OperatorCategory::Assignment
+            | OperatorCategory::Conditional => Some(Associativity::Right),
+            OperatorCategory::Sequence => Some(Associativity::Left),
+            _ if self.binary_precedence().is_some() => Some(Associativity::Left),
+            _ => None,
+        }
+    }
+
+    pub fn is_assignment(&self) -> bool {
+        self.operator_category() == Some(OperatorCategory::Assignment)
+    }
+}
+
+impl<'a> TokenState<'a> {
+    /**
+     * Longest multi byte punctuator at the current index
+     *
+     * `?.` followed by a digit is `?` and a number so `a?.5:b` stays a conditional.
+     */
+    pub(super) fn read_punctuator(&mut self) -> Option<Punctuator> {
+        let rest = &self.buffer[self.index..];
+        // Every punctuator is two bytes or longer and ends in one of these
+        if !matches!(
+            rest.get(1),
+            Some(b'=' | b'>' | b'.' | b'+' | b'-' | b'*' | b'&' | b'|' | b'?' | b'<')
+        ) {
+            return None;
+        }
+        let punctuator = PUNCTUATORS
+            .iter()
+            .find(|punctuator| rest.starts_with(punctuator.get_value()))
+            .copied()?;
+
+        if punctuator == Punctuator::OPTIONAL_CHAIN
+            && rest.get(2).is_some_and(|byte| byte.is_ascii_digit())
+        {
+            return None;
+        }
+
+        self.index += punctuator.get_value().len();
+        Some(punctuator)
+    }
+
+    /**
+     * PrivateIdentifier starting at the `#` under the current index
+     *
+     * @returns the name without the `#`, reserved words are valid private names
+     */
+    pub(super) fn read_private_name(&mut self) -> Result<Symbol, Errors> {
+        let start = self.index;
+        self.index += 1;
+
+        match self.peek(0) {
+            Some(byte) if self.is_identifier_start(byte) => {}
+            _ => return Err(self.error("Expected a name after #", start)),
+        }
+
+        Ok(match self.read_identifier()? {
+            TokenKind::Name { value, .. } => value,
+            TokenKind::Identifier(identifier) => Symbol::intern(&identifier.to_string()),
+            TokenKind::TsKeyword(keyword) => {
+                Symbol::intern(&String::from_utf8_lossy(keyword.get_value()))
+            }
+            _ => Symbol::intern(""),
+        })
+    }
+}
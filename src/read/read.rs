@@ -1,11 +1,75 @@
-use std::fs;
 use std::fs::File;
-use std::io::Error;
+use std::io::{BufRead, BufReader, Read};
 
+use crate::error::errors::Errors;
+
+use super::encoding::{Encoding, SourceText};
+use super::lexer::Lexer;
 use super::tokenizer::{Token, TokenTrait};
+use super::typescript::Dialect;
+
+/**
+ * Tokenizes a file without loading it all at once
+ *
+ * UTF-16 files are decoded first, they have to be read whole. A UTF-8 byte order mark is lexed
+ * as whitespace.
+ *
+ * @returns the tokens in the dialect of the file extension, a missing file is an IoError and
+ *          bytes that aren't valid in the file's encoding an EncodingError
+ */
+pub fn read_file(file_path: &str) -> Result<Vec<Token>, Errors> {
+    let file = File::open(file_path).map_err(Errors::IoError)?;
+    let dialect = Dialect::from_path(file_path);
+
+    let mut reader = BufReader::new(file);
+    let (encoding, _) = Encoding::detect(reader.fill_buf().map_err(Errors::IoError)?);
+    if encoding != Encoding::Utf8 {
+        let source = decode(reader)?;
+        return Token::tokenize_with_dialect(source.text.as_bytes(), dialect);
+    }
+
+    Lexer::from_reader(reader, dialect).collect()
+}
+
+/**
+ * Reads a file as text, whatever encoding it was saved in
+ *
+ * @returns the text without its byte order mark, SourceText::encode gives the bytes to write back
+ */
+pub fn read_source(file_path: &str) -> Result<SourceText, Errors> {
+    let file = File::open(file_path).map_err(Errors::IoError)?;
+
+    decode(file)
+}
+
+fn decode(mut reader: impl Read) -> Result<SourceText, Errors> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(Errors::IoError)?;
+    SourceText::decode(&bytes)
+}
+
+/**
+ * Tokenizes a file even when parts of it can't be lexed
+ *
+ * UTF-16 files are decoded first, as in read_file.
+ *
+ * @returns the tokens with bad input as TokenKind::Error tokens and a TokenError for each,
+ *          only failing to read or decode the file is an error
+ */
+pub fn read_file_recovering(file_path: &str) -> Result<(Vec<Token>, Vec<Errors>), Errors> {
+    let file = File::open(file_path).map_err(Errors::IoError)?;
+    let dialect = Dialect::from_path(file_path);
 
-fn read_file(file_path: &str) {
-    let data: &[u8] = fs::read(file_path).unwrap().as_ref();
+    let mut reader = BufReader::new(file);
+    let (encoding, _) = Encoding::detect(reader.fill_buf().map_err(Errors::IoError)?);
+    if encoding != Encoding::Utf8 {
+        let source = decode(reader)?;
+        return Ok(Token::tokenize_recovering(source.text.as_bytes(), dialect));
+    }
 
-    let tokens = Token::tokenize(data);
+    let tokens = Lexer::from_reader(reader, dialect)
+        .with_recovery()
+        .collect::<Result<Vec<Token>, Errors>>()?;
+    let diagnostics = Token::diagnostics(&tokens);
+    Ok((tokens, diagnostics))
 }
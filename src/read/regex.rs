@@ -0,0 +1,198 @@
+use crate::error::errors::Errors;
+
+use super::jsx::JsxToken;
+use super::punctuator::Punctuator;
+use super::template::TemplatePart;
+use super::tokenizer::{
+    is_id_continue, is_line_terminator, Identifier, Literal, Nesting, SourceContext, TokenKind,
+    TokenState,
+};
+
+/**
+ * Regular expression literal, `/[}{]/g` has the body `[}{]` and the flags `g`
+ *
+ * The body is kept as written, the pattern itself isn't validated.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegexLiteral {
+    pub body: String,
+    pub flags: String,
+}
+
+impl<'a> TokenState<'a> {
+    /**
+     * RegularExpressionLiteral starting at the `/` under the current index
+     *
+     * Only called where a division can't appear, see track_expression_position.
+     *
+     * @returns the literal, a line break before the closing `/` or an unknown, repeated
+     *          or conflicting flag is a located TokenError
+     */
+    pub(super) fn read_regex(&mut self) -> Result<RegexLiteral, Errors> {
+        let start = self.index;
+        self.index += 1;
+
+        let mut in_class = false;
+        loop {
+            let (character, length) = match self.peek_char() {
+                Some(next) if !is_line_terminator(next.0) => next,
+                _ => return Err(self.error("Unterminated regular expression", start)),
+            };
+            self.index += length;
+
+            match character {
+                '\\' => match self.peek_char() {
+                    Some((escaped, length)) if !is_line_terminator(escaped) => self.index += length,
+                    _ => return Err(self.error("Unterminated regular expression", start)),
+                },
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '/' if !in_class => break,
+                _ => {}
+            }
+        }
+        let body = String::from_utf8_lossy(&self.buffer[start + 1..self.index - 1]).into_owned();
+
+        let flags_start = self.index;
+        let mut flags = String::new();
+        while let Some((character, length)) = self.peek_char() {
+            if character != '\\' && !is_id_continue(character) {
+                break;
+            }
+            self.index += length;
+
+            let conflicting = match character {
+                'u' => flags.contains('v'),
+                'v' => flags.contains('u'),
+                _ => false,
+            };
+            let known = matches!(character, 'd' | 'g' | 'i' | 'm' | 's' | 'u' | 'v' | 'y');
+            if !known || conflicting || flags.contains(character) {
+                return Err(self.error("Invalid regular expression flags", flags_start));
+            }
+            flags.push(character);
+        }
+
+        Ok(RegexLiteral { body, flags })
+    }
+
+    /**
+     * Records what the token just lexed means for the next one
+     *
+     * After something that ends an expression (`a`, `1`, `)`, `]`) a `/` is a division, anywhere
+     * else it starts a regex. Braces and parens are tracked so `}` and `)` can be told apart:
+     * `if (a) /x/` and `{}` block ends allow a regex, `(a) / 2` and `({}) / 2` don't.
+     *
+     * This is the only place the nesting changes, so the state after any token can be rebuilt
+     * by replaying the kinds before it.
+     */
+    pub(super) fn track_expression_position(&mut self, kind: &TokenKind) {
+        let condition_keyword = std::mem::take(&mut self.condition_keyword);
+
+        let (expression_allowed, brace_is_block) = match kind {
+            TokenKind::Literal(Literal::CURLY_BRACKET_LEFT) => {
+                self.nesting.push(if self.brace_is_block {
+                    Nesting::Block
+                } else {
+                    Nesting::Brace
+                });
+                (true, true)
+            }
+            TokenKind::Literal(Literal::CURLY_BRACKET_RIGHT) => match self.nesting.pop() {
+                Some(Nesting::Brace) => (false, false),
+                _ => (true, true),
+            },
+            TokenKind::Literal(Literal::OPEN_BRACKET_LEFT) => {
+                self.parens.push(condition_keyword);
+                (true, false)
+            }
+            TokenKind::Literal(Literal::OPEN_BRACKET_RIGHT) => {
+                // `if (a) {` opens a block like `function () {` does
+                (self.parens.pop().unwrap_or(false), true)
+            }
+            TokenKind::Literal(Literal::SQUARE_BRACKET_RIGHT) => (false, false),
+            TokenKind::Literal(Literal::SEMICOLON) => (true, true),
+            // `a! / 2` is a non-null assertion followed by a division
+            TokenKind::Literal(Literal::EXCLAMATION_MARK)
+                if self.dialect.is_typescript() && !self.expression_allowed =>
+            {
+                (false, false)
+            }
+            TokenKind::Literal(_) => (true, false),
+            // `=> {` is a function body, `a++ / 2` a division
+            TokenKind::Punctuator(Punctuator::ARROW) => (true, true),
+            TokenKind::Punctuator(Punctuator::INCREMENT | Punctuator::DECREMENT) => (false, false),
+            TokenKind::Punctuator(_) => (true, false),
+            TokenKind::Identifier(identifier) => match identifier {
+                Identifier::THIS
+                | Identifier::SUPER
+                | Identifier::NULL
+                | Identifier::TRUE
+                | Identifier::FALSE => (false, false),
+                Identifier::IF | Identifier::WHILE | Identifier::FOR | Identifier::WITH => {
+                    self.condition_keyword = true;
+                    (true, false)
+                }
+                Identifier::ELSE | Identifier::DO | Identifier::TRY | Identifier::FINALLY => {
+                    (true, true)
+                }
+                Identifier::STRICT_YIELD | Identifier::MODULE_ASYNC => (true, false),
+                // `let`, `of`, `get` ... are names wherever a regex could follow them
+                identifier if !identifier.is_reserved(SourceContext::Sloppy) => (false, true),
+                _ => (true, false),
+            },
+            // A Middle closes one substitution and opens the next
+            TokenKind::Template(template) => match template.part {
+                TemplatePart::Head => {
+                    self.nesting.push(Nesting::Template);
+                    (true, false)
+                }
+                TemplatePart::Middle => (true, false),
+                TemplatePart::Tail => {
+                    self.nesting.pop();
+                    (false, false)
+                }
+                TemplatePart::NoSubstitution => (false, false),
+            },
+            // Names can be followed by a class or function body
+            TokenKind::Name { .. }
+            | TokenKind::PrivateName(_)
+            | TokenKind::TsKeyword(_)
+            | TokenKind::Decorator(_) => (false, true),
+            TokenKind::Number(_) | TokenKind::String(_) | TokenKind::Regex(_) => (false, false),
+            // Whatever was meant, an operand is the likelier next token
+            TokenKind::Error(_) => (true, false),
+            // Only ExpressionStart hands back to JavaScript, a finished element ends an expression
+            TokenKind::Jsx(token) => {
+                match token {
+                    JsxToken::TagOpen => self.nesting.push(Nesting::JsxTag { closing: false }),
+                    JsxToken::TagClose => self.nesting.push(Nesting::JsxTag { closing: true }),
+                    JsxToken::TagEnd => match self.nesting.pop() {
+                        // The children of the element that just closed
+                        Some(Nesting::JsxTag { closing: true }) => {
+                            self.nesting.pop();
+                        }
+                        _ => self.nesting.push(Nesting::JsxChildren),
+                    },
+                    JsxToken::SelfClosingEnd | JsxToken::ExpressionEnd => {
+                        self.nesting.pop();
+                    }
+                    JsxToken::ExpressionStart => {
+                        self.nesting.push(Nesting::JsxExpression);
+                        return self.set_expression_position(true, false);
+                    }
+                    _ => {}
+                }
+                (false, false)
+            }
+            TokenKind::EndOfFile => (true, true),
+        };
+
+        self.set_expression_position(expression_allowed, brace_is_block);
+    }
+
+    fn set_expression_position(&mut self, expression_allowed: bool, brace_is_block: bool) {
+        self.expression_allowed = expression_allowed;
+        self.brace_is_block = brace_is_block;
+    }
+}
@@ -0,0 +1,172 @@
+use crate::error::errors::Errors;
+
+use super::tokenizer::{is_line_terminator, TokenState};
+
+/**
+ * Quoted string literal, the raw text including the quotes is the token's span
+ *
+ * value is the cooked string with every escape decoded, lone surrogates become U+FFFD.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct StringLiteral {
+    pub value: String,
+    pub legacy_octal: bool, // `\1` or `\8` escapes, both rejected in strict mode
+}
+
+impl<'a> TokenState<'a> {
+    /**
+     * StringLiteral starting at the quote under the current index
+     *
+     * @returns the literal with its cooked value, an unescaped line break or missing
+     *          closing quote is a TokenError located at the opening quote
+     */
+    pub(super) fn read_string(&mut self) -> Result<StringLiteral, Errors> {
+        let start = self.index;
+        let quote = self.buffer[start];
+        self.index += 1;
+
+        let mut literal = StringLiteral {
+            value: String::new(),
+            legacy_octal: false,
+        };
+
+        loop {
+            match self.peek(0) {
+                None | Some(b'\n' | b'\r') => {
+                    return Err(self.error("Unterminated string literal", start))
+                }
+                Some(byte) if byte == quote => {
+                    self.index += 1;
+                    return Ok(literal);
+                }
+                Some(b'\\') => {
+                    if self.read_escape(&mut literal.value, false)? {
+                        literal.legacy_octal = true;
+                    }
+                }
+                Some(_) => {
+                    // U+2028 and U+2029 are allowed unescaped since ES2019
+                    let (character, length) =
+                        self.peek_char().unwrap_or((char::REPLACEMENT_CHARACTER, 1));
+                    literal.value.push(character);
+                    self.index += length;
+                    if is_line_terminator(character) {
+                        self.new_line();
+                    }
+                }
+            }
+        }
+    }
+
+    /**
+     * Decodes the escape sequence under the current index (the backslash) into value
+     *
+     * @args value: &mut String - the cooked string being built
+     * @args template: bool - templates don't allow the legacy octal escapes
+     *
+     * @returns true if the escape was a legacy octal or `\8` / `\9` escape
+     */
+    pub(super) fn read_escape(
+        &mut self,
+        value: &mut String,
+        template: bool,
+    ) -> Result<bool, Errors> {
+        let start = self.index;
+        self.index += 1;
+
+        let (character, length) = match self.peek_char() {
+            Some(next) => next,
+            None => return Err(self.error("Unterminated string literal", start)),
+        };
+
+        if is_line_terminator(character) {
+            // Line continuation, contributes nothing to the value
+            self.index += if self.buffer[self.index..].starts_with(b"\r\n") {
+                2
+            } else {
+                length
+            };
+            self.new_line();
+            return Ok(false);
+        }
+
+        self.index += length;
+        let decoded = match character {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'b' => '\u{08}',
+            'f' => '\u{0C}',
+            'v' => '\u{0B}',
+            '0' if !self.peek(0).is_some_and(|byte| byte.is_ascii_digit()) => '\0',
+            '0'..='7' => {
+                if template {
+                    return Err(self.error("Octal escapes are not allowed in templates", start));
+                }
+                value.push(self.read_legacy_octal_escape(character));
+                return Ok(true);
+            }
+            '8' | '9' => {
+                if template {
+                    return Err(self.error("\\8 and \\9 are not allowed in templates", start));
+                }
+                value.push(character);
+                return Ok(true);
+            }
+            'x' => {
+                let high = self.peek(0).and_then(|byte| (byte as char).to_digit(16));
+                let low = self.peek(1).and_then(|byte| (byte as char).to_digit(16));
+                match (high, low) {
+                    (Some(high), Some(low)) => {
+                        self.index += 2;
+                        char::from_u32(high * 16 + low).unwrap_or(char::REPLACEMENT_CHARACTER)
+                    }
+                    _ => return Err(self.error("Invalid hexadecimal escape sequence", start)),
+                }
+            }
+            'u' => self.read_string_unicode_escape(start)?,
+            other => other, // identity escape, `\'` `\"` `\\` and any other character
+        };
+
+        value.push(decoded);
+        Ok(false)
+    }
+
+    // `\1` to `\377`, three digits only when the first one is 0-3
+    fn read_legacy_octal_escape(&mut self, first: char) -> char {
+        let mut code_point = first.to_digit(8).unwrap_or(0);
+        let max_digits = if code_point <= 3 { 3 } else { 2 };
+
+        for _ in 1..max_digits {
+            match self.peek(0).and_then(|byte| (byte as char).to_digit(8)) {
+                Some(digit) => {
+                    code_point = code_point * 8 + digit;
+                    self.index += 1;
+                }
+                None => break,
+            }
+        }
+
+        char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    // `\uD83D\uDE00` surrogate pairs are joined into one character
+    fn read_string_unicode_escape(&mut self, start: usize) -> Result<char, Errors> {
+        let code_point = self.read_escaped_code_point(start)?;
+
+        if (0xD800..=0xDBFF).contains(&code_point) && self.buffer[self.index..].starts_with(b"\\u")
+        {
+            let resume = self.index;
+            self.index += 2;
+            match self.read_escaped_code_point(resume) {
+                Ok(low @ 0xDC00..=0xDFFF) => {
+                    let combined = 0x10000 + ((code_point - 0xD800) << 10) + (low - 0xDC00);
+                    return Ok(char::from_u32(combined).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                _ => self.index = resume,
+            }
+        }
+
+        Ok(char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
@@ -0,0 +1,111 @@
+use crate::error::errors::Errors;
+
+use super::tokenizer::{is_line_terminator, TokenState};
+
+/**
+ * Which piece of a template literal a token is
+ *
+ * `a${b}c${d}e` lexes as Head(`a${), b, Middle(}c${), d, Tail(}e`)
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TemplatePart {
+    NoSubstitution,
+    Head,
+    Middle,
+    Tail,
+}
+
+/**
+ * Template characters between the delimiters of one template piece
+ *
+ * cooked is None when an escape is invalid, that is only allowed in tagged templates.
+ * raw keeps the escapes as written with CRLF and CR normalised to LF (String.raw value).
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateLiteral {
+    pub part: TemplatePart,
+    pub cooked: Option<String>,
+    pub raw: String,
+}
+
+impl<'a> TokenState<'a> {
+    /**
+     * Template piece starting at a backtick or at the `}` closing a substitution
+     *
+     * @args opening: bool - the piece starts at a backtick
+     *
+     * @returns the piece, after a Head or Middle the nesting sends the matching `}` back here
+     */
+    pub(super) fn read_template(&mut self, opening: bool) -> Result<TemplateLiteral, Errors> {
+        let start = self.index;
+        self.index += 1;
+
+        let mut cooked = Some(String::new());
+        let mut raw = String::new();
+        let mut raw_start = self.index;
+
+        let substitution = loop {
+            match self.peek(0) {
+                None => return Err(self.error("Unterminated template literal", start)),
+                Some(b'`') => break false,
+                Some(b'$') if self.peek(1) == Some(b'{') => break true,
+                Some(b'\\') => {
+                    let mut escaped = String::new();
+                    let valid = self.read_escape(&mut escaped, true).is_ok();
+                    match cooked.as_mut() {
+                        Some(value) if valid => value.push_str(&escaped),
+                        _ => cooked = None,
+                    }
+                }
+                Some(b'\r') => {
+                    // CRLF and CR are LF in both values
+                    push_raw(&mut raw, &self.buffer[raw_start..self.index]);
+                    raw.push('\n');
+                    if let Some(value) = cooked.as_mut() {
+                        value.push('\n');
+                    }
+                    self.index += if self.peek(1) == Some(b'\n') { 2 } else { 1 };
+                    raw_start = self.index;
+                    self.new_line();
+                }
+                Some(_) => {
+                    let (character, length) =
+                        self.peek_char().unwrap_or((char::REPLACEMENT_CHARACTER, 1));
+                    if let Some(value) = cooked.as_mut() {
+                        value.push(character);
+                    }
+                    self.index += length;
+                    if is_line_terminator(character) {
+                        self.new_line();
+                    }
+                }
+            }
+        };
+
+        push_raw(&mut raw, &self.buffer[raw_start..self.index]);
+
+        let part = if substitution {
+            self.index += 2;
+            if opening {
+                TemplatePart::Head
+            } else {
+                TemplatePart::Middle
+            }
+        } else {
+            self.index += 1;
+            if opening {
+                TemplatePart::NoSubstitution
+            } else {
+                TemplatePart::Tail
+            }
+        };
+
+        Ok(TemplateLiteral { part, cooked, raw })
+    }
+}
+
+// Appends source bytes to the raw value, CRs left inside line continuations become LF
+fn push_raw(raw: &mut String, bytes: &[u8]) {
+    let text = String::from_utf8_lossy(bytes);
+    raw.push_str(&text.replace("\r\n", "\n").replace('\r', "\n"));
+}
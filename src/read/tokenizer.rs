@@ -1,365 +1,707 @@
-use std::collections::HashMap;
-
-use serde::de::value;
-use serde_json::Value;
+use std::borrow::Cow;
 
 use crate::error::errors::Errors;
 
-#[derive(Copy, Clone, Debug)]
-pub(crate) enum Token {
+use super::interner::Symbol;
+use super::jsx::JsxToken;
+use super::lexer::Lexer;
+use super::number::NumericLiteral;
+use super::punctuator::Punctuator;
+use super::regex::RegexLiteral;
+use super::string::StringLiteral;
+use super::template::TemplateLiteral;
+use super::tokens::KeywordCategory;
+pub use super::tokens::{Identifier, Literal};
+use super::trivia::{Trivia, TriviaList};
+use super::typescript::{Dialect, TsKeyword};
+
+/**
+ * Location of a token inside the source buffer
+ *
+ * start/end are byte offsets (end exclusive), line is 1-based and
+ * column is the 0-based byte offset from the start of that line.
+ */
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/**
+ * Goal the source is parsed with, decides which words are reserved
+ *
+ * Module code is always strict, a script becomes strict through a
+ * "use strict" directive prologue (see `SourceContext::detect`).
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SourceContext {
+    Sloppy,
+    Strict,
+    Module,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenKind {
     Identifier(Identifier),
+    Name { value: Symbol, escaped: bool }, // user identifier, value has its escapes decoded
+    TsKeyword(TsKeyword),                  // contextual keyword, TypeScript dialect only
+    Decorator(Symbol),                     // `@name`, value without the `@`
     Literal(Literal),
+    Punctuator(Punctuator),
+    PrivateName(Symbol), // `#name` class member, value without the `#`
+    Number(NumericLiteral),
+    String(StringLiteral),
+    Template(TemplateLiteral),
+    Regex(RegexLiteral),
+    Jsx(JsxToken),
+    Error(&'static str), // input that couldn't be lexed, only produced when recovering
+    EndOfFile,           // zero width, holds the trivia after the last token
 }
 
-pub(crate) trait TokenTrait {
-    fn tokenize(buffer: &[u8]) -> Result<Vec<Token>, Errors>;
-    fn set_possible_tokens_from_u8(
-        current_bytes: &mut Vec<u8>,
-        current_possible_tokens: &mut Vec<Token>,
-        byte: u8,
-    );
-    fn get_next_possible_byte_in_token_based_on_index(index: u8, token: &Token) -> Option<u8>;
+impl TokenKind {
+    /**
+     * Whether the token can't be used as an identifier in the given context
+     *
+     * Names spelled with escapes (`\u0061wait`) are checked against the reserved words as well,
+     * an escape never turns a reserved word into a valid identifier.
+     */
+    pub fn is_reserved(&self, context: SourceContext) -> bool {
+        match self {
+            TokenKind::Identifier(identifier) => identifier.is_reserved(context),
+            TokenKind::Name {
+                value,
+                escaped: true,
+            } => value.with_str(|value| {
+                Identifier::from_bytes(value.as_bytes())
+                    .is_some_and(|identifier| identifier.is_reserved(context))
+            }),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+    pub newline_before: bool, // a line terminator sits between this token and the previous one
+    pub leading_trivia: TriviaList, // everything since the previous token's trailing trivia
+    pub trailing_trivia: TriviaList, // whitespace and comments up to the end of the line
+}
+
+impl Token {
+    pub fn text<'a>(&self, buffer: &'a [u8]) -> &'a [u8] {
+        &buffer[self.span.start..self.span.end]
+    }
+
+    /**
+     * @returns a TokenError for every Error token, located at the bad input
+     */
+    pub fn diagnostics(tokens: &[Token]) -> Vec<Errors> {
+        tokens
+            .iter()
+            .filter_map(|token| match token.kind {
+                TokenKind::Error(message) => Some(Errors::TokenError(message, token.span)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Comments in front of the token, the last one is usually its documentation
+    pub fn leading_comments(&self) -> impl Iterator<Item = &Trivia> {
+        self.leading_trivia
+            .iter()
+            .filter(|trivia| trivia.kind.is_comment())
+    }
+}
 
-    fn to_string(self) -> String;
+pub trait TokenTrait {
+    fn tokenize(buffer: &[u8]) -> Result<Vec<Token>, Errors>;
+    fn tokenize_with_dialect(buffer: &[u8], dialect: Dialect) -> Result<Vec<Token>, Errors>;
+    fn tokenize_recovering(buffer: &[u8], dialect: Dialect) -> (Vec<Token>, Vec<Errors>);
 }
 
 impl TokenTrait for Token {
     fn tokenize(buffer: &[u8]) -> Result<Vec<Token>, Errors> {
-        // Convert the buffer to a UTF-8 string slice
-        let mut state = TokenState {
-            current_bytes: Vec::new(),
-            current_possible_tokens: Vec::new(),
+        Self::tokenize_with_dialect(buffer, Dialect::JavaScript)
+    }
+
+    fn tokenize_with_dialect(buffer: &[u8], dialect: Dialect) -> Result<Vec<Token>, Errors> {
+        // Single pass over the buffer, every token comes with the trivia around it, ending with EndOfFile
+        Lexer::new(buffer, dialect).collect()
+    }
+
+    /**
+     * Tokenizes the whole buffer whatever it contains
+     *
+     * @returns every token, bad input as TokenKind::Error tokens, and a TokenError for each of them
+     */
+    fn tokenize_recovering(buffer: &[u8], dialect: Dialect) -> (Vec<Token>, Vec<Errors>) {
+        let tokens: Vec<Token> = Lexer::new(buffer, dialect)
+            .with_recovery()
+            .filter_map(Result::ok)
+            .collect();
+        let diagnostics = Token::diagnostics(&tokens);
+        (tokens, diagnostics)
+    }
+}
+
+// What an open curly bracket belongs to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum Nesting {
+    Block,                    // statement blocks, class and function bodies
+    Brace,                    // object literals and other braces inside an expression
+    Template, // `${` inside a template, closed by the `}` that continues the template
+    JsxTag { closing: bool }, // inside `<...>` or `</...>`
+    JsxChildren, // between an opening and a closing tag
+    JsxExpression, // `{` inside an element, closed by ExpressionEnd
+}
+
+pub(super) struct TokenState<'a> {
+    pub(super) buffer: Cow<'a, [u8]>, // the whole source, or the unread part of a stream
+    pub(super) index: usize,
+    pub(super) base: usize, // source offset of buffer[0], spans are source offsets
+    pub(super) dialect: Dialect,
+    pub(super) line: usize,
+    pub(super) line_start: usize, // source offset where the current line begins
+    pub(super) newline_before: bool, // set while reading trivia, reset after every token
+    pub(super) started: bool,     // a token was read, `-->` only opens a comment at a line start
+    pub(super) finished: bool,    // EndOfFile was returned
+    pub(super) anchor: (usize, usize, usize), // index, line and line start of the last token or comment start
+    pub(super) nesting: Vec<Nesting>, // open `{`, `${` and JSX elements, decides what a `}` closes
+    pub(super) parens: Vec<bool>,     // open `(`, true when it holds an if/while/for/with condition
+    pub(super) expression_allowed: bool, // the previous token can't end an expression, a `/` starts a regex
+    pub(super) brace_is_block: bool,     // a `{` at this point opens a block rather than an object
+    pub(super) condition_keyword: bool,  // the previous token was if, while, for or with
+    pub(super) recovering: bool, // bad input becomes an Error token instead of ending the lexing
+    pub(super) read_to: usize, // furthest index the current token looked at, when that's past its end
+}
+
+impl<'a> TokenState<'a> {
+    pub(super) fn new(buffer: &'a [u8], dialect: Dialect) -> Self {
+        Self {
+            buffer: Cow::Borrowed(buffer),
+            index: 0,
+            base: 0,
+            dialect,
+            line: 1,
+            line_start: 0,
+            newline_before: false,
+            started: false,
+            finished: false,
+            anchor: (0, 1, 0),
+            nesting: Vec::new(),
+            parens: Vec::new(),
+            expression_allowed: true,
+            brace_is_block: true,
+            condition_keyword: false,
+            recovering: false,
+            read_to: 0,
+        }
+    }
+
+    pub(super) fn peek(&self, offset: usize) -> Option<u8> {
+        self.buffer.get(self.index + offset).copied()
+    }
+
+    /**
+     * Decodes the UTF-8 character at the current index
+     *
+     * @returns Option<(char, usize)> - the character and its length in bytes,
+     *          invalid sequences come back as U+FFFD with a length of 1
+     */
+    pub(super) fn peek_char(&self) -> Option<(char, usize)> {
+        let first = self.peek(0)?;
+        if first < 0x80 {
+            return Some((first as char, 1));
+        }
+
+        let length = match first {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return Some((char::REPLACEMENT_CHARACTER, 1)),
         };
+        let end = (self.index + length).min(self.buffer.len());
 
-        // Iterate over each character in the decoded string
-        for byte in buffer {
-            // Call the trait method
-            Self::set_possible_tokens_from_u8(
-                &mut state.current_bytes,
-                &mut state.current_possible_tokens,
-                byte,
-            );
+        match std::str::from_utf8(&self.buffer[self.index..end]) {
+            Ok(decoded) => decoded.chars().next().map(|character| (character, length)),
+            Err(_) => Some((char::REPLACEMENT_CHARACTER, 1)),
         }
+    }
 
-        // Return the final result (possibly a collection of tokens)
-        Ok(state.current_possible_tokens) // Return actual result
+    fn span_from(&self, start: usize, line: usize, column: usize) -> Span {
+        Span {
+            start: self.base + start,
+            end: self.base + self.index,
+            line,
+            column,
+        }
     }
 
-    fn set_possible_tokens_from_u8(
-        current_bytes: &mut Vec<u8>,
-        current_possible_tokens: &mut Vec<Token>,
-        byte: u8,
-    ) {
-        if current_possible_tokens.len() == 0 {}
-
-        // Use retain to keep tokens that still have a valid next character
-        current_possible_tokens.retain(|token| {
-            match Self::get_next_possible_byte_in_token_based_on_index(
-                current_bytes.len().try_into().unwrap(),
-                token,
-            ) {
-                Some(next_byte) => {
-                    // If next_char and character are the same then we can keep it in our vec, otherwise remove
-                    next_byte == byte
+    // Column of a buffer index on the current line
+    pub(super) fn column_of(&self, index: usize) -> usize {
+        self.base + index - self.line_start
+    }
+
+    pub(super) fn error(&self, message: &'static str, start: usize) -> Errors {
+        let (line, column) = self.location_of(start);
+        Errors::TokenError(
+            message,
+            Span {
+                start: self.base + start,
+                end: self.base + self.index.max(start),
+                line,
+                column,
+            },
+        )
+    }
+
+    // Remembers where a token or comment starts so errors inside it can be located
+    pub(super) fn mark(&mut self) {
+        self.anchor = (self.index, self.line, self.line_start);
+    }
+
+    /**
+     * Line and column of an offset that was already consumed
+     *
+     * Offsets on the current line are direct, earlier ones are counted forward from the last mark.
+     */
+    fn location_of(&self, offset: usize) -> (usize, usize) {
+        if self.base + offset >= self.line_start {
+            return (self.line, self.column_of(offset));
+        }
+
+        let (anchor, mut line, mut line_start) = self.anchor;
+        let mut index = anchor;
+        while index < offset {
+            let length = match self.buffer[index] {
+                b'\r' if self.buffer.get(index + 1) == Some(&b'\n') => 2,
+                b'\n' | b'\r' => 1,
+                0xE2 if matches!(
+                    self.buffer.get(index + 1..index + 3),
+                    Some([0x80, 0xA8 | 0xA9])
+                ) =>
+                {
+                    3
+                }
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            };
+            index += length;
+            line += 1;
+            line_start = self.base + index;
+        }
+
+        (line, (self.base + offset).saturating_sub(line_start))
+    }
+
+    pub(super) fn new_line(&mut self) {
+        self.line += 1;
+        self.line_start = self.base + self.index;
+        self.newline_before = true;
+    }
+
+    pub(super) fn next_token(&mut self) -> Result<Option<Token>, Errors> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        // Whitespace between JSX children is text
+        let in_children = self.nesting.last() == Some(&Nesting::JsxChildren);
+        let leading_trivia = if in_children {
+            TriviaList::new()
+        } else {
+            self.read_trivia(false)?
+        };
+
+        let start = self.index;
+        let line = self.line;
+        let column = self.column_of(start);
+        let newline_before = self.newline_before;
+        self.read_to = start;
+        self.mark();
+
+        let kind = match self.peek(0) {
+            Some(byte) => match self.read_token_kind(byte, start, in_children) {
+                Ok(kind) => kind,
+                Err(Errors::TokenError(message, _)) if self.recovering => {
+                    self.skip_bad_input(start);
+                    TokenKind::Error(message)
                 }
-                None => false, // Remove this token from the Vec
+                Err(error) => return Err(error),
+            },
+            None if matches!(
+                self.nesting.last(),
+                Some(Nesting::JsxTag { .. } | Nesting::JsxChildren)
+            ) =>
+            {
+                if !self.recovering {
+                    return Err(self.error("Unterminated JSX element", start));
+                }
+                // Nothing is left to close the elements, EndOfFile follows
+                self.nesting.clear();
+                TokenKind::Error("Unterminated JSX element")
+            }
+            None => {
+                self.finished = true;
+                return Ok(Some(Token {
+                    kind: TokenKind::EndOfFile,
+                    span: self.span_from(start, line, column),
+                    newline_before,
+                    leading_trivia,
+                    trailing_trivia: TriviaList::new(),
+                }));
             }
-        });
+        };
+
+        self.track_expression_position(&kind);
+        let span = self.span_from(start, line, column);
+        self.started = true;
+
+        // Line breaks inside the token don't count, a multi-line comment after it does
+        self.newline_before = false;
+        let trailing_trivia = if self.nesting.last() == Some(&Nesting::JsxChildren) {
+            TriviaList::new()
+        } else {
+            self.read_trivia(true)?
+        };
+
+        Ok(Some(Token {
+            kind,
+            span,
+            newline_before,
+            leading_trivia,
+            trailing_trivia,
+        }))
+    }
+
+    // Kind of the token at start, the trivia in front of it was read already
+    fn read_token_kind(
+        &mut self,
+        byte: u8,
+        start: usize,
+        in_children: bool,
+    ) -> Result<TokenKind, Errors> {
+        let starts_number = byte.is_ascii_digit()
+            || byte == b'.' && self.peek(1).is_some_and(|next| next.is_ascii_digit());
+
+        Ok(
+            if let Some(Nesting::JsxTag { closing }) = self.nesting.last() {
+                self.read_jsx_tag_token(*closing)?
+            } else if in_children {
+                self.read_jsx_child()?
+            } else if !in_children && self.buffer[start..].starts_with(b"/*") {
+                // read_trivia leaves a comment that never ends to be lexed here when recovering
+                self.skip_block_comment()?;
+                return Err(self.error("Unterminated comment", start));
+            } else if starts_number {
+                TokenKind::Number(self.read_number()?)
+            } else if self.is_identifier_start(byte) {
+                self.read_identifier()?
+            } else if byte == b'\'' || byte == b'"' {
+                TokenKind::String(self.read_string()?)
+            } else if byte == b'`' {
+                TokenKind::Template(self.read_template(true)?)
+            } else if byte == b'}' && self.nesting.last() == Some(&Nesting::Template) {
+                TokenKind::Template(self.read_template(false)?)
+            } else if byte == b'}' && self.nesting.last() == Some(&Nesting::JsxExpression) {
+                self.index += 1;
+                TokenKind::Jsx(JsxToken::ExpressionEnd)
+            } else if self.dialect.is_jsx() && self.expression_allowed && self.starts_jsx_element()
+            {
+                self.read_jsx_element_start()
+            } else if byte == b'/' && self.expression_allowed {
+                TokenKind::Regex(self.read_regex()?)
+            } else if byte == b'#' {
+                TokenKind::PrivateName(self.read_private_name()?)
+            } else if let Some(decorator) = self.read_decorator() {
+                decorator
+            } else if let Some(punctuator) = self.read_punctuator() {
+                TokenKind::Punctuator(punctuator)
+            } else if let Some(literal) = Literal::from_byte(byte) {
+                self.index += 1;
+                TokenKind::Literal(literal)
+            } else {
+                self.index += 1;
+                return Err(self.error("Unexpected character", start));
+            },
+        )
     }
 
     /**
+     * Moves past input that failed to lex, at least one character
      *
-     * @args index: u8 - next index to check in token
-     * @args token: Option<&Token> - the token to get for
-     *
+     * Most errors already stop after the bad part, a bad first character is skipped here.
+     */
+    fn skip_bad_input(&mut self, start: usize) {
+        if self.index > start {
+            return;
+        }
+        let (character, length) = self.peek_char().unwrap_or((char::REPLACEMENT_CHARACTER, 1));
+        self.index = start + length;
+        if is_line_terminator(character) {
+            self.new_line();
+        }
+    }
+
+    pub(super) fn is_identifier_start(&self, byte: u8) -> bool {
+        match byte {
+            b'\\' => true,
+            0x80.. => self
+                .peek_char()
+                .is_some_and(|(character, _)| is_id_start(character)),
+            _ => is_id_start(byte as char),
+        }
+    }
+
+    /**
+     * IdentifierName, reserved words are only recognised when written without escapes
      *
-     * @returns Option<u8>
-     * @returns u8 if it has another u8
-     * @returns None if it doesn't have any more char's
+     * @returns TokenKind::Identifier for reserved words and TokenKind::Name for everything else
      */
-    fn get_next_possible_byte_in_token_based_on_index(index: u8, token: &Token) -> Option<u8> {
-        match token.get_value() {
-            ValueType::Str(string_value) => {
-                if string_value.len() == index as usize {
-                    // Check if the index is at the end of the string
-                    None
-                } else {
-                    let next_char = string_value.chars().nth(index as usize); // Get the character at the next index
-                    let return_char = next_char.map(|character| CharType::Char(character));
-                    return_char
-                }
+    pub(super) fn read_identifier(&mut self) -> Result<TokenKind, Errors> {
+        let start = self.index;
+
+        // Plain ASCII names are read straight off the buffer, nothing is allocated for them
+        while let Some(byte) = self.peek(0) {
+            let valid = if self.index == start {
+                is_ascii_id_start(byte)
+            } else {
+                is_ascii_id_continue(byte)
+            };
+            if !valid {
+                break;
             }
-            ValueType::U8(unsigned8) => {
-                if index > 0 {
-                    None
-                } else {
-                    Some(unsigned8) // Return the unsigned 8-bit value wrapped in Some
+            self.index += 1;
+        }
+        if !matches!(self.peek(0), Some(b'\\' | 0x80..)) {
+            let bytes = &self.buffer[start..self.index];
+            if let Some(keyword) = self.keyword(bytes) {
+                return Ok(keyword);
+            }
+            // Only ASCII was read
+            let name = std::str::from_utf8(bytes).unwrap_or_default();
+            return Ok(TokenKind::Name {
+                value: Symbol::intern(name),
+                escaped: false,
+            });
+        }
+
+        // Escapes and non-ASCII characters, the ASCII part is known to be valid
+        let mut value = String::from_utf8_lossy(&self.buffer[start..self.index]).into_owned();
+        let mut escaped = false;
+
+        while let Some(byte) = self.peek(0) {
+            let escape_start = self.index;
+            let (character, length) = if byte == b'\\' {
+                if self.peek(1) != Some(b'u') {
+                    self.index += 1;
+                    return Err(self.error("Invalid escape in identifier", escape_start));
                 }
+                self.index += 2;
+                escaped = true;
+                (self.read_unicode_escape(escape_start)?, 0)
+            } else {
+                self.peek_char().unwrap_or((char::REPLACEMENT_CHARACTER, 1))
+            };
+
+            let valid = if value.is_empty() {
+                is_id_start(character)
+            } else {
+                is_id_continue(character)
+            };
+            if !valid {
+                if self.index != escape_start {
+                    return Err(self.error("Invalid escape in identifier", escape_start));
+                }
+                break;
+            }
+
+            self.index += length;
+            value.push(character);
+        }
+
+        if !escaped {
+            if let Some(keyword) = self.keyword(&self.buffer[start..self.index]) {
+                return Ok(keyword);
             }
         }
+
+        Ok(TokenKind::Name {
+            value: Symbol::intern(&value),
+            escaped,
+        })
     }
 
-    fn to_string(self) -> String {
-        match self.get_value() {
-            ValueType::Str(v) => v.to_string(),
-            ValueType::U8(v) => v.to_string(),
+    // The reserved word or TypeScript keyword spelled by the bytes
+    fn keyword(&self, bytes: &[u8]) -> Option<TokenKind> {
+        // `abstract` is a TypeScript keyword before it is an old future reserved word
+        if self.dialect.is_typescript() {
+            if let Some(keyword) = TsKeyword::from_bytes(bytes) {
+                return Some(TokenKind::TsKeyword(keyword));
+            }
         }
+        Identifier::from_bytes(bytes).map(TokenKind::Identifier)
     }
-}
 
-struct TokenState {
-    current_bytes: Vec<u8>, // changing based on iterations - value is the char
-    current_possible_tokens: Vec<Token>, // possible tokens with that order of chars
-}
+    /**
+     * Reads the hex digits of `\uXXXX` or `\u{X...}`, the index must sit right after the `u`
+     *
+     * @args start: usize - index of the backslash, used for the error location
+     */
+    fn read_unicode_escape(&mut self, start: usize) -> Result<char, Errors> {
+        let code_point = self.read_escaped_code_point(start)?;
 
-pub(crate) trait SharedToken {
-    fn get_value(&self) -> &[u8];
-    fn byte_at_index(&self) -> u8;
-}
+        // Surrogates are only meaningful in strings, they can't be stored in a Rust char
+        Ok(char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
 
-impl SharedToken for Token {
-    fn get_value(&self) -> &[u8] {
-        match self {
-            Token::Identifier(identifier) => identifier.get_value(),
-            Token::Literal(literal) => literal.get_value(),
+    pub(super) fn read_escaped_code_point(&mut self, start: usize) -> Result<u32, Errors> {
+        let braced = self.peek(0) == Some(b'{');
+        if braced {
+            self.index += 1;
+        }
+
+        let mut code_point: u32 = 0;
+        let mut digits = 0;
+        while let Some(digit) = self.peek(0).and_then(|byte| (byte as char).to_digit(16)) {
+            if !braced && digits == 4 {
+                break;
+            }
+            code_point = code_point.saturating_mul(16).saturating_add(digit);
+            digits += 1;
+            self.index += 1;
+        }
+
+        let complete = if braced {
+            digits > 0 && self.peek(0) == Some(b'}')
+        } else {
+            digits == 4
+        };
+        if !complete || code_point > 0x10FFFF {
+            return Err(self.error("Invalid Unicode escape sequence", start));
+        }
+        if braced {
+            self.index += 1;
         }
+
+        Ok(code_point)
     }
-    fn byte_at_index(&self, index: u8) -> u8 {
-        match self {
-            Token::Identifier(identifier) => identifier.byte_at_index(index),
-            Token::Literal(literal) => literal.get_value(),
+}
+
+impl SourceContext {
+    /**
+     * Works out the context of a source file from its directive prologue
+     *
+     * @args buffer: &[u8] - the source
+     * @args module: bool - the file is loaded as an ES module
+     *
+     * @returns SourceContext::Module for modules, Strict when the script starts with "use strict"
+     */
+    pub fn detect(buffer: &[u8], module: bool) -> SourceContext {
+        if module {
+            return SourceContext::Module;
+        }
+
+        let mut state = TokenState::new(buffer, Dialect::JavaScript);
+        loop {
+            if state.read_trivia(false).is_err() {
+                return SourceContext::Sloppy;
+            }
+
+            // A directive is a string literal statement, anything else ends the prologue
+            let quote = match state.peek(0) {
+                Some(quote @ (b'\'' | b'"')) => quote,
+                _ => return SourceContext::Sloppy,
+            };
+            let content_start = state.index + 1;
+            let mut content_end = content_start;
+            loop {
+                match buffer.get(content_end) {
+                    Some(b'\\') => content_end += 2,
+                    Some(b'\n' | b'\r') | None => return SourceContext::Sloppy,
+                    Some(byte) if *byte == quote => break,
+                    Some(_) => content_end += 1,
+                }
+            }
+
+            // The directive has to be spelled exactly, "use\x20strict" doesn't count
+            if &buffer[content_start..content_end] == b"use strict" {
+                return SourceContext::Strict;
+            }
+
+            state.index = content_end + 1;
+            state.newline_before = false;
+            if state.read_trivia(false).is_err() {
+                return SourceContext::Sloppy;
+            }
+            match state.peek(0) {
+                Some(b';') => state.index += 1,
+                None => return SourceContext::Sloppy,
+                Some(_) if state.newline_before => {}
+                Some(_) => return SourceContext::Sloppy,
+            }
         }
     }
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
-#[allow(non_camel_case_types, unused)]
-pub(crate) enum Identifier {
-    BREAK,
-    CASE,
-    CATCH,
-    CLASS,
-    CONST,
-    CONTINUE,
-    DEBUGGER,
-    DEFAULT,
-    DELETE,
-    DO,
-    ELSE,
-    EXPORT,
-    EXTENDS,
-    FALSE,
-    FINALLY,
-    FOR,
-    FUNCTION,
-    IF,
-    IMPORT,
-    IN,
-    INSTANCEOF,
-    NEW,
-    NULL,
-    RETURN,
-    SUPER,
-    SWITCH,
-    THIS,
-    THROW,
-    TRUE,
-    TRY,
-    TYPEOF,
-    VAR,
-    VOID,
-    WHILE,
-    WITH,
-    STRICT_LET,
-    STRICT_STATIC,
-    STRICT_YIELD,
-    STRICT_IMPLEMENTS,
-    STRICT_INTERFACE,
-    STRICT_PACKAGE,
-    STRICT_PRIVATE,
-    STRICT_PROTECTED,
-    STRICT_PUBLIC,
-    MODULE_ASYNC,
-    FUTURE_ENUM,
-    FUTURE_OLD_ABSTRACT,
-    FUTURE_OLD_BOOLEAN,
-    FUTURE_OLD_BYTE,
-    FUTURE_OLD_CHAR,
-    FUTURE_OLD_DOUBLE,
-    FUTURE_OLD_FINAL,
-    FUTURE_OLD_FLOAT,
-    FUTURE_OLD_GOTO,
-    FUTURE_OLD_INT,
-    FUTURE_OLD_LONG,
-    FUTURE_OLD_NATIVE,
-    FUTURE_OLD_SHORT,
-    FUTURE_OLD_SYNCHRONIZED,
-    FUTURE_OLD_THROWS,
-    FUTURE_OLD_TRANSIENT,
-    FUTURE_OLD_VOLATILE,
-    SPECIAL_STRICT_ARGUMENTS,
-    SPECIAL_AS,
-    SPECIAL_STRICT_EVAL,
-    SPECIAL_FROM,
-    SPECIAL_GET,
-    SPECIAL_OF,
-    SPECIAL_SET,
+pub(super) fn is_id_start(character: char) -> bool {
+    character == '$' || character == '_' || unicode_ident::is_xid_start(character)
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
-#[allow(non_camel_case_types, unused)]
-pub(crate) enum Literal {
-    BACKSLASH,
-    CARRIAGE_RETURN,
-    LINE_FEED,
-    SINGLE_QUOTE,
-    DOUBLE_QUOTE,
-    BACK_TICK,
-    PLUS,
-    MINUS,
-    EQUAL,
-    ASTERISK,
-    SLASH,
-    GREATER_THAN,
-    LESS_THAN,
-    AMPERSAND,
-    SPACE,
-    TAB,
-    COMMA,
-    PERIOD,
-    SEMICOLON,
-    COLON,
-    EXCLAMATION_MARK,
-    QUESTION_MARK,
-    PIPE,
-    CARET,
-    OPEN_BRACKET_LEFT,
-    OPEN_BRACKET_RIGHT,
-    SQUARE_BRACKET_LEFT,
-    SQUARE_BRACKET_RIGHT,
-    CURLY_BRACKET_LEFT,
-    CURLY_BRACKET_RIGHT,
-    TILDE,
-    DOLLAR,
-    AT,
-    UNDERSCORE,
-    PERCENT,
+fn is_ascii_id_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'$' || byte == b'_'
 }
 
-impl SharedToken for Identifier {
-    fn get_value(&self) -> &[u8] {
-        match self {
-            Identifier::BREAK => b"break",
-            Identifier::CASE => b"case",
-            Identifier::CATCH => b"catch",
-            Identifier::CLASS => b"class",
-            Identifier::CONST => b"const",
-            Identifier::CONTINUE => b"continue",
-            Identifier::DEBUGGER => b"debugger",
-            Identifier::DEFAULT => b"default",
-            Identifier::DELETE => b"delete",
-            Identifier::DO => b"do",
-            Identifier::ELSE => b"else",
-            Identifier::EXPORT => b"export",
-            Identifier::EXTENDS => b"extends",
-            Identifier::FALSE => b"false",
-            Identifier::FINALLY => b"finally",
-            Identifier::FOR => b"for",
-            Identifier::FUNCTION => b"function",
-            Identifier::IF => b"if",
-            Identifier::IMPORT => b"import",
-            Identifier::IN => b"in",
-            Identifier::INSTANCEOF => b"instanceof",
-            Identifier::NEW => b"new",
-            Identifier::NULL => b"null",
-            Identifier::RETURN => b"return",
-            Identifier::SUPER => b"super",
-            Identifier::SWITCH => b"switch",
-            Identifier::THIS => b"this",
-            Identifier::THROW => b"throw",
-            Identifier::TRUE => b"true",
-            Identifier::TRY => b"try",
-            Identifier::TYPEOF => b"typeof",
-            Identifier::VAR => b"var",
-            Identifier::VOID => b"void",
-            Identifier::WHILE => b"while",
-            Identifier::WITH => b"with",
-            Identifier::STRICT_LET => b"let",
-            Identifier::STRICT_STATIC => b"static",
-            Identifier::STRICT_YIELD => b"yield",
-            Identifier::STRICT_IMPLEMENTS => b"implements",
-            Identifier::STRICT_INTERFACE => b"interface",
-            Identifier::STRICT_PACKAGE => b"package",
-            Identifier::STRICT_PRIVATE => b"private",
-            Identifier::STRICT_PROTECTED => b"protected",
-            Identifier::STRICT_PUBLIC => b"public",
-            Identifier::MODULE_ASYNC => b"async",
-            Identifier::FUTURE_ENUM => b"enum",
-            Identifier::FUTURE_OLD_ABSTRACT => b"abstract",
-            Identifier::FUTURE_OLD_BOOLEAN => b"boolean",
-            Identifier::FUTURE_OLD_BYTE => b"byte",
-            Identifier::FUTURE_OLD_CHAR => b"char",
-            Identifier::FUTURE_OLD_DOUBLE => b"double",
-            Identifier::FUTURE_OLD_FINAL => b"final",
-            Identifier::FUTURE_OLD_FLOAT => b"float",
-            Identifier::FUTURE_OLD_GOTO => b"goto",
-            Identifier::FUTURE_OLD_INT => b"int",
-            Identifier::FUTURE_OLD_LONG => b"long",
-            Identifier::FUTURE_OLD_NATIVE => b"native",
-            Identifier::FUTURE_OLD_SHORT => b"short",
-            Identifier::FUTURE_OLD_SYNCHRONIZED => b"synchronized",
-            Identifier::FUTURE_OLD_THROWS => b"throws",
-            Identifier::FUTURE_OLD_TRANSIENT => b"transient",
-            Identifier::FUTURE_OLD_VOLATILE => b"volatile",
-            Identifier::SPECIAL_STRICT_ARGUMENTS => b"arguments",
-            Identifier::SPECIAL_AS => b"as",
-            Identifier::SPECIAL_STRICT_EVAL => b"eval",
-            Identifier::SPECIAL_FROM => b"from",
-            Identifier::SPECIAL_GET => b"get",
-            Identifier::SPECIAL_OF => b"of",
-            Identifier::SPECIAL_SET => b"set",
-        }
+fn is_ascii_id_continue(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'$' || byte == b'_'
+}
+
+pub(super) fn is_id_continue(character: char) -> bool {
+    // ZWNJ and ZWJ are allowed inside identifiers
+    matches!(character, '$' | '\u{200C}' | '\u{200D}') || unicode_ident::is_xid_continue(character)
+}
+
+pub(super) fn is_line_terminator(character: char) -> bool {
+    matches!(character, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+// WhiteSpace production of ECMAScript (line terminators excluded)
+pub(super) fn is_whitespace(character: char) -> bool {
+    matches!(
+        character,
+        '\t' | '\u{0B}' | '\u{0C}' | ' ' | '\u{A0}' | '\u{FEFF}' | '\u{1680}' | '\u{2000}'
+            ..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}'
+    )
+}
+
+pub trait SharedToken {
+    fn get_value(&self) -> &[u8];
+
+    fn byte_at_index(&self, index: u8) -> Option<u8> {
+        self.get_value().get(index as usize).copied()
     }
 }
 
-impl SharedToken for Literal {
-    fn get_value(&self) -> ValueType {
-        match self {
-            Literal::BACKSLASH => ValueType::U8(0x5C),
-            Literal::CARRIAGE_RETURN => ValueType::U8(0x0D),
-            Literal::LINE_FEED => ValueType::U8(0x0A),
-            Literal::SINGLE_QUOTE => ValueType::U8(0x27),
-            Literal::DOUBLE_QUOTE => ValueType::U8(0x22),
-            Literal::BACK_TICK => ValueType::U8(0x60),
-            Literal::PLUS => ValueType::U8(0x2B),
-            Literal::MINUS => ValueType::U8(0x2D),
-            Literal::EQUAL => ValueType::U8(0x3D),
-            Literal::ASTERISK => ValueType::U8(0x2A),
-            Literal::SLASH => ValueType::U8(0x2F),
-            Literal::GREATER_THAN => ValueType::U8(0x3E),
-            Literal::LESS_THAN => ValueType::U8(0x3C),
-            Literal::AMPERSAND => ValueType::U8(0x26),
-            Literal::SPACE => ValueType::U8(0x20),
-            Literal::TAB => ValueType::U8(0x09),
-            Literal::COMMA => ValueType::U8(0x2C),
-            Literal::PERIOD => ValueType::U8(0x2E),
-            Literal::SEMICOLON => ValueType::U8(0x3B),
-            Literal::COLON => ValueType::U8(0x3A),
-            Literal::EXCLAMATION_MARK => ValueType::U8(0x21),
-            Literal::QUESTION_MARK => ValueType::U8(0x3F),
-            Literal::PIPE => ValueType::U8(0x7C),
-            Literal::CARET => ValueType::U8(0x5E),
-            Literal::OPEN_BRACKET_LEFT => ValueType::U8(0x28),
-            Literal::OPEN_BRACKET_RIGHT => ValueType::U8(0x29),
-            Literal::SQUARE_BRACKET_LEFT => ValueType::U8(0x5B),
-            Literal::SQUARE_BRACKET_RIGHT => ValueType::U8(0x5D),
-            Literal::CURLY_BRACKET_LEFT => ValueType::U8(0x7B),
-            Literal::CURLY_BRACKET_RIGHT => ValueType::U8(0x7D),
-            Literal::TILDE => ValueType::U8(0x7E),
-            Literal::DOLLAR => ValueType::U8(0x24),
-            Literal::AT => ValueType::U8(0x40),
-            Literal::UNDERSCORE => ValueType::U8(0x5F),
-            Literal::PERCENT => ValueType::U8(0x25),
+impl Identifier {
+    /**
+     * Whether the word can't be used as an identifier in the given context
+     *
+     * Strict words are reserved in strict and module code, MODULE_ASYNC (`await`) only in modules.
+     * The FUTURE_OLD_* words were only reserved up to ECMAScript 3 and contextual words are plain
+     * identifiers with a special meaning in some positions.
+     */
+    pub fn is_reserved(&self, context: SourceContext) -> bool {
+        match self.category() {
+            KeywordCategory::Reserved | KeywordCategory::Future => true,
+            KeywordCategory::Strict => context != SourceContext::Sloppy,
+            KeywordCategory::Module => context == SourceContext::Module,
+            KeywordCategory::FutureOld | KeywordCategory::Contextual => false,
         }
     }
 }
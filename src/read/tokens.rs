@@ -1,117 +1,335 @@
-// Available reserved
-const BREAK: &str = "break";
-const CASE: &str = "case";
-const CATCH: &str = "catch";
-const CLASS: &str = "class";
-const CONST: &str = "const";
-const CONTINUE: &str = "continue";
-const DEBUGGER: &str = "debugger";
-const DEFAULT: &str = "default";
-const DELETE: &str = "delete";
-const DO: &str = "do";
-const ELSE: &str = "else";
-const EXPORT: &str = "export";
-const EXTENDS: &str = "extends";
-const FALSE: &str = "false";
-const FINALLY: &str = "finally";
-const FOR: &str = "for";
-const FUNCTION: &str = "function";
-const IF: &str = "if";
-const IMPORT: &str = "import";
-const IN: &str = "in";
-const INSTANCEOF: &str = "instanceof";
-const NEW: &str = "new";
-const NULL: &str = "null";
-const RETURN: &str = "return";
-const SUPER: &str = "super";
-const SWITCH: &str = "switch";
-const THIS: &str = "this";
-const THROW: &str = "throw";
-const TRUE: &str = "true";
-const TRY: &str = "try";
-const TYPEOF: &str = "typeof";
-const VAR: &str = "var";
-const VOID: &str = "void";
-const WHILE: &str = "whie";
-const WITH: &str = "with";
-
-// Only strict mode
-const STRICT_LET: &str = "let";
-const STRICT_STATIC: &str = "static";
-const STRICT_YIELD: &str = "yield";
-const STRICT_IMPLEMENTS: &str = "implements";
-const STRICT_INTERFACE: &str = "interface";
-const STRICT_PACKAGE: &str = "package";
-const STRICT_PRIVATE: &str = "private";
-const STRICT_PROTECTED: &str = "protected";
-const STRICT_PUBLIC: &str = "public";
-
-// Only in modules or async function bodies
-const MODULE_ASYNC: &str = "await";
-
-// Future reseved
-const FUTURE_ENUM: &str = "enum";
-
-// Future reserved by ECMAScript 1 to 3
-const FUTURE_OLD_ABSTRACT: &str = "abstract";
-const FUTURE_OLD_BOOLEAN: &str = "boolean";
-const FUTURE_OLD_BYTE: &str = "byte";
-const FUTURE_OLD_CHAR: &str = "char";
-const FUTURE_OLD_DOUBLE: &str = "double";
-const FUTURE_OLD_FINAL: &str = "final";
-const FUTURE_OLD_FLOAT: &str = "float";
-const FUTURE_OLD_GOTO: &str = "goto";
-const FUTURE_OLD_INT: &str = "int";
-const FUTURE_OLD_LONG: &str = "long";
-const FUTURE_OLD_NATIVE: &str = "native";
-const FUTURE_OLD_SHORT: &str = "short";
-const FUTURE_OLD_SYNCHRONIZED: &str = "synchronized";
-const FUTURE_OLD_THROWS: &str = "throws";
-const FUTURE_OLD_TRANSIENT: &str = "transient";
-const FUTURE_OLD_VOLATILE: &str = "volatile";
-
-// Identifiers with special meanings
-const SPECIAL_STRICT_ARGUMENTS: &str = "arguments"; // only works in strict
-const SPECIAL_AS: &str = "as";
-const SPECIAL_STRICT_EVAL: &str = "eval"; // only works in strict
-const SPECIAL_FROM: &str = "from";
-const SPECIAL_GET: &str = "get";
-const SPECIAL_OF: &str = "of";
-const SPECIAL_SET: &str = "set";
-
-// String literals (UTF-8 Hex)
-const LITERAL_BACKSLASH: u8 = 0x5C; // \
-const LITERAL_CARRIAGE_RETURN: u8 = 0x0D; // CR
-const LITERAL_LINE_FEED: u8 = 0x0A; // LF
-const LITERAL_SINGLE_QUOTE: u8 = 0x27; // '
-const LITERAL_DOUBLE_QUOTE: u8 = 0x22; // "
-const LITERAL_BACK_TICK: u8 = 0x60; // `
-const LITERAL_PLUS: u8 = 0x2B; // +
-const LITERAL_MINUS: u8 = 0x2D; // -
-const LITERAL_EQUAL: u8 = 0x3D; // =
-const LITERAL_ASTERISK: u8 = 0x2A; // *
-const LITERAL_SLASH: u8 = 0x2F; // /
-const LITERAL_GREATER_THAN: u8 = 0x3E; // >
-const LITERAL_LESS_THAN: u8 = 0x3C; // <
-const LITERAL_AMPERSAND: u8 = 0x26; // &
-const LITERAL_SPACE: u8 = 0x20; //
-const LITERAL_TAB: u8 = 0x09; //
-const LITERAL_COMMA: u8 = 0x2C; // ,
-const LITERAL_PERIOD: u8 = 0x2E; // .
-const LITERAL_SEMICOLON: u8 = 0x3B; // ;
-const LITERAL_COLON: u8 = 0x3A; // :
-const LITERAL_EXCLAMATION_MARK: u8 = 0x21; // !
-const LITERAL_QUESTION_MARK: u8 = 0x3F; // ?
-const LITERAL_PIPE: u8 = 0x7C; // |
-const LITERAL_CARET: u8 = 0x5E; // ^
-const LITERAL_OPEN_BRACKET_LEFT: u8 = 0x28; // (
-const LITERAL_OPEN_BRACKET_RIGHT: u8 = 0x29; // )
-const LITERAL_SQUARE_BRACKET_LEFT: u8 = 0x5B; // [
-const LITERAL_SQUARE_BRACKET_RIGHT: u8 = 0x5D; // ]
-const LITERAL_CURLY_BRACKET_LEFT: u8 = 0x7B; // {
-const LITERAL_CURLY_BRACKET_RIGHT: u8 = 0x7D; // }
-const LITERAL_TILDE: u8 = 0x7E; // ~
-const LITERAL_DOLLAR: u8 = 0x24; // $
-const LITERAL_AT: u8 = 0x40; // @
-const LITERAL_UNDERSCORE: u8 = 0x5F; // _
-const LITERAL_PERCENT: u8 = 0x25; // %
+use std::fmt;
+
+use super::tokenizer::SharedToken;
+
+/**
+ * Generates Identifier from the table below, with one `&str` constant per word
+ *
+ * Every row is `VARIANT => "spelling"`, the block it sits in is its KeywordCategory.
+ */
+macro_rules! identifiers {
+    ($($category:ident { $($variant:ident => $spelling:literal,)* })*) => {
+        $($(pub const $variant: &str = $spelling;)*)*
+
+        #[derive(PartialEq, Eq, Copy, Clone, Debug)]
+        #[allow(non_camel_case_types, unused)]
+        pub enum Identifier {
+            $($($variant,)*)*
+        }
+
+        impl Identifier {
+            // Every word of the table, in table order
+            pub const ALL: &'static [Identifier] = &[$($(Identifier::$variant,)*)*];
+
+            // Const so the keyword table can be built at compile time
+            pub const fn as_bytes(&self) -> &'static [u8] {
+                match self {
+                    $($(Identifier::$variant => $spelling.as_bytes(),)*)*
+                }
+            }
+
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $($(Identifier::$variant => $spelling,)*)*
+                }
+            }
+
+            pub const fn category(&self) -> KeywordCategory {
+                match self {
+                    $($(Identifier::$variant => KeywordCategory::$category,)*)*
+                }
+            }
+        }
+    };
+}
+
+/**
+ * Generates Literal from the table below, with one `u8` constant per byte
+ *
+ * Every row is `VARIANT, CONSTANT => byte`.
+ */
+macro_rules! literals {
+    ($($variant:ident, $constant:ident => $byte:literal,)*) => {
+        $(pub const $constant: u8 = $byte;)*
+
+        #[derive(PartialEq, Eq, Copy, Clone, Debug)]
+        #[allow(non_camel_case_types, unused)]
+        pub enum Literal {
+            $($variant,)*
+        }
+
+        impl Literal {
+            pub const ALL: &'static [Literal] = &[$(Literal::$variant,)*];
+
+            pub const fn as_byte(&self) -> u8 {
+                match self {
+                    $(Literal::$variant => $byte,)*
+                }
+            }
+
+            pub const fn as_bytes(&self) -> &'static [u8] {
+                match self {
+                    $(Literal::$variant => &[$byte],)*
+                }
+            }
+        }
+    };
+}
+
+/**
+ * Generates TsKeyword from the table below
+ *
+ * Every row is `VARIANT => "spelling"`, like the identifier table without the categories.
+ * The doc comment in front of the rows goes on the enum.
+ */
+macro_rules! ts_keywords {
+    ($(#[$attribute:meta])* $($variant:ident => $spelling:literal,)*) => {
+        $(#[$attribute])*
+        #[derive(PartialEq, Eq, Copy, Clone, Debug)]
+        #[allow(non_camel_case_types, unused)]
+        pub enum TsKeyword {
+            $($variant,)*
+        }
+
+        impl TsKeyword {
+            pub const ALL: &'static [TsKeyword] = &[$(TsKeyword::$variant,)*];
+
+            pub const fn as_bytes(&self) -> &'static [u8] {
+                match self {
+                    $(TsKeyword::$variant => $spelling.as_bytes(),)*
+                }
+            }
+
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(TsKeyword::$variant => $spelling,)*
+                }
+            }
+        }
+    };
+}
+
+// Where a word is reserved, the block of the table it is listed in
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum KeywordCategory {
+    Reserved,   // reserved everywhere
+    Strict,     // reserved in strict mode code and modules
+    Module,     // reserved in modules and async function bodies
+    Future,     // future reserved word, reserved everywhere
+    FutureOld,  // future reserved by ECMAScript 1 to 3, valid names today
+    Contextual, // valid names with a special meaning in some positions
+}
+
+identifiers! {
+    Reserved {
+        BREAK => "break",
+        CASE => "case",
+        CATCH => "catch",
+        CLASS => "class",
+        CONST => "const",
+        CONTINUE => "continue",
+        DEBUGGER => "debugger",
+        DEFAULT => "default",
+        DELETE => "delete",
+        DO => "do",
+        ELSE => "else",
+        EXPORT => "export",
+        EXTENDS => "extends",
+        FALSE => "false",
+        FINALLY => "finally",
+        FOR => "for",
+        FUNCTION => "function",
+        IF => "if",
+        IMPORT => "import",
+        IN => "in",
+        INSTANCEOF => "instanceof",
+        NEW => "new",
+        NULL => "null",
+        RETURN => "return",
+        SUPER => "super",
+        SWITCH => "switch",
+        THIS => "this",
+        THROW => "throw",
+        TRUE => "true",
+        TRY => "try",
+        TYPEOF => "typeof",
+        VAR => "var",
+        VOID => "void",
+        WHILE => "while",
+        WITH => "with",
+    }
+    Strict {
+        STRICT_LET => "let",
+        STRICT_STATIC => "static",
+        STRICT_YIELD => "yield",
+        STRICT_IMPLEMENTS => "implements",
+        STRICT_INTERFACE => "interface",
+        STRICT_PACKAGE => "package",
+        STRICT_PRIVATE => "private",
+        STRICT_PROTECTED => "protected",
+        STRICT_PUBLIC => "public",
+    }
+    Module {
+        MODULE_ASYNC => "await",
+    }
+    Future {
+        FUTURE_ENUM => "enum",
+    }
+    FutureOld {
+        FUTURE_OLD_ABSTRACT => "abstract",
+        FUTURE_OLD_BOOLEAN => "boolean",
+        FUTURE_OLD_BYTE => "byte",
+        FUTURE_OLD_CHAR => "char",
+        FUTURE_OLD_DOUBLE => "double",
+        FUTURE_OLD_FINAL => "final",
+        FUTURE_OLD_FLOAT => "float",
+        FUTURE_OLD_GOTO => "goto",
+        FUTURE_OLD_INT => "int",
+        FUTURE_OLD_LONG => "long",
+        FUTURE_OLD_NATIVE => "native",
+        FUTURE_OLD_SHORT => "short",
+        FUTURE_OLD_SYNCHRONIZED => "synchronized",
+        FUTURE_OLD_THROWS => "throws",
+        FUTURE_OLD_TRANSIENT => "transient",
+        FUTURE_OLD_VOLATILE => "volatile",
+    }
+    Contextual {
+        SPECIAL_STRICT_ARGUMENTS => "arguments", // can't be assigned in strict mode
+        SPECIAL_AS => "as",
+        SPECIAL_STRICT_EVAL => "eval", // can't be assigned in strict mode
+        SPECIAL_FROM => "from",
+        SPECIAL_GET => "get",
+        SPECIAL_OF => "of",
+        SPECIAL_SET => "set",
+    }
+}
+
+ts_keywords! {
+    /**
+     * TypeScript keywords that are plain identifiers in JavaScript
+     *
+     * All of them stay valid names (`let type = 1`), the parser decides from the position.
+     * `interface`, `enum`, `implements` and the access modifiers already are `Identifier`s.
+     */
+    ABSTRACT => "abstract",
+    ACCESSOR => "accessor",
+    ASSERTS => "asserts",
+    DECLARE => "declare",
+    GLOBAL => "global",
+    INFER => "infer",
+    IS => "is",
+    KEYOF => "keyof",
+    MODULE => "module",
+    NAMESPACE => "namespace",
+    OUT => "out",
+    OVERRIDE => "override",
+    READONLY => "readonly",
+    SATISFIES => "satisfies",
+    TYPE => "type",
+    UNIQUE => "unique",
+}
+
+// Single byte literals (UTF-8 Hex)
+literals! {
+    BACKSLASH, LITERAL_BACKSLASH => 0x5C, // \
+    CARRIAGE_RETURN, LITERAL_CARRIAGE_RETURN => 0x0D, // CR
+    LINE_FEED, LITERAL_LINE_FEED => 0x0A, // LF
+    SINGLE_QUOTE, LITERAL_SINGLE_QUOTE => 0x27, // '
+    DOUBLE_QUOTE, LITERAL_DOUBLE_QUOTE => 0x22, // "
+    BACK_TICK, LITERAL_BACK_TICK => 0x60, // `
+    PLUS, LITERAL_PLUS => 0x2B, // +
+    MINUS, LITERAL_MINUS => 0x2D, // -
+    EQUAL, LITERAL_EQUAL => 0x3D, // =
+    ASTERISK, LITERAL_ASTERISK => 0x2A, // *
+    SLASH, LITERAL_SLASH => 0x2F, // /
+    GREATER_THAN, LITERAL_GREATER_THAN => 0x3E, // >
+    LESS_THAN, LITERAL_LESS_THAN => 0x3C, // <
+    AMPERSAND, LITERAL_AMPERSAND => 0x26, // &
+    SPACE, LITERAL_SPACE => 0x20, //
+    TAB, LITERAL_TAB => 0x09, //
+    COMMA, LITERAL_COMMA => 0x2C, // ,
+    PERIOD, LITERAL_PERIOD => 0x2E, // .
+    SEMICOLON, LITERAL_SEMICOLON => 0x3B, // ;
+    COLON, LITERAL_COLON => 0x3A, // :
+    EXCLAMATION_MARK, LITERAL_EXCLAMATION_MARK => 0x21, // !
+    QUESTION_MARK, LITERAL_QUESTION_MARK => 0x3F, // ?
+    PIPE, LITERAL_PIPE => 0x7C, // |
+    CARET, LITERAL_CARET => 0x5E, // ^
+    OPEN_BRACKET_LEFT, LITERAL_OPEN_BRACKET_LEFT => 0x28, // (
+    OPEN_BRACKET_RIGHT, LITERAL_OPEN_BRACKET_RIGHT => 0x29, // )
+    SQUARE_BRACKET_LEFT, LITERAL_SQUARE_BRACKET_LEFT => 0x5B, // [
+    SQUARE_BRACKET_RIGHT, LITERAL_SQUARE_BRACKET_RIGHT => 0x5D, // ]
+    CURLY_BRACKET_LEFT, LITERAL_CURLY_BRACKET_LEFT => 0x7B, // {
+    CURLY_BRACKET_RIGHT, LITERAL_CURLY_BRACKET_RIGHT => 0x7D, // }
+    TILDE, LITERAL_TILDE => 0x7E, // ~
+    DOLLAR, LITERAL_DOLLAR => 0x24, // $
+    AT, LITERAL_AT => 0x40, // @
+    UNDERSCORE, LITERAL_UNDERSCORE => 0x5F, // _
+    PERCENT, LITERAL_PERCENT => 0x25, // %
+}
+
+// Literal of every byte, built at compile time from the table
+const LITERAL_TABLE: [Option<Literal>; 256] = {
+    let mut table = [None; 256];
+    let mut index = 0;
+    while index < Literal::ALL.len() {
+        table[Literal::ALL[index].as_byte() as usize] = Some(Literal::ALL[index]);
+        index += 1;
+    }
+    table
+};
+
+impl Identifier {
+    pub fn is_strict_reserved(&self) -> bool {
+        self.category() == KeywordCategory::Strict
+    }
+
+    // `enum`, and the words ECMAScript 1 to 3 kept for later
+    pub fn is_future_reserved(&self) -> bool {
+        matches!(
+            self.category(),
+            KeywordCategory::Future | KeywordCategory::FutureOld
+        )
+    }
+
+    // `as`, `from`, `get`, `of`, `set`, `arguments` and `eval`
+    pub fn is_contextual(&self) -> bool {
+        self.category() == KeywordCategory::Contextual
+    }
+}
+
+impl Literal {
+    pub fn from_byte(byte: u8) -> Option<Literal> {
+        LITERAL_TABLE[byte as usize]
+    }
+}
+
+impl SharedToken for Identifier {
+    fn get_value(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl SharedToken for TsKeyword {
+    fn get_value(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl SharedToken for Literal {
+    fn get_value(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_byte() as char)
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    slice,
+};
+
+use crate::error::errors::Errors;
+
+use super::tokenizer::{is_line_terminator, is_whitespace, Span, TokenState};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,     // a run of spaces, tabs and other WhiteSpace characters
+    LineTerminator, // one LF, CR, CRLF, U+2028 or U+2029
+    LineComment,    // `// ...` up to the line terminator
+    BlockComment,   // `/* ... */`
+    DocComment,     // `/** ... */`
+    Hashbang,       // `#!...` on the first line
+    HtmlComment,    // `<!--` anywhere or `-->` at the start of a line (Annex B)
+}
+
+impl TriviaKind {
+    pub fn is_comment(&self) -> bool {
+        !matches!(self, TriviaKind::Whitespace | TriviaKind::LineTerminator)
+    }
+}
+
+/**
+ * Source text between two tokens
+ *
+ * Trivia on the same line after a token is its trailing trivia, everything up to the
+ * next token is that token's leading trivia. Concatenating leading trivia, token text
+ * and trailing trivia of every token reproduces the source.
+ */
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+/**
+ * The trivia on one side of a token
+ *
+ * Most tokens have none or a single run of whitespace, those are stored inline so lexing
+ * doesn't allocate for them. Reads like a slice.
+ */
+#[derive(Clone, Default)]
+pub enum TriviaList {
+    #[default]
+    Empty,
+    One(Trivia),
+    Many(Vec<Trivia>),
+}
+
+impl TriviaList {
+    pub fn new() -> Self {
+        Self::Empty
+    }
+
+    pub fn push(&mut self, trivia: Trivia) {
+        match self {
+            TriviaList::Empty => *self = TriviaList::One(trivia),
+            TriviaList::One(first) => *self = TriviaList::Many(vec![*first, trivia]),
+            TriviaList::Many(list) => list.push(trivia),
+        }
+    }
+}
+
+impl Deref for TriviaList {
+    type Target = [Trivia];
+
+    fn deref(&self) -> &[Trivia] {
+        match self {
+            TriviaList::Empty => &[],
+            TriviaList::One(trivia) => slice::from_ref(trivia),
+            TriviaList::Many(list) => list,
+        }
+    }
+}
+
+impl DerefMut for TriviaList {
+    fn deref_mut(&mut self) -> &mut [Trivia] {
+        match self {
+            TriviaList::Empty => &mut [],
+            TriviaList::One(trivia) => slice::from_mut(trivia),
+            TriviaList::Many(list) => list,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a TriviaList {
+    type Item = &'a Trivia;
+    type IntoIter = slice::Iter<'a, Trivia>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Trivia> for TriviaList {
+    fn from_iter<I: IntoIterator<Item = Trivia>>(iterator: I) -> Self {
+        let mut list = TriviaList::new();
+        for trivia in iterator {
+            list.push(trivia);
+        }
+        list
+    }
+}
+
+// Equal when they hold the same trivia, however it's stored
+impl PartialEq for TriviaList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for TriviaList {}
+
+impl fmt::Debug for TriviaList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> TokenState<'a> {
+    /**
+     * Collects the whitespace, line terminators and comments in front of the next token
+     *
+     * @args trailing: bool - stop before the first line terminator
+     */
+    pub(super) fn read_trivia(&mut self, trailing: bool) -> Result<TriviaList, Errors> {
+        let mut trivia = TriviaList::new();
+
+        while let Some(byte) = self.peek(0) {
+            let start = self.index;
+            let line = self.line;
+            let column = self.column_of(start);
+
+            let kind = match byte {
+                b'\n' | b'\r' if trailing => break,
+                b'\n' => {
+                    self.index += 1;
+                    self.new_line();
+                    TriviaKind::LineTerminator
+                }
+                b'\r' => {
+                    self.index += if self.peek(1) == Some(b'\n') { 2 } else { 1 };
+                    self.new_line();
+                    TriviaKind::LineTerminator
+                }
+                b'/' if self.peek(1) == Some(b'/') => {
+                    self.skip_line_comment(2);
+                    TriviaKind::LineComment
+                }
+                // When recovering a comment that never ends is left for an Error token
+                b'/' if self.peek(1) == Some(b'*')
+                    && self.recovering
+                    && !self.buffer[start + 2..]
+                        .windows(2)
+                        .any(|pair| pair == b"*/") =>
+                {
+                    break
+                }
+                b'/' if self.peek(1) == Some(b'*') => {
+                    self.skip_block_comment()?;
+                    let text = &self.buffer[start..self.index];
+                    // `/**/` is an empty block comment, not a doc comment
+                    if text.starts_with(b"/**") && text.len() > 4 {
+                        TriviaKind::DocComment
+                    } else {
+                        TriviaKind::BlockComment
+                    }
+                }
+                b'#' if self.base + start == 0 && self.peek(1) == Some(b'!') => {
+                    self.skip_line_comment(2);
+                    TriviaKind::Hashbang
+                }
+                b'<' if self.buffer[start..].starts_with(b"<!--") => {
+                    self.skip_line_comment(4);
+                    TriviaKind::HtmlComment
+                }
+                b'-' if (self.newline_before || !self.started)
+                    && self.buffer[start..].starts_with(b"-->") =>
+                {
+                    self.skip_line_comment(3);
+                    TriviaKind::HtmlComment
+                }
+                _ => match self.peek_char() {
+                    Some((character, _)) if is_line_terminator(character) && trailing => break,
+                    Some((character, length)) if is_line_terminator(character) => {
+                        self.index += length;
+                        self.new_line();
+                        TriviaKind::LineTerminator
+                    }
+                    Some((character, _)) if is_whitespace(character) => {
+                        while let Some((character, length)) = self.peek_char() {
+                            if !is_whitespace(character) {
+                                break;
+                            }
+                            self.index += length;
+                        }
+                        TriviaKind::Whitespace
+                    }
+                    _ => break,
+                },
+            };
+
+            trivia.push(Trivia {
+                kind,
+                span: Span {
+                    start: self.base + start,
+                    end: self.base + self.index,
+                    line,
+                    column,
+                },
+            });
+        }
+
+        Ok(trivia)
+    }
+
+    // Skips the comment opener and everything up to the line terminator
+    fn skip_line_comment(&mut self, opener_length: usize) {
+        self.index += opener_length;
+        while let Some((character, length)) = self.peek_char() {
+            if is_line_terminator(character) {
+                break;
+            }
+            self.index += length;
+        }
+    }
+
+    pub(super) fn skip_block_comment(&mut self) -> Result<(), Errors> {
+        let start = self.index;
+        self.mark();
+        self.index += 2;
+
+        loop {
+            match self.peek(0) {
+                None => return Err(self.error("Unterminated comment", start)),
+                Some(b'*') if self.peek(1) == Some(b'/') => {
+                    self.index += 2;
+                    return Ok(());
+                }
+                Some(b'\r') if self.peek(1) == Some(b'\n') => {
+                    self.index += 2;
+                    self.new_line();
+                }
+                Some(_) => {
+                    let (character, length) = self.peek_char().unwrap_or(('\0', 1));
+                    self.index += length;
+                    if is_line_terminator(character) {
+                        self.new_line();
+                    }
+                }
+            }
+        }
+    }
+}
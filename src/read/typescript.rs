@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use super::interner::Symbol;
+use super::punctuator::Punctuator;
+pub use super::tokens::TsKeyword;
+use super::tokenizer::{Literal, SharedToken, SourceContext, Span, Token, TokenKind, TokenState};
+use super::trivia::TriviaList;
+
+/**
+ * Language the source is lexed as, picked from the file extension
+ *
+ * TypeScript adds contextual keywords and lexes `!` after an expression as the
+ * non-null assertion, Jsx and Tsx lex elements where an expression can start.
+ * Decorators are lexed in every dialect.
+ */
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Dialect {
+    #[default]
+    JavaScript,
+    TypeScript,
+    Jsx,
+    Tsx,
+}
+
+impl Dialect {
+    /**
+     * @args path: &str - file name or path, only the extension is looked at
+     *
+     * @returns TypeScript for `.ts`, `.mts` and `.cts`, Jsx for `.jsx`, Tsx for `.tsx`
+     *          and JavaScript for everything else
+     */
+    pub fn from_path(path: &str) -> Dialect {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("ts" | "mts" | "cts") => Dialect::TypeScript,
+            Some("jsx") => Dialect::Jsx,
+            Some("tsx") => Dialect::Tsx,
+            _ => Dialect::JavaScript,
+        }
+    }
+
+    pub fn is_typescript(&self) -> bool {
+        matches!(self, Dialect::TypeScript | Dialect::Tsx)
+    }
+
+    pub fn is_jsx(&self) -> bool {
+        matches!(self, Dialect::Jsx | Dialect::Tsx)
+    }
+}
+
+impl Token {
+    /**
+     * Splits one `>` off the front of the token
+     *
+     * The lexer can't tell `Array<Array<T>>` from a shift, so `>>` comes out as one token.
+     * A parser closing a type argument list takes the first `>` and continues with the rest.
+     *
+     * @returns the `>` and what is left of the token (`>>=` leaves `>=`), None when the token
+     *          doesn't start with `>`
+     */
+    pub fn split_greater_than(&self) -> Option<(Token, Option<Token>)> {
+        let rest = match &self.kind {
+            TokenKind::Literal(Literal::GREATER_THAN) => None,
+            TokenKind::Punctuator(punctuator) => Some(match punctuator {
+                Punctuator::GREATER_EQUAL => TokenKind::Literal(Literal::EQUAL),
+                Punctuator::SHIFT_RIGHT => TokenKind::Literal(Literal::GREATER_THAN),
+                Punctuator::SHIFT_RIGHT_ASSIGN => TokenKind::Punctuator(Punctuator::GREATER_EQUAL),
+                Punctuator::UNSIGNED_SHIFT_RIGHT => TokenKind::Punctuator(Punctuator::SHIFT_RIGHT),
+                Punctuator::UNSIGNED_SHIFT_RIGHT_ASSIGN => {
+                    TokenKind::Punctuator(Punctuator::SHIFT_RIGHT_ASSIGN)
+                }
+                _ => return None,
+            }),
+            _ => return None,
+        };
+
+        let span = self.span;
+        let greater_than = Token {
+            kind: TokenKind::Literal(Literal::GREATER_THAN),
+            span: Span {
+                end: span.start + 1,
+                ..span
+            },
+            newline_before: self.newline_before,
+            leading_trivia: self.leading_trivia.clone(),
+            trailing_trivia: if rest.is_some() {
+                TriviaList::new()
+            } else {
+                self.trailing_trivia.clone()
+            },
+        };
+        let rest = rest.map(|kind| Token {
+            kind,
+            span: Span {
+                start: span.start + 1,
+                column: span.column + 1,
+                ..span
+            },
+            newline_before: false,
+            leading_trivia: TriviaList::new(),
+            trailing_trivia: self.trailing_trivia.clone(),
+        });
+
+        Some((greater_than, rest))
+    }
+}
+
+impl<'a> TokenState<'a> {
+    /**
+     * `@name` at the current index, the rest of `@a.b(c)` is lexed as usual
+     *
+     * @returns None when there's no `@` or no name follows it
+     */
+    pub(super) fn read_decorator(&mut self) -> Option<TokenKind> {
+        if self.peek(0) != Some(b'@') {
+            return None;
+        }
+        let start = self.index;
+        self.index += 1;
+
+        match self.peek(0) {
+            Some(byte) if self.is_identifier_start(byte) => {}
+            _ => {
+                self.index = start;
+                return None;
+            }
+        }
+
+        match self.read_identifier() {
+            Ok(TokenKind::Name { value, .. }) => Some(TokenKind::Decorator(value)),
+            Ok(TokenKind::TsKeyword(keyword)) => Some(TokenKind::Decorator(Symbol::intern(
+                &String::from_utf8_lossy(keyword.get_value()),
+            ))),
+            Ok(TokenKind::Identifier(identifier))
+                if !identifier.is_reserved(SourceContext::Sloppy) =>
+            {
+                Some(TokenKind::Decorator(Symbol::intern(
+                    &identifier.to_string(),
+                )))
+            }
+            _ => {
+                // `@class` or a broken escape, leave the `@` on its own
+                self.index = start;
+                None
+            }
+        }
+    }
+}
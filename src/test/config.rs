@@ -1,4 +1,5 @@
 // src/test/config.rs
+#![allow(unused_imports, dead_code)]
 
 use crate::config;  // Import the `config` module (from `lib.rs`)
 
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        error::errors::Errors,
+        read::{
+            encoding::{Encoding, SourceText},
+            lexer::Lexer,
+            read::{read_file, read_file_recovering, read_source},
+            tokenizer::{Span, Token, TokenKind, TokenTrait},
+            typescript::Dialect,
+        },
+        utils::test_utils,
+    };
+
+    fn utf16(text: &str, encoding: Encoding) -> Vec<u8> {
+        SourceText {
+            text: text.to_owned(),
+            encoding,
+            bom: true,
+        }
+        .encode()
+    }
+
+    fn encoding_error(result: Result<Vec<Token>, Errors>) -> Span {
+        match result {
+            Err(Errors::EncodingError(_, span)) => span,
+            other => panic!(
+                "Expected an encoding error, got {:?}",
+                other.map(|t| t.len())
+            ),
+        }
+    }
+
+    #[test]
+    fn test_bom_round_trip() {
+        let bytes = b"\xEF\xBB\xBFlet a = 1;\n";
+        let source = SourceText::decode(bytes).unwrap();
+
+        assert_eq!(source.text, "let a = 1;\n");
+        assert_eq!(source.encoding, Encoding::Utf8);
+        assert!(source.bom);
+        assert_eq!(source.encode(), bytes);
+
+        let plain = SourceText::decode(b"let a = 1;\n").unwrap();
+        assert!(!plain.bom);
+        assert_eq!(plain.encode(), b"let a = 1;\n");
+    }
+
+    #[test]
+    fn test_utf16_decoding() {
+        let text = "let caf\u{e9} = '\u{1F600}';\n";
+        for encoding in [Encoding::Utf16Le, Encoding::Utf16Be] {
+            let bytes = utf16(text, encoding);
+            assert_eq!(Encoding::detect(&bytes), (encoding, true));
+
+            let source = SourceText::decode(&bytes).unwrap();
+            assert_eq!(source.text, text);
+            assert_eq!(source.encoding, encoding);
+            assert_eq!(source.encode(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_read_utf16_file() {
+        let text = "const greeting = \"h\u{e9}llo\";\n";
+        let file_path = test_utils::create_temp_js_file("");
+        fs::write(&file_path, utf16(text, Encoding::Utf16Le)).unwrap();
+        let tokens = read_file(&file_path);
+        let source = read_source(&file_path);
+        test_utils::remove_config_from_path(&file_path);
+
+        assert_eq!(tokens.unwrap(), Token::tokenize(text.as_bytes()).unwrap());
+        assert_eq!(source.unwrap().encoding, Encoding::Utf16Le);
+    }
+
+    #[test]
+    fn test_read_utf16_file_recovering() {
+        let text = "let a = 1;\nlet b = a # 2;\n";
+        let file_path = test_utils::create_temp_js_file("");
+        fs::write(&file_path, utf16(text, Encoding::Utf16Le)).unwrap();
+        let recovered = read_file_recovering(&file_path);
+        test_utils::remove_config_from_path(&file_path);
+
+        // Only the stray `#` is reported, at its place in the decoded text
+        let (tokens, diagnostics) = recovered.unwrap();
+        let expected = Token::tokenize_recovering(text.as_bytes(), Dialect::JavaScript);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(format!("{:?}", diagnostics), format!("{:?}", expected.1));
+        assert_eq!(tokens, expected.0);
+    }
+
+    #[test]
+    fn test_invalid_utf16() {
+        // A lone high surrogate on the second line
+        let mut bytes = utf16("a\n", Encoding::Utf16Le);
+        bytes.extend_from_slice(&[0x00, 0xD8, b'b', 0x00]);
+
+        match SourceText::decode(&bytes) {
+            Err(Errors::EncodingError(message, span)) => {
+                assert_eq!(message, "Invalid UTF-16");
+                assert_eq!((span.start, span.end), (6, 8));
+                assert_eq!((span.line, span.column), (2, 0));
+            }
+            other => panic!("Expected an encoding error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let source = b"let a = 1;\nlet \xFFb = 2;\n";
+
+        let span = encoding_error(Token::tokenize(source));
+        assert_eq!((span.start, span.end), (15, 16));
+        assert_eq!((span.line, span.column), (2, 4));
+
+        // The same place when decoding, and when the stream hands out a byte at a time
+        match SourceText::decode(source) {
+            Err(Errors::EncodingError(_, decoded)) => assert_eq!(decoded, span),
+            other => panic!("Expected an encoding error, got {:?}", other),
+        }
+        let streamed = Lexer::from_reader(&source[..], Dialect::JavaScript)
+            .with_chunk_size(1)
+            .collect();
+        assert_eq!(encoding_error(streamed), span);
+
+        let error = Errors::EncodingError("Invalid UTF-8", span);
+        assert_eq!(error.to_string(), "Encoding Error: Invalid UTF-8 at 2:5");
+    }
+
+    #[test]
+    fn test_invalid_utf8_in_strings_and_comments() {
+        let span = encoding_error(Token::tokenize(b"x = 'caf\xE9';"));
+        assert_eq!((span.start, span.line, span.column), (8, 1, 8));
+
+        let span = encoding_error(Token::tokenize(b"x = 1; // caf\xE9\ny = 2;"));
+        assert_eq!((span.start, span.line, span.column), (13, 1, 13));
+
+        // A sequence cut off by the end of the file
+        let source = b"x = 1;\n// \xE2\x82";
+        let span = encoding_error(Token::tokenize(source));
+        assert_eq!(
+            (span.start, span.end, span.line, span.column),
+            (10, 12, 2, 3)
+        );
+        let streamed = Lexer::from_reader(&source[..], Dialect::JavaScript)
+            .with_chunk_size(2)
+            .collect();
+        assert_eq!(encoding_error(streamed), span);
+    }
+
+    #[test]
+    fn test_recovering_ignores_encoding() {
+        let (tokens, diagnostics) =
+            Token::tokenize_recovering(b"let a = 'caf\xE9';", Dialect::JavaScript);
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::EndOfFile);
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{
+        parse::{ast::SourceType, estree::to_estree, parser::parse},
+        read::typescript::Dialect,
+    };
+
+    fn estree(source: &str, dialect: Dialect) -> Value {
+        let program = parse(source.as_bytes(), dialect, SourceType::Module).unwrap();
+        to_estree(&program, source.as_bytes())
+    }
+
+    // The node at a JSON pointer, `/body/0/expression`
+    fn at<'a>(tree: &'a Value, pointer: &str) -> &'a Value {
+        tree.pointer(pointer)
+            .unwrap_or_else(|| panic!("Nothing at {}", pointer))
+    }
+
+    #[test]
+    fn test_javascript_is_plain_estree() {
+        let tree = estree(
+            "let a = 1, b = 1.5, c = 0x1Fn, d = /x+/g;\nf(`a${b}`, ...c);",
+            Dialect::JavaScript,
+        );
+        assert_eq!(tree["type"], "Program");
+        assert_eq!(tree["sourceType"], "module");
+
+        let declarations = at(&tree, "/body/0/declarations");
+        // Whole numbers are written like JavaScript writes them, not as 1.0
+        assert_eq!(declarations[0]["init"]["value"], json!(1));
+        assert_eq!(declarations[1]["init"]["value"], json!(1.5));
+        assert_eq!(declarations[2]["init"]["bigint"], "31");
+        assert_eq!(
+            declarations[3]["init"]["regex"],
+            json!({ "pattern": "x+", "flags": "g" })
+        );
+        assert_eq!(declarations[3]["init"]["raw"], "/x+/g");
+
+        let call = at(&tree, "/body/1/expression");
+        assert_eq!(call["type"], "CallExpression");
+        assert_eq!(call["optional"], false);
+        assert_eq!(call["arguments"][0]["quasis"][1]["tail"], true);
+        assert_eq!(call["arguments"][1]["type"], "SpreadElement");
+        assert_eq!(call["range"], json!([42, 58]));
+        assert_eq!(
+            call["loc"],
+            json!({ "start": { "line": 2, "column": 0 }, "end": { "line": 2, "column": 16 } })
+        );
+
+        // No TypeScript fields where there is no TypeScript
+        let text = tree.to_string();
+        assert!(!text.contains("typeAnnotation") && !text.contains("declare"));
+    }
+
+    #[test]
+    fn test_positions_count_utf16_units() {
+        // The emoji is 4 bytes and 2 UTF-16 units, JavaScript sees `a` at index 6
+        let tree = estree("\"😀\"; a", Dialect::JavaScript);
+        let identifier = at(&tree, "/body/1/expression");
+        assert_eq!(identifier["range"], json!([6, 7]));
+        assert_eq!(identifier["loc"]["start"]["column"], 6);
+        assert_eq!(at(&tree, "/body/0/expression/value"), "😀");
+    }
+
+    #[test]
+    fn test_typescript_nodes() {
+        let tree = estree(
+            "let a: number = 1;
+function f(): void;
+abstract class B<T> implements C {
+  constructor(private d?: string) {}
+  abstract m(): void;
+}
+interface E extends F { g?: T[] }",
+            Dialect::TypeScript,
+        );
+        // The annotation belongs to the identifier and widens its range
+        let id = at(&tree, "/body/0/declarations/0/id");
+        assert_eq!(
+            id["typeAnnotation"]["typeAnnotation"]["type"],
+            "TSNumberKeyword"
+        );
+        assert_eq!(id["range"], json!([4, 13]));
+
+        assert_eq!(at(&tree, "/body/1/type"), "TSDeclareFunction");
+
+        let class = at(&tree, "/body/2");
+        assert_eq!(class["abstract"], true);
+        assert_eq!(class["typeParameters"]["params"][0]["name"]["name"], "T");
+        assert_eq!(class["implements"][0]["type"], "TSClassImplements");
+        let constructor = at(class, "/body/body/0/value/params/0");
+        assert_eq!(constructor["type"], "TSParameterProperty");
+        assert_eq!(constructor["accessibility"], "private");
+        assert_eq!(constructor["parameter"]["optional"], true);
+        let method = at(class, "/body/body/1");
+        assert_eq!(method["type"], "TSAbstractMethodDefinition");
+        assert_eq!(method["value"]["type"], "TSEmptyBodyFunctionExpression");
+
+        let interface = at(&tree, "/body/3");
+        assert_eq!(interface["extends"][0]["type"], "TSInterfaceHeritage");
+        let member = at(interface, "/body/body/0");
+        assert_eq!(member["type"], "TSPropertySignature");
+        assert_eq!(member["optional"], true);
+        assert_eq!(
+            member["typeAnnotation"]["typeAnnotation"]["type"],
+            "TSArrayType"
+        );
+    }
+
+    #[test]
+    fn test_jsx_nodes() {
+        let tree = estree("<a.b c=\"d\" {...e}>{}<>f</></a.b>;", Dialect::Jsx);
+        let element = at(&tree, "/body/0/expression");
+        assert_eq!(element["type"], "JSXElement");
+        let opening = &element["openingElement"];
+        assert_eq!(opening["name"]["type"], "JSXMemberExpression");
+        assert_eq!(opening["name"]["property"]["type"], "JSXIdentifier");
+        assert_eq!(opening["attributes"][0]["value"]["type"], "Literal");
+        assert_eq!(opening["attributes"][1]["type"], "JSXSpreadAttribute");
+
+        // `{}` holds an empty expression between its braces
+        let container = &element["children"][0];
+        assert_eq!(container["range"], json!([18, 20]));
+        assert_eq!(container["expression"]["type"], "JSXEmptyExpression");
+        assert_eq!(container["expression"]["range"], json!([19, 19]));
+        assert_eq!(element["children"][1]["type"], "JSXFragment");
+        assert_eq!(element["closingElement"]["type"], "JSXClosingElement");
+    }
+}
@@ -0,0 +1,28 @@
+#!/usr/bin/env node
+/**
+ * Copyright (c) Example Corp.
+ *
+ * Licensed under the MIT license.
+ */
+'use strict';
+
+// Leading line comment
+const answer = 42; // trailing line comment
+
+/* block */ let value = /* inline */ answer / 2; /* trailing block */
+
+/**
+ * Adds two numbers
+ * @param {number} a
+ */
+function add(a, b) {
+    return a + b; /* multi
+    line trailing comment */
+}
+
+<!-- legacy html comment opener
+var legacy = 1;
+--> legacy html comment closer
+
+/**/
+const empty = /**/ null;
@@ -0,0 +1,21 @@
+import React from "react";
+
+/**
+ * Renders the list of users
+ */
+export default function UserList({ users, onSelect }) {
+  const count = users.length / 2;
+  return (
+    <section className="user-list" data-count={count}>
+      {/* Header */}
+      <h1>Users &amp; groups &#8212; {users.length}</h1>
+      <>
+        {users.map((user) => (
+          <UserCard key={user.id} {...user} onClick={() => onSelect(user)} />
+        ))}
+      </>
+      <svg:rect width="10" />
+      <Foo.Bar title='It&apos;s "quoted"'>a &unknown; b</Foo.Bar>
+    </section>
+  );
+}
@@ -0,0 +1,10 @@
+import * as React from "react";
+
+type Props<T> = { items: Array<Array<T>>; render: (item: T) => React.ReactNode };
+
+const identity = <T,>(value: T): T => value;
+
+export function List<T>({ items, render }: Props<T>) {
+  const first = items[0]!;
+  return <ul>{first.map((item) => <li>{render(item)}</li>)}</ul>;
+}
@@ -0,0 +1,24 @@
+// TypeScript specific syntax, tokenized with the TypeScript dialect
+import type { Config } from "./config";
+
+declare module "cheetah" {
+  export type Nested = Array<Array<Map<string, number>>>;
+}
+
+namespace Shapes {
+  export abstract class Shape {
+    @observable readonly sides: number = 0;
+    private cache?: Record<keyof Shape, unknown>;
+
+    @memoize()
+    override area(this: Shape): number {
+      return this.cache!.sides! / 2;
+    }
+  }
+}
+
+type Unwrap<T> = T extends Promise<infer U> ? U : T;
+const settings = { retries: 3 } satisfies Partial<Config>;
+function isShape(value: unknown): value is Shapes.Shape {
+  return value instanceof Shapes.Shape;
+}
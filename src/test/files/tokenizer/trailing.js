@@ -0,0 +1,6 @@
+// A file that ends in trivia without a newline at the end
+class Stack {
+  constructor() { this.items = []; }
+}
+/* done */  
+   
\ No newline at end of file
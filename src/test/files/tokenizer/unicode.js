@@ -0,0 +1,6 @@
+const text = `line
+${ /* inside */ value }`;
+	// tabbed
+let café = "naïve\u0041"; 　/* wide space */
+ x = /re[/]gex/g;
+
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use proptest::{prelude::*, sample::Index};
+
+    use crate::{
+        error::errors::Errors,
+        read::{
+            incremental::TextEdit,
+            tokenizer::{Token, TokenKind, TokenTrait},
+            typescript::Dialect,
+        },
+    };
+
+    // Pieces sources and replacements are built from, picked to hit every lexer mode. The long
+    // name tells a Tsx generic arrow from an element only after LOOKAHEAD bytes.
+    const FRAGMENTS: [&str; 51] = [
+        "a", "b1", " ", "  ", "\n", "\r\n", "\u{2028}", "é", "1.5", "0x1F", "1n", "'s'", "\"d\"",
+        "`t`", "`t${", "}`", "}x${", "/re/g", "/", "(", ")", "{", "}", "[", "]", "if", "return",
+        "=", "=>", "+", "++", "-", "// c\n", "/* c */", "/*\n*/", "<!--", "-->", ";", ",", ".",
+        "?.", "#p", "@d", "type", "<a>", "</a>", "<", ">", "<Abcdefghijklmnopqrstuvw",
+        "Abcdefghijklmnopqrstuvw>", "extends",
+    ];
+
+    const DIALECTS: [Dialect; 4] = [
+        Dialect::JavaScript,
+        Dialect::TypeScript,
+        Dialect::Jsx,
+        Dialect::Tsx,
+    ];
+
+    fn source() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(&FRAGMENTS[..]), 0..40)
+            .prop_map(|fragments| fragments.concat())
+    }
+
+    // Rounds down to a character boundary
+    fn boundary(source: &str, index: Index) -> usize {
+        let mut offset = index.index(source.len() + 1);
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    fn describe(result: Result<Vec<Token>, Errors>) -> Result<Vec<Token>, String> {
+        result.map_err(|error| error.to_string())
+    }
+
+    // Token::relex on a copy of the tokens
+    fn relex(
+        tokens: &[Token],
+        edit: &TextEdit,
+        source: &[u8],
+        dialect: Dialect,
+    ) -> Result<Vec<Token>, Errors> {
+        let mut tokens = tokens.to_vec();
+        Token::relex(&mut tokens, edit, source, dialect)?;
+        Ok(tokens)
+    }
+
+    // Re-lexing after the edit has to give exactly what lexing the edited source gives
+    fn check(source: &str, edit: &TextEdit, dialect: Dialect) -> Result<(), TestCaseError> {
+        let tokens = match Token::tokenize_with_dialect(source.as_bytes(), dialect) {
+            Ok(tokens) => tokens,
+            Err(_) => return Ok(()),
+        };
+        let edited = edit.apply(source.as_bytes());
+        let expected = describe(Token::tokenize_with_dialect(&edited, dialect));
+        let relexed = describe(relex(&tokens, edit, &edited, dialect));
+        prop_assert_eq!(relexed, expected);
+        Ok(())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn relex_matches_tokenize(
+            source in source(),
+            start in any::<Index>(),
+            length in any::<Index>(),
+            text in source(),
+            dialect in prop::sample::select(&DIALECTS[..]),
+        ) {
+            let start = boundary(&source, start);
+            let end = start + boundary(&source[start..], length);
+            check(&source, &TextEdit::new(start, end, &text), dialect)?;
+        }
+
+        #[test]
+        fn relex_fixtures(
+            fixture in 0..6usize,
+            start in any::<Index>(),
+            length in 0..40usize,
+            text in prop::sample::select(&FRAGMENTS[..]),
+        ) {
+            let mut paths: Vec<_> = fs::read_dir("src/test/files/tokenizer")
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect();
+            paths.sort();
+            let path = &paths[fixture];
+            let source = fs::read_to_string(path).unwrap();
+
+            let start = boundary(&source, start);
+            let mut end = (start + length).min(source.len());
+            while !source.is_char_boundary(end) {
+                end -= 1;
+            }
+            let dialect = Dialect::from_path(path.to_str().unwrap());
+            check(&source, &TextEdit::new(start, end, text), dialect)?;
+        }
+    }
+
+    #[test]
+    fn relex_keeps_tokens_before_the_edit() {
+        let source = "let first = 1;\nlet second = 2;\nlet third = 3;\n";
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
+
+        let edit = TextEdit::new(source.len() - 3, source.len() - 2, "30");
+        let edited = edit.apply(source.as_bytes());
+        let relexed = relex(&tokens, &edit, &edited, Dialect::JavaScript).unwrap();
+
+        assert_eq!(relexed, Token::tokenize(&edited).unwrap());
+        assert_eq!(relexed[..5], tokens[..5]);
+        assert_eq!(relexed.last().unwrap().span.end, edited.len());
+    }
+
+    #[test]
+    fn relex_follows_a_mode_change() {
+        // Opening a template turns the rest of the source into its text
+        let source = "a = 1;\nb = 2;\nc = 3;\n";
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
+
+        let edit = TextEdit::new(4, 5, "`${1}");
+        let edited = edit.apply(source.as_bytes());
+        let mut relexed = tokens.clone();
+        assert!(Token::relex(&mut relexed, &edit, &edited, Dialect::JavaScript).is_err());
+        // A failed relex leaves the tokens alone
+        assert_eq!(relexed, tokens);
+
+        let edit = TextEdit::new(4, 5, "`1`");
+        let edited = edit.apply(source.as_bytes());
+        let relexed = relex(&tokens, &edit, &edited, Dialect::JavaScript).unwrap();
+        assert_eq!(relexed, Token::tokenize(&edited).unwrap());
+        assert!(matches!(relexed[2].kind, TokenKind::Template(_)));
+    }
+
+    #[test]
+    fn relex_rereads_a_long_generic_arrow() {
+        // The `,` decides whether the `<` far in front of it opens an element, the edits
+        // turn a generic arrow into an element that never closes and an element into an arrow
+        // followed by a stray closing tag
+        let name = "Abcdefghijklmnopqrstuvw";
+        let arrow = format!("f = <{name},>(x) => <{name}></{name}>;");
+        let element = format!("f = <{name} >(x) => <{name}></{name}></{name}>;");
+        for (source, text) in [(arrow, " "), (element, ",")] {
+            let tokens = Token::tokenize_with_dialect(source.as_bytes(), Dialect::Tsx).unwrap();
+            let at = name.len() + 5;
+            let edit = TextEdit::new(at, at + 1, text);
+            let edited = edit.apply(source.as_bytes());
+
+            let expected = describe(Token::tokenize_with_dialect(&edited, Dialect::Tsx));
+            assert!(expected.is_err());
+            assert_eq!(
+                describe(relex(&tokens, &edit, &edited, Dialect::Tsx)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn relex_rejects_an_edit_of_another_source() {
+        let tokens = Token::tokenize(b"a + b").unwrap();
+        let edit = TextEdit::new(2, 9, "");
+        let result = relex(&tokens, &edit, b"a", Dialect::JavaScript);
+        assert!(matches!(result, Err(Errors::DeveloperError(_))));
+    }
+}
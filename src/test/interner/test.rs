@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::read::{
+        interner::{Interner, Symbol},
+        tokenizer::{Token, TokenKind, TokenTrait},
+        typescript::Dialect,
+    };
+
+    #[test]
+    fn test_intern_returns_one_symbol_per_name() {
+        let first = Symbol::intern("interned_name");
+        let second = Symbol::intern("interned_name");
+        let other = Symbol::intern("interned_other");
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(&*first.resolve(), "interned_name");
+        assert_eq!(first, "interned_name");
+        assert_eq!(other.to_string(), "interned_other");
+        assert_eq!(
+            &*Interner::global().resolve(first).unwrap(),
+            "interned_name"
+        );
+        assert!(!Interner::global().is_empty());
+    }
+
+    #[test]
+    fn test_intern_across_threads() {
+        let names: Vec<String> = (0..200).map(|index| format!("shared_{}", index)).collect();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let names = names.clone();
+                thread::spawn(move || {
+                    names
+                        .iter()
+                        .map(|name| Symbol::intern(name))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let results: Vec<Vec<Symbol>> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        for symbols in &results {
+            assert_eq!(symbols, &results[0]);
+        }
+        for (symbol, name) in results[0].iter().zip(&names) {
+            assert_eq!(&*symbol.resolve(), name);
+        }
+    }
+
+    #[test]
+    fn test_entered_interner() {
+        let global = Symbol::intern("session_outside");
+        let interner = Arc::new(Interner::new());
+        assert!(interner.is_empty());
+
+        let (tokens, inside) = interner.enter(|| {
+            let tokens = Token::tokenize(b"let session_name = 1").unwrap();
+            (tokens, Symbol::intern("session_inside"))
+        });
+        let name = match tokens[1].kind {
+            TokenKind::Name { value, .. } => value,
+            ref other => panic!("expected a name, got {:?}", other),
+        };
+        // The names went into the session, the global interner is used again after it
+        assert_eq!(interner.len(), 2);
+        assert_eq!(&*interner.resolve(name).unwrap(), "session_name");
+        assert_eq!(&*interner.resolve(inside).unwrap(), "session_inside");
+        assert_eq!(interner.resolve(global), None);
+        assert_eq!(Symbol::intern("session_outside"), global);
+        assert_eq!(Arc::strong_count(&interner), 1);
+
+        // Entering again reads the symbols made before, nested sessions see only their own
+        interner.enter(|| {
+            assert_eq!(name, "session_name");
+            assert_eq!(name.to_string(), "session_name");
+            let nested = Arc::new(Interner::new());
+            nested.enter(|| assert_eq!(&*Symbol::intern("nested").resolve(), "nested"));
+            assert_eq!(nested.len(), 1);
+            assert_eq!(Symbol::intern("session_name"), name);
+        });
+
+        // Symbols resolve in the interner that made them, wherever they are used
+        assert_eq!(name, "session_name");
+        assert!(format!("{:?}", tokens[1].kind).contains("\"session_name\""));
+        let other = Arc::new(Interner::new());
+        other.enter(|| {
+            assert_eq!(&*name.resolve(), "session_name");
+            // Same name, another interner, another symbol
+            assert_ne!(Symbol::intern("session_name"), name);
+            assert_eq!(other.resolve(name), None);
+        });
+
+        // Leaving on a panic still leaves the interner
+        let result = std::panic::catch_unwind(|| interner.enter(|| panic!("inside")));
+        assert!(result.is_err());
+        assert_eq!(Symbol::intern("session_outside"), global);
+
+        // Once the interner is gone its symbols print as ids and equal no name
+        drop(interner);
+        assert_eq!(format!("{:?}", inside), "Symbol(1)");
+        assert!(inside != "session_inside");
+    }
+
+    #[test]
+    fn test_tokens_share_symbols() {
+        let first = Token::tokenize(b"let answer = 42").unwrap();
+        let second = Token::tokenize(b"print(answer)").unwrap();
+
+        let symbol = |kind: &TokenKind| match kind {
+            TokenKind::Name { value, .. } => *value,
+            other => panic!("expected a name, got {:?}", other),
+        };
+        assert_eq!(symbol(&first[1].kind), symbol(&second[2].kind));
+
+        // Escapes are decoded before interning
+        let escaped = Token::tokenize(br"\u0061nswer").unwrap();
+        assert_eq!(symbol(&escaped[0].kind), Symbol::intern("answer"));
+
+        // TypeScript keywords are valid private names
+        let tokens = Token::tokenize_with_dialect(b"this.#type", Dialect::TypeScript).unwrap();
+        assert_eq!(
+            tokens[2].kind,
+            TokenKind::PrivateName(Symbol::intern("type"))
+        );
+    }
+}
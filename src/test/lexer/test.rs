@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{self, Read},
+        path::PathBuf,
+        time::Instant,
+    };
+
+    use crate::{
+        error::errors::Errors,
+        read::{
+            lexer::Lexer,
+            read::{read_file, read_file_recovering},
+            tokenizer::{Token, TokenKind, TokenTrait},
+            typescript::Dialect,
+        },
+        utils::test_utils,
+    };
+
+    // Hands out at most `step` bytes per read and is interrupted every other call
+    struct Trickle {
+        bytes: Vec<u8>,
+        position: usize,
+        step: usize,
+        interrupt: bool,
+    }
+
+    impl Read for Trickle {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "try again"));
+            }
+            let length = self
+                .step
+                .min(buffer.len())
+                .min(self.bytes.len() - self.position);
+            buffer[..length].copy_from_slice(&self.bytes[self.position..self.position + length]);
+            self.position += length;
+            Ok(length)
+        }
+    }
+
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _buffer: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    fn fixtures() -> Vec<PathBuf> {
+        let mut fixtures: Vec<PathBuf> = fs::read_dir("src/test/files/tokenizer")
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        fixtures.sort();
+        fixtures.push(PathBuf::from("src/test/files/javascript-original.js"));
+        fixtures
+    }
+
+    #[test]
+    fn test_lexer_matches_tokenize() {
+        let source = b"let a = `x${b}y` / 2; // done\n";
+        let lexed: Vec<Token> = Lexer::new(source, Dialect::JavaScript)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(lexed, Token::tokenize(source).unwrap());
+        assert_eq!(lexed.last().unwrap().kind, TokenKind::EndOfFile);
+    }
+
+    #[test]
+    fn test_lexer_streams_across_chunks() {
+        for path in fixtures() {
+            let buffer = fs::read(&path).unwrap();
+            let dialect = Dialect::from_path(&path.to_string_lossy());
+            let expected = Token::tokenize_with_dialect(&buffer, dialect).unwrap();
+
+            for chunk_size in [1, 2, 3, 7, 64, 4096] {
+                let streamed: Vec<Token> = Lexer::from_reader(buffer.as_slice(), dialect)
+                    .with_chunk_size(chunk_size)
+                    .collect::<Result<_, _>>()
+                    .unwrap();
+                assert_eq!(
+                    streamed,
+                    expected,
+                    "{} with chunks of {}",
+                    path.display(),
+                    chunk_size
+                );
+            }
+
+            let reader = Trickle {
+                bytes: buffer.clone(),
+                position: 0,
+                step: 5,
+                interrupt: false,
+            };
+            let streamed: Vec<Token> = Lexer::from_reader(reader, dialect)
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(streamed, expected, "{} trickled", path.display());
+        }
+    }
+
+    #[test]
+    fn test_lexer_waits_for_the_end_of_a_long_jsx_name() {
+        // Deciding `<T,>` from `<T>` reads past the name, a chunk can end before the `,`
+        let source = b"f = <Abcdefghijklmnopqrstuvw,>(x) => x;";
+        let expected = Token::tokenize_with_dialect(source, Dialect::Tsx).unwrap();
+        for chunk_size in [1, 8, 20] {
+            let streamed: Vec<Token> = Lexer::from_reader(&source[..], Dialect::Tsx)
+                .with_chunk_size(chunk_size)
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(streamed, expected, "chunks of {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_lexer_reports_errors_once() {
+        let source = b"a\nb = 'never closed";
+        let mut lexer = Lexer::from_reader(&source[..], Dialect::JavaScript).with_chunk_size(3);
+
+        assert!(lexer.next().unwrap().is_ok());
+        assert!(lexer.next().unwrap().is_ok());
+        assert!(lexer.next().unwrap().is_ok());
+        match lexer.next() {
+            Some(Err(Errors::TokenError(message, span))) => {
+                assert_eq!(message, "Unterminated string literal");
+                assert_eq!((span.start, span.line, span.column), (6, 2, 4));
+            }
+            other => panic!("expected a token error, got {:?}", other),
+        }
+        assert!(lexer.next().is_none());
+
+        let mut lexer = Lexer::from_reader(Broken, Dialect::JavaScript);
+        assert!(matches!(lexer.next(), Some(Err(Errors::IoError(_)))));
+        assert!(lexer.next().is_none());
+    }
+
+    #[test]
+    fn test_read_file_errors_instead_of_panicking() {
+        match read_file("src/test/files/does-not-exist.js") {
+            Err(Errors::IoError(error)) => assert_eq!(error.kind(), io::ErrorKind::NotFound),
+            other => panic!("expected an IO error, got {:?}", other),
+        }
+    }
+
+    // `cargo test --release test_lexer_throughput -- --nocapture` for a number worth comparing
+    #[test]
+    fn test_lexer_throughput() {
+        let fixture = fs::read("src/test/files/javascript-original.js").unwrap();
+        let fixture_tokens = Token::tokenize(&fixture).unwrap().len() - 1;
+
+        let mut source = Vec::new();
+        let mut copies = 0;
+        while source.len() < 4 * 1024 * 1024 {
+            source.extend_from_slice(&fixture);
+            source.push(b'\n');
+            copies += 1;
+        }
+
+        let started = Instant::now();
+        let tokens = Token::tokenize(&source).unwrap();
+        let seconds = started.elapsed().as_secs_f64();
+
+        let megabytes = source.len() as f64 / (1024.0 * 1024.0);
+        println!(
+            "lexed {:.1} MB into {} tokens in {:.3}s, {:.1} MB/s",
+            megabytes,
+            tokens.len(),
+            seconds,
+            megabytes / seconds
+        );
+        assert_eq!(tokens.len(), fixture_tokens * copies + 1);
+    }
+
+    #[test]
+    fn test_lexer_recovering_across_chunks() {
+        let source = "a = '\n\u{1}\u{2}/* x */ b; c = /re/g; /* open";
+        let (expected, _) = Token::tokenize_recovering(source.as_bytes(), Dialect::JavaScript);
+        for chunk_size in [1, 2, 5, 64] {
+            let streamed: Vec<Token> = Lexer::from_reader(source.as_bytes(), Dialect::JavaScript)
+                .with_chunk_size(chunk_size)
+                .with_recovery()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(streamed, expected, "chunks of {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_read_file_recovering() {
+        let file_path = test_utils::create_temp_js_file("/* header */\nlet a = \u{1};\n");
+        let result = read_file_recovering(&file_path);
+        test_utils::remove_config_from_path(&file_path);
+
+        let (tokens, diagnostics) = result.unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(tokens[0].leading_comments().count(), 1);
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::EndOfFile);
+    }
+}
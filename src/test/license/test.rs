@@ -1,9 +1,9 @@
 use crate::config;
-use crate::utils::{test_utils, macros};
+use crate::utils::test_utils;
 
 #[cfg(test)]
 mod tests {
-    use config::{Empty, EmptyGeneric, Property, PropertyWithDefault};
+    use config::{Property, PropertyWithDefault};
 
     use crate::{assert_eq_with_cleanup, assert_with_cleanup, generators::license::{License, Licenses}};
     use super::*;
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::read::{
+        line_index::{ColumnUnit, LineIndex, Position},
+        tokenizer::{Token, TokenTrait},
+        typescript::Dialect,
+    };
+
+    fn position(line: usize, column: usize) -> Position {
+        Position { line, column }
+    }
+
+    #[test]
+    fn test_line_terminators() {
+        let source = "a\nb\r\nc\rd\u{2028}e\u{2029}f";
+        let index = LineIndex::new(source.as_bytes());
+
+        assert_eq!(index.line_count(), 6);
+        let lines: Vec<&str> = (1..=6)
+            .map(|line| &source[index.line_range(line).unwrap()])
+            .collect();
+        assert_eq!(lines, vec!["a", "b", "c", "d", "e", "f"]);
+        assert_eq!(index.line_range(0), None);
+        assert_eq!(index.line_range(7), None);
+
+        // CRLF is one terminator, the LF still belongs to line 2
+        assert_eq!(index.position(3, ColumnUnit::Utf8), Some(position(2, 1)));
+        assert_eq!(index.position(4, ColumnUnit::Utf8), Some(position(2, 2)));
+        assert_eq!(index.position(5, ColumnUnit::Utf8), Some(position(3, 0)));
+        assert_eq!(
+            index.position(source.len(), ColumnUnit::Utf8),
+            Some(position(6, 1))
+        );
+        assert_eq!(index.position(source.len() + 1, ColumnUnit::Utf8), None);
+    }
+
+    #[test]
+    fn test_columns_in_every_unit() {
+        // é is 2 bytes and 1 UTF-16 unit, 😀 is 4 bytes and 2 UTF-16 units
+        let source = "x\nlet é = '😀' + b";
+        let index = LineIndex::new(source.as_bytes());
+        let offset = source.find('b').unwrap();
+
+        assert_eq!(
+            index.position(offset, ColumnUnit::Utf8),
+            Some(position(2, 18))
+        );
+        assert_eq!(
+            index.position(offset, ColumnUnit::Utf16),
+            Some(position(2, 15))
+        );
+        assert_eq!(
+            index.position(offset, ColumnUnit::Char),
+            Some(position(2, 14))
+        );
+
+        for unit in [ColumnUnit::Utf8, ColumnUnit::Utf16, ColumnUnit::Char] {
+            for (offset, _) in source.char_indices() {
+                let found = index.position(offset, unit).unwrap();
+                assert_eq!(index.offset(found, unit), Some(offset), "{:?}", unit);
+            }
+        }
+
+        // Inside the emoji: the UTF-16 column between its surrogates has no offset
+        let emoji = source.find('😀').unwrap();
+        assert_eq!(
+            index.position(emoji + 2, ColumnUnit::Utf16),
+            Some(position(2, 9))
+        );
+        assert_eq!(index.offset(position(2, 10), ColumnUnit::Utf16), None);
+        assert_eq!(index.offset(position(2, 100), ColumnUnit::Char), None);
+        assert_eq!(index.offset(position(3, 0), ColumnUnit::Char), None);
+
+        // Counted from the start of the source, what JavaScript would index the string with
+        let units: Vec<u16> = source.encode_utf16().collect();
+        let utf16 = index.offset_in(offset, ColumnUnit::Utf16).unwrap();
+        assert_eq!(units[utf16], u16::from(b'b'));
+        assert_eq!(index.offset_in(offset, ColumnUnit::Char), Some(16));
+        assert_eq!(index.offset_in(emoji + 2, ColumnUnit::Utf16), Some(11));
+        assert_eq!(index.offset_in(source.len() + 1, ColumnUnit::Utf16), None);
+    }
+
+    #[test]
+    fn test_invalid_utf8_counts_one_unit_per_byte() {
+        let source = b"a\xFF\xFEb\nc";
+        let index = LineIndex::new(source);
+        assert_eq!(index.position(3, ColumnUnit::Utf16), Some(position(1, 3)));
+        assert_eq!(index.position(5, ColumnUnit::Char), Some(position(2, 0)));
+    }
+
+    #[test]
+    fn test_agrees_with_token_spans() {
+        for entry in fs::read_dir("src/test/files/tokenizer").unwrap() {
+            let path = entry.unwrap().path();
+            let source = fs::read(&path).unwrap();
+            let dialect = Dialect::from_path(&path.to_string_lossy());
+            let index = LineIndex::new(&source);
+
+            for token in Token::tokenize_with_dialect(&source, dialect).unwrap() {
+                let span = token.span;
+                assert_eq!(
+                    index.position(span.start, ColumnUnit::Utf8),
+                    Some(position(span.line, span.column)),
+                    "{}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
@@ -1,3 +1,12 @@
 pub mod config;
+pub mod encoding;
+pub mod estree;
+pub mod incremental;
+pub mod interner;
+pub mod lexer;
 pub mod license;
+pub mod line_index;
+pub mod parser;
+pub mod scope;
 pub mod tokenizer;
+pub mod visit;
@@ -0,0 +1,1054 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        error::errors::Errors,
+        parse::{
+            ast::{
+                Accessibility, ClassMember, ExportDefault, Expression, ForHead, ImportSpecifier,
+                JsxAttributeItem, JsxAttributeValue, JsxChild, JsxElementName, MethodKind, Pattern,
+                Program, SourceType, Statement, TsModuleKind, TsModuleName, TsType, TsTypeMember,
+                VariableKind,
+            },
+            component::find_components,
+            parser::{parse, parse_recovering},
+        },
+        read::typescript::Dialect,
+    };
+
+    fn script(source: &str) -> Program {
+        parse(source.as_bytes(), Dialect::JavaScript, SourceType::Script).unwrap()
+    }
+
+    fn module(source: &str) -> Program {
+        parse(source.as_bytes(), Dialect::JavaScript, SourceType::Module).unwrap()
+    }
+
+    fn typescript(source: &str) -> Program {
+        parse(source.as_bytes(), Dialect::TypeScript, SourceType::Module).unwrap()
+    }
+
+    fn jsx(source: &str, dialect: Dialect) -> Program {
+        parse(source.as_bytes(), dialect, SourceType::Module).unwrap()
+    }
+
+    fn parse_error(source: &str, source_type: SourceType) -> (&'static str, usize, usize) {
+        match parse(source.as_bytes(), Dialect::JavaScript, source_type) {
+            Err(Errors::ParseError(message, span)) => (message, span.line, span.column),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+
+    fn expression(statement: &Statement) -> &Expression {
+        match statement {
+            Statement::Expression(statement) => &statement.expression,
+            other => panic!("Expected an expression statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_javascript_fixture() {
+        let source = fs::read_to_string("src/test/files/javascript-original.js").unwrap();
+
+        // The fixture has a stray `continue` and mixes module syntax with `with`,
+        // so each part parses on its own terms
+        let program = script(&source[..source.find("continue;").unwrap()]);
+        assert!(program.body.len() > 20);
+        assert!(matches!(program.body[0], Statement::Variable(_)));
+
+        let (message, line, _) = parse_error(&source, SourceType::Script);
+        assert_eq!(message, "continue is only allowed in loops");
+        assert_eq!(line, 128);
+
+        let module_part = &source[source.find("export default").unwrap()..];
+        let imports = module(&module_part[..module_part.find("with").unwrap()]);
+        assert!(matches!(imports.body[0], Statement::ExportDefault(_)));
+        assert!(matches!(imports.body[1], Statement::Import(_)));
+        assert_eq!(
+            parse_error(module_part, SourceType::Module).0,
+            "with isn't allowed in strict mode"
+        );
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        let program = script("a + b * c ** d ** e; a ?? b; x = y = z;");
+
+        let Expression::Binary(sum) = expression(&program.body[0]) else {
+            panic!("Expected a binary expression");
+        };
+        let Expression::Binary(product) = &sum.right else {
+            panic!("Expected * on the right of +");
+        };
+        let Expression::Binary(power) = &product.right else {
+            panic!("Expected ** on the right of *");
+        };
+        assert!(matches!(power.right, Expression::Binary(_)));
+        assert!(matches!(
+            expression(&program.body[1]),
+            Expression::Logical(_)
+        ));
+
+        let Expression::Assignment(assignment) = expression(&program.body[2]) else {
+            panic!("Expected an assignment");
+        };
+        assert!(matches!(assignment.right, Expression::Assignment(_)));
+
+        let (message, _, _) = parse_error("a ?? b || c;", SourceType::Script);
+        assert_eq!(
+            message,
+            "?? can't be mixed with || or && without parentheses"
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        let program = script("let answer = 42;\nfoo(answer);");
+        assert_eq!(program.span.end, 29);
+
+        let Statement::Variable(declaration) = &program.body[0] else {
+            panic!("Expected a variable declaration");
+        };
+        assert_eq!(declaration.kind, VariableKind::Let);
+        assert_eq!((declaration.span.start, declaration.span.end), (0, 16));
+        let init = declaration.declarations[0].init.as_ref().unwrap();
+        assert_eq!((init.span().start, init.span().end), (13, 15));
+
+        let call = expression(&program.body[1]);
+        assert_eq!(
+            (call.span().start, call.span().end, call.span().line),
+            (17, 28, 2)
+        );
+    }
+
+    #[test]
+    fn test_automatic_semicolon_insertion() {
+        let program = script("function f() {\n  return\n  1\n}\na\n++b\nlet c = 1\nc");
+        let Statement::Function(function) = &program.body[0] else {
+            panic!("Expected a function");
+        };
+        // `return` ends at the line break, `1` is a statement of its own
+        assert_eq!(function.body.as_ref().unwrap().body.len(), 2);
+        assert!(
+            matches!(&function.body.as_ref().unwrap().body[0], Statement::Return(r) if r.argument.is_none())
+        );
+
+        // `++` after a line break belongs to the next line
+        assert!(matches!(
+            expression(&program.body[1]),
+            Expression::Identifier(_)
+        ));
+        assert!(matches!(expression(&program.body[2]), Expression::Update(u) if u.prefix));
+        assert_eq!(program.body.len(), 5);
+
+        assert_eq!(
+            parse_error("a b", SourceType::Script).0,
+            "Missing semicolon"
+        );
+    }
+
+    #[test]
+    fn test_classes() {
+        let program = script(
+            "class A extends B {\n  #count = 0;\n  static #instances;\n  static { A.#instances = 0; }\n  constructor() { super(); }\n  get count() { return this.#count; }\n  static async *items() {}\n  #secret() {}\n}",
+        );
+        let Statement::Class(class) = &program.body[0] else {
+            panic!("Expected a class");
+        };
+        assert!(class.super_class.is_some());
+
+        let members = &class.body.body;
+        assert_eq!(members.len(), 7);
+        assert!(matches!(&members[0], ClassMember::Property(p) if !p.is_static));
+        assert!(matches!(&members[1], ClassMember::Property(p) if p.is_static));
+        assert!(matches!(&members[2], ClassMember::StaticBlock(_)));
+        assert!(matches!(&members[3], ClassMember::Method(m) if m.kind == MethodKind::Constructor));
+        assert!(matches!(&members[4], ClassMember::Method(m) if m.kind == MethodKind::Get));
+        assert!(matches!(&members[5], ClassMember::Method(m)
+            if m.is_static && m.value.is_async && m.value.is_generator));
+
+        assert_eq!(
+            parse_error(
+                "class A { constructor() {} constructor() {} }",
+                SourceType::Script
+            )
+            .0,
+            "A class can only have one constructor"
+        );
+    }
+
+    #[test]
+    fn test_functions_and_arrows() {
+        let program = script(
+            "async function f(a, b = 1, ...rest) { await a; }\nfunction* g() { yield* f(); }\nconst h = async (x) => x;\nconst i = y => ({ y });",
+        );
+        let Statement::Function(function) = &program.body[0] else {
+            panic!("Expected a function");
+        };
+        assert!(function.is_async);
+        assert_eq!(function.params.len(), 3);
+        assert!(matches!(function.params[1].pattern, Pattern::Assignment(_)));
+        assert!(matches!(function.params[2].pattern, Pattern::Rest(_)));
+
+        let Statement::Variable(declaration) = &program.body[2] else {
+            panic!("Expected a variable declaration");
+        };
+        assert!(
+            matches!(&declaration.declarations[0].init, Some(Expression::Arrow(a)) if a.is_async)
+        );
+
+        // `yield` and `await` are names outside generators and async functions
+        script("var yield = 1, await = 2;");
+        assert_eq!(
+            parse_error("function* g() { var yield; }", SourceType::Script).0,
+            "Unexpected reserved word"
+        );
+    }
+
+    #[test]
+    fn test_destructuring() {
+        let program = script("const { a, b: [c, , ...d], e = 1, ...f } = g;\n[x, { y }] = [1, { y: 2 }];\nfor ({ z } of list);");
+        let Statement::Variable(declaration) = &program.body[0] else {
+            panic!("Expected a variable declaration");
+        };
+        let names: Vec<String> = declaration.declarations[0]
+            .id
+            .bound_names()
+            .iter()
+            .map(|identifier| identifier.name.to_string())
+            .collect();
+        assert_eq!(names, ["a", "c", "d", "e", "f"]);
+
+        let Expression::Assignment(assignment) = expression(&program.body[1]) else {
+            panic!("Expected an assignment");
+        };
+        assert!(matches!(assignment.left, Pattern::Array(_)));
+        assert!(matches!(program.body[2], Statement::ForOf(_)));
+
+        assert_eq!(
+            parse_error("({ a = 1 });", SourceType::Script).0,
+            "Invalid shorthand property initializer"
+        );
+        assert_eq!(
+            parse_error("a + b = c;", SourceType::Script).0,
+            "Invalid assignment target"
+        );
+    }
+
+    #[test]
+    fn test_optional_chaining() {
+        let program = script("a?.b.c(); a?.[0]?.();");
+        let Expression::Chain(chain) = expression(&program.body[0]) else {
+            panic!("Expected a chain expression");
+        };
+        assert!(matches!(&chain.expression, Expression::Call(c) if !c.optional));
+        assert!(matches!(expression(&program.body[1]), Expression::Chain(_)));
+
+        let (message, line, column) = parse_error("new.target;", SourceType::Script);
+        assert_eq!(
+            (message, line, column),
+            ("new.target is only allowed in functions", 1, 0)
+        );
+        assert_eq!(
+            parse_error("a?.b = 1;", SourceType::Script).0,
+            "Invalid assignment target"
+        );
+    }
+
+    #[test]
+    fn test_context_errors() {
+        assert_eq!(
+            parse_error("return 1;", SourceType::Script).0,
+            "return is only allowed in functions"
+        );
+        assert_eq!(
+            parse_error("break;", SourceType::Script).0,
+            "break is only allowed in loops and switch statements"
+        );
+        assert_eq!(
+            parse_error("a: while (true) { continue b; }", SourceType::Script).0,
+            "Undefined label"
+        );
+        assert_eq!(
+            parse_error("'use strict'; delete x;", SourceType::Script).0,
+            "Deleting a variable isn't allowed in strict mode"
+        );
+        assert_eq!(
+            parse_error("let a = ", SourceType::Script).0,
+            "Unexpected end of input"
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_early_errors() {
+        let octal = "Numbers with a leading zero aren't allowed in strict mode";
+        assert_eq!(
+            parse_error("\"use strict\"; 0777", SourceType::Script),
+            (octal, 1, 14)
+        );
+        assert_eq!(parse_error("({ 089: 1 })", SourceType::Module).0, octal);
+        script("0777; 089; ({ 017: '\\1' });");
+
+        let escape = "Octal escapes aren't allowed in strict mode";
+        assert_eq!(
+            parse_error("'use strict'; '\\01'", SourceType::Script).0,
+            escape
+        );
+        // A directive before "use strict" is strict mode code too
+        assert_eq!(
+            parse_error("function f() { '\\01'; 'use strict'; }", SourceType::Script),
+            (escape, 1, 15)
+        );
+        script("'\\01'; 'not strict';");
+    }
+
+    #[test]
+    fn test_duplicate_parameters() {
+        let duplicate = "Duplicate parameter name";
+        // "use strict" in the body applies to the parameters before it
+        assert_eq!(
+            parse_error("function f(a, a) { 'use strict' }", SourceType::Script),
+            (duplicate, 1, 14)
+        );
+        assert_eq!(
+            parse_error("function f(a, a) {}", SourceType::Module).0,
+            duplicate
+        );
+        assert_eq!(
+            parse_error("function f(a, [a]) {}", SourceType::Script).0,
+            duplicate
+        );
+        assert_eq!(parse_error("(a, a) => 1", SourceType::Script).0, duplicate);
+        assert_eq!(
+            parse_error("({ m(a, a) {} })", SourceType::Script).0,
+            duplicate
+        );
+
+        script("function f(a, a) {} (function (b, b) {});");
+    }
+
+    #[test]
+    fn test_duplicate_lexical_bindings() {
+        let duplicate = "Duplicate lexical binding";
+        assert_eq!(
+            parse_error("let [a, a] = o;", SourceType::Script),
+            (duplicate, 1, 8)
+        );
+        assert_eq!(
+            parse_error("const a = 1, a = 2;", SourceType::Script).0,
+            duplicate
+        );
+        assert_eq!(
+            parse_error("for (let { a, b: a } of o) {}", SourceType::Script).0,
+            duplicate
+        );
+
+        script("var [a, a] = o; var b = 1, b = 2;");
+    }
+
+    #[test]
+    fn test_private_names() {
+        let duplicate = "Duplicate private name";
+        let undeclared = "Undeclared private name";
+        assert_eq!(
+            parse_error("class C { #a; #a }", SourceType::Script),
+            (duplicate, 1, 14)
+        );
+        assert_eq!(
+            parse_error("class C { #a; get #a() {} }", SourceType::Script).0,
+            duplicate
+        );
+        assert_eq!(
+            parse_error(
+                "class C { get #a() {} static set #a(v) {} }",
+                SourceType::Script
+            )
+            .0,
+            duplicate
+        );
+        assert_eq!(
+            parse_error("class C { m() { this.#b } }", SourceType::Script),
+            (undeclared, 1, 21)
+        );
+        assert_eq!(
+            parse_error("this.#a", SourceType::Script),
+            (undeclared, 1, 5)
+        );
+        assert_eq!(parse_error("#a in o", SourceType::Script).0, undeclared);
+        // The inner class can't see a name only its outer class declares
+        assert_eq!(
+            parse_error(
+                "class C { m() { class D { #b } this.#b } }",
+                SourceType::Script
+            )
+            .0,
+            undeclared
+        );
+
+        script("class C { get #a() {} set #a(v) {} static #b; m() { #a in this; this.#c } #c }");
+        script("class C { #a; m() { return class { n() { this.#a } } } }");
+        typescript("class C { #m(): void; #m(a?: number) {} }");
+    }
+
+    #[test]
+    fn test_let_of() {
+        let program = script("for (let of of x) {} let of = 1;");
+        let Statement::ForOf(statement) = &program.body[0] else {
+            panic!("Expected a for-of statement");
+        };
+        let ForHead::Variable(declaration) = &statement.left else {
+            panic!("Expected a declaration");
+        };
+        assert_eq!(declaration.kind, VariableKind::Let);
+        assert!(matches!(program.body[1], Statement::Variable(_)));
+
+        assert!(parse(
+            b"for (let of x) {}",
+            Dialect::JavaScript,
+            SourceType::Script
+        )
+        .is_err());
+        assert!(parse(
+            b"for (let of x) {}",
+            Dialect::JavaScript,
+            SourceType::Module
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_modules() {
+        let program = module(
+            "import a, { b as c, \"d-e\" as d } from './a.js';\nimport * as ns from 'b';\nimport data from './data.json' with { type: 'json' };\nexport const x = 1;\nexport { x as y, c };\nexport * as all from 'c';\nexport default class {}\nawait x;",
+        );
+        assert_eq!(program.source_type, SourceType::Module);
+
+        let Statement::Import(import) = &program.body[0] else {
+            panic!("Expected an import");
+        };
+        assert_eq!(import.specifiers.len(), 3);
+        assert!(matches!(&import.specifiers[0], ImportSpecifier::Default(a) if a.name == "a"));
+        assert!(matches!(&import.specifiers[2], ImportSpecifier::Named(n) if n.local.name == "d"));
+        let Statement::Import(json) = &program.body[2] else {
+            panic!("Expected an import");
+        };
+        assert_eq!(json.attributes.len(), 1);
+
+        assert!(matches!(program.body[3], Statement::ExportNamed(_)));
+        assert!(matches!(&program.body[5], Statement::ExportAll(e) if e.exported.is_some()));
+        assert!(matches!(&program.body[6], Statement::ExportDefault(e)
+            if matches!(e.declaration, ExportDefault::Class(_))));
+        assert!(matches!(expression(&program.body[7]), Expression::Await(_)));
+
+        // A script reads `import(...)` but no declarations
+        script("import('./a.js').then(run);");
+        assert_eq!(
+            parse_error("export { default };", SourceType::Module).0,
+            "Only local bindings can be exported"
+        );
+    }
+
+    #[test]
+    fn test_parse_typescript_fixtures() {
+        let source = fs::read_to_string("src/test/files/tokenizer/generics.ts").unwrap();
+        let program = typescript(&source);
+        assert_eq!(program.body.len(), 6);
+        assert!(matches!(&program.body[0], Statement::Import(i) if i.type_only));
+        assert!(matches!(&program.body[1], Statement::TsModule(m)
+            if m.declare && matches!(m.id, TsModuleName::String(_))));
+        assert!(
+            matches!(&program.body[2], Statement::TsModule(m) if m.kind == TsModuleKind::Namespace)
+        );
+        assert!(matches!(&program.body[3], Statement::TsTypeAlias(a)
+            if matches!(a.type_annotation, TsType::Conditional(_))));
+        let Statement::Function(function) = &program.body[5] else {
+            panic!("Expected a function");
+        };
+        assert!(matches!(
+            function.return_type.as_ref().map(|r| &r.type_annotation),
+            Some(TsType::Predicate(_))
+        ));
+
+        // Like the JavaScript fixture it ends with `with`, which modules don't allow
+        let source = fs::read_to_string("src/test/files/typescript-original.ts").unwrap();
+        let program = typescript(&source[..source.find("with (Math)").unwrap()]);
+        assert!(program.body.len() > 30);
+    }
+
+    #[test]
+    fn test_typescript_declarations() {
+        let program = typescript(
+            "interface A<T> extends B { a?: T; readonly [key: string]: T; m(): void }
+type C = A<string> | null;
+const enum D { E = 1, F }
+declare global { interface Window { g: number } }
+declare const h: number;
+function f(a: string): void;
+function f(a: any) {}",
+        );
+        let Statement::TsInterface(interface) = &program.body[0] else {
+            panic!("Expected an interface");
+        };
+        assert_eq!(interface.extends.len(), 1);
+        assert!(matches!(&interface.body.body[0], TsTypeMember::Property(p) if p.optional));
+        assert!(matches!(&interface.body.body[1], TsTypeMember::Index(i) if i.readonly));
+        assert!(matches!(&interface.body.body[2], TsTypeMember::Method(_)));
+
+        assert!(matches!(&program.body[1], Statement::TsTypeAlias(a)
+            if matches!(a.type_annotation, TsType::Union(_))));
+        assert!(
+            matches!(&program.body[2], Statement::TsEnum(e) if e.is_const && e.members.len() == 2)
+        );
+        assert!(matches!(&program.body[3], Statement::TsModule(m)
+            if m.kind == TsModuleKind::Global && m.declare));
+        assert!(matches!(&program.body[4], Statement::Variable(v) if v.declare));
+        // The overload has no body
+        assert!(matches!(&program.body[5], Statement::Function(f) if f.body.is_none()));
+        assert!(matches!(&program.body[6], Statement::Function(f) if f.body.is_some()));
+
+        // The words stay names where no declaration follows them
+        typescript("let type = 1; type = 2; declare(type); namespace;");
+
+        let program = typescript("export default interface I { a: number }");
+        assert!(matches!(&program.body[0], Statement::ExportDefault(e)
+            if matches!(&e.declaration, ExportDefault::TsInterface(i) if i.id.name == "I")));
+        assert_eq!(
+            parse_error("export default interface I {}", SourceType::Module).0,
+            "Unexpected reserved word"
+        );
+    }
+
+    #[test]
+    fn test_typescript_classes() {
+        let program = typescript(
+            "abstract class A<T> extends B<T> implements C {
+  private readonly a: number = 1;
+  b?: string;
+  abstract m(): void;
+  constructor(protected c: T, d?: number) { super(); }
+}",
+        );
+        let Statement::Class(class) = &program.body[0] else {
+            panic!("Expected a class");
+        };
+        assert!(class.is_abstract);
+        assert!(class.type_parameters.is_some() && class.super_type_arguments.is_some());
+        assert_eq!(class.implements.len(), 1);
+
+        let members = &class.body.body;
+        assert!(matches!(&members[0], ClassMember::Property(p)
+            if p.modifiers.readonly && p.modifiers.accessibility == Some(Accessibility::Private)));
+        assert!(matches!(&members[1], ClassMember::Property(p) if p.optional));
+        assert!(matches!(&members[2], ClassMember::Method(m)
+            if m.modifiers.is_abstract && m.value.body.is_none()));
+        let ClassMember::Method(constructor) = &members[3] else {
+            panic!("Expected the constructor");
+        };
+        let params = &constructor.value.params;
+        assert_eq!(
+            params[0].modifiers.accessibility,
+            Some(Accessibility::Protected)
+        );
+        assert!(params[1].optional && params[1].type_annotation.is_some());
+    }
+
+    #[test]
+    fn test_accessor_members() {
+        let source = "class A {
+  accessor a = 1;
+  static accessor #b: string;
+  public override accessor c;
+  accessor;
+  accessor = 2;
+  accessor() {}
+  accessor
+  d;
+}";
+        let program = typescript(source);
+        let Statement::Class(class) = &program.body[0] else {
+            panic!("Expected a class");
+        };
+        let accessors: Vec<bool> = class
+            .body
+            .body
+            .iter()
+            .map(|member| matches!(member, ClassMember::Property(p) if p.accessor))
+            .collect();
+        assert_eq!(
+            accessors,
+            [true, true, true, false, false, false, false, false]
+        );
+        assert!(matches!(&class.body.body[1], ClassMember::Property(p)
+            if p.is_static && p.type_annotation.is_some()));
+
+        // `accessor` isn't TypeScript only
+        let program = script("class A { accessor a = 1 }");
+        let Statement::Class(class) = &program.body[0] else {
+            panic!("Expected a class");
+        };
+        assert!(matches!(&class.body.body[0], ClassMember::Property(p) if p.accessor));
+        assert_eq!(
+            parse_error("class A { accessor m() {} }", SourceType::Script).0,
+            "An accessor field can't be a method"
+        );
+    }
+
+    #[test]
+    fn test_typescript_expressions() {
+        let program = typescript(
+            "a as const;
+b satisfies C;
+d!.e;
+f<string>(g);
+h < i > j;
+const k = <T,>(l: T): T => l;
+new Map<string, Array<number>>();",
+        );
+        assert!(matches!(expression(&program.body[0]), Expression::TsAs(_)));
+        assert!(matches!(
+            expression(&program.body[1]),
+            Expression::TsSatisfies(_)
+        ));
+        assert!(matches!(expression(&program.body[2]), Expression::Member(m)
+            if matches!(m.object, Expression::TsNonNull(_))));
+        assert!(
+            matches!(expression(&program.body[3]), Expression::Call(c) if c.type_arguments.is_some())
+        );
+        // Without a call after it, `<` is a comparison
+        assert!(matches!(
+            expression(&program.body[4]),
+            Expression::Binary(_)
+        ));
+
+        let Statement::Variable(declaration) = &program.body[5] else {
+            panic!("Expected a variable declaration");
+        };
+        assert!(
+            matches!(&declaration.declarations[0].init, Some(Expression::Arrow(a))
+            if a.type_parameters.is_some() && a.return_type.is_some())
+        );
+        // `>>>` closes three type argument lists
+        assert!(
+            matches!(expression(&program.body[6]), Expression::New(n) if n.type_arguments.is_some())
+        );
+
+        // JavaScript reads none of it
+        assert_eq!(
+            parse_error("let a: number;", SourceType::Script).0,
+            "Missing semicolon"
+        );
+    }
+
+    #[test]
+    fn test_jsx() {
+        let source = fs::read_to_string("src/test/files/tokenizer/component.jsx").unwrap();
+        let program = jsx(&source, Dialect::Jsx);
+        let Statement::ExportDefault(export) = &program.body[1] else {
+            panic!("Expected a default export");
+        };
+        let ExportDefault::Function(function) = &export.declaration else {
+            panic!("Expected a function");
+        };
+        let Statement::Return(statement) = &function.body.as_ref().unwrap().body[1] else {
+            panic!("Expected a return");
+        };
+        let Some(Expression::JsxElement(section)) = &statement.argument else {
+            panic!("Expected an element");
+        };
+        let attributes = &section.opening_element.attributes;
+        assert!(matches!(&attributes[0], JsxAttributeItem::Attribute(a)
+            if matches!(&a.value, Some(JsxAttributeValue::String(s)) if s.value == "user-list")));
+        assert!(matches!(&attributes[1], JsxAttributeItem::Attribute(a)
+            if matches!(a.value, Some(JsxAttributeValue::ExpressionContainer(_)))));
+
+        // Whitespace between the tags stays as text children
+        let children: Vec<&JsxChild> = section
+            .children
+            .iter()
+            .filter(|child| !matches!(child, JsxChild::Text(t) if t.value.trim().is_empty()))
+            .collect();
+        assert!(matches!(children[0], JsxChild::ExpressionContainer(c) if c.expression.is_none()));
+        let JsxChild::Element(heading) = children[1] else {
+            panic!("Expected the heading");
+        };
+        assert!(matches!(&heading.children[0], JsxChild::Text(t)
+            if t.value == "Users & groups \u{2014} " && t.raw == "Users &amp; groups &#8212; "));
+        let JsxChild::Fragment(fragment) = children[2] else {
+            panic!("Expected a fragment");
+        };
+        let card = fragment
+            .children
+            .iter()
+            .find_map(|child| match child {
+                JsxChild::ExpressionContainer(c) => c.expression.as_ref(),
+                _ => None,
+            })
+            .unwrap();
+        assert!(matches!(card, Expression::Call(_)));
+        assert!(matches!(children[3], JsxChild::Element(e)
+            if matches!(e.opening_element.name, JsxElementName::Namespaced(_))
+                && e.opening_element.self_closing));
+        assert!(matches!(children[4], JsxChild::Element(e)
+            if matches!(e.opening_element.name, JsxElementName::Member(_))
+                && e.closing_element.is_some()));
+
+        let program = jsx("<a {...b}>{...c}</a>;", Dialect::Jsx);
+        let Expression::JsxElement(element) = expression(&program.body[0]) else {
+            panic!("Expected an element");
+        };
+        assert!(matches!(
+            element.opening_element.attributes[0],
+            JsxAttributeItem::Spread(_)
+        ));
+        assert!(matches!(element.children[0], JsxChild::Spread(_)));
+
+        let error = parse(
+            "<a><b></a></b>;".as_bytes(),
+            Dialect::Jsx,
+            SourceType::Module,
+        );
+        assert!(matches!(error, Err(Errors::ParseError(message, span))
+            if message == "The closing tag doesn't match the opening tag" && span.column == 8));
+    }
+
+    #[test]
+    fn test_find_components() {
+        let source = fs::read_to_string("src/test/files/tokenizer/component.jsx").unwrap();
+        let program = jsx(&source, Dialect::Jsx);
+        let components = find_components(&program);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].name.unwrap().name, "UserList");
+        let props: Vec<&str> = components[0]
+            .props
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(props, ["users", "onSelect"]);
+
+        // The props type is followed through the generic alias
+        let source = fs::read_to_string("src/test/files/tokenizer/component.tsx").unwrap();
+        let program = jsx(&source, Dialect::Tsx);
+        let components = find_components(&program);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].name.unwrap().name, "List");
+        assert!(components[0]
+            .props
+            .iter()
+            .all(|prop| prop.type_annotation.is_some() && !prop.optional));
+
+        let program = jsx(
+            "interface Base { id: string }
+interface Props extends Base { title?: string }
+const Card: React.FC<Props> = (props) => <div />;
+const Button = memo(({ label = 'OK', ...rest }: { label?: string }) => <button />);
+function renderRow() { return <tr />; }
+function Empty() { return null; }
+export default () => { if (a) { return <>{a}</>; } return null; };",
+            Dialect::Tsx,
+        );
+        let components = find_components(&program);
+        let names: Vec<Option<String>> = components
+            .iter()
+            .map(|c| c.name.map(|name| name.name.to_string()))
+            .collect();
+        assert_eq!(
+            names,
+            [Some("Card".to_string()), Some("Button".to_string()), None]
+        );
+
+        let props: Vec<(&str, bool)> = components[0]
+            .props
+            .iter()
+            .map(|p| (p.name.as_str(), p.optional))
+            .collect();
+        assert_eq!(props, [("title", true), ("id", false)]);
+        let button = &components[1].props;
+        assert!(button[0].default.is_some() && button[0].type_annotation.is_some());
+        assert!(button[1].rest && button[1].name == "rest");
+
+        // Only memo and forwardRef are looked through, a hook or styled call isn't a component
+        let program = jsx(
+            "const A = React.memo(React.forwardRef((props, ref) => <a />));
+const Item = useCallback(() => <li />, []);
+const B = styled.div(() => <a />);
+const C = React.useMemo(() => <a />, []);",
+            Dialect::Tsx,
+        );
+        let names: Vec<String> = find_components(&program)
+            .iter()
+            .map(|c| c.name.unwrap().name.to_string())
+            .collect();
+        assert_eq!(names, ["A"]);
+    }
+
+    #[test]
+    fn test_component_props_of_cyclic_interfaces() {
+        let props = |source: &str| -> Vec<String> {
+            let program = jsx(source, Dialect::Tsx);
+            let components = find_components(&program);
+            components[0].props.iter().map(|p| p.name.clone()).collect()
+        };
+
+        assert_eq!(
+            props("interface A extends A { a: 1 } const C = (p: A) => <div/>;"),
+            ["a"]
+        );
+        assert_eq!(
+            props(
+                "interface A extends B { a: 1 } interface B extends A { b: 2 } const C = (p: A) => <div/>;"
+            ),
+            ["a", "b"]
+        );
+        // Two bases sharing one, its members still come once
+        assert_eq!(
+            props(
+                "interface A { a: 1 } interface B extends A {} interface C extends A {} interface D extends B, C {} const E = (p: D) => <div/>;"
+            ),
+            ["a"]
+        );
+        // Every name is expanded once, however often it's listed
+        let bases = vec!["A"; 64].join(", ");
+        assert_eq!(
+            props(&format!(
+                "interface A extends {bases} {{ a: 1 }} const C = (p: A) => <div/>;"
+            )),
+            ["a"]
+        );
+    }
+
+    #[test]
+    fn test_nesting_depth() {
+        let nested = |open: &str, inner: &str, close: &str, depth: usize| {
+            format!("{}{inner}{};", open.repeat(depth), close.repeat(depth))
+        };
+        let run = move || {
+            // Deep but real nesting parses
+            for (source, dialect) in [
+                (nested("(", "a", ")", 100), Dialect::JavaScript),
+                (nested("[", "a", "]", 100), Dialect::JavaScript),
+                (nested("!", "a", "", 200), Dialect::JavaScript),
+                (nested("new ", "a", "", 200), Dialect::JavaScript),
+                (nested("{", "", "}", 200), Dialect::JavaScript),
+                (nested("if (a) ", "b", "", 200), Dialect::JavaScript),
+                (nested("<a>", "", "</a>", 200), Dialect::Jsx),
+                (
+                    format!("type T = {}", nested("(", "A", ")", 200)),
+                    Dialect::TypeScript,
+                ),
+                (
+                    format!("type T = {}", nested("keyof ", "A", "", 200)),
+                    Dialect::TypeScript,
+                ),
+            ] {
+                let result = parse(source.as_bytes(), dialect, SourceType::Script);
+                assert!(result.is_ok(), "{:?}", result.err());
+            }
+
+            // Deeper is an error, not a stack overflow
+            for source in [
+                nested("(", "", "", 100_000),
+                nested("[", "a", "]", 100_000),
+                nested("{", "", "}", 100_000),
+                nested("-", "a", "", 100_000),
+            ] {
+                let error = parse(source.as_bytes(), Dialect::JavaScript, SourceType::Script);
+                assert!(matches!(error, Err(Errors::ParseError(message, _))
+                    if message == "Too deeply nested"));
+                let (_, errors) =
+                    parse_recovering(source.as_bytes(), Dialect::JavaScript, SourceType::Script);
+                assert!(diagnostics(&errors)[0].starts_with("Parse Error: Too deeply nested"));
+            }
+        };
+        // The stack of a main thread, test threads get less
+        std::thread::Builder::new()
+            .stack_size(8 << 20)
+            .spawn(run)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    // Messages and 1-based positions of the diagnostics, as they are shown
+    fn diagnostics(errors: &[Errors]) -> Vec<String> {
+        errors.iter().map(|error| error.to_string()).collect()
+    }
+
+    #[test]
+    fn test_recovery_at_statements() {
+        let source = "let a = f(1, ;\nlet b = 2;\nfunction g() {\n  const c = b +;\n  return c;\n}\nif (a) {\n  b(\n}\nlet d = = 1\nexport const e = 3;";
+        let (program, errors) =
+            parse_recovering(source.as_bytes(), Dialect::JavaScript, SourceType::Module);
+        assert_eq!(
+            diagnostics(&errors),
+            vec![
+                "Parse Error: Unexpected token at 1:14",
+                "Parse Error: Unexpected token at 4:16",
+                "Parse Error: Unexpected token at 9:1",
+                "Parse Error: Unexpected token at 10:9",
+            ]
+        );
+
+        let body = &program.body;
+        assert_eq!(body.len(), 6);
+        let Statement::Error(span) = body[0] else {
+            panic!("Expected an error, got {:?}", body[0]);
+        };
+        assert_eq!(&source[span.start..span.end], "let a = f(1, ;");
+        assert!(matches!(body[1], Statement::Variable(_)));
+
+        // The function is kept, only its broken statement is skipped
+        let Statement::Function(function) = &body[2] else {
+            panic!("Expected a function, got {:?}", body[2]);
+        };
+        let statements = &function.body.as_ref().unwrap().body;
+        assert!(matches!(statements[0], Statement::Error(_)));
+        assert!(matches!(statements[1], Statement::Return(_)));
+
+        // `}` closes the block the unclosed `(` is in
+        let Statement::If(node) = &body[3] else {
+            panic!("Expected an if statement, got {:?}", body[3]);
+        };
+        assert_eq!(node.span.line, 7);
+        let Statement::Block(block) = &node.consequent else {
+            panic!("Expected a block, got {:?}", node.consequent);
+        };
+        assert!(matches!(block.body[..], [Statement::Error(_)]));
+
+        // A line starting a statement ends the skipping, without a `;`
+        assert!(matches!(body[4], Statement::Error(_)));
+        assert!(matches!(body[5], Statement::ExportNamed(_)));
+
+        // Expression statements end the skipping too when no more indented
+        let source = "foo(;\nbar();\n}\nbaz();\nqux(1,\n  a.b(2);";
+        let (program, errors) =
+            parse_recovering(source.as_bytes(), Dialect::JavaScript, SourceType::Script);
+        assert_eq!(errors.len(), 3);
+        let expressions: Vec<bool> = program
+            .body
+            .iter()
+            .map(|statement| matches!(statement, Statement::Expression(_)))
+            .collect();
+        assert_eq!(expressions, [false, true, false, true, false]);
+        let Statement::Error(span) = program.body[4] else {
+            panic!("Expected an error, got {:?}", program.body[4]);
+        };
+        assert_eq!(&source[span.start..span.end], "qux(1,\n  a.b(2);");
+
+        // Valid sources come out the same as from parse, without diagnostics
+        for (path, dialect) in [
+            ("src/test/files/tokenizer/generics.ts", Dialect::TypeScript),
+            ("src/test/files/tokenizer/component.jsx", Dialect::Jsx),
+            ("src/test/files/tokenizer/component.tsx", Dialect::Tsx),
+        ] {
+            let source = fs::read(path).unwrap();
+            let (program, errors) = parse_recovering(&source, dialect, SourceType::Module);
+            assert!(errors.is_empty(), "{}: {:?}", path, errors);
+            assert_eq!(
+                program,
+                parse(&source, dialect, SourceType::Module).unwrap()
+            );
+        }
+
+        // The stray `continue` and the imports of the JavaScript fixture are all there is to skip
+        let source = fs::read("src/test/files/javascript-original.js").unwrap();
+        let (program, errors) = parse_recovering(&source, Dialect::JavaScript, SourceType::Script);
+        let lines: Vec<usize> = errors
+            .iter()
+            .filter_map(|error| error.span())
+            .map(|span| span.line)
+            .collect();
+        assert_eq!(lines, vec![128, 138, 139]);
+        let skipped = program
+            .body
+            .iter()
+            .filter(|statement| matches!(statement, Statement::Error(_)))
+            .count();
+        assert_eq!(skipped, 3);
+        assert!(matches!(program.body.last(), Some(Statement::With(_))));
+    }
+
+    #[test]
+    fn test_recovery_at_members() {
+        let source = "class A {\n  a = 1;\n  b( {\n  c() { return 1 }\n  d: number;\n}\ninterface I {\n  e: string;\n  f: ;\n  g: number, h: ?, i: boolean\n}";
+        let (program, errors) =
+            parse_recovering(source.as_bytes(), Dialect::TypeScript, SourceType::Module);
+        assert_eq!(
+            diagnostics(&errors),
+            vec![
+                "Parse Error: Expected , or } at 4:4",
+                "Parse Error: Expected a type at 9:6",
+                "Parse Error: Expected a type at 10:17",
+            ]
+        );
+
+        let Statement::Class(class) = &program.body[0] else {
+            panic!("Expected a class, got {:?}", program.body[0]);
+        };
+        let members = &class.body.body;
+        assert_eq!(members.len(), 3);
+        assert!(matches!(members[0], ClassMember::Property(_)));
+        let ClassMember::Error(span) = members[1] else {
+            panic!("Expected an error, got {:?}", members[1]);
+        };
+        assert_eq!(&source[span.start..span.end], "b( {\n  c() { return 1 }");
+        assert!(matches!(members[2], ClassMember::Property(_)));
+
+        let Statement::TsInterface(interface) = &program.body[1] else {
+            panic!("Expected an interface, got {:?}", program.body[1]);
+        };
+        let names: Vec<Option<String>> = interface
+            .body
+            .body
+            .iter()
+            .map(|member| match member {
+                TsTypeMember::Property(property) => property.key.static_name(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("e".to_string()),
+                None,
+                Some("g".to_string()),
+                None,
+                Some("i".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recovery_at_the_end_and_lexer_errors() {
+        // Every block still open is closed by the end, with one error
+        let source = "class A {\n  a() {\n    if (b) {\n      c();\n";
+        let (program, errors) =
+            parse_recovering(source.as_bytes(), Dialect::JavaScript, SourceType::Module);
+        assert_eq!(
+            diagnostics(&errors),
+            vec!["Parse Error: Unexpected end of input at 5:1"]
+        );
+        let Statement::Class(class) = &program.body[0] else {
+            panic!("Expected a class, got {:?}", program.body[0]);
+        };
+        assert!(matches!(class.body.body[..], [ClassMember::Method(_)]));
+
+        // The lexer reports the bad string, the statement it breaks isn't reported again
+        let source = "let a = 'b\nlet c = 1;\n#!\nlet d = 2;";
+        let (program, errors) =
+            parse_recovering(source.as_bytes(), Dialect::JavaScript, SourceType::Module);
+        assert_eq!(
+            diagnostics(&errors),
+            vec![
+                "Syntax Error: Unterminated string literal at 1:9",
+                "Syntax Error: Expected a name after # at 3:1",
+            ]
+        );
+        let kinds: Vec<bool> = program
+            .body
+            .iter()
+            .map(|statement| matches!(statement, Statement::Error(_)))
+            .collect();
+        assert_eq!(kinds, vec![true, false, true, false]);
+    }
+}
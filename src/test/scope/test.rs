@@ -0,0 +1,364 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse::{
+            ast::SourceType,
+            parser::parse,
+            scope::{Access, BindingKind, ScopeId, ScopeKind, ScopeTree},
+        },
+        read::{interner::Symbol, typescript::Dialect},
+    };
+
+    fn analyze(source: &str, dialect: Dialect, source_type: SourceType) -> ScopeTree {
+        ScopeTree::analyze(&parse(source.as_bytes(), dialect, source_type).unwrap())
+    }
+
+    fn script(source: &str) -> ScopeTree {
+        analyze(source, Dialect::JavaScript, SourceType::Script)
+    }
+
+    fn module(source: &str) -> ScopeTree {
+        analyze(source, Dialect::JavaScript, SourceType::Module)
+    }
+
+    // Names declared in a scope, in declaration order
+    fn declared(tree: &ScopeTree, scope: ScopeId) -> Vec<String> {
+        tree.scope(scope)
+            .bindings
+            .iter()
+            .map(|&binding| tree.binding(binding).name.to_string())
+            .collect()
+    }
+
+    fn kinds(tree: &ScopeTree) -> Vec<ScopeKind> {
+        tree.scopes().map(|(_, scope)| scope.kind).collect()
+    }
+
+    #[test]
+    fn test_hoisting_and_block_scoping() {
+        let tree = script(
+            "if (a) { var b = 1; let c = 2; function d() { var e; } }\n\
+             for (var i = 0; i < 1; i++) {}\n\
+             for (let j of k) {}\n\
+             try {} catch (f) { var g; }",
+        );
+        // var leaves blocks and catch clauses, let and const stay, a block function does both
+        assert_eq!(declared(&tree, tree.top_level()), vec!["b", "i", "g", "d"]);
+        assert_eq!(tree.top_level(), tree.root());
+
+        let block = tree.scope(tree.root()).children[0];
+        assert_eq!(tree.scope(block).kind, ScopeKind::Block);
+        assert_eq!(declared(&tree, block), vec!["c", "d"]);
+        let function = tree.scope(block).children[0];
+        assert_eq!(declared(&tree, function), vec!["e"]);
+
+        use ScopeKind::*;
+        assert_eq!(
+            kinds(&tree),
+            vec![Global, Block, Function, Block, Block, Block, Block, Catch, Block]
+        );
+        let catch = tree
+            .scopes()
+            .find(|(_, scope)| scope.kind == Catch)
+            .unwrap()
+            .0;
+        assert_eq!(declared(&tree, catch), vec!["f"]);
+
+        // `var a; var a;` is one binding, shadowing a var in a function is another
+        let tree = script("var a; var a = 1; function f() { let a; a; }");
+        let a: Vec<_> = tree
+            .bindings()
+            .filter(|(_, binding)| binding.name == "a")
+            .collect();
+        assert_eq!(a.len(), 2);
+        assert_eq!(a[0].1.references.len(), 0);
+        assert_eq!(a[1].1.kind, BindingKind::Let);
+        assert_eq!(a[1].1.references.len(), 1);
+    }
+
+    #[test]
+    fn test_block_functions_in_sloppy_scripts() {
+        let tree = script(
+            "{ function f() {} }\nf();\n\
+             function g() { if (a) { function h() {} } return h; }",
+        );
+        assert_eq!(tree.unresolved().count(), 1);
+        assert_eq!(tree.implicit_globals().count(), 0);
+        assert_eq!(declared(&tree, tree.top_level()), vec!["g", "f"]);
+        let block = tree.scope(tree.root()).children[0];
+        assert_eq!(declared(&tree, block), vec!["f"]);
+        let h = tree
+            .bindings()
+            .filter(|(_, binding)| binding.name == "h")
+            .map(|(_, binding)| tree.scope(binding.scope).kind)
+            .collect::<Vec<_>>();
+        assert_eq!(h, vec![ScopeKind::Block, ScopeKind::Function]);
+
+        // Not where a var of the name would clash or be another name, nor in strict code
+        for source in [
+            "let f;\n{ function f() {} }",
+            "{ let f; { function f() {} } }",
+            "function g(f) { { function f() {} } }",
+            "{ async function f() {} }\n{ function* f() {} }",
+            "\"use strict\";\n{ function f() {} }",
+        ] {
+            let tree = script(source);
+            let f = tree
+                .bindings()
+                .filter(|(_, binding)| binding.name == "f")
+                .filter(|(_, binding)| tree.scope(binding.scope).is_var_scope())
+                .filter(|(_, binding)| binding.kind == BindingKind::Function);
+            assert_eq!(f.count(), 0, "{}", source);
+        }
+        let tree = module("{ function f() {} }\nf();");
+        assert_eq!(declared(&tree, tree.top_level()), Vec::<String>::new());
+        assert_eq!(tree.unresolved().count(), 1);
+    }
+
+    #[test]
+    fn test_parameter_defaults_scope() {
+        // Defaults can't see the vars of the body, closures in them neither
+        let tree = script(
+            "function f(a = b, c = () => d) { var b, d; return a + c; }\n\
+             const g = (h = i) => { var i; };",
+        );
+        let unresolved: Vec<String> = tree
+            .unresolved()
+            .map(|reference| reference.name.to_string())
+            .collect();
+        assert_eq!(unresolved, vec!["b", "d", "i"]);
+
+        use ScopeKind::*;
+        assert_eq!(
+            kinds(&tree),
+            vec![
+                Global,
+                Function,
+                Function,
+                FunctionBody,
+                Function,
+                FunctionBody
+            ]
+        );
+        let body = tree
+            .scopes()
+            .find(|(_, scope)| scope.kind == FunctionBody)
+            .unwrap()
+            .0;
+        assert_eq!(declared(&tree, body), vec!["b", "d"]);
+        assert!(tree.scope(body).is_var_scope());
+
+        // A var redeclaring a parameter is that parameter, without defaults there's one scope
+        let tree = script("function f(a = 1) { var a; return a; }\nfunction g(b) { var c; }");
+        assert_eq!(tree.unused_parameters().len(), 1);
+        assert_eq!(tree.binding(tree.unused_parameters()[0]).name, "b");
+        assert_eq!(kinds(&tree), vec![Global, Function, FunctionBody, Function]);
+    }
+
+    #[test]
+    fn test_module_and_script_top_level() {
+        let tree = module("import x from \"x\";\nexport const a = 1;\nvar b;\nexport { b };");
+        assert_eq!(kinds(&tree), vec![ScopeKind::Global, ScopeKind::Module]);
+        assert_ne!(tree.top_level(), tree.root());
+        assert_eq!(declared(&tree, tree.top_level()), vec!["x", "a", "b"]);
+        assert!(declared(&tree, tree.root()).is_empty());
+        assert!(tree.scope(tree.top_level()).strict);
+
+        let b = tree.lookup(tree.top_level(), Symbol::intern("b")).unwrap();
+        assert_eq!(tree.binding(b).references.len(), 1);
+        assert_eq!(tree.binding(b).kind, BindingKind::Var);
+
+        let tree = script("var a; function f() {}");
+        assert_eq!(kinds(&tree), vec![ScopeKind::Global, ScopeKind::Function]);
+        assert_eq!(declared(&tree, tree.top_level()), vec!["a", "f"]);
+        assert!(!tree.scope(tree.root()).strict);
+    }
+
+    #[test]
+    fn test_references_resolve() {
+        // Functions are called before they are declared, a named expression sees its own name
+        let tree = script(
+            "f();\nfunction f(a) { return a + b; }\nconst g = function h() { h(); };\nc.d = e;",
+        );
+        for (_, reference) in tree.references() {
+            let resolved = reference.binding.is_some();
+            match &*reference.name.resolve() {
+                "f" | "a" | "h" => assert!(resolved, "{} should resolve", reference.name),
+                _ => assert!(!resolved, "{} should be a global", reference.name),
+            }
+        }
+        let unresolved: Vec<String> = tree
+            .unresolved()
+            .map(|reference| reference.name.to_string())
+            .collect();
+        assert_eq!(unresolved, vec!["b", "c", "e"]);
+        // h is bound in the function, not next to g
+        assert_eq!(declared(&tree, tree.top_level()), vec!["f", "g"]);
+
+        // Compound assignments read and write, a destructuring assignment writes
+        let tree = script("let a, b, c;\na += 1;\n[b, c = a] = [];\nb++;");
+        let name = Symbol::intern;
+        let accesses: Vec<(Symbol, Access)> = tree
+            .references()
+            .map(|(_, reference)| (reference.name, reference.access))
+            .collect();
+        assert_eq!(
+            accesses,
+            vec![
+                (name("a"), Access::ReadWrite),
+                (name("b"), Access::Write),
+                (name("c"), Access::Write),
+                (name("a"), Access::Read),
+                (name("b"), Access::ReadWrite),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_implicit_globals() {
+        let tree = script("a = 1;\nvar b; b = 2;\nfunction f() { c++; d; }");
+        let globals: Vec<String> = tree
+            .implicit_globals()
+            .map(|reference| reference.name.to_string())
+            .collect();
+        assert_eq!(globals, vec!["a", "c"]);
+
+        // Strict code throws instead, modules and classes are always strict
+        let tree = script("function f() { \"use strict\"; a = 1; }\nclass A { m() { b = 1; } }");
+        assert_eq!(tree.implicit_globals().count(), 0);
+        assert_eq!(tree.unresolved().count(), 2);
+        assert_eq!(module("a = 1;").implicit_globals().count(), 0);
+        assert_eq!(
+            script("\"use strict\";\na = 1;").implicit_globals().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_with_references_are_dynamic() {
+        let tree = script("var q;\nwith (o) { p = 1; q = 2; (() => r)(); }\ns = 3;");
+        assert_eq!(
+            kinds(&tree),
+            vec![
+                ScopeKind::Global,
+                ScopeKind::With,
+                ScopeKind::Block,
+                ScopeKind::Function
+            ]
+        );
+
+        // `p` may be a property of `o`, only `s` surely makes a global
+        let globals: Vec<String> = tree
+            .implicit_globals()
+            .map(|reference| reference.name.to_string())
+            .collect();
+        assert_eq!(globals, vec!["s"]);
+
+        let dynamic: Vec<(String, bool)> = tree
+            .references()
+            .map(|(_, reference)| (reference.name.to_string(), reference.dynamic))
+            .collect();
+        assert_eq!(
+            dynamic,
+            vec![
+                ("o".to_string(), false),
+                ("p".to_string(), true),
+                ("q".to_string(), true),
+                ("r".to_string(), true),
+                ("s".to_string(), false),
+            ]
+        );
+        // `q` still resolves to the var when `o` has no such property
+        let (_, q) = tree.references().nth(2).unwrap();
+        assert!(q.binding.is_some());
+    }
+
+    #[test]
+    fn test_unused_parameters() {
+        let tree = analyze(
+            "function f(a, { b, c }, d = a, ...e) { c = 1; return d; }\n\
+             class A { constructor(private x, y, z) { this.y = z; } }\n\
+             declare function g(h: number): void;\n\
+             const i = (j, k) => k;",
+            Dialect::TypeScript,
+            SourceType::Module,
+        );
+        let unused: Vec<String> = tree
+            .unused_parameters()
+            .into_iter()
+            .map(|binding| tree.binding(binding).name.to_string())
+            .collect();
+        // c is only written, x is a property and g has no body
+        assert_eq!(unused, vec!["b", "c", "e", "y", "j"]);
+    }
+
+    #[test]
+    fn test_arguments_classes_and_types() {
+        let tree = script(
+            "function f() { return () => arguments; }\n\
+             const A = class B extends C { static { var d; } m() { return B; } };",
+        );
+        let arguments = tree
+            .bindings()
+            .find(|(_, binding)| binding.kind == BindingKind::Arguments)
+            .unwrap()
+            .1;
+        // The arrow has no arguments, f's is used
+        assert_eq!(tree.scope(arguments.scope).kind, ScopeKind::Function);
+        assert_eq!(tree.scope(arguments.scope).parent, Some(tree.root()));
+        assert_eq!(arguments.references.len(), 1);
+
+        let b = tree
+            .bindings()
+            .find(|(_, binding)| binding.name == "B")
+            .unwrap()
+            .1;
+        assert_eq!(tree.scope(b.scope).kind, ScopeKind::Class);
+        assert_eq!(b.references.len(), 1);
+        let d = tree
+            .bindings()
+            .find(|(_, binding)| binding.name == "d")
+            .unwrap()
+            .1;
+        assert_eq!(tree.scope(d.scope).kind, ScopeKind::Function);
+
+        // Types are declared but not walked, enum and namespace names are values
+        let tree = analyze(
+            "interface I { a: T }\ntype U = V;\nenum E { A = x }\nnamespace N.M { var y; }\n\
+             let z: I = <U>w;",
+            Dialect::TypeScript,
+            SourceType::Module,
+        );
+        assert_eq!(
+            declared(&tree, tree.top_level()),
+            vec!["I", "U", "E", "N", "z"]
+        );
+        let unresolved: Vec<String> = tree
+            .unresolved()
+            .map(|reference| reference.name.to_string())
+            .collect();
+        assert_eq!(unresolved, vec!["x", "w"]);
+        let namespace = tree
+            .scopes()
+            .find(|(_, scope)| scope.kind == ScopeKind::TsModule)
+            .unwrap()
+            .0;
+        assert_eq!(declared(&tree, namespace), vec!["y"]);
+    }
+
+    #[test]
+    fn test_jsx_components_are_references() {
+        let tree = analyze(
+            "import { List } from \"./list\";\nconst ui = {};\n\
+             const a = <List><div /><ui.Row /></List>;",
+            Dialect::Jsx,
+            SourceType::Module,
+        );
+        let references: Vec<String> = tree
+            .references()
+            .map(|(_, reference)| reference.name.to_string())
+            .collect();
+        assert_eq!(references, vec!["List", "ui"]);
+        assert_eq!(tree.unresolved().count(), 0);
+    }
+}
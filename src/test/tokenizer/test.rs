@@ -1,8 +1,4 @@
-use std::collections::HashMap;
-
-use crate::read::tokenizer::{self, Identifier, Literal, TokenTrait, ValueType};
-use crate::utils::test_utils;
-use crate::{assert_eq_with_cleanup, assert_with_cleanup};
+use crate::read::tokenizer::{Identifier, Literal};
 
 // Constants for Identifiers
 const BREAK: Identifier = Identifier::BREAK;
@@ -116,23 +112,53 @@ const PERCENT: Literal = Literal::PERCENT;
 mod tests {
     use crate::{
         assert_with_cleanup,
-        read::tokenizer::{
-            self, CharType, Identifier, Literal, SharedToken, Token, TokenTrait, ValueType,
+        error::errors::Errors,
+        read::{
+            interner::Symbol,
+            jsx::JsxToken,
+            number::{NumberBase, NumberValue, NumericLiteral},
+            punctuator::{Associativity, OperatorCategory, Punctuator},
+            read::read_file,
+            regex::RegexLiteral,
+            string::StringLiteral,
+            template::{TemplateLiteral, TemplatePart},
+            tokenizer::{
+                Identifier, Literal, SharedToken, SourceContext, Span, Token, TokenKind, TokenTrait,
+            },
+            tokens,
+            trivia::{Trivia, TriviaKind, TriviaList},
+            typescript::{Dialect, TsKeyword},
         },
         utils::test_utils,
     };
 
+    use std::{fs, path::PathBuf};
+
     use crate::test::tokenizer::test;
 
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        Token::tokenize(source.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .filter(|kind| *kind != TokenKind::EndOfFile)
+            .collect()
+    }
+
+    fn name(value: &str) -> TokenKind {
+        TokenKind::Name {
+            value: Symbol::intern(value),
+            escaped: false,
+        }
+    }
+
     #[test]
     fn test_final() {
         let content = r#"const"#;
 
         let file_path = test_utils::create_temp_js_file(content);
 
-        let buffer: Vec<u8> = file_path.clone().into_bytes();
-
-        let result = tokenizer::Token::tokenize(buffer);
+        let result = read_file(&file_path);
 
         // Print the result for debugging
         match &result {
@@ -150,230 +176,1518 @@ mod tests {
             &file_path
         );
 
+        let tokens = result.unwrap();
+        assert_with_cleanup!(
+            tokens.len() == 2
+                && tokens[0].kind == TokenKind::Identifier(Identifier::CONST)
+                && tokens[1].kind == TokenKind::EndOfFile,
+            "File should contain a single const token\n",
+            &file_path
+        );
+
         test_utils::remove_config_from_path(&file_path);
     }
 
     #[test]
     fn test_get_value() {
-        assert_eq!(test::BREAK.get_value(), ValueType::Str("break"));
-        assert_eq!(test::CASE.get_value(), ValueType::Str("case"));
-        assert_eq!(test::CATCH.get_value(), ValueType::Str("catch"));
-        assert_eq!(test::CLASS.get_value(), ValueType::Str("class"));
-        assert_eq!(test::CONST.get_value(), ValueType::Str("const"));
-        assert_eq!(test::CONTINUE.get_value(), ValueType::Str("continue"));
-        assert_eq!(test::DEBUGGER.get_value(), ValueType::Str("debugger"));
-        assert_eq!(test::DEFAULT.get_value(), ValueType::Str("default"));
-        assert_eq!(test::DELETE.get_value(), ValueType::Str("delete"));
-        assert_eq!(test::DO.get_value(), ValueType::Str("do"));
-        assert_eq!(test::ELSE.get_value(), ValueType::Str("else"));
-        assert_eq!(test::EXPORT.get_value(), ValueType::Str("export"));
-        assert_eq!(test::EXTENDS.get_value(), ValueType::Str("extends"));
-        assert_eq!(test::FALSE.get_value(), ValueType::Str("false"));
-        assert_eq!(test::FINALLY.get_value(), ValueType::Str("finally"));
-        assert_eq!(test::FOR.get_value(), ValueType::Str("for"));
-        assert_eq!(test::FUNCTION.get_value(), ValueType::Str("function"));
-        assert_eq!(test::IF.get_value(), ValueType::Str("if"));
-        assert_eq!(test::IMPORT.get_value(), ValueType::Str("import"));
-        assert_eq!(test::IN.get_value(), ValueType::Str("in"));
-        assert_eq!(test::INSTANCEOF.get_value(), ValueType::Str("instanceof"));
-        assert_eq!(test::NEW.get_value(), ValueType::Str("new"));
-        assert_eq!(test::NULL.get_value(), ValueType::Str("null"));
-        assert_eq!(test::RETURN.get_value(), ValueType::Str("return"));
-        assert_eq!(test::SUPER.get_value(), ValueType::Str("super"));
-        assert_eq!(test::SWITCH.get_value(), ValueType::Str("switch"));
-        assert_eq!(test::THIS.get_value(), ValueType::Str("this"));
-        assert_eq!(test::THROW.get_value(), ValueType::Str("throw"));
-        assert_eq!(test::TRUE.get_value(), ValueType::Str("true"));
-        assert_eq!(test::TRY.get_value(), ValueType::Str("try"));
-        assert_eq!(test::TYPEOF.get_value(), ValueType::Str("typeof"));
-        assert_eq!(test::VAR.get_value(), ValueType::Str("var"));
-        assert_eq!(test::VOID.get_value(), ValueType::Str("void"));
-        assert_eq!(test::WHILE.get_value(), ValueType::Str("while"));
-        assert_eq!(test::WITH.get_value(), ValueType::Str("with"));
-        assert_eq!(test::STRICT_LET.get_value(), ValueType::Str("let"));
-        assert_eq!(test::STRICT_STATIC.get_value(), ValueType::Str("static"));
-        assert_eq!(test::STRICT_YIELD.get_value(), ValueType::Str("yield"));
-        assert_eq!(
-            test::STRICT_IMPLEMENTS.get_value(),
-            ValueType::Str("implements")
-        );
-        assert_eq!(
-            test::STRICT_INTERFACE.get_value(),
-            ValueType::Str("interface")
-        );
-        assert_eq!(test::STRICT_PACKAGE.get_value(), ValueType::Str("package"));
-        assert_eq!(test::STRICT_PRIVATE.get_value(), ValueType::Str("private"));
-        assert_eq!(
-            test::STRICT_PROTECTED.get_value(),
-            ValueType::Str("protected")
-        );
-        assert_eq!(test::STRICT_PUBLIC.get_value(), ValueType::Str("public"));
-        assert_eq!(test::MODULE_ASYNC.get_value(), ValueType::Str("async"));
-        assert_eq!(test::FUTURE_ENUM.get_value(), ValueType::Str("enum"));
-        assert_eq!(
-            test::FUTURE_OLD_ABSTRACT.get_value(),
-            ValueType::Str("abstract")
-        );
-        assert_eq!(
-            test::FUTURE_OLD_BOOLEAN.get_value(),
-            ValueType::Str("boolean")
-        );
-        assert_eq!(test::FUTURE_OLD_BYTE.get_value(), ValueType::Str("byte"));
-        assert_eq!(test::FUTURE_OLD_CHAR.get_value(), ValueType::Str("char"));
-        assert_eq!(
-            test::FUTURE_OLD_DOUBLE.get_value(),
-            ValueType::Str("double")
-        );
-        assert_eq!(test::FUTURE_OLD_FINAL.get_value(), ValueType::Str("final"));
-        assert_eq!(test::FUTURE_OLD_FLOAT.get_value(), ValueType::Str("float"));
-        assert_eq!(test::FUTURE_OLD_GOTO.get_value(), ValueType::Str("goto"));
-        assert_eq!(test::FUTURE_OLD_INT.get_value(), ValueType::Str("int"));
-        assert_eq!(test::FUTURE_OLD_LONG.get_value(), ValueType::Str("long"));
-        assert_eq!(
-            test::FUTURE_OLD_NATIVE.get_value(),
-            ValueType::Str("native")
-        );
-        assert_eq!(test::FUTURE_OLD_SHORT.get_value(), ValueType::Str("short"));
+        assert_eq!(test::BREAK.get_value(), "break".as_bytes());
+        assert_eq!(test::CASE.get_value(), "case".as_bytes());
+        assert_eq!(test::CATCH.get_value(), "catch".as_bytes());
+        assert_eq!(test::CLASS.get_value(), "class".as_bytes());
+        assert_eq!(test::CONST.get_value(), "const".as_bytes());
+        assert_eq!(test::CONTINUE.get_value(), "continue".as_bytes());
+        assert_eq!(test::DEBUGGER.get_value(), "debugger".as_bytes());
+        assert_eq!(test::DEFAULT.get_value(), "default".as_bytes());
+        assert_eq!(test::DELETE.get_value(), "delete".as_bytes());
+        assert_eq!(test::DO.get_value(), "do".as_bytes());
+        assert_eq!(test::ELSE.get_value(), "else".as_bytes());
+        assert_eq!(test::EXPORT.get_value(), "export".as_bytes());
+        assert_eq!(test::EXTENDS.get_value(), "extends".as_bytes());
+        assert_eq!(test::FALSE.get_value(), "false".as_bytes());
+        assert_eq!(test::FINALLY.get_value(), "finally".as_bytes());
+        assert_eq!(test::FOR.get_value(), "for".as_bytes());
+        assert_eq!(test::FUNCTION.get_value(), "function".as_bytes());
+        assert_eq!(test::IF.get_value(), "if".as_bytes());
+        assert_eq!(test::IMPORT.get_value(), "import".as_bytes());
+        assert_eq!(test::IN.get_value(), "in".as_bytes());
+        assert_eq!(test::INSTANCEOF.get_value(), "instanceof".as_bytes());
+        assert_eq!(test::NEW.get_value(), "new".as_bytes());
+        assert_eq!(test::NULL.get_value(), "null".as_bytes());
+        assert_eq!(test::RETURN.get_value(), "return".as_bytes());
+        assert_eq!(test::SUPER.get_value(), "super".as_bytes());
+        assert_eq!(test::SWITCH.get_value(), "switch".as_bytes());
+        assert_eq!(test::THIS.get_value(), "this".as_bytes());
+        assert_eq!(test::THROW.get_value(), "throw".as_bytes());
+        assert_eq!(test::TRUE.get_value(), "true".as_bytes());
+        assert_eq!(test::TRY.get_value(), "try".as_bytes());
+        assert_eq!(test::TYPEOF.get_value(), "typeof".as_bytes());
+        assert_eq!(test::VAR.get_value(), "var".as_bytes());
+        assert_eq!(test::VOID.get_value(), "void".as_bytes());
+        assert_eq!(test::WHILE.get_value(), "while".as_bytes());
+        assert_eq!(test::WITH.get_value(), "with".as_bytes());
+        assert_eq!(test::STRICT_LET.get_value(), "let".as_bytes());
+        assert_eq!(test::STRICT_STATIC.get_value(), "static".as_bytes());
+        assert_eq!(test::STRICT_YIELD.get_value(), "yield".as_bytes());
+        assert_eq!(test::STRICT_IMPLEMENTS.get_value(), "implements".as_bytes());
+        assert_eq!(test::STRICT_INTERFACE.get_value(), "interface".as_bytes());
+        assert_eq!(test::STRICT_PACKAGE.get_value(), "package".as_bytes());
+        assert_eq!(test::STRICT_PRIVATE.get_value(), "private".as_bytes());
+        assert_eq!(test::STRICT_PROTECTED.get_value(), "protected".as_bytes());
+        assert_eq!(test::STRICT_PUBLIC.get_value(), "public".as_bytes());
+        assert_eq!(test::MODULE_ASYNC.get_value(), "await".as_bytes());
+        assert_eq!(test::FUTURE_ENUM.get_value(), "enum".as_bytes());
+        assert_eq!(test::FUTURE_OLD_ABSTRACT.get_value(), "abstract".as_bytes());
+        assert_eq!(test::FUTURE_OLD_BOOLEAN.get_value(), "boolean".as_bytes());
+        assert_eq!(test::FUTURE_OLD_BYTE.get_value(), "byte".as_bytes());
+        assert_eq!(test::FUTURE_OLD_CHAR.get_value(), "char".as_bytes());
+        assert_eq!(test::FUTURE_OLD_DOUBLE.get_value(), "double".as_bytes());
+        assert_eq!(test::FUTURE_OLD_FINAL.get_value(), "final".as_bytes());
+        assert_eq!(test::FUTURE_OLD_FLOAT.get_value(), "float".as_bytes());
+        assert_eq!(test::FUTURE_OLD_GOTO.get_value(), "goto".as_bytes());
+        assert_eq!(test::FUTURE_OLD_INT.get_value(), "int".as_bytes());
+        assert_eq!(test::FUTURE_OLD_LONG.get_value(), "long".as_bytes());
+        assert_eq!(test::FUTURE_OLD_NATIVE.get_value(), "native".as_bytes());
+        assert_eq!(test::FUTURE_OLD_SHORT.get_value(), "short".as_bytes());
         assert_eq!(
             test::FUTURE_OLD_SYNCHRONIZED.get_value(),
-            ValueType::Str("synchronized")
+            "synchronized".as_bytes()
         );
+        assert_eq!(test::FUTURE_OLD_THROWS.get_value(), "throws".as_bytes());
         assert_eq!(
-            test::FUTURE_OLD_THROWS.get_value(),
-            ValueType::Str("throws")
+            test::FUTURE_OLD_TRANSIENT.get_value(),
+            "transient".as_bytes()
         );
+        assert_eq!(test::FUTURE_OLD_VOLATILE.get_value(), "volatile".as_bytes());
         assert_eq!(
-            test::FUTURE_OLD_TRANSIENT.get_value(),
-            ValueType::Str("transient")
+            test::SPECIAL_STRICT_ARGUMENTS.get_value(),
+            "arguments".as_bytes()
         );
+        assert_eq!(test::SPECIAL_AS.get_value(), "as".as_bytes());
+        assert_eq!(test::SPECIAL_STRICT_EVAL.get_value(), "eval".as_bytes());
+        assert_eq!(test::SPECIAL_FROM.get_value(), "from".as_bytes());
+        assert_eq!(test::SPECIAL_GET.get_value(), "get".as_bytes());
+        assert_eq!(test::SPECIAL_OF.get_value(), "of".as_bytes());
+        assert_eq!(test::SPECIAL_SET.get_value(), "set".as_bytes());
+
+        assert_eq!(test::BACKSLASH.get_value(), &[0x5c]);
+        assert_eq!(test::CARRIAGE_RETURN.get_value(), &[0x0D]);
+        assert_eq!(test::LINE_FEED.get_value(), &[0x0A]);
+        assert_eq!(test::SINGLE_QUOTE.get_value(), &[0x27]);
+        assert_eq!(test::DOUBLE_QUOTE.get_value(), &[0x22]);
+        assert_eq!(test::BACK_TICK.get_value(), &[0x60]);
+        assert_eq!(test::PLUS.get_value(), &[0x2B]);
+        assert_eq!(test::MINUS.get_value(), &[0x2D]);
+        assert_eq!(test::EQUAL.get_value(), &[0x3D]);
+        assert_eq!(test::ASTERISK.get_value(), &[0x2A]);
+        assert_eq!(test::SLASH.get_value(), &[0x2F]);
+        assert_eq!(test::GREATER_THAN.get_value(), &[0x3E]);
+        assert_eq!(test::LESS_THAN.get_value(), &[0x3C]);
+        assert_eq!(test::AMPERSAND.get_value(), &[0x26]);
+        assert_eq!(test::SPACE.get_value(), &[0x20]);
+        assert_eq!(test::TAB.get_value(), &[0x09]);
+        assert_eq!(test::COMMA.get_value(), &[0x2C]);
+        assert_eq!(test::PERIOD.get_value(), &[0x2E]);
+        assert_eq!(test::SEMICOLON.get_value(), &[0x3B]);
+        assert_eq!(test::COLON.get_value(), &[0x3A]);
+        assert_eq!(test::EXCLAMATION_MARK.get_value(), &[0x21]);
+        assert_eq!(test::QUESTION_MARK.get_value(), &[0x3F]);
+        assert_eq!(test::PIPE.get_value(), &[0x7C]);
+        assert_eq!(test::CARET.get_value(), &[0x5E]);
+        assert_eq!(test::OPEN_BRACKET_LEFT.get_value(), &[0x28]);
+        assert_eq!(test::OPEN_BRACKET_RIGHT.get_value(), &[0x29]);
+        assert_eq!(test::SQUARE_BRACKET_LEFT.get_value(), &[0x5B]);
+        assert_eq!(test::SQUARE_BRACKET_RIGHT.get_value(), &[0x5D]);
+        assert_eq!(test::CURLY_BRACKET_LEFT.get_value(), &[0x7B]);
+        assert_eq!(test::CURLY_BRACKET_RIGHT.get_value(), &[0x7D]);
+        assert_eq!(test::TILDE.get_value(), &[0x7E]);
+        assert_eq!(test::DOLLAR.get_value(), &[0x24]);
+        assert_eq!(test::AT.get_value(), &[0x40]);
+        assert_eq!(test::UNDERSCORE.get_value(), &[0x5F]);
+        assert_eq!(test::PERCENT.get_value(), &[0x25]);
+    }
+
+    #[test]
+    fn test_identifier_from_bytes() {
+        let words = [
+            test::BREAK,
+            test::CASE,
+            test::CATCH,
+            test::CLASS,
+            test::CONST,
+            test::CONTINUE,
+            test::DEBUGGER,
+            test::DEFAULT,
+            test::DELETE,
+            test::DO,
+            test::ELSE,
+            test::EXPORT,
+            test::EXTENDS,
+            test::FALSE,
+            test::FINALLY,
+            test::FOR,
+            test::FUNCTION,
+            test::IF,
+            test::IMPORT,
+            test::IN,
+            test::INSTANCEOF,
+            test::NEW,
+            test::NULL,
+            test::RETURN,
+            test::SUPER,
+            test::SWITCH,
+            test::THIS,
+            test::THROW,
+            test::TRUE,
+            test::TRY,
+            test::TYPEOF,
+            test::VAR,
+            test::VOID,
+            test::WHILE,
+            test::WITH,
+            test::STRICT_LET,
+            test::STRICT_STATIC,
+            test::STRICT_YIELD,
+            test::STRICT_IMPLEMENTS,
+            test::STRICT_INTERFACE,
+            test::STRICT_PACKAGE,
+            test::STRICT_PRIVATE,
+            test::STRICT_PROTECTED,
+            test::STRICT_PUBLIC,
+            test::MODULE_ASYNC,
+            test::FUTURE_ENUM,
+            test::FUTURE_OLD_ABSTRACT,
+            test::FUTURE_OLD_BOOLEAN,
+            test::FUTURE_OLD_BYTE,
+            test::FUTURE_OLD_CHAR,
+            test::FUTURE_OLD_DOUBLE,
+            test::FUTURE_OLD_FINAL,
+            test::FUTURE_OLD_FLOAT,
+            test::FUTURE_OLD_GOTO,
+            test::FUTURE_OLD_INT,
+            test::FUTURE_OLD_LONG,
+            test::FUTURE_OLD_NATIVE,
+            test::FUTURE_OLD_SHORT,
+            test::FUTURE_OLD_SYNCHRONIZED,
+            test::FUTURE_OLD_THROWS,
+            test::FUTURE_OLD_TRANSIENT,
+            test::FUTURE_OLD_VOLATILE,
+            test::SPECIAL_STRICT_ARGUMENTS,
+            test::SPECIAL_AS,
+            test::SPECIAL_STRICT_EVAL,
+            test::SPECIAL_FROM,
+            test::SPECIAL_GET,
+            test::SPECIAL_OF,
+            test::SPECIAL_SET,
+        ];
+        for word in words {
+            assert_eq!(Identifier::from_bytes(word.get_value()), Some(word));
+        }
+
+        // Prefixes, extensions and near misses that share a slot
+        for bytes in [
+            &b""[..],
+            b"d",
+            b"de",
+            b"defaul",
+            b"defaults",
+            b"Default",
+            b"synchronizedd",
+            b"wxile",
+        ] {
+            assert_eq!(Identifier::from_bytes(bytes), None, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn test_keyword_table() {
+        assert_eq!(Identifier::ALL.len(), 69);
+        for identifier in Identifier::ALL {
+            assert_eq!(
+                Identifier::from_bytes(identifier.get_value()),
+                Some(*identifier)
+            );
+            assert_eq!(identifier.to_string().as_bytes(), identifier.get_value());
+        }
+        assert_eq!(TsKeyword::ALL.len(), 16);
+        for keyword in TsKeyword::ALL {
+            assert_eq!(TsKeyword::from_bytes(keyword.get_value()), Some(*keyword));
+        }
+        assert_eq!(TsKeyword::from_bytes(b"types"), None);
+        assert_eq!(TsKeyword::from_bytes(b"break"), None);
+        for literal in Literal::ALL {
+            assert_eq!(Literal::from_byte(literal.as_byte()), Some(*literal));
+        }
+        assert_eq!(Literal::from_byte(b'a'), None);
+        assert_eq!(Literal::CURLY_BRACKET_LEFT.to_string(), "{");
+
+        // The old string constants drifted from the enum, both come from the table now
+        assert_eq!(tokens::WHILE, "while");
+        assert_eq!(tokens::MODULE_ASYNC, "await");
+        assert_eq!(tokens::LITERAL_PERCENT, b'%');
         assert_eq!(
-            test::FUTURE_OLD_VOLATILE.get_value(),
-            ValueType::Str("volatile")
+            Token::tokenize(b"while").unwrap()[0].kind,
+            TokenKind::Identifier(Identifier::WHILE)
         );
+
+        assert!(Identifier::STRICT_LET.is_strict_reserved());
+        assert!(!Identifier::WHILE.is_strict_reserved());
+        assert!(Identifier::FUTURE_ENUM.is_future_reserved());
+        assert!(Identifier::FUTURE_OLD_GOTO.is_future_reserved());
+        assert!(!Identifier::MODULE_ASYNC.is_future_reserved());
+        assert!(Identifier::SPECIAL_OF.is_contextual());
+        assert!(!Identifier::STRICT_YIELD.is_contextual());
+    }
+
+    #[test]
+    fn test_tokenize_spans() {
+        let source = "const a = b;\n  return a";
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
+
+        assert_eq!(tokens.len(), 8);
+        assert_eq!(tokens[0].kind, TokenKind::Identifier(Identifier::CONST));
         assert_eq!(
-            test::SPECIAL_STRICT_ARGUMENTS.get_value(),
-            ValueType::Str("arguments")
+            tokens[0].span,
+            Span {
+                start: 0,
+                end: 5,
+                line: 1,
+                column: 0
+            }
         );
-        assert_eq!(test::SPECIAL_AS.get_value(), ValueType::Str("as"));
+        assert_eq!(tokens[1].kind, name("a"));
+        assert_eq!(tokens[1].text(source.as_bytes()), b"a");
+        assert_eq!(tokens[2].kind, TokenKind::Literal(Literal::EQUAL));
+        assert_eq!(tokens[4].kind, TokenKind::Literal(Literal::SEMICOLON));
+        assert!(!tokens[4].newline_before);
+
+        assert_eq!(tokens[5].kind, TokenKind::Identifier(Identifier::RETURN));
+        assert!(tokens[5].newline_before);
         assert_eq!(
-            test::SPECIAL_STRICT_EVAL.get_value(),
-            ValueType::Str("eval")
+            tokens[5].span,
+            Span {
+                start: 15,
+                end: 21,
+                line: 2,
+                column: 2
+            }
         );
-        assert_eq!(test::SPECIAL_FROM.get_value(), ValueType::Str("from"));
-        assert_eq!(test::SPECIAL_GET.get_value(), ValueType::Str("get"));
-        assert_eq!(test::SPECIAL_OF.get_value(), ValueType::Str("of"));
-        assert_eq!(test::SPECIAL_SET.get_value(), ValueType::Str("set"));
+    }
 
-        assert_eq!(test::BACKSLASH.get_value(), ValueType::U8(0x5c));
-        assert_eq!(test::CARRIAGE_RETURN.get_value(), ValueType::U8(0x0D));
-        assert_eq!(test::LINE_FEED.get_value(), ValueType::U8(0x0A));
-        assert_eq!(test::SINGLE_QUOTE.get_value(), ValueType::U8(0x27));
-        assert_eq!(test::DOUBLE_QUOTE.get_value(), ValueType::U8(0x22));
-        assert_eq!(test::BACK_TICK.get_value(), ValueType::U8(0x60));
-        assert_eq!(test::PLUS.get_value(), ValueType::U8(0x2B));
-        assert_eq!(test::MINUS.get_value(), ValueType::U8(0x2D));
-        assert_eq!(test::EQUAL.get_value(), ValueType::U8(0x3D));
-        assert_eq!(test::ASTERISK.get_value(), ValueType::U8(0x2A));
-        assert_eq!(test::SLASH.get_value(), ValueType::U8(0x2F));
-        assert_eq!(test::GREATER_THAN.get_value(), ValueType::U8(0x3E));
-        assert_eq!(test::LESS_THAN.get_value(), ValueType::U8(0x3C));
-        assert_eq!(test::AMPERSAND.get_value(), ValueType::U8(0x26));
-        assert_eq!(test::SPACE.get_value(), ValueType::U8(0x20));
-        assert_eq!(test::TAB.get_value(), ValueType::U8(0x09));
-        assert_eq!(test::COMMA.get_value(), ValueType::U8(0x2C));
-        assert_eq!(test::PERIOD.get_value(), ValueType::U8(0x2E));
-        assert_eq!(test::SEMICOLON.get_value(), ValueType::U8(0x3B));
-        assert_eq!(test::COLON.get_value(), ValueType::U8(0x3A));
-        assert_eq!(test::EXCLAMATION_MARK.get_value(), ValueType::U8(0x21));
-        assert_eq!(test::QUESTION_MARK.get_value(), ValueType::U8(0x3F));
-        assert_eq!(test::PIPE.get_value(), ValueType::U8(0x7C));
-        assert_eq!(test::CARET.get_value(), ValueType::U8(0x5E));
-        assert_eq!(test::OPEN_BRACKET_LEFT.get_value(), ValueType::U8(0x28));
-        assert_eq!(test::OPEN_BRACKET_RIGHT.get_value(), ValueType::U8(0x29));
-        assert_eq!(test::SQUARE_BRACKET_LEFT.get_value(), ValueType::U8(0x5B));
-        assert_eq!(test::SQUARE_BRACKET_RIGHT.get_value(), ValueType::U8(0x5D));
-        assert_eq!(test::CURLY_BRACKET_LEFT.get_value(), ValueType::U8(0x7B));
-        assert_eq!(test::CURLY_BRACKET_RIGHT.get_value(), ValueType::U8(0x7D));
-        assert_eq!(test::TILDE.get_value(), ValueType::U8(0x7E));
-        assert_eq!(test::DOLLAR.get_value(), ValueType::U8(0x24));
-        assert_eq!(test::AT.get_value(), ValueType::U8(0x40));
-        assert_eq!(test::UNDERSCORE.get_value(), ValueType::U8(0x5F));
-        assert_eq!(test::PERCENT.get_value(), ValueType::U8(0x25));
+    #[test]
+    fn test_tokenize_keywords_need_exact_match() {
+        assert_eq!(
+            kinds("in instanceof inside do double"),
+            vec![
+                TokenKind::Identifier(Identifier::IN),
+                TokenKind::Identifier(Identifier::INSTANCEOF),
+                name("inside"),
+                TokenKind::Identifier(Identifier::DO),
+                TokenKind::Identifier(Identifier::FUTURE_OLD_DOUBLE),
+            ]
+        );
     }
 
     #[test]
-    fn test_to_string() {
-        let token = Token::Identifier(Identifier::CASE);
-        assert_eq!(token.to_string(), "case");
+    fn test_tokenize_skips_comments_and_line_terminators() {
+        let source = "a // class\r\n/* function\u{2028} */ b\u{2029}c";
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
 
-        let token_u8 = Token::Literal(Literal::ASTERISK);
-        assert_eq!(token_u8.to_string(), "42");
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[0].kind, name("a"));
+        assert_eq!(tokens[1].span.line, 3);
+        assert!(tokens[1].newline_before);
+        assert_eq!(tokens[2].span.line, 4);
+        assert_eq!(tokens[2].span.column, 0);
     }
 
     #[test]
-    fn test_get_next_possible_char_in_token_based_on_index() {
-        let token_default = Token::Identifier(Identifier::DEFAULT);
+    fn test_tokenize_errors_are_located() {
+        let error = Token::tokenize(b"a\n  /* never closed").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Syntax Error: Unterminated comment at 2:3"
+        );
+
+        let error = Token::tokenize(b"a \x01").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Syntax Error: Unexpected character at 1:3"
+        );
+    }
+
+    #[test]
+    fn test_tokenize_names() {
+        assert_eq!(
+            kinds("fetchUser $el _private café ℮x a\u{200D}b"),
+            vec![
+                name("fetchUser"),
+                name("$el"),
+                name("_private"),
+                name("café"),
+                name("℮x"),
+                name("a\u{200D}b"),
+            ]
+        );
+
+        // Escaped names are decoded and never become reserved words
+        assert_eq!(
+            kinds(r"\u0061wait \u{63}lass"),
+            vec![
+                TokenKind::Name {
+                    value: Symbol::intern("await"),
+                    escaped: true
+                },
+                TokenKind::Name {
+                    value: Symbol::intern("class"),
+                    escaped: true
+                },
+            ]
+        );
+
+        assert!(Token::tokenize(r"a\u{1F600}".as_bytes()).is_err());
+        assert!(Token::tokenize(r"\u00".as_bytes()).is_err());
+        assert!(Token::tokenize(r"a\x41".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_is_reserved_by_context() {
+        let contexts = [
+            SourceContext::Sloppy,
+            SourceContext::Strict,
+            SourceContext::Module,
+        ];
+        let reserved =
+            |identifier: Identifier| contexts.map(|context| identifier.is_reserved(context));
+
+        assert_eq!(reserved(Identifier::CLASS), [true, true, true]);
+        assert_eq!(reserved(Identifier::FUTURE_ENUM), [true, true, true]);
+        assert_eq!(reserved(Identifier::STRICT_LET), [false, true, true]);
+        assert_eq!(reserved(Identifier::STRICT_YIELD), [false, true, true]);
+        assert_eq!(reserved(Identifier::STRICT_INTERFACE), [false, true, true]);
+        assert_eq!(reserved(Identifier::MODULE_ASYNC), [false, false, true]);
+        assert_eq!(reserved(Identifier::FUTURE_OLD_INT), [false, false, false]);
+        assert_eq!(reserved(Identifier::SPECIAL_OF), [false, false, false]);
+        assert_eq!(
+            reserved(Identifier::SPECIAL_STRICT_EVAL),
+            [false, false, false]
+        );
+
+        let escaped_await = &kinds(r"\u0061wait")[0];
+        assert!(!escaped_await.is_reserved(SourceContext::Strict));
+        assert!(escaped_await.is_reserved(SourceContext::Module));
+        assert!(!name("fetchUser").is_reserved(SourceContext::Module));
+    }
 
+    #[test]
+    fn test_detect_context() {
+        assert_eq!(
+            SourceContext::detect(b"let x = 1;", false),
+            SourceContext::Sloppy
+        );
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(0, &token_default),
-            Some(CharType::Char('d'))
+            SourceContext::detect(b"let x = 1;", true),
+            SourceContext::Module
         );
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(1, &token_default),
-            Some(CharType::Char('e'))
+            SourceContext::detect(b"#!/usr/bin/env node\n", true),
+            SourceContext::Module
         );
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(2, &token_default),
-            Some(CharType::Char('f'))
+            SourceContext::detect(b"// header\n'use strict';\nlet x;", false),
+            SourceContext::Strict
         );
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(3, &token_default),
-            Some(CharType::Char('a'))
+            SourceContext::detect(b"\"use asm\"\n/* */ \"use strict\"", false),
+            SourceContext::Strict
+        );
+
+        // Only an exact directive inside the prologue counts
+        assert_eq!(
+            SourceContext::detect(b"'use\\x20strict'; let x;", false),
+            SourceContext::Sloppy
         );
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(4, &token_default),
-            Some(CharType::Char('u'))
+            SourceContext::detect(b"'a' + b; 'use strict';", false),
+            SourceContext::Sloppy
+        );
+        assert_eq!(
+            SourceContext::detect(b"foo(); 'use strict';", false),
+            SourceContext::Sloppy
+        );
+    }
+
+    fn number(source: &str) -> NumericLiteral {
+        match Token::tokenize(source.as_bytes()).unwrap().remove(0).kind {
+            TokenKind::Number(literal) => literal,
+            kind => panic!("{} lexed as {:?}", source, kind),
+        }
+    }
+
+    #[test]
+    fn test_tokenize_numbers() {
+        let cases = [
+            ("0", 0.0),
+            ("42", 42.0),
+            ("1_000_000", 1_000_000.0),
+            ("3.25", 3.25),
+            (".5", 0.5),
+            ("5.", 5.0),
+            ("1e3", 1000.0),
+            ("2.5E-2", 0.025),
+            ("1_0.0_1e+1_0", 10.01e10),
+            ("0xFF", 255.0),
+            ("0XdEaD_bEeF", 3_735_928_559.0),
+            ("0o17", 15.0),
+            ("0b1010_1010", 170.0),
+        ];
+        for (source, expected) in cases {
+            let literal = number(source);
+            assert_eq!(literal.value, NumberValue::Number(expected), "{}", source);
+            assert!(!literal.legacy, "{}", source);
+        }
+
+        assert_eq!(number("0x1F").base, NumberBase::Hexadecimal);
+        assert_eq!(number("0b1").base, NumberBase::Binary);
+
+        let legacy = number("017");
+        assert_eq!(legacy.value, NumberValue::Number(15.0));
+        assert_eq!(legacy.base, NumberBase::Octal);
+        assert!(legacy.legacy);
+
+        let legacy = number("089.5");
+        assert_eq!(legacy.value, NumberValue::Number(89.5));
+        assert_eq!(legacy.base, NumberBase::Decimal);
+        assert!(legacy.legacy);
+
+        assert_eq!(number("0n").value, NumberValue::BigInt("0".to_string()));
+        assert_eq!(number("123n").value, NumberValue::BigInt("123".to_string()));
+        assert_eq!(
+            number("0xFFFF_FFFF_FFFF_FFFF_FFFFn").value,
+            NumberValue::BigInt("1208925819614629174706175".to_string())
         );
+        assert_eq!(number("0b11n").value, NumberValue::BigInt("3".to_string()));
+
+        // Member access after an integer still works
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(5, &token_default),
-            Some(CharType::Char('l'))
+            kinds("1..toString"),
+            vec![
+                TokenKind::Number(number("1.")),
+                TokenKind::Literal(Literal::PERIOD),
+                name("toString"),
+            ]
         );
+    }
+
+    #[test]
+    fn test_tokenize_number_errors() {
+        let cases = [
+            ("1__0", "Numeric separators are not allowed here"),
+            ("1_", "Numeric separators are not allowed here"),
+            ("1_.5", "Numeric separators are not allowed here"),
+            ("1._5", "Numeric separators are not allowed here"),
+            ("0_1", "Numeric separators are not allowed here"),
+            ("0x_1", "Numeric separators are not allowed here"),
+            ("017_1", "Numeric separators are not allowed here"),
+            ("0b2", "Expected binary digits after 0b"),
+            ("0x", "Expected hexadecimal digits after 0x"),
+            ("0o8", "Expected octal digits after 0o"),
+            ("1e", "Missing exponent in numeric literal"),
+            ("1.5n", "Invalid BigInt literal"),
+            ("1e3n", "Invalid BigInt literal"),
+            ("017n", "BigInt literals can't have a leading zero"),
+            (
+                "0b12",
+                "Identifier or digit starts immediately after numeric literal",
+            ),
+            (
+                "3in x",
+                "Identifier or digit starts immediately after numeric literal",
+            ),
+        ];
+
+        for (source, message) in cases {
+            match Token::tokenize(format!("let a =\n  {}", source).as_bytes()) {
+                Err(Errors::TokenError(error, span)) => {
+                    assert_eq!(error, message, "{}", source);
+                    assert_eq!(
+                        (span.line, span.column, span.start),
+                        (2, 2, 10),
+                        "{}",
+                        source
+                    );
+                }
+                result => panic!("{} should fail, got {:?}", source, result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_number_to_string() {
+        let cases = [
+            ("30_000", "30000"),
+            ("0.1", "0.1"),
+            ("1.50", "1.5"),
+            ("1e21", "1e+21"),
+            ("123456789012345678901", "123456789012345680000"),
+            ("0.000001", "0.000001"),
+            ("1e-7", "1e-7"),
+            ("1.5e-10", "1.5e-10"),
+            ("0x10", "16"),
+            ("0.0", "0"),
+            ("10n", "10n"),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(number(source).value.to_string(), expected, "{}", source);
+        }
+    }
+
+    fn string(source: &str) -> StringLiteral {
+        match Token::tokenize(source.as_bytes()).unwrap().remove(0).kind {
+            TokenKind::String(literal) => literal,
+            kind => panic!("{} lexed as {:?}", source, kind),
+        }
+    }
+
+    #[test]
+    fn test_tokenize_strings() {
+        let cases = [
+            (r#"'it\'s'"#, "it's"),
+            (r#""say \"hi\"""#, "say \"hi\""),
+            (r#"'"' "#, "\""),
+            (r#""it's""#, "it's"),
+            (r"'a\nb\tc\r\b\f\v\0'", "a\nb\tc\r\u{08}\u{0C}\u{0B}\0"),
+            (r"'\x41\x7a'", "Az"),
+            (r"'A\u{1F600}\u{000041}'", "A😀A"),
+            (r"'😀'", "😀"),
+            (r"'\uD83D'", "\u{FFFD}"),
+            (r"'\q\\'", "q\\"),
+            (
+                "'line \\\ncontinued \\\r\nand \\\u{2028}done'",
+                "line continued and done",
+            ),
+            ("'\u{2028}'", "\u{2028}"),
+            ("'caf\u{e9}'", "café"),
+        ];
+        for (source, expected) in cases {
+            let literal = string(source);
+            assert_eq!(literal.value, expected, "{}", source);
+            assert!(!literal.legacy_octal, "{}", source);
+        }
+
+        let legacy = string(r"'\101\08\8\400'");
+        assert_eq!(legacy.value, "A\u{0}88\u{20}0");
+        assert!(legacy.legacy_octal);
+
+        // The raw text is the token span, quotes included
+        let source = r#"call('it\'s', "x")"#;
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
+        assert_eq!(tokens[2].text(source.as_bytes()), br"'it\'s'");
+        assert_eq!(tokens[3].kind, TokenKind::Literal(Literal::COMMA));
+        assert_eq!(tokens[4].text(source.as_bytes()), br#""x""#);
+    }
+
+    #[test]
+    fn test_tokenize_strings_keep_declarations_after_apostrophes() {
+        let source = "const note = \"don't\";\nfunction after() {}";
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
+
+        assert_eq!(tokens[5].kind, TokenKind::Identifier(Identifier::FUNCTION));
+        assert_eq!((tokens[5].span.line, tokens[5].span.column), (2, 0));
+        assert!(tokens[5].newline_before);
+    }
+
+    #[test]
+    fn test_tokenize_string_errors() {
+        let cases = [
+            ("x = 'open", "Unterminated string literal", (1, 4)),
+            ("x = 'open\n'", "Unterminated string literal", (1, 4)),
+            ("x = \"a\\\nb\\\nc", "Unterminated string literal", (1, 4)),
+            (
+                "x = 'a\\\n \\x4'",
+                "Invalid hexadecimal escape sequence",
+                (2, 1),
+            ),
+            (
+                "x = '\\u{110000}'",
+                "Invalid Unicode escape sequence",
+                (1, 5),
+            ),
+            ("x = '\\u12'", "Invalid Unicode escape sequence", (1, 5)),
+        ];
+
+        for (source, message, location) in cases {
+            match Token::tokenize(source.as_bytes()) {
+                Err(Errors::TokenError(error, span)) => {
+                    assert_eq!(error, message, "{}", source);
+                    assert_eq!((span.line, span.column), location, "{}", source);
+                }
+                result => panic!("{} should fail, got {:?}", source, result),
+            }
+        }
+
+        // A line continuation inside a string isn't a line break before the next token
+        let tokens = Token::tokenize(b"'a\\\nb' c").unwrap();
+        assert!(!tokens[0].newline_before);
+        assert!(!tokens[1].newline_before);
+        assert_eq!(tokens[1].span.line, 2);
+    }
+
+    fn template(part: TemplatePart, cooked: &str, raw: &str) -> TokenKind {
+        TokenKind::Template(TemplateLiteral {
+            part,
+            cooked: Some(cooked.to_string()),
+            raw: raw.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_tokenize_templates() {
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(6, &token_default),
-            Some(CharType::Char('t'))
+            kinds(r"`plain \n text`"),
+            vec![template(
+                TemplatePart::NoSubstitution,
+                "plain \n text",
+                r"plain \n text"
+            )]
         );
+
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(7, &token_default),
-            None
+            kinds("`a${b}c${d}e`"),
+            vec![
+                template(TemplatePart::Head, "a", "a"),
+                name("b"),
+                template(TemplatePart::Middle, "c", "c"),
+                name("d"),
+                template(TemplatePart::Tail, "e", "e"),
+            ]
         );
+
+        // Object literals and nested templates inside a substitution
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(100, &token_default),
-            None
+            kinds("`x${ {a: `in${1}`}.a }y`"),
+            vec![
+                template(TemplatePart::Head, "x", "x"),
+                TokenKind::Literal(Literal::CURLY_BRACKET_LEFT),
+                name("a"),
+                TokenKind::Literal(Literal::COLON),
+                template(TemplatePart::Head, "in", "in"),
+                TokenKind::Number(number("1")),
+                template(TemplatePart::Tail, "", ""),
+                TokenKind::Literal(Literal::CURLY_BRACKET_RIGHT),
+                TokenKind::Literal(Literal::PERIOD),
+                name("a"),
+                template(TemplatePart::Tail, "y", "y"),
+            ]
         );
 
-        let token_carriage_return = Token::Literal(Literal::CARRIAGE_RETURN);
+        // CRLF is LF in both values, a line continuation only survives in raw
+        assert_eq!(
+            kinds("`a\r\nb\\\r\nc`"),
+            vec![template(TemplatePart::NoSubstitution, "a\nbc", "a\nb\\\nc")]
+        );
 
+        // Invalid escapes are only an error for untagged templates, the lexer drops the cooked value
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(0, &token_carriage_return),
-            Some(CharType::U8(0x0D))
+            kinds(r"tag`\unicode and \1`"),
+            vec![
+                name("tag"),
+                TokenKind::Template(TemplateLiteral {
+                    part: TemplatePart::NoSubstitution,
+                    cooked: None,
+                    raw: r"\unicode and \1".to_string(),
+                }),
+            ]
         );
+    }
+
+    #[test]
+    fn test_tokenize_templates_keep_braces_balanced() {
+        let source = "class A {\n  m() { return `${ {a: '}'} }`; }\n}\nfunction after() {}";
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
+
+        let mut depth = 0;
+        let mut class_body_end = None;
+        for (index, token) in tokens.iter().enumerate() {
+            match token.kind {
+                TokenKind::Literal(Literal::CURLY_BRACKET_LEFT) => depth += 1,
+                TokenKind::Literal(Literal::CURLY_BRACKET_RIGHT) => {
+                    depth -= 1;
+                    if depth == 0 && class_body_end.is_none() {
+                        class_body_end = Some(index);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let class_body_end = class_body_end.unwrap();
+        assert_eq!(tokens[class_body_end].span.line, 3);
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(1, &token_carriage_return),
-            None
+            tokens[class_body_end + 1].kind,
+            TokenKind::Identifier(Identifier::FUNCTION)
         );
+        assert_eq!(depth, 0);
+    }
+
+    #[test]
+    fn test_tokenize_template_errors() {
+        match Token::tokenize(b"let a = `\n${b}\n") {
+            Err(Errors::TokenError(error, span)) => {
+                assert_eq!(error, "Unterminated template literal");
+                assert_eq!((span.line, span.column), (2, 3));
+            }
+            result => panic!("should fail, got {:?}", result),
+        }
+
+        match Token::tokenize(b"let a = `open") {
+            Err(Errors::TokenError(error, span)) => {
+                assert_eq!(error, "Unterminated template literal");
+                assert_eq!((span.line, span.column), (1, 8));
+            }
+            result => panic!("should fail, got {:?}", result),
+        }
+    }
+
+    fn regex(body: &str, flags: &str) -> TokenKind {
+        TokenKind::Regex(RegexLiteral {
+            body: body.to_string(),
+            flags: flags.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_tokenize_regex() {
         assert_eq!(
-            Token::get_next_possible_char_in_token_based_on_index(20, &token_carriage_return),
-            None
+            kinds("const re = /[}{]/g;"),
+            vec![
+                TokenKind::Identifier(Identifier::CONST),
+                name("re"),
+                TokenKind::Literal(Literal::EQUAL),
+                regex("[}{]", "g"),
+                TokenKind::Literal(Literal::SEMICOLON),
+            ]
         );
+
+        // A `/` inside a class or escaped doesn't end the body
+        assert_eq!(kinds(r"x(/[/]\/a/dgimsy)")[2], regex(r"[/]\/a", "dgimsy"));
+        assert_eq!(kinds("/=/v")[0], regex("=", "v"));
+
+        let regex_after = [
+            "return /a/",
+            "typeof /a/",
+            "(/a/)",
+            "[1, /a/]",
+            "x = /a/",
+            "!/a/",
+            "a ? /a/ : /b/",
+            "if (x) /a/.test(y)",
+            "while (f(x)) /a/",
+            "{} /a/",
+            "function f() {} /a/",
+            "`${/a/}`",
+            "x;\n/a/",
+        ];
+        for source in regex_after {
+            assert!(
+                kinds(source)
+                    .iter()
+                    .any(|kind| matches!(kind, TokenKind::Regex(_))),
+                "{}",
+                source
+            );
+        }
+
+        let division_after = [
+            "a / b / c",
+            "1 / 2",
+            "f(x) / 2",
+            "a[0] / 2",
+            "this / 2",
+            "({}) / 2",
+            "x = {} / 2",
+            "`a` / 2",
+            "`${a}` / 2",
+            "'a' / 2",
+            "let / 2",
+            "/a/ / 2",
+        ];
+        for source in division_after {
+            let tokens = kinds(source);
+            let slashes = tokens
+                .iter()
+                .filter(|kind| **kind == TokenKind::Literal(Literal::SLASH))
+                .count();
+            assert!(slashes >= 1, "{} lexed as {:?}", source, tokens);
+            assert!(
+                source.starts_with('/')
+                    || !tokens
+                        .iter()
+                        .any(|kind| matches!(kind, TokenKind::Regex(_))),
+                "{} lexed as {:?}",
+                source,
+                tokens
+            );
+        }
     }
 
     #[test]
-    fn test_set_possible_tokens_from_char() {
-        let mut current_chars: Vec<CharType> = Vec::new();
-        let mut current_possible_tokens: Vec<Token> = Vec::new();
+    fn test_tokenize_regex_errors() {
+        let cases = [
+            ("x = /abc", "Unterminated regular expression", (1, 4)),
+            ("x = /a\n/", "Unterminated regular expression", (1, 4)),
+            ("x = /[/\n]/", "Unterminated regular expression", (1, 4)),
+            ("x = /a\\\n/", "Unterminated regular expression", (1, 4)),
+            ("x = /a/gg", "Invalid regular expression flags", (1, 7)),
+            ("x = /a/x", "Invalid regular expression flags", (1, 7)),
+            ("x = /a/uv", "Invalid regular expression flags", (1, 7)),
+        ];
+
+        for (source, message, location) in cases {
+            match Token::tokenize(source.as_bytes()) {
+                Err(Errors::TokenError(error, span)) => {
+                    assert_eq!(error, message, "{}", source);
+                    assert_eq!((span.line, span.column), location, "{}", source);
+                }
+                result => panic!("{} should fail, got {:?}", source, result),
+            }
+        }
+    }
 
-        Token::set_possible_tokens_from_char(&mut current_chars, &mut current_possible_tokens, 'b');
+    // Leading trivia, token text and trailing trivia of every token in order
+    fn untokenize(tokens: &[Token], buffer: &[u8]) -> Vec<u8> {
+        let mut output = Vec::new();
+        for token in tokens {
+            for trivia in &token.leading_trivia {
+                output.extend_from_slice(&buffer[trivia.span.start..trivia.span.end]);
+            }
+            output.extend_from_slice(token.text(buffer));
+            for trivia in &token.trailing_trivia {
+                output.extend_from_slice(&buffer[trivia.span.start..trivia.span.end]);
+            }
+        }
+        output
+    }
+
+    fn trivia_kinds(trivia: &[Trivia]) -> Vec<TriviaKind> {
+        trivia.iter().map(|trivia| trivia.kind).collect()
+    }
 
-        assert_eq!(current_possible_tokens.len(), 1);
+    #[test]
+    fn test_tokenize_trivia() {
+        let source = "#!/usr/bin/env node\n/** Docs */\nconst a = 1; // one\n/**/ b";
+        let tokens = Token::tokenize(source.as_bytes()).unwrap();
+
+        assert_eq!(
+            trivia_kinds(&tokens[0].leading_trivia),
+            vec![
+                TriviaKind::Hashbang,
+                TriviaKind::LineTerminator,
+                TriviaKind::DocComment,
+                TriviaKind::LineTerminator,
+            ]
+        );
+        let documentation = tokens[0].leading_comments().last().unwrap();
+        assert_eq!(
+            &source[documentation.span.start..documentation.span.end],
+            "/** Docs */"
+        );
+        assert_eq!((documentation.span.line, documentation.span.column), (2, 0));
+
+        // `;` keeps the comment on its line, the next line belongs to `b`
+        assert_eq!(tokens[4].kind, TokenKind::Literal(Literal::SEMICOLON));
+        assert_eq!(
+            trivia_kinds(&tokens[4].trailing_trivia),
+            vec![TriviaKind::Whitespace, TriviaKind::LineComment]
+        );
+        assert_eq!(
+            trivia_kinds(&tokens[5].leading_trivia),
+            vec![
+                TriviaKind::LineTerminator,
+                TriviaKind::BlockComment,
+                TriviaKind::Whitespace,
+            ]
+        );
+        assert!(tokens[5].newline_before);
+        // A single run of whitespace is stored without allocating
+        assert!(matches!(tokens[1].trailing_trivia, TriviaList::One(_)));
+
+        assert_eq!(tokens[6].kind, TokenKind::EndOfFile);
+        assert_eq!(tokens[6].span.start, source.len());
+    }
+
+    #[test]
+    fn test_tokenize_html_like_comments() {
+        assert_eq!(kinds("a <!-- b\n--> c\nd"), vec![name("a"), name("d")]);
+        // `-->` after a token on the same line is a decrement and a comparison
+        assert_eq!(
+            kinds("a --> b"),
+            vec![
+                name("a"),
+                TokenKind::Punctuator(Punctuator::DECREMENT),
+                TokenKind::Literal(Literal::GREATER_THAN),
+                name("b"),
+            ]
+        );
+        // `#!` is only a hashbang at the very start of the file
+        assert!(Token::tokenize(b" #!/usr/bin/env node").is_err());
+    }
+
+    #[test]
+    fn test_tokenize_multi_line_trailing_comment_breaks_the_line() {
+        let tokens = Token::tokenize(b"a /* one\ntwo */ b").unwrap();
+
+        assert_eq!(
+            trivia_kinds(&tokens[0].trailing_trivia),
+            vec![
+                TriviaKind::Whitespace,
+                TriviaKind::BlockComment,
+                TriviaKind::Whitespace,
+            ]
+        );
+        assert!(tokens[1].leading_trivia.is_empty());
+        assert!(tokens[1].newline_before);
+    }
+
+    #[test]
+    fn test_tokenize_round_trips_fixtures() {
+        let mut fixtures = vec![
+            PathBuf::from("src/test/files/javascript-original.js"),
+            PathBuf::from("src/test/files/typescript-original.ts"),
+        ];
+        let mut corpus: Vec<PathBuf> = fs::read_dir("src/test/files/tokenizer")
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        corpus.sort();
+        fixtures.extend(corpus);
+
+        for path in fixtures {
+            let buffer = fs::read(&path).unwrap();
+            let dialect = Dialect::from_path(&path.to_string_lossy());
+            let tokens = Token::tokenize_with_dialect(&buffer, dialect)
+                .unwrap_or_else(|error| panic!("{} failed: {}", path.display(), error));
+
+            assert_eq!(
+                tokens.last().map(|token| &token.kind),
+                Some(&TokenKind::EndOfFile)
+            );
+            assert_eq!(
+                String::from_utf8_lossy(&untokenize(&tokens, &buffer)),
+                String::from_utf8_lossy(&buffer),
+                "{} doesn't round trip",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_tokenize_round_trips_line_endings() {
+        let sources = [
+            "a\r\nb\rc\n",
+            "/* a\r\n b */\r\n// c\r",
+            "x\u{2028}y\u{2029}\u{FEFF}z \u{3000}",
+            "`a\r\nb${c}d`\r\n",
+            "",
+            "   ",
+        ];
+
+        for source in sources {
+            let tokens = Token::tokenize(source.as_bytes()).unwrap();
+            assert_eq!(untokenize(&tokens, source.as_bytes()), source.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_tokenize_punctuators() {
+        let punctuator = |punctuator| TokenKind::Punctuator(punctuator);
+        let literal = |literal| TokenKind::Literal(literal);
+
+        assert_eq!(
+            kinds("a >>>= b >>> c >> d > e"),
+            vec![
+                name("a"),
+                punctuator(Punctuator::UNSIGNED_SHIFT_RIGHT_ASSIGN),
+                name("b"),
+                punctuator(Punctuator::UNSIGNED_SHIFT_RIGHT),
+                name("c"),
+                punctuator(Punctuator::SHIFT_RIGHT),
+                name("d"),
+                literal(Literal::GREATER_THAN),
+                name("e"),
+            ]
+        );
+        assert_eq!(
+            kinds("a === b !== c ?? d ??= e ** f **= g"),
+            vec![
+                name("a"),
+                punctuator(Punctuator::STRICT_EQUAL),
+                name("b"),
+                punctuator(Punctuator::STRICT_NOT_EQUAL),
+                name("c"),
+                punctuator(Punctuator::NULLISH_COALESCING),
+                name("d"),
+                punctuator(Punctuator::NULLISH_ASSIGN),
+                name("e"),
+                punctuator(Punctuator::EXPONENT),
+                name("f"),
+                punctuator(Punctuator::EXPONENT_ASSIGN),
+                name("g"),
+            ]
+        );
+        assert_eq!(
+            kinds("f(...args) => a?.b"),
+            vec![
+                name("f"),
+                literal(Literal::OPEN_BRACKET_LEFT),
+                punctuator(Punctuator::ELLIPSIS),
+                name("args"),
+                literal(Literal::OPEN_BRACKET_RIGHT),
+                punctuator(Punctuator::ARROW),
+                name("a"),
+                punctuator(Punctuator::OPTIONAL_CHAIN),
+                name("b"),
+            ]
+        );
+        // `?.5` is a conditional followed by a number
+        assert_eq!(
+            kinds("a?.5:b"),
+            vec![
+                name("a"),
+                literal(Literal::QUESTION_MARK),
+                TokenKind::Number(number(".5")),
+                literal(Literal::COLON),
+                name("b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_private_names() {
+        assert_eq!(
+            kinds("this.#count = #if in obj"),
+            vec![
+                TokenKind::Identifier(Identifier::THIS),
+                TokenKind::Literal(Literal::PERIOD),
+                TokenKind::PrivateName(Symbol::intern("count")),
+                TokenKind::Literal(Literal::EQUAL),
+                TokenKind::PrivateName(Symbol::intern("if")),
+                TokenKind::Identifier(Identifier::IN),
+                name("obj"),
+            ]
+        );
+
+        let error = Token::tokenize(b"this.# x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Syntax Error: Expected a name after # at 1:6"
+        );
+    }
+
+    #[test]
+    fn test_tokenize_punctuators_keep_regex_detection() {
+        // After `=>` a `{` is a body, so the `}` closing it allows a regex
+        let tokens = kinds("x => {}\n/re/g.test(s)");
+        assert_eq!(tokens[4], regex("re", "g"));
+
+        // `a++ / 2` divides, `a = /=/` is a regex
+        assert_eq!(kinds("a++ / 2")[2], TokenKind::Literal(Literal::SLASH));
+        assert_eq!(kinds("a = /=/")[2], regex("=", ""));
+        assert_eq!(
+            kinds("a /= 2")[1],
+            TokenKind::Punctuator(Punctuator::DIVIDE_ASSIGN)
+        );
+    }
+
+    #[test]
+    fn test_operator_metadata() {
+        let operator = |source: &str| kinds(source).remove(0);
+
+        assert_eq!(operator("**").binary_precedence(), Some(12));
+        assert_eq!(operator("**").associativity(), Some(Associativity::Right));
+        assert!(operator("*").binary_precedence() > operator("+").binary_precedence());
+        assert!(operator("+").binary_precedence() > operator("<<").binary_precedence());
+        assert!(operator("<<").binary_precedence() > operator("<=").binary_precedence());
+        assert_eq!(
+            operator("instanceof").binary_precedence(),
+            operator("<").binary_precedence()
+        );
+        assert!(operator("===").binary_precedence() > operator("&").binary_precedence());
+        assert!(operator("&").binary_precedence() > operator("^").binary_precedence());
+        assert!(operator("^").binary_precedence() > operator("|").binary_precedence());
+        assert!(operator("|").binary_precedence() > operator("&&").binary_precedence());
+        assert!(operator("&&").binary_precedence() > operator("||").binary_precedence());
+        assert!(operator("||").binary_precedence() > operator("??").binary_precedence());
+        assert_eq!(operator("-").associativity(), Some(Associativity::Left));
+
+        for source in ["=", "+=", "**=", ">>>=", "&&=", "||=", "??="] {
+            let kind = operator(source);
+            assert!(kind.is_assignment(), "{}", source);
+            assert_eq!(kind.binary_precedence(), None, "{}", source);
+            assert_eq!(
+                kind.associativity(),
+                Some(Associativity::Right),
+                "{}",
+                source
+            );
+        }
+        for source in ["==", "=>", "...", "?.", "++", "!", "typeof", "a", ";"] {
+            assert!(!operator(source).is_assignment(), "{}", source);
+        }
+
+        assert_eq!(
+            operator("=>").operator_category(),
+            Some(OperatorCategory::Arrow)
+        );
+        assert_eq!(
+            operator("--").operator_category(),
+            Some(OperatorCategory::Update)
+        );
+        assert_eq!(
+            operator("void").operator_category(),
+            Some(OperatorCategory::Unary)
+        );
+        assert_eq!(operator("{").operator_category(), None);
+        assert_eq!(operator("a").operator_category(), None);
+    }
+
+    fn typescript_kinds(source: &str) -> Vec<TokenKind> {
+        Token::tokenize_with_dialect(source.as_bytes(), Dialect::TypeScript)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .filter(|kind| *kind != TokenKind::EndOfFile)
+            .collect()
+    }
+
+    #[test]
+    fn test_dialect_from_path() {
+        assert_eq!(Dialect::from_path("src/index.ts"), Dialect::TypeScript);
+        assert_eq!(Dialect::from_path("App.TSX"), Dialect::Tsx);
+        assert_eq!(Dialect::from_path("types.d.ts"), Dialect::TypeScript);
+        assert_eq!(Dialect::from_path("server.mts"), Dialect::TypeScript);
+        assert_eq!(Dialect::from_path("index.js"), Dialect::JavaScript);
+        assert_eq!(Dialect::from_path("App.jsx"), Dialect::Jsx);
+        assert_eq!(Dialect::from_path("Makefile"), Dialect::JavaScript);
+    }
+
+    #[test]
+    fn test_tokenize_typescript_keywords() {
+        let source = "declare abstract class A { readonly x: keyof T }";
+
+        assert_eq!(
+            typescript_kinds(source),
+            vec![
+                TokenKind::TsKeyword(TsKeyword::DECLARE),
+                TokenKind::TsKeyword(TsKeyword::ABSTRACT),
+                TokenKind::Identifier(Identifier::CLASS),
+                name("A"),
+                TokenKind::Literal(Literal::CURLY_BRACKET_LEFT),
+                TokenKind::TsKeyword(TsKeyword::READONLY),
+                name("x"),
+                TokenKind::Literal(Literal::COLON),
+                TokenKind::TsKeyword(TsKeyword::KEYOF),
+                name("T"),
+                TokenKind::Literal(Literal::CURLY_BRACKET_RIGHT),
+            ]
+        );
+
+        // JavaScript keeps them as names, escaped spellings are names in both dialects
+        let javascript = kinds(source);
+        assert_eq!(javascript[0], name("declare"));
+        assert_eq!(
+            javascript[1],
+            TokenKind::Identifier(Identifier::FUTURE_OLD_ABSTRACT)
+        );
+        assert_eq!(
+            typescript_kinds("\\u0074ype"),
+            vec![TokenKind::Name {
+                value: Symbol::intern("type"),
+                escaped: true
+            }]
+        );
+        assert!(!TokenKind::TsKeyword(TsKeyword::TYPE).is_reserved(SourceContext::Strict));
+    }
+
+    #[test]
+    fn test_tokenize_decorators() {
+        assert_eq!(
+            kinds("@Component({}) @get @a.b class"),
+            vec![
+                TokenKind::Decorator(Symbol::intern("Component")),
+                TokenKind::Literal(Literal::OPEN_BRACKET_LEFT),
+                TokenKind::Literal(Literal::CURLY_BRACKET_LEFT),
+                TokenKind::Literal(Literal::CURLY_BRACKET_RIGHT),
+                TokenKind::Literal(Literal::OPEN_BRACKET_RIGHT),
+                TokenKind::Decorator(Symbol::intern("get")),
+                TokenKind::Decorator(Symbol::intern("a")),
+                TokenKind::Literal(Literal::PERIOD),
+                name("b"),
+                TokenKind::Identifier(Identifier::CLASS),
+            ]
+        );
+        assert_eq!(
+            typescript_kinds("@override"),
+            vec![TokenKind::Decorator(Symbol::intern("override"))]
+        );
+        // No name after the `@`
+        assert_eq!(
+            kinds("@ x @class"),
+            vec![
+                TokenKind::Literal(Literal::AT),
+                name("x"),
+                TokenKind::Literal(Literal::AT),
+                TokenKind::Identifier(Identifier::CLASS),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_non_null_assertion() {
+        assert_eq!(
+            typescript_kinds("a! / b")[2],
+            TokenKind::Literal(Literal::SLASH)
+        );
+        assert_eq!(typescript_kinds("!/re/")[1], regex("re", ""));
+        assert_eq!(kinds("a! / b/")[2], regex(" b", ""));
+    }
+
+    #[test]
+    fn test_split_greater_than() {
+        let source = "Array<Array<T>>= x";
+        let tokens = Token::tokenize_with_dialect(source.as_bytes(), Dialect::TypeScript).unwrap();
+
+        let closing = &tokens[5];
+        assert_eq!(
+            closing.kind,
+            TokenKind::Punctuator(Punctuator::SHIFT_RIGHT_ASSIGN)
+        );
+
+        let (first, rest) = closing.split_greater_than().unwrap();
+        assert_eq!(first.kind, TokenKind::Literal(Literal::GREATER_THAN));
+        assert_eq!(first.text(source.as_bytes()), b">");
+
+        let rest = rest.unwrap();
+        assert_eq!(rest.kind, TokenKind::Punctuator(Punctuator::GREATER_EQUAL));
+        assert_eq!(rest.text(source.as_bytes()), b">=");
+        assert_eq!(rest.span.column, 14);
+
+        let (second, rest) = rest.split_greater_than().unwrap();
+        assert_eq!(second.span.start, 14);
+        assert_eq!(rest.unwrap().kind, TokenKind::Literal(Literal::EQUAL));
+
+        let single = Token::tokenize(b">").unwrap().remove(0);
+        let (last, rest) = single.split_greater_than().unwrap();
+        assert_eq!(last.kind, TokenKind::Literal(Literal::GREATER_THAN));
+        assert!(rest.is_none());
+        assert!(tokens[0].split_greater_than().is_none());
+    }
+
+    #[test]
+    fn test_read_file_picks_dialect() {
+        let tokens = read_file("src/test/files/tokenizer/generics.ts").unwrap();
+        assert!(tokens
+            .iter()
+            .any(|token| token.kind == TokenKind::TsKeyword(TsKeyword::SATISFIES)));
+        assert!(tokens
+            .iter()
+            .any(|token| token.kind == TokenKind::Decorator(Symbol::intern("memoize"))));
+    }
+
+    fn jsx_kinds(source: &str, dialect: Dialect) -> Vec<TokenKind> {
+        Token::tokenize_with_dialect(source.as_bytes(), dialect)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .filter(|kind| *kind != TokenKind::EndOfFile)
+            .collect()
+    }
+
+    fn jsx_name(value: &str) -> TokenKind {
+        TokenKind::Jsx(JsxToken::Name(Symbol::intern(value)))
+    }
+
+    #[test]
+    fn test_tokenize_jsx_elements() {
+        let jsx = |token| TokenKind::Jsx(token);
+
+        assert_eq!(
+            jsx_kinds("<a b=\"c\">d {e}</a>", Dialect::Jsx),
+            vec![
+                jsx(JsxToken::TagOpen),
+                jsx_name("a"),
+                jsx_name("b"),
+                TokenKind::Literal(Literal::EQUAL),
+                jsx(JsxToken::String("c".to_string())),
+                jsx(JsxToken::TagEnd),
+                jsx(JsxToken::Text("d ".to_string())),
+                jsx(JsxToken::ExpressionStart),
+                name("e"),
+                jsx(JsxToken::ExpressionEnd),
+                jsx(JsxToken::TagClose),
+                jsx_name("a"),
+                jsx(JsxToken::TagEnd),
+            ]
+        );
+        assert_eq!(
+            jsx_kinds("x = <><my-el {...p} /></> / 2", Dialect::Jsx),
+            vec![
+                name("x"),
+                TokenKind::Literal(Literal::EQUAL),
+                jsx(JsxToken::TagOpen),
+                jsx(JsxToken::TagEnd),
+                jsx(JsxToken::TagOpen),
+                jsx_name("my-el"),
+                jsx(JsxToken::ExpressionStart),
+                TokenKind::Punctuator(Punctuator::ELLIPSIS),
+                name("p"),
+                jsx(JsxToken::ExpressionEnd),
+                jsx(JsxToken::SelfClosingEnd),
+                jsx(JsxToken::TagClose),
+                jsx(JsxToken::TagEnd),
+                TokenKind::Literal(Literal::SLASH),
+                TokenKind::Number(number("2")),
+            ]
+        );
+        // Plain JavaScript and comparisons keep `<`
+        assert_eq!(kinds("<a>")[0], TokenKind::Literal(Literal::LESS_THAN));
+        assert_eq!(
+            jsx_kinds("a < b", Dialect::Jsx)[1],
+            TokenKind::Literal(Literal::LESS_THAN)
+        );
+    }
+
+    #[test]
+    fn test_tokenize_jsx_text_and_entities() {
+        let tokens = jsx_kinds(
+            "<p title='&quot;hi&quot;'>\n  a &amp; b &#x41;&#66; &nope; c\n</p>",
+            Dialect::Jsx,
+        );
+
+        assert_eq!(
+            tokens[4],
+            TokenKind::Jsx(JsxToken::String("\"hi\"".to_string()))
+        );
+        assert_eq!(
+            tokens[6..12],
+            [
+                TokenKind::Jsx(JsxToken::Text("\n  a ".to_string())),
+                TokenKind::Jsx(JsxToken::Entity("&".to_string())),
+                TokenKind::Jsx(JsxToken::Text(" b ".to_string())),
+                TokenKind::Jsx(JsxToken::Entity("A".to_string())),
+                TokenKind::Jsx(JsxToken::Entity("B".to_string())),
+                TokenKind::Jsx(JsxToken::Text(" &nope; c\n".to_string())),
+            ]
+        );
+        // Comments and quotes in text aren't JavaScript
+        assert_eq!(
+            jsx_kinds("<p>// it's /* text */</p>", Dialect::Jsx)[3],
+            TokenKind::Jsx(JsxToken::Text("// it's /* text */".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_tokenize_jsx_nested_expressions() {
+        let source = "<ul>{items.map(item => <li key={item}>{`${item}`}</li>)}</ul>";
+        let tokens = jsx_kinds(source, Dialect::Jsx);
+
+        assert_eq!(tokens.last(), Some(&TokenKind::Jsx(JsxToken::TagEnd)));
+        assert_eq!(
+            tokens
+                .iter()
+                .filter(|kind| **kind == TokenKind::Jsx(JsxToken::ExpressionEnd))
+                .count(),
+            3
+        );
+
+        // Object literals and blocks inside a container keep their braces
+        let tokens = jsx_kinds("<a b={{ c: () => { return 1 } }}>x</a>", Dialect::Jsx);
+        assert_eq!(
+            tokens[tokens.len() - 4],
+            TokenKind::Jsx(JsxToken::Text("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_tokenize_tsx_generic_arrow() {
+        let tokens = jsx_kinds("const f = <T,>(x: T) => x", Dialect::Tsx);
+        assert_eq!(tokens[3], TokenKind::Literal(Literal::LESS_THAN));
+
+        let tokens = jsx_kinds("const f = <T extends object>(x: T) => x", Dialect::Tsx);
+        assert_eq!(tokens[3], TokenKind::Literal(Literal::LESS_THAN));
+
+        let tokens = jsx_kinds("const e = <T extends={1} />", Dialect::Tsx);
+        assert_eq!(tokens[3], TokenKind::Jsx(JsxToken::TagOpen));
+    }
+
+    #[test]
+    fn test_tokenize_jsx_errors() {
+        let cases = [
+            ("<a b=\"c>", "Unterminated JSX string", (1, 5)),
+            ("<a>text", "Unterminated JSX element", (1, 7)),
+            ("<a #>", "Unexpected character in JSX tag", (1, 3)),
+        ];
+
+        for (source, message, location) in cases {
+            match Token::tokenize_with_dialect(source.as_bytes(), Dialect::Jsx) {
+                Err(Errors::TokenError(error, span)) => {
+                    assert_eq!(error, message, "{}", source);
+                    assert_eq!((span.line, span.column), location, "{}", source);
+                }
+                result => panic!("{} should fail, got {:?}", source, result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_tokenize_recovering() {
+        let source = "let a = 1;\n\u{1}let b = 'open\nlet c = \\x;\n";
+        let (tokens, diagnostics) =
+            Token::tokenize_recovering(source.as_bytes(), Dialect::JavaScript);
+
+        let messages: Vec<String> = diagnostics.iter().map(|error| error.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Syntax Error: Unexpected character at 2:1",
+                "Syntax Error: Unterminated string literal at 2:10",
+                "Syntax Error: Invalid escape in identifier at 3:9",
+            ]
+        );
+
+        // Everything after the bad input is still lexed and the source round-trips
+        assert!(tokens.iter().any(|token| token.kind == name("c")));
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::EndOfFile);
+        assert_eq!(untokenize(&tokens, source.as_bytes()), source.as_bytes());
+
+        let error = tokens
+            .iter()
+            .find(|token| matches!(token.kind, TokenKind::Error(_)))
+            .unwrap();
+        assert_eq!(error.text(source.as_bytes()), "\u{1}".as_bytes());
+
+        // The plain tokenizer still stops at the first one
+        assert!(Token::tokenize(source.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_tokenize_recovering_to_the_end() {
+        let source = "a /* never closed\n b";
+        let (tokens, diagnostics) =
+            Token::tokenize_recovering(source.as_bytes(), Dialect::JavaScript);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[1].kind, TokenKind::Error("Unterminated comment"));
+        assert_eq!(tokens[1].span.end, source.len());
+        assert_eq!(tokens[2].kind, TokenKind::EndOfFile);
+        assert_eq!(tokens[2].span.line, 2);
+
+        let source = "const e = <div><b>text";
+        let (tokens, diagnostics) = Token::tokenize_recovering(source.as_bytes(), Dialect::Jsx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            tokens[tokens.len() - 2].kind,
+            TokenKind::Error("Unterminated JSX element")
+        );
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::EndOfFile);
     }
 }
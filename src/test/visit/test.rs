@@ -0,0 +1,268 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::ops::ControlFlow;
+
+    use crate::{
+        parse::{
+            ast::{Identifier, Program, SourceType},
+            parser::parse,
+            visit::{traverse, walk_identifier, Ancestors, Flow, Node, NodeKind, Visit},
+            visit_mut::{traverse_mut, AncestorKinds, NodeMut, VisitMut},
+        },
+        read::{interner::Symbol, typescript::Dialect},
+    };
+
+    fn program(source: &str, dialect: Dialect) -> Program {
+        parse(source.as_bytes(), dialect, SourceType::Module).unwrap()
+    }
+
+    #[derive(Default)]
+    struct Kinds {
+        entered: Vec<NodeKind>,
+        exited: Vec<NodeKind>,
+    }
+
+    impl<'ast> Visit<'ast> for Kinds {
+        fn enter(&mut self, node: Node<'ast>, _: &Ancestors<'ast>) -> Flow {
+            self.entered.push(node.kind());
+            Flow::Continue
+        }
+
+        fn exit(&mut self, node: Node<'ast>, _: &Ancestors<'ast>) -> ControlFlow<()> {
+            self.exited.push(node.kind());
+            ControlFlow::Continue(())
+        }
+    }
+
+    #[test]
+    fn test_enter_and_exit_in_source_order() {
+        let program = program(
+            "let a = `x${b}y${c}z`;\ncontinue_: for (;;) break;",
+            Dialect::JavaScript,
+        );
+        let mut kinds = Kinds::default();
+        assert_eq!(traverse(&mut kinds, &program), ControlFlow::Continue(()));
+
+        use NodeKind::*;
+        assert_eq!(
+            kinds.entered,
+            vec![
+                Program,
+                VariableDeclaration,
+                VariableDeclarator,
+                Identifier,
+                TemplateLiteral,
+                TemplateElement,
+                Identifier,
+                TemplateElement,
+                Identifier,
+                TemplateElement,
+                LabeledStatement,
+                Identifier,
+                ForStatement,
+                BreakStatement,
+            ]
+        );
+        // Children exit before their parent, the program last
+        assert_eq!(kinds.exited.len(), kinds.entered.len());
+        assert_eq!(kinds.exited[..3], [Identifier, TemplateElement, Identifier]);
+        assert_eq!(kinds.exited.last(), Some(&Program));
+    }
+
+    #[test]
+    fn test_typescript_and_jsx_nodes_are_visited() {
+        let program = program(
+            "type A = { a: string } | B<C>;\nconst x = <Row id={(y as T)!} />;",
+            Dialect::Tsx,
+        );
+        let mut kinds = Kinds::default();
+        let _ = traverse(&mut kinds, &program);
+
+        let mut counts = HashMap::new();
+        for kind in kinds.entered {
+            *counts.entry(kind).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&NodeKind::TsTypeAliasDeclaration], 1);
+        assert_eq!(counts[&NodeKind::TsPropertySignature], 1);
+        // B<C>, C and T
+        assert_eq!(counts[&NodeKind::TsTypeReference], 3);
+        assert_eq!(counts[&NodeKind::TsTypeArguments], 1);
+        assert_eq!(counts[&NodeKind::JsxAttribute], 1);
+        assert_eq!(counts[&NodeKind::TsAsExpression], 1);
+        assert_eq!(counts[&NodeKind::TsNonNullExpression], 1);
+        // A, a, B, C, x, Row, id, y and T
+        assert_eq!(counts[&NodeKind::Identifier], 9);
+    }
+
+    // Names of the functions each identifier sits in, nearest first
+    #[derive(Default)]
+    struct Scopes {
+        found: Vec<(String, Vec<String>)>,
+    }
+
+    impl<'ast> Visit<'ast> for Scopes {
+        fn visit_identifier(
+            &mut self,
+            node: &'ast Identifier,
+            ancestors: &mut Ancestors<'ast>,
+        ) -> ControlFlow<()> {
+            let functions = ancestors
+                .iter()
+                .filter_map(|ancestor| match ancestor {
+                    Node::Function(function) => Some(
+                        function
+                            .id
+                            .as_ref()
+                            .map_or("anonymous".to_string(), |id| id.name.to_string()),
+                    ),
+                    _ => None,
+                })
+                .collect();
+            self.found.push((node.name.to_string(), functions));
+            walk_identifier(self, node, ancestors)
+        }
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let program = program(
+            "function outer(a) { return function () { return a + b; }; }",
+            Dialect::JavaScript,
+        );
+        let mut scopes = Scopes::default();
+        let _ = traverse(&mut scopes, &program);
+
+        let names = |list: &[&str]| list.iter().map(|name| name.to_string()).collect();
+        assert_eq!(
+            scopes.found,
+            vec![
+                ("outer".to_string(), names(&["outer"])),
+                ("a".to_string(), names(&["outer"])),
+                ("a".to_string(), names(&["anonymous", "outer"])),
+                ("b".to_string(), names(&["anonymous", "outer"])),
+            ]
+        );
+
+        struct Parents(Vec<(NodeKind, usize)>);
+        impl<'ast> Visit<'ast> for Parents {
+            fn enter(&mut self, node: Node<'ast>, ancestors: &Ancestors<'ast>) -> Flow {
+                if let Node::Identifier(_) = node {
+                    let parent = ancestors.parent().unwrap();
+                    self.0.push((parent.kind(), ancestors.depth()));
+                }
+                Flow::Continue
+            }
+        }
+        let program = self::program("x.y = 1;", Dialect::JavaScript);
+        let mut parents = Parents(Vec::new());
+        let _ = traverse(&mut parents, &program);
+        // Program > ExpressionStatement > AssignmentExpression > MemberExpression
+        assert_eq!(
+            parents.0,
+            vec![
+                (NodeKind::MemberExpression, 4),
+                (NodeKind::MemberExpression, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_early_termination() {
+        // Skip leaves out the function bodies, their exits still run
+        struct SkipFunctions(Kinds);
+        impl<'ast> Visit<'ast> for SkipFunctions {
+            fn enter(&mut self, node: Node<'ast>, ancestors: &Ancestors<'ast>) -> Flow {
+                self.0.enter(node, ancestors);
+                match node {
+                    Node::Function(_) | Node::ArrowFunction(_) => Flow::Skip,
+                    _ => Flow::Continue,
+                }
+            }
+
+            fn exit(&mut self, node: Node<'ast>, ancestors: &Ancestors<'ast>) -> ControlFlow<()> {
+                self.0.exit(node, ancestors)
+            }
+        }
+        let program = program(
+            "function f(a) { a; }\nconst g = () => b;\nc;",
+            Dialect::JavaScript,
+        );
+        let mut skip = SkipFunctions(Kinds::default());
+        assert_eq!(traverse(&mut skip, &program), ControlFlow::Continue(()));
+        let identifiers = |kinds: &[NodeKind]| {
+            kinds
+                .iter()
+                .filter(|kind| **kind == NodeKind::Identifier)
+                .count()
+        };
+        // g and c
+        assert_eq!(identifiers(&skip.0.entered), 2);
+        assert_eq!(skip.0.exited.len(), skip.0.entered.len());
+
+        // Stop ends the traversal at the first call, nothing after it is entered
+        struct FirstCall(Vec<NodeKind>);
+        impl<'ast> Visit<'ast> for FirstCall {
+            fn enter(&mut self, node: Node<'ast>, _: &Ancestors<'ast>) -> Flow {
+                self.0.push(node.kind());
+                match node {
+                    Node::CallExpression(_) => Flow::Stop,
+                    _ => Flow::Continue,
+                }
+            }
+        }
+        let program = self::program("a;\nb(c);\nd();", Dialect::JavaScript);
+        let mut first = FirstCall(Vec::new());
+        assert_eq!(traverse(&mut first, &program), ControlFlow::Break(()));
+        assert_eq!(first.0.last(), Some(&NodeKind::CallExpression));
+        assert_eq!(
+            first
+                .0
+                .iter()
+                .filter(|kind| **kind == NodeKind::Identifier)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_visit_mut_renames() {
+        struct Rename {
+            exits: usize,
+        }
+        impl VisitMut for Rename {
+            fn enter(&mut self, node: NodeMut<'_>, ancestors: &AncestorKinds) -> Flow {
+                // Only names read as values, property keys stay as they are
+                if let NodeMut::Identifier(identifier) = node {
+                    if identifier.name == "a"
+                        && ancestors.parent() != Some(NodeKind::MemberExpression)
+                    {
+                        identifier.name = Symbol::intern("renamed");
+                    }
+                }
+                Flow::Continue
+            }
+
+            fn exit(&mut self, _: NodeMut<'_>, _: &AncestorKinds) -> ControlFlow<()> {
+                self.exits += 1;
+                ControlFlow::Continue(())
+            }
+        }
+
+        let mut program = program("let a = 1;\nf(a, b.a);", Dialect::JavaScript);
+        let mut rename = Rename { exits: 0 };
+        assert_eq!(
+            traverse_mut(&mut rename, &mut program),
+            ControlFlow::Continue(())
+        );
+
+        let mut names = Scopes::default();
+        let _ = traverse(&mut names, &program);
+        let names: Vec<&str> = names.found.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["renamed", "f", "renamed", "b", "a"]);
+
+        let mut kinds = Kinds::default();
+        let _ = traverse(&mut kinds, &program);
+        assert_eq!(rename.exits, kinds.exited.len());
+    }
+}
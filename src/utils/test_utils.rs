@@ -27,7 +27,7 @@ pub fn create_temp_file(content: &str) -> String {
 // Function to remove a config file
 pub fn remove_config_from_path(file_path: &str) {
     if Path::new(file_path).exists() {
-        fs::remove_file(file_path).expect(&("Unable to remove file".to_owned() + file_path));
+        fs::remove_file(file_path).unwrap_or_else(|_e| panic!("Unable to remove file{}", file_path));
     }
 }
 